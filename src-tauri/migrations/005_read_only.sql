@@ -0,0 +1,2 @@
+-- Add read-only flag to block writes on sensitive connections
+ALTER TABLE connections ADD COLUMN read_only INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Track connection usage so the UI can sort by most recently used
+ALTER TABLE connections ADD COLUMN last_connected_at TEXT;
+ALTER TABLE connections ADD COLUMN query_count INTEGER NOT NULL DEFAULT 0;
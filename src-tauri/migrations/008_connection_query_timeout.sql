@@ -0,0 +1,2 @@
+-- Per-connection default query timeout; NULL falls back to the global setting
+ALTER TABLE connections ADD COLUMN query_timeout_secs INTEGER;
@@ -0,0 +1,8 @@
+-- Queries that ran longer than the slow_query_threshold_ms setting
+CREATE TABLE IF NOT EXISTS slow_queries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    connection_uuid TEXT NOT NULL,
+    query TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,2 @@
+-- Statements run on every new database session, e.g. SET search_path or SET ROLE
+ALTER TABLE connections ADD COLUMN session_setup_sql TEXT;
@@ -0,0 +1,2 @@
+-- Rows per page for this connection's table grid; NULL uses the app default
+ALTER TABLE connections ADD COLUMN default_page_size INTEGER;
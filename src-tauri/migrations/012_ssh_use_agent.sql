@@ -0,0 +1,2 @@
+-- Authenticate the SSH tunnel through the running ssh-agent instead of a key file or password
+ALTER TABLE connections ADD COLUMN ssh_use_agent INTEGER NOT NULL DEFAULT 0;
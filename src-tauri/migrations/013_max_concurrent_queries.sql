@@ -0,0 +1,2 @@
+-- Queries a pooled connection runs at once; NULL uses the app default
+ALTER TABLE connections ADD COLUMN max_concurrent_queries INTEGER;
@@ -0,0 +1,2 @@
+-- Directory of the server's Unix domain socket, used instead of the host; NULL connects over TCP
+ALTER TABLE connections ADD COLUMN socket_path TEXT;
@@ -0,0 +1,307 @@
+use crate::commands::connections::{
+    insert_exported_connection, unique_connection_name, ExportedConnection,
+};
+use crate::commands::snippets::{insert_snippet, list_snippets};
+use crate::db::models::{Connection, SavedQuery, SnippetFormData};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use tauri::State;
+
+const BUNDLE_FORMAT: &str = "dbcooper-bundle";
+const BUNDLE_VERSION: u32 = 1;
+const SALT_LEN: usize = 16;
+
+/// Encrypted bundle file. The payload is a `BundleContents` JSON document sealed with
+/// AES-256-GCM under a key derived from the passphrase with Argon2id.
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedBundle {
+    format: String,
+    version: u32,
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Connection as stored in a bundle; the original UUID links its saved queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundledConnection {
+    uuid: String,
+    #[serde(flatten)]
+    connection: ExportedConnection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundledSavedQuery {
+    connection_uuid: String,
+    name: String,
+    query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BundleContents {
+    exported_at: String,
+    connections: Vec<BundledConnection>,
+    saved_queries: Vec<BundledSavedQuery>,
+    snippets: Vec<SnippetFormData>,
+}
+
+/// Number of items written to or merged from a bundle. `skipped` counts items that
+/// already existed locally (or whose connection is missing) and were left alone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BundleCounts {
+    pub connections: u32,
+    pub saved_queries: u32,
+    pub snippets: u32,
+    pub skipped: u32,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    if passphrase.is_empty() {
+        return Err("Passphrase must not be empty".to_string());
+    }
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    Ok(key)
+}
+
+fn encrypt_contents(contents: &BundleContents, passphrase: &str) -> Result<String, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt)?;
+
+    let plaintext = serde_json::to_vec(contents).map_err(|e| e.to_string())?;
+    let cipher = Aes256Gcm::new(&key.into());
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| "Failed to encrypt bundle".to_string())?;
+
+    let bundle = EncryptedBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        kdf: "argon2id".to_string(),
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    };
+    serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())
+}
+
+fn decrypt_contents(data: &str, passphrase: &str) -> Result<BundleContents, String> {
+    let bundle: EncryptedBundle =
+        serde_json::from_str(data).map_err(|e| format!("Not a valid bundle file: {}", e))?;
+    if bundle.format != BUNDLE_FORMAT {
+        return Err("Not a valid bundle file".to_string());
+    }
+    if bundle.version != BUNDLE_VERSION {
+        return Err(format!(
+            "Unsupported bundle version: {}. Expected version {}.",
+            bundle.version, BUNDLE_VERSION
+        ));
+    }
+
+    let decode = |field: &str| {
+        STANDARD
+            .decode(field)
+            .map_err(|e| format!("Corrupted bundle: {}", e))
+    };
+    let salt = decode(&bundle.salt)?;
+    let nonce = decode(&bundle.nonce)?;
+    let ciphertext = decode(&bundle.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err("Corrupted bundle: invalid nonce".to_string());
+    }
+
+    let key = derive_key(passphrase, &salt)?;
+    let cipher = Aes256Gcm::new(&key.into());
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Failed to decrypt bundle: wrong passphrase or corrupted file".to_string())?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| format!("Corrupted bundle: {}", e))
+}
+
+/// Write every connection, saved query and snippet to `output_path`, encrypted with
+/// `passphrase`
+pub async fn write_bundle(
+    pool: &SqlitePool,
+    output_path: &str,
+    passphrase: &str,
+) -> Result<BundleCounts, String> {
+    let connections: Vec<Connection> = sqlx::query_as("SELECT * FROM connections ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let saved_queries: Vec<SavedQuery> = sqlx::query_as("SELECT * FROM saved_queries ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let snippets = list_snippets(pool, None, &[]).await?;
+
+    let contents = BundleContents {
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        connections: connections
+            .into_iter()
+            .map(|c| BundledConnection {
+                uuid: c.uuid.clone(),
+                connection: ExportedConnection::from(c),
+            })
+            .collect(),
+        saved_queries: saved_queries
+            .into_iter()
+            .map(|q| BundledSavedQuery {
+                connection_uuid: q.connection_uuid,
+                name: q.name,
+                query: q.query,
+            })
+            .collect(),
+        snippets: snippets
+            .into_iter()
+            .map(|s| SnippetFormData {
+                title: s.title,
+                body: s.body,
+                db_type: s.db_type,
+                tags: s.tags,
+            })
+            .collect(),
+    };
+
+    let counts = BundleCounts {
+        connections: contents.connections.len() as u32,
+        saved_queries: contents.saved_queries.len() as u32,
+        snippets: contents.snippets.len() as u32,
+        skipped: 0,
+    };
+    let encrypted = encrypt_contents(&contents, passphrase)?;
+    tokio::fs::write(output_path, encrypted)
+        .await
+        .map_err(|e| format!("Failed to write bundle: {}", e))?;
+    Ok(counts)
+}
+
+/// Whether a local connection points at the same database as a bundled one
+fn same_target(local: &Connection, bundled: &ExportedConnection) -> bool {
+    local.name == bundled.name
+        && local.db_type == bundled.db_type
+        && local.host == bundled.host
+        && local.port == bundled.port
+        && local.database == bundled.database
+        && local.username == bundled.username
+        && local.file_path == bundled.file_path
+}
+
+/// Decrypt the bundle at `input_path` and merge it into the app database.
+///
+/// A bundled connection identical to a local one (same name and target) is reused
+/// rather than duplicated; other name clashes get a " (n)" suffix. Saved queries are
+/// re-attached to the imported connections, and saved queries or snippets that already
+/// exist verbatim are skipped.
+pub async fn read_bundle(
+    pool: &SqlitePool,
+    input_path: &str,
+    passphrase: &str,
+) -> Result<BundleCounts, String> {
+    let data = tokio::fs::read_to_string(input_path)
+        .await
+        .map_err(|e| format!("Failed to read bundle: {}", e))?;
+    let contents = decrypt_contents(&data, passphrase)?;
+
+    let mut counts = BundleCounts::default();
+    let local_connections: Vec<Connection> = sqlx::query_as("SELECT * FROM connections")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut existing_names: Vec<String> =
+        local_connections.iter().map(|c| c.name.clone()).collect();
+
+    // Bundle UUID -> local UUID
+    let mut uuid_map: HashMap<String, String> = HashMap::new();
+    for bundled in contents.connections {
+        if let Some(local) = local_connections
+            .iter()
+            .find(|c| same_target(c, &bundled.connection))
+        {
+            uuid_map.insert(bundled.uuid, local.uuid.clone());
+            counts.skipped += 1;
+            continue;
+        }
+
+        let name = unique_connection_name(&bundled.connection.name, &existing_names);
+        let uuid = insert_exported_connection(pool, &bundled.connection, &name).await?;
+        existing_names.push(name);
+        uuid_map.insert(bundled.uuid, uuid);
+        counts.connections += 1;
+    }
+
+    for saved in contents.saved_queries {
+        let Some(connection_uuid) = uuid_map.get(&saved.connection_uuid) else {
+            counts.skipped += 1;
+            continue;
+        };
+
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM saved_queries WHERE connection_uuid = ? AND name = ? AND query = ?)",
+        )
+        .bind(connection_uuid)
+        .bind(&saved.name)
+        .bind(&saved.query)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+        if exists {
+            counts.skipped += 1;
+            continue;
+        }
+
+        sqlx::query("INSERT INTO saved_queries (connection_uuid, name, query) VALUES (?, ?, ?)")
+            .bind(connection_uuid)
+            .bind(&saved.name)
+            .bind(&saved.query)
+            .execute(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        counts.saved_queries += 1;
+    }
+
+    let local_snippets = list_snippets(pool, None, &[]).await?;
+    for snippet in contents.snippets {
+        if local_snippets
+            .iter()
+            .any(|s| s.title == snippet.title && s.body == snippet.body)
+        {
+            counts.skipped += 1;
+            continue;
+        }
+        insert_snippet(pool, &snippet).await?;
+        counts.snippets += 1;
+    }
+
+    Ok(counts)
+}
+
+#[tauri::command]
+pub async fn export_bundle(
+    pool: State<'_, SqlitePool>,
+    output_path: String,
+    passphrase: String,
+) -> Result<BundleCounts, String> {
+    write_bundle(pool.inner(), &output_path, &passphrase).await
+}
+
+#[tauri::command]
+pub async fn import_bundle(
+    pool: State<'_, SqlitePool>,
+    input_path: String,
+    passphrase: String,
+) -> Result<BundleCounts, String> {
+    read_bundle(pool.inner(), &input_path, &passphrase).await
+}
@@ -1,4 +1,5 @@
 use crate::db::models::{Connection, ConnectionFormData};
+use serde::Serialize;
 use sqlx::SqlitePool;
 use tauri::State;
 use uuid::Uuid;
@@ -11,6 +12,195 @@ pub async fn get_connections(pool: State<'_, SqlitePool>) -> Result<Vec<Connecti
         .map_err(|e| e.to_string())
 }
 
+/// Stamp the time a connection was last opened
+pub async fn record_connection_opened(pool: &SqlitePool, uuid: &str) -> Result<(), String> {
+    sqlx::query("UPDATE connections SET last_connected_at = datetime('now') WHERE uuid = ?")
+        .bind(uuid)
+        .execute(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Increment the number of queries run on a connection
+pub async fn record_query_executed(pool: &SqlitePool, uuid: &str) -> Result<(), String> {
+    sqlx::query("UPDATE connections SET query_count = query_count + 1 WHERE uuid = ?")
+        .bind(uuid)
+        .execute(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Record that a connection was opened without delaying the caller
+pub fn spawn_record_connection_opened(sqlite_pool: &SqlitePool, uuid: &str) {
+    let sqlite_pool = sqlite_pool.clone();
+    let uuid = uuid.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = record_connection_opened(&sqlite_pool, &uuid).await {
+            println!("[Connections] Failed to record connection usage: {}", e);
+        }
+    });
+}
+
+/// Count an executed query without delaying the caller
+pub fn spawn_record_query_executed(sqlite_pool: &SqlitePool, uuid: &str) {
+    let sqlite_pool = sqlite_pool.clone();
+    let uuid = uuid.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = record_query_executed(&sqlite_pool, &uuid).await {
+            println!("[Connections] Failed to record query usage: {}", e);
+        }
+    });
+}
+
+/// Page size used when browsing a table without an explicit limit
+pub const DEFAULT_PAGE_SIZE: i64 = 100;
+/// Upper bound on a connection's configured page size
+pub const MAX_PAGE_SIZE: i64 = 10_000;
+
+/// Rows per page for a table grid: `limit` when the caller gives one, otherwise the
+/// connection's `default_page_size` clamped to `1..=MAX_PAGE_SIZE`, otherwise
+/// `DEFAULT_PAGE_SIZE`
+pub async fn resolve_page_size(
+    pool: &SqlitePool,
+    uuid: &str,
+    limit: Option<i64>,
+) -> Result<i64, String> {
+    if let Some(limit) = limit {
+        return Ok(limit);
+    }
+
+    let default_page_size: Option<i64> =
+        sqlx::query_scalar("SELECT default_page_size FROM connections WHERE uuid = ?")
+            .bind(uuid)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| e.to_string())?
+            .flatten();
+
+    Ok(default_page_size
+        .map(|size| size.clamp(1, MAX_PAGE_SIZE))
+        .unwrap_or(DEFAULT_PAGE_SIZE))
+}
+
+/// A problem with one field of a connection form, reported by `validate_connection`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConnectionFieldError {
+    /// Form field name, as serialized in `ConnectionFormData`
+    pub field: String,
+    pub message: String,
+}
+
+impl ConnectionFieldError {
+    fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+fn require(errors: &mut Vec<ConnectionFieldError>, field: &str, value: &str, label: &str) {
+    if value.trim().is_empty() {
+        errors.push(ConnectionFieldError::new(
+            field,
+            format!("{} is required", label),
+        ));
+    }
+}
+
+fn check_port(errors: &mut Vec<ConnectionFieldError>, field: &str, port: i64) {
+    if !(1..=65535).contains(&port) {
+        errors.push(ConnectionFieldError::new(
+            field,
+            "Port must be between 1 and 65535",
+        ));
+    }
+}
+
+/// A Postgres connection with a socket directory needs no host
+fn has_socket_path(connection: &ConnectionFormData) -> bool {
+    connection
+        .socket_path
+        .as_deref()
+        .is_some_and(|path| !path.trim().is_empty())
+}
+
+/// Check that a connection has the fields its `db_type` needs before it is saved.
+/// Returns one error per invalid field; an empty list means the connection is valid.
+///
+/// Redis is not required to have a username, since servers without ACLs only take a password.
+#[tauri::command]
+pub fn validate_connection(connection: ConnectionFormData) -> Vec<ConnectionFieldError> {
+    let mut errors = Vec::new();
+    require(&mut errors, "name", &connection.name, "Name");
+
+    match connection.db_type.as_str() {
+        "sqlite" | "sqlite3" => require(
+            &mut errors,
+            "file_path",
+            connection.file_path.as_deref().unwrap_or_default(),
+            "Database file",
+        ),
+        "postgres" | "postgresql" if has_socket_path(&connection) => {
+            if !connection
+                .socket_path
+                .as_deref()
+                .unwrap_or_default()
+                .trim()
+                .starts_with('/')
+            {
+                errors.push(ConnectionFieldError::new(
+                    "socket_path",
+                    "Socket path must be an absolute directory",
+                ));
+            }
+            require(&mut errors, "username", &connection.username, "Username");
+        }
+        "postgres" | "postgresql" | "mysql" | "clickhouse" => {
+            require(&mut errors, "host", &connection.host, "Host");
+            check_port(&mut errors, "port", connection.port);
+            require(&mut errors, "username", &connection.username, "Username");
+        }
+        "oracle" => {
+            require(&mut errors, "host", &connection.host, "Host");
+            check_port(&mut errors, "port", connection.port);
+            require(
+                &mut errors,
+                "database",
+                &connection.database,
+                "Service name or SID",
+            );
+            require(&mut errors, "username", &connection.username, "Username");
+        }
+        "redis" | "cassandra" | "scylla" | "opensearch" | "elasticsearch" => {
+            require(&mut errors, "host", &connection.host, "Host");
+            check_port(&mut errors, "port", connection.port);
+        }
+        other => errors.push(ConnectionFieldError::new(
+            "db_type",
+            format!("Unsupported database type: {}", other),
+        )),
+    }
+
+    if connection.ssh_enabled {
+        require(&mut errors, "ssh_host", &connection.ssh_host, "SSH host");
+        check_port(&mut errors, "ssh_port", connection.ssh_port);
+        require(&mut errors, "ssh_user", &connection.ssh_user, "SSH user");
+        if connection.ssh_use_key && !connection.ssh_use_agent {
+            require(
+                &mut errors,
+                "ssh_key_path",
+                &connection.ssh_key_path,
+                "SSH key file",
+            );
+        }
+    }
+
+    errors
+}
+
 #[tauri::command]
 pub async fn get_connection_by_uuid(
     pool: State<'_, SqlitePool>,
@@ -32,11 +222,13 @@ pub async fn create_connection(
     let ssl = if data.ssl { 1 } else { 0 };
     let ssh_enabled = if data.ssh_enabled { 1 } else { 0 };
     let ssh_use_key = if data.ssh_use_key { 1 } else { 0 };
+    let ssh_use_agent = if data.ssh_use_agent { 1 } else { 0 };
+    let read_only = if data.read_only { 1 } else { 0 };
 
     sqlx::query_as::<_, Connection>(
         r#"
-        INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key, ssh_use_agent, read_only, query_timeout_secs, session_setup_sql, default_page_size, max_concurrent_queries, socket_path)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING *
         "#,
     )
@@ -58,6 +250,13 @@ pub async fn create_connection(
     .bind(&data.ssh_password)
     .bind(&data.ssh_key_path)
     .bind(ssh_use_key)
+    .bind(ssh_use_agent)
+    .bind(read_only)
+    .bind(data.query_timeout_secs)
+    .bind(&data.session_setup_sql)
+    .bind(data.default_page_size)
+    .bind(data.max_concurrent_queries)
+    .bind(&data.socket_path)
     .fetch_one(pool.inner())
     .await
     .map_err(|e| e.to_string())
@@ -72,13 +271,17 @@ pub async fn update_connection(
     let ssl = if data.ssl { 1 } else { 0 };
     let ssh_enabled = if data.ssh_enabled { 1 } else { 0 };
     let ssh_use_key = if data.ssh_use_key { 1 } else { 0 };
+    let ssh_use_agent = if data.ssh_use_agent { 1 } else { 0 };
+    let read_only = if data.read_only { 1 } else { 0 };
 
     sqlx::query_as::<_, Connection>(
         r#"
         UPDATE connections
         SET type = ?, name = ?, host = ?, port = ?, database = ?, username = ?, password = ?, ssl = ?,
             db_type = ?, file_path = ?,
-            ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_user = ?, ssh_password = ?, ssh_key_path = ?, ssh_use_key = ?,
+            ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_user = ?, ssh_password = ?, ssh_key_path = ?, ssh_use_key = ?, ssh_use_agent = ?,
+            read_only = ?, query_timeout_secs = ?, session_setup_sql = ?, default_page_size = ?,
+            max_concurrent_queries = ?, socket_path = ?,
             updated_at = datetime('now')
         WHERE id = ?
         RETURNING *
@@ -101,6 +304,13 @@ pub async fn update_connection(
     .bind(&data.ssh_password)
     .bind(&data.ssh_key_path)
     .bind(ssh_use_key)
+    .bind(ssh_use_agent)
+    .bind(read_only)
+    .bind(data.query_timeout_secs)
+    .bind(&data.session_setup_sql)
+    .bind(data.default_page_size)
+    .bind(data.max_concurrent_queries)
+    .bind(&data.socket_path)
     .bind(id)
     .fetch_one(pool.inner())
     .await
@@ -138,6 +348,51 @@ pub struct ExportedConnection {
     pub ssh_password: String,
     pub ssh_key_path: String,
     pub ssh_use_key: bool,
+    #[serde(default)]
+    pub ssh_use_agent: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub query_timeout_secs: Option<i64>,
+    #[serde(default)]
+    pub session_setup_sql: Option<String>,
+    #[serde(default)]
+    pub default_page_size: Option<i64>,
+    #[serde(default)]
+    pub max_concurrent_queries: Option<i64>,
+    #[serde(default)]
+    pub socket_path: Option<String>,
+}
+
+impl From<Connection> for ExportedConnection {
+    fn from(connection: Connection) -> Self {
+        ExportedConnection {
+            connection_type: connection.connection_type,
+            name: connection.name,
+            host: connection.host,
+            port: connection.port,
+            database: connection.database,
+            username: connection.username,
+            password: connection.password,
+            ssl: connection.ssl == 1,
+            db_type: connection.db_type,
+            file_path: connection.file_path,
+            ssh_enabled: connection.ssh_enabled == 1,
+            ssh_host: connection.ssh_host,
+            ssh_port: connection.ssh_port,
+            ssh_user: connection.ssh_user,
+            ssh_password: connection.ssh_password,
+            ssh_key_path: connection.ssh_key_path,
+            ssh_use_key: connection.ssh_use_key == 1,
+            ssh_use_agent: connection.ssh_use_agent == 1,
+            read_only: connection.read_only == 1,
+            query_timeout_secs: connection.query_timeout_secs,
+            session_setup_sql: connection.session_setup_sql,
+            default_page_size: connection.default_page_size,
+            max_concurrent_queries: connection.max_concurrent_queries,
+            socket_path: connection.socket_path,
+        }
+    }
 }
 
 /// Export file format
@@ -159,30 +414,10 @@ pub async fn export_connection(
         .await
         .map_err(|e| e.to_string())?;
 
-    let exported = ExportedConnection {
-        connection_type: connection.connection_type,
-        name: connection.name,
-        host: connection.host,
-        port: connection.port,
-        database: connection.database,
-        username: connection.username,
-        password: connection.password,
-        ssl: connection.ssl == 1,
-        db_type: connection.db_type,
-        file_path: connection.file_path,
-        ssh_enabled: connection.ssh_enabled == 1,
-        ssh_host: connection.ssh_host,
-        ssh_port: connection.ssh_port,
-        ssh_user: connection.ssh_user,
-        ssh_password: connection.ssh_password,
-        ssh_key_path: connection.ssh_key_path,
-        ssh_use_key: connection.ssh_use_key == 1,
-    };
-
     Ok(ConnectionsExport {
         version: 1,
         exported_at: chrono::Utc::now().to_rfc3339(),
-        connections: vec![exported],
+        connections: vec![ExportedConnection::from(connection)],
     })
 }
 
@@ -207,56 +442,80 @@ pub async fn import_connections(
         .map_err(|e| e.to_string())?;
 
     for conn in data.connections {
-        let uuid = Uuid::new_v4().to_string();
-        let ssl = if conn.ssl { 1 } else { 0 };
-        let ssh_enabled = if conn.ssh_enabled { 1 } else { 0 };
-        let ssh_use_key = if conn.ssh_use_key { 1 } else { 0 };
-
-        // Generate a unique name if there's a conflict
-        let mut final_name = conn.name.clone();
-        if existing_names.contains(&final_name) {
-            let mut counter = 1;
-            loop {
-                let candidate = format!("{} ({})", conn.name, counter);
-                if !existing_names.contains(&candidate) {
-                    final_name = candidate;
-                    break;
-                }
-                counter += 1;
-            }
+        let final_name = unique_connection_name(&conn.name, &existing_names);
+        if insert_exported_connection(pool.inner(), &conn, &final_name)
+            .await
+            .is_ok()
+        {
+            imported_count += 1;
         }
+    }
 
-        let result = sqlx::query(
-            r#"
-            INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(&uuid)
-        .bind(&conn.connection_type)
-        .bind(&final_name)
-        .bind(&conn.host)
-        .bind(conn.port)
-        .bind(&conn.database)
-        .bind(&conn.username)
-        .bind(&conn.password)
-        .bind(ssl)
-        .bind(&conn.db_type)
-        .bind(&conn.file_path)
-        .bind(ssh_enabled)
-        .bind(&conn.ssh_host)
-        .bind(conn.ssh_port)
-        .bind(&conn.ssh_user)
-        .bind(&conn.ssh_password)
-        .bind(&conn.ssh_key_path)
-        .bind(ssh_use_key)
-        .execute(pool.inner())
-        .await;
+    Ok(imported_count)
+}
 
-        if result.is_ok() {
-            imported_count += 1;
+/// Pick a name not in `existing_names`, appending " (1)", " (2)", ... on conflict
+pub(crate) fn unique_connection_name(name: &str, existing_names: &[String]) -> String {
+    if !existing_names.iter().any(|n| n == name) {
+        return name.to_string();
+    }
+    let mut counter = 1;
+    loop {
+        let candidate = format!("{} ({})", name, counter);
+        if !existing_names.contains(&candidate) {
+            return candidate;
         }
+        counter += 1;
     }
+}
 
-    Ok(imported_count)
+/// Insert an exported connection under `name` with a fresh UUID, returning the UUID
+pub(crate) async fn insert_exported_connection(
+    pool: &SqlitePool,
+    conn: &ExportedConnection,
+    name: &str,
+) -> Result<String, String> {
+    let uuid = Uuid::new_v4().to_string();
+    let ssl = if conn.ssl { 1 } else { 0 };
+    let ssh_enabled = if conn.ssh_enabled { 1 } else { 0 };
+    let ssh_use_key = if conn.ssh_use_key { 1 } else { 0 };
+    let ssh_use_agent = if conn.ssh_use_agent { 1 } else { 0 };
+    let read_only = if conn.read_only { 1 } else { 0 };
+
+    sqlx::query(
+        r#"
+        INSERT INTO connections (uuid, type, name, host, port, database, username, password, ssl, db_type, file_path, ssh_enabled, ssh_host, ssh_port, ssh_user, ssh_password, ssh_key_path, ssh_use_key, ssh_use_agent, read_only, query_timeout_secs, session_setup_sql, default_page_size, max_concurrent_queries, socket_path)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&uuid)
+    .bind(&conn.connection_type)
+    .bind(name)
+    .bind(&conn.host)
+    .bind(conn.port)
+    .bind(&conn.database)
+    .bind(&conn.username)
+    .bind(&conn.password)
+    .bind(ssl)
+    .bind(&conn.db_type)
+    .bind(&conn.file_path)
+    .bind(ssh_enabled)
+    .bind(&conn.ssh_host)
+    .bind(conn.ssh_port)
+    .bind(&conn.ssh_user)
+    .bind(&conn.ssh_password)
+    .bind(&conn.ssh_key_path)
+    .bind(ssh_use_key)
+    .bind(ssh_use_agent)
+    .bind(read_only)
+    .bind(conn.query_timeout_secs)
+    .bind(&conn.session_setup_sql)
+    .bind(conn.default_page_size)
+    .bind(conn.max_concurrent_queries)
+    .bind(&conn.socket_path)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(uuid)
 }
@@ -1,23 +1,46 @@
 //! Unified database commands that dispatch to the correct driver based on db_type.
 //!
 //! This module provides a single set of Tauri commands that work with PostgreSQL,
-//! SQLite, Redis, and ClickHouse databases by dispatching to the appropriate driver.
+//! SQLite, Redis, ClickHouse, and OpenSearch databases by dispatching to the appropriate driver.
 
+use crate::commands::connections::{spawn_record_connection_opened, spawn_record_query_executed};
+use crate::commands::editor;
+use crate::commands::settings::{load_connection_variables, spawn_store_last_query};
+use crate::commands::slow_queries::spawn_record_if_slow;
+#[cfg(feature = "cassandra")]
+use crate::database::cassandra::{CassandraConfig, CassandraDriver};
 use crate::database::clickhouse::ClickhouseDriver;
-use crate::database::postgres::PostgresDriver;
-use crate::database::redis::{RedisDriver, RedisKeyDetails, RedisKeyListResponse};
+use crate::database::display::TypeOverrides;
+use crate::database::opensearch::OpenSearchDriver;
+#[cfg(feature = "oracle")]
+use crate::database::oracle::{OracleConfig, OracleDriver, OracleService};
+use crate::database::postgres::{PostgresDriver, LARGE_OBJECT_PREVIEW_LIMIT};
+use crate::database::query_stream::QueryStreamManager;
+use crate::database::redis::{
+    decode_base64_value, RedisDriver, RedisKeyDetails, RedisKeyListResponse,
+    RedisReplicationStatus, RedisScanPage, RedisStringValue,
+};
+use crate::database::script::{
+    dollar_quote_tag_len, run_statements, split_statements, StatementResult,
+};
 use crate::database::sqlite::SqliteDriver;
+use crate::database::table_watch::{start_table_watch, TableChange, TableWatchManager};
+use crate::database::undo::{driver_identity, RowEdit, UndoEntry, UndoHistory};
 use crate::database::{
-    ClickhouseConfig, ClickhouseProtocol, DatabaseDriver, PostgresConfig, RedisConfig, SqliteConfig,
+    query_cache, timeouts, ClickhouseConfig, ClickhouseProtocol, DatabaseDriver, DatabaseType,
+    OpenSearchConfig, PostgresConfig, RedisConfig, SqliteConfig,
 };
 use crate::db::models::{
-    Connection, QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure,
-    TestConnectionResult,
+    BlockingQuery, ClusterNode, ColumnProfile, Connection, DatabaseInfo, DeepTestConnectionResult,
+    DriverCapabilities, IndexSuggestion, LargeObject, QueryResult, ReferencingRows, RoutineInfo,
+    SchemaOverview, SequenceValue, ServerOverview, TableDataResponse, TableInfo, TablePartitions,
+    TablePermissions, TableStructure, TableWithStructure, TestConnectionResult,
 };
 use crate::ssh_tunnel::SshTunnel;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
-use tauri::{AppHandle, Emitter, State};
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, Emitter, Runtime, State};
 
 #[derive(Clone, Serialize)]
 pub struct RedisScanProgressPayload {
@@ -28,6 +51,26 @@ pub struct RedisScanProgressPayload {
     pub keys: Vec<String>,
 }
 
+#[derive(Clone, Serialize)]
+pub struct SchemaTablePayload {
+    pub overview_id: String,
+    pub index: usize,
+    pub total: usize,
+    pub table: TableWithStructure,
+}
+
+#[derive(Clone, Serialize)]
+pub struct SchemaCompletePayload {
+    pub overview_id: String,
+    pub table_count: usize,
+}
+
+/// Progress reported while a schema overview is streamed
+pub enum SchemaStreamEvent {
+    Table(SchemaTablePayload),
+    Complete(SchemaCompletePayload),
+}
+
 /// Creates the appropriate database driver based on the db_type, with optional SSH tunnel
 async fn create_driver_with_ssh(
     db_type: &str,
@@ -38,6 +81,7 @@ async fn create_driver_with_ssh(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
     ssh_enabled: Option<bool>,
     ssh_host: Option<String>,
     ssh_port: Option<i64>,
@@ -45,6 +89,8 @@ async fn create_driver_with_ssh(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    ssh_use_agent: Option<bool>,
+    session_setup_sql: Option<String>,
 ) -> Result<(Box<dyn DatabaseDriver>, Option<SshTunnel>), String> {
     let (effective_host, effective_port, tunnel) = if ssh_enabled.unwrap_or(false) {
         let ssh_host_val = ssh_host.unwrap_or_default();
@@ -53,6 +99,7 @@ async fn create_driver_with_ssh(
         let ssh_password_val = ssh_password.unwrap_or_default();
         let ssh_key_path_val = ssh_key_path.unwrap_or_default();
         let use_key = ssh_use_key.unwrap_or(false);
+        let use_agent = ssh_use_agent.unwrap_or(false);
 
         let key_path = if use_key && !ssh_key_path_val.is_empty() {
             Some(ssh_key_path_val.as_str())
@@ -68,15 +115,17 @@ async fn create_driver_with_ssh(
         let remote_host = host.clone().unwrap_or_default();
         let remote_port = port.unwrap_or(5432) as u16;
 
-        // Use a 20 second timeout for SSH tunnel creation (can take longer due to network/auth)
+        // SSH tunnel creation can take longer due to network/auth
+        let ssh_timeout = timeouts::ssh_tunnel_timeout();
         let tunnel = match tokio::time::timeout(
-            std::time::Duration::from_secs(20),
+            ssh_timeout,
             SshTunnel::new(
                 &ssh_host_val,
                 ssh_port_val,
                 &ssh_user_val,
                 password_opt,
                 key_path,
+                use_agent,
                 &remote_host,
                 remote_port,
             ),
@@ -85,7 +134,12 @@ async fn create_driver_with_ssh(
         {
             Ok(Ok(tunnel)) => tunnel,
             Ok(Err(e)) => return Err(format!("SSH tunnel failed: {}", e)),
-            Err(_) => return Err("SSH tunnel connection timed out after 20 seconds".to_string()),
+            Err(_) => {
+                return Err(format!(
+                    "SSH tunnel connection timed out after {} seconds",
+                    ssh_timeout.as_secs()
+                ))
+            }
         };
 
         (
@@ -106,12 +160,17 @@ async fn create_driver_with_ssh(
                 username: username.unwrap_or_default(),
                 password: password.unwrap_or_default(),
                 ssl: ssl.unwrap_or(false),
+                socket_path,
+                session_setup_sql,
             };
             Box::new(PostgresDriver::new(config))
         }
         "sqlite" | "sqlite3" => {
             let path = file_path.ok_or("File path is required for SQLite connections")?;
-            let config = SqliteConfig { file_path: path };
+            let config = SqliteConfig {
+                file_path: path,
+                session_setup_sql,
+            };
             Box::new(SqliteDriver::new(config))
         }
         "redis" => {
@@ -133,17 +192,110 @@ async fn create_driver_with_ssh(
                 password: password.unwrap_or_default(),
                 protocol: ClickhouseProtocol::Http,
                 ssl: ssl.unwrap_or(false),
+                session_setup_sql,
             };
             Box::new(ClickhouseDriver::new(config))
         }
+        "opensearch" | "elasticsearch" => {
+            let config = OpenSearchConfig {
+                host: effective_host,
+                port: effective_port,
+                username,
+                password,
+                ssl: ssl.unwrap_or(false),
+            };
+            Box::new(OpenSearchDriver::new(config))
+        }
+        #[cfg(feature = "cassandra")]
+        "cassandra" | "scylla" => {
+            let config = CassandraConfig {
+                host: effective_host,
+                port: effective_port,
+                keyspace: database.unwrap_or_default(),
+                username,
+                password,
+                ssl: ssl.unwrap_or(false),
+                session_setup_sql,
+            };
+            Box::new(CassandraDriver::new(config))
+        }
+        #[cfg(feature = "oracle")]
+        "oracle" => {
+            let config = OracleConfig {
+                host: effective_host,
+                port: effective_port,
+                service: OracleService::parse(&database.unwrap_or_default()),
+                username: username.unwrap_or_default(),
+                password: password.unwrap_or_default(),
+                ssl: ssl.unwrap_or(false),
+                session_setup_sql,
+            };
+            Box::new(OracleDriver::new(config))
+        }
         _ => return Err(format!("Unsupported database type: {}", db_type)),
     };
 
     Ok((driver, tunnel))
 }
 
+/// Creates the driver for a saved connection, opening its SSH tunnel if configured, and
+/// stamps the connection as used
+pub(crate) async fn create_driver_for_connection(
+    sqlite_pool: &SqlitePool,
+    conn: &Connection,
+) -> Result<(Box<dyn DatabaseDriver>, Option<SshTunnel>), String> {
+    let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+    let created = create_driver_with_ssh(
+        &conn.db_type,
+        Some(conn.host.clone()),
+        Some(conn.port),
+        Some(conn.database.clone()),
+        Some(conn.username.clone()),
+        Some(conn.password.clone()),
+        Some(conn.ssl == 1),
+        conn.file_path.clone(),
+        conn.socket_path.clone(),
+        Some(conn.ssh_enabled == 1),
+        non_empty(&conn.ssh_host),
+        Some(conn.ssh_port),
+        non_empty(&conn.ssh_user),
+        non_empty(&conn.ssh_password),
+        non_empty(&conn.ssh_key_path),
+        Some(conn.ssh_use_key == 1),
+        Some(conn.ssh_use_agent == 1),
+        conn.session_setup_sql.clone(),
+    )
+    .await?;
+    spawn_record_connection_opened(sqlite_pool, &conn.uuid);
+    Ok(created)
+}
+
+/// Config for a Postgres connection given as command arguments, with the usual defaults
+/// for omitted fields
+fn postgres_config_from_params(
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> PostgresConfig {
+    PostgresConfig {
+        host: host.unwrap_or_default(),
+        port: port.unwrap_or(5432),
+        database: database.unwrap_or_default(),
+        username: username.unwrap_or_default(),
+        password: password.unwrap_or_default(),
+        ssl: ssl.unwrap_or(false),
+        socket_path,
+        session_setup_sql,
+    }
+}
+
 /// Simple driver creation without SSH support (for backwards compatibility)
-fn create_driver(
+pub(crate) fn create_driver(
     db_type: &str,
     host: Option<String>,
     port: Option<i64>,
@@ -152,22 +304,29 @@ fn create_driver(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
 ) -> Result<Box<dyn DatabaseDriver>, String> {
     match db_type {
         "postgres" | "postgresql" => {
-            let config = PostgresConfig {
-                host: host.unwrap_or_default(),
-                port: port.unwrap_or(5432),
-                database: database.unwrap_or_default(),
-                username: username.unwrap_or_default(),
-                password: password.unwrap_or_default(),
-                ssl: ssl.unwrap_or(false),
-            };
+            let config = postgres_config_from_params(
+                host,
+                port,
+                database,
+                username,
+                password,
+                ssl,
+                socket_path,
+                session_setup_sql,
+            );
             Ok(Box::new(PostgresDriver::new(config)))
         }
         "sqlite" | "sqlite3" => {
             let path = file_path.ok_or("File path is required for SQLite connections")?;
-            let config = SqliteConfig { file_path: path };
+            let config = SqliteConfig {
+                file_path: path,
+                session_setup_sql,
+            };
             Ok(Box::new(SqliteDriver::new(config)))
         }
         "redis" => {
@@ -189,9 +348,46 @@ fn create_driver(
                 password: password.unwrap_or_default(),
                 protocol: ClickhouseProtocol::Http,
                 ssl: ssl.unwrap_or(false),
+                session_setup_sql,
             };
             Ok(Box::new(ClickhouseDriver::new(config)))
         }
+        "opensearch" | "elasticsearch" => {
+            let config = OpenSearchConfig {
+                host: host.unwrap_or_else(|| "localhost".to_string()),
+                port: port.unwrap_or(9200),
+                username,
+                password,
+                ssl: ssl.unwrap_or(false),
+            };
+            Ok(Box::new(OpenSearchDriver::new(config)))
+        }
+        #[cfg(feature = "cassandra")]
+        "cassandra" | "scylla" => {
+            let config = CassandraConfig {
+                host: host.unwrap_or_else(|| "localhost".to_string()),
+                port: port.unwrap_or(9042),
+                keyspace: database.unwrap_or_default(),
+                username,
+                password,
+                ssl: ssl.unwrap_or(false),
+                session_setup_sql,
+            };
+            Ok(Box::new(CassandraDriver::new(config)))
+        }
+        #[cfg(feature = "oracle")]
+        "oracle" => {
+            let config = OracleConfig {
+                host: host.unwrap_or_else(|| "localhost".to_string()),
+                port: port.unwrap_or(1521),
+                service: OracleService::parse(&database.unwrap_or_default()),
+                username: username.unwrap_or_default(),
+                password: password.unwrap_or_default(),
+                ssl: ssl.unwrap_or(false),
+                session_setup_sql,
+            };
+            Ok(Box::new(OracleDriver::new(config)))
+        }
         _ => Err(format!("Unsupported database type: {}", db_type)),
     }
 }
@@ -206,13 +402,125 @@ pub async fn unified_test_connection(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
 ) -> Result<TestConnectionResult, String> {
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
     driver.test_connection().await
 }
 
+/// Check connectivity, then whether the user can list tables and read a row from the
+/// first one, so missing privileges show up before the connection is saved
+#[tauri::command]
+pub async fn test_connection_deep(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> Result<DeepTestConnectionResult, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    Ok(test_connection_deep_with_driver(driver.as_ref()).await)
+}
+
+/// Run the `test_connection_deep` probes against an existing driver
+pub async fn test_connection_deep_with_driver(
+    driver: &dyn DatabaseDriver,
+) -> DeepTestConnectionResult {
+    let mut result = DeepTestConnectionResult {
+        can_connect: false,
+        can_list: false,
+        can_read: false,
+        probed_table: None,
+        message: String::new(),
+    };
+
+    match driver.test_connection().await {
+        Ok(test) if test.success => result.can_connect = true,
+        Ok(test) => {
+            result.message = test.message;
+            return result;
+        }
+        Err(e) => {
+            result.message = format!("Connection failed: {}", e);
+            return result;
+        }
+    }
+
+    let tables = match driver.list_tables().await {
+        Ok(tables) => tables,
+        Err(e) => {
+            result.message = format!("Connected, but listing tables failed: {}", e);
+            return result;
+        }
+    };
+    result.can_list = true;
+
+    let Some(table) = tables.first() else {
+        result.message =
+            "Connected and listed tables, but there are no tables to read from".to_string();
+        return result;
+    };
+    result.probed_table = Some(format!("{}.{}", table.schema, table.name));
+    match driver
+        .get_table_data(&table.schema, &table.name, 1, 1, None, None, None)
+        .await
+    {
+        Ok(_) => {
+            result.can_read = true;
+            result.message = format!(
+                "Connected, listed {} tables and read from {}.{}",
+                tables.len(),
+                table.schema,
+                table.name
+            );
+        }
+        Err(e) => {
+            result.message = format!(
+                "Connected and listed tables, but reading {}.{} failed: {}",
+                table.schema, table.name, e
+            );
+        }
+    }
+    result
+}
+
+/// Which optional features the driver for `db_type` supports, so the UI can disable
+/// actions such as row editing or transactions up front
+#[tauri::command]
+pub fn get_driver_capabilities(db_type: String) -> Result<DriverCapabilities, String> {
+    DatabaseType::from_str(&db_type)
+        .map(|db| db.capabilities())
+        .ok_or_else(|| format!("Unsupported database type: {}", db_type))
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn unified_list_tables(
     db_type: String,
@@ -223,6 +531,8 @@ pub async fn unified_list_tables(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     ssh_enabled: Option<bool>,
     ssh_host: Option<String>,
     ssh_port: Option<i64>,
@@ -230,6 +540,10 @@ pub async fn unified_list_tables(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    ssh_use_agent: Option<bool>,
+    name_filter: Option<String>,
+    page: Option<i64>,
+    limit: Option<i64>,
 ) -> Result<Vec<TableInfo>, String> {
     let (driver, _tunnel) = create_driver_with_ssh(
         &db_type,
@@ -240,6 +554,7 @@ pub async fn unified_list_tables(
         password,
         ssl,
         file_path,
+        socket_path,
         ssh_enabled,
         ssh_host,
         ssh_port,
@@ -247,9 +562,13 @@ pub async fn unified_list_tables(
         ssh_password,
         ssh_key_path,
         ssh_use_key,
+        ssh_use_agent,
+        session_setup_sql,
     )
     .await?;
-    driver.list_tables().await
+    driver
+        .list_tables_paged(name_filter.as_deref(), page, limit)
+        .await
 }
 
 #[tauri::command]
@@ -262,6 +581,8 @@ pub async fn unified_get_table_data(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     schema: String,
     table: String,
     page: i64,
@@ -269,11 +590,22 @@ pub async fn unified_get_table_data(
     filter: Option<String>,
     sort_column: Option<String>,
     sort_direction: Option<String>,
+    type_overrides: Option<HashMap<String, String>>,
 ) -> Result<TableDataResponse, String> {
+    let type_overrides = TypeOverrides::parse(&type_overrides.unwrap_or_default())?;
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
-    driver
+    let mut response = driver
         .get_table_data(
             &schema,
             &table,
@@ -283,7 +615,9 @@ pub async fn unified_get_table_data(
             sort_column,
             sort_direction,
         )
-        .await
+        .await?;
+    type_overrides.apply(&mut response.data);
+    Ok(response)
 }
 
 #[tauri::command]
@@ -296,17 +630,46 @@ pub async fn unified_get_table_structure(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     schema: String,
     table: String,
 ) -> Result<TableStructure, String> {
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
     driver.get_table_structure(&schema, &table).await
 }
 
+/// Optional behavior of [`unified_execute_query`]; every field may be omitted
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryOptions {
+    /// Fill `{{name}}` placeholders from the variables saved for the connection
+    pub use_variables: bool,
+    /// Serve a repeated SELECT from the result cache for this many seconds
+    pub cache_ttl_secs: Option<u64>,
+    /// Timeout for this call, overriding the saved connection's default
+    pub timeout_secs: Option<i64>,
+    /// Values bound to the query's placeholders
+    pub params: Vec<serde_json::Value>,
+    /// Display types for result columns, keyed by column name
+    pub type_overrides: HashMap<String, String>,
+}
+
 #[tauri::command]
-pub async fn unified_execute_query(
+pub async fn unified_execute_query<R: Runtime>(
+    app: AppHandle<R>,
+    sqlite_pool: State<'_, SqlitePool>,
     db_type: String,
     host: Option<String>,
     port: Option<i64>,
@@ -315,21 +678,105 @@ pub async fn unified_execute_query(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     query: String,
+    connection_uuid: Option<String>,
+    options: Option<QueryOptions>,
 ) -> Result<QueryResult, String> {
+    let QueryOptions {
+        use_variables,
+        cache_ttl_secs,
+        timeout_secs,
+        params,
+        type_overrides,
+    } = options.unwrap_or_default();
+    let type_overrides = TypeOverrides::parse(&type_overrides)?;
+    if let Some(uuid) = &connection_uuid {
+        spawn_store_last_query(sqlite_pool.inner(), uuid, &query);
+    }
+    let (query, params) = if use_variables {
+        let uuid = connection_uuid
+            .as_deref()
+            .ok_or("Query variables require a saved connection")?;
+        let variables = load_connection_variables(sqlite_pool.inner(), uuid).await?;
+        let (query, variable_params) = bind_query_variables(&query, &variables, &db_type)?;
+        if !params.is_empty() && !variable_params.is_empty() {
+            return Err("Query variables cannot be combined with bind parameters".to_string());
+        }
+        (query, [params, variable_params].concat())
+    } else {
+        (query, params)
+    };
+
+    let policy = ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref()).await?;
+    policy.check_query(&query, &db_type)?;
+
+    let cacheable = query_cache::is_cacheable(&query, &db_type);
+    let cache_ttl = cache_ttl_secs.filter(|ttl| *ttl > 0 && params.is_empty() && cacheable);
+    let cache_target = query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    );
+    let cache_login = query_cache::login(
+        username.as_deref(),
+        password.as_deref(),
+        ssl,
+        session_setup_sql.as_deref(),
+    );
+    if cache_ttl.is_some() {
+        if let Some(mut cached) = query_cache::get(&cache_target, &cache_login, &query).await {
+            type_overrides.apply(&mut cached.data);
+            return Ok(cached);
+        }
+    }
+    let _cache_guard = (!cacheable).then(|| query_cache::write_guard(cache_target.clone()));
+
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
-    driver.execute_query(&query).await
-}
+    if let Some(uuid) = &connection_uuid {
+        spawn_record_connection_opened(sqlite_pool.inner(), uuid);
+        spawn_record_query_executed(sqlite_pool.inner(), uuid);
+    }
+    let limit = timeouts::resolve_query_timeout(timeout_secs, policy.query_timeout_secs);
+    let execution = if params.is_empty() {
+        driver.execute_query(&query)
+    } else {
+        driver.execute_query_with_params(&query, &params)
+    };
+    let mut result = timeouts::with_timeout(limit, execution).await?;
+    if result.error.is_none() {
+        result.affected_tables = editor::affected_tables(&query, &db_type);
+    }
 
-// ============================================================================
-// Row editing commands (UPDATE/DELETE)
-// ============================================================================
+    if let Some(ttl) = cache_ttl.filter(|_| result.error.is_none()) {
+        query_cache::insert(&cache_target, &cache_login, &query, result.clone(), ttl).await;
+    }
+    if let Some(uuid) = &connection_uuid {
+        spawn_record_if_slow(&app, sqlite_pool.inner(), uuid, &query, &result);
+    }
+    type_overrides.apply(&mut result.data);
+    Ok(result)
+}
 
-/// Update a row in a table
+/// Run a script of `;`-separated statements one at a time, returning a result per
+/// statement. Execution stops after the first statement that fails.
 #[tauri::command]
-pub async fn update_table_row(
+pub async fn execute_script(
+    sqlite_pool: State<'_, SqlitePool>,
     db_type: String,
     host: Option<String>,
     port: Option<i64>,
@@ -338,67 +785,48 @@ pub async fn update_table_row(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
-    schema: String,
-    table: String,
-    primary_key_columns: Vec<String>,
-    primary_key_values: Vec<serde_json::Value>,
-    updates: serde_json::Map<String, serde_json::Value>,
-) -> Result<QueryResult, String> {
-    if primary_key_columns.is_empty() || primary_key_columns.len() != primary_key_values.len() {
-        return Err("Primary key columns and values must match".to_string());
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    script: String,
+    connection_uuid: Option<String>,
+) -> Result<Vec<StatementResult>, String> {
+    let statements = split_statements(&script);
+    if statements.is_empty() {
+        return Err("Script contains no statements".to_string());
     }
-
-    if updates.is_empty() {
-        return Err("No updates provided".to_string());
+    let policy = ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref()).await?;
+    for statement in &statements {
+        policy.check_query(statement, &db_type)?;
     }
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
 
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
-
-    // Build the UPDATE query
-    let table_ref = if db_type == "sqlite" || db_type == "sqlite3" {
-        format!("\"{}\"", escape_sql_identifier(&table))
-    } else {
-        format!(
-            "\"{}\".\"{}\"",
-            escape_sql_identifier(&schema),
-            escape_sql_identifier(&table)
-        )
-    };
-
-    // Build SET clause
-    let set_parts: Vec<String> = updates
-        .iter()
-        .map(|(col, val)| {
-            let formatted_value = format_sql_value(val);
-            format!("\"{}\" = {}", escape_sql_identifier(col), formatted_value)
-        })
-        .collect();
-    let set_clause = set_parts.join(", ");
-
-    // Build WHERE clause for primary key
-    let where_parts: Vec<String> = primary_key_columns
-        .iter()
-        .zip(primary_key_values.iter())
-        .map(|(col, val)| {
-            let formatted_value = format_sql_value(val);
-            format!("\"{}\" = {}", escape_sql_identifier(col), formatted_value)
-        })
-        .collect();
-    let where_clause = where_parts.join(" AND ");
-
-    let query = format!(
-        "UPDATE {} SET {} WHERE {}",
-        table_ref, set_clause, where_clause
-    );
-
-    driver.execute_query(&query).await
+    run_statements(driver.as_ref(), statements).await
 }
 
-/// Update a row in a table with raw SQL support
+/// Run a query that returns several result sets, such as a batch of statements or a
+/// Postgres function returning refcursors, with one result per set. Databases without
+/// multiple result sets return the single result of the query.
 #[tauri::command]
-pub async fn update_table_row_with_raw_sql(
+pub async fn execute_multi_result(
+    sqlite_pool: State<'_, SqlitePool>,
     db_type: String,
     host: Option<String>,
     port: Option<i64>,
@@ -407,22 +835,1682 @@ pub async fn update_table_row_with_raw_sql(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
-    schema: String,
-    table: String,
-    primary_key_columns: Vec<String>,
-    primary_key_values: Vec<serde_json::Value>,
-    updates: Vec<serde_json::Value>,
-) -> Result<QueryResult, String> {
-    if primary_key_columns.is_empty() || primary_key_columns.len() != primary_key_values.len() {
-        return Err("Primary key columns and values must match".to_string());
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    query: String,
+    connection_uuid: Option<String>,
+) -> Result<Vec<QueryResult>, String> {
+    let policy = ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref()).await?;
+    for statement in split_statements(&query) {
+        policy.check_query(&statement, &db_type)?;
     }
-
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.execute_multi_result(&query).await
+}
+
+/// Default number of rows per `query-rows` event
+const STREAM_BATCH_SIZE: usize = 500;
+
+#[derive(Clone, Serialize)]
+pub struct QueryRowsPayload {
+    pub stream_id: String,
+    pub rows: Vec<serde_json::Value>,
+}
+
+/// Outcome of a streamed query
+#[derive(Clone, Debug, Serialize)]
+pub struct QueryStreamSummary {
+    pub stream_id: String,
+    pub row_count: u64,
+    /// The stream was stopped by `cancel_query_stream` before all rows were fetched
+    pub cancelled: bool,
+}
+
+/// Run a query and emit its rows as `query-rows` events in batches. The fetch stops as soon
+/// as `cancel_query_stream` is called with the same `stream_id`.
+#[tauri::command]
+pub async fn stream_query(
+    sqlite_pool: State<'_, SqlitePool>,
+    app: AppHandle,
+    stream_manager: State<'_, QueryStreamManager>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    stream_id: String,
+    query: String,
+    batch_size: Option<i64>,
+    connection_uuid: Option<String>,
+) -> Result<QueryStreamSummary, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_query(&query, &db_type)?;
+    let _cache_guard = (!query_cache::is_cacheable(&query, &db_type)).then(|| {
+        query_cache::write_guard(query_cache::target(
+            &db_type,
+            host.as_deref(),
+            port,
+            database.as_deref(),
+            file_path.as_deref(),
+        ))
+    });
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    let batch_size = batch_size
+        .filter(|size| *size > 0)
+        .map_or(STREAM_BATCH_SIZE, |size| size as usize);
+
+    let cancel = stream_manager.start(&stream_id).await;
+    let event_stream_id = stream_id.clone();
+    let result = driver
+        .stream_query(&query, batch_size, &cancel, &mut |rows| {
+            let _ = app.emit(
+                "query-rows",
+                QueryRowsPayload {
+                    stream_id: event_stream_id.clone(),
+                    rows,
+                },
+            );
+        })
+        .await;
+    stream_manager.finish(&stream_id, &cancel).await;
+
+    Ok(QueryStreamSummary {
+        stream_id,
+        row_count: result?,
+        cancelled: cancel.is_cancelled(),
+    })
+}
+
+/// Stop a streamed query. Returns `false` if no stream with that id is running.
+#[tauri::command]
+pub async fn cancel_query_stream(
+    stream_manager: State<'_, QueryStreamManager>,
+    stream_id: String,
+) -> Result<bool, String> {
+    Ok(stream_manager.cancel(&stream_id).await)
+}
+
+/// Aggregate functions accepted by `column_aggregate`
+const COLUMN_AGGREGATES: &[&str] = &["SUM", "AVG", "MIN", "MAX", "COUNT"];
+
+/// Whether a column type holds numbers that SUM/AVG can operate on
+fn is_numeric_column_type(data_type: &str) -> bool {
+    let lower = data_type.to_lowercase();
+    if lower.contains("interval") || lower.contains("point") {
+        return false;
+    }
+    [
+        "int", "numeric", "decimal", "real", "float", "double", "money", "serial",
+    ]
+    .iter()
+    .any(|t| lower.contains(t))
+}
+
+/// Compute an aggregate over a table column server-side, honouring the browse view filter
+#[tauri::command]
+pub async fn column_aggregate(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    column: String,
+    agg: String,
+    filter: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let agg = agg.to_uppercase();
+    if !COLUMN_AGGREGATES.contains(&agg.as_str()) {
+        return Err(format!(
+            "Unsupported aggregate '{}'. Expected one of: {}",
+            agg,
+            COLUMN_AGGREGATES.join(", ")
+        ));
+    }
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let structure = driver.get_table_structure(&schema, &table).await?;
+    let column_info = structure
+        .columns
+        .iter()
+        .find(|c| c.name == column)
+        .ok_or_else(|| format!("Column '{}' not found in table '{}'", column, table))?;
+    if (agg == "SUM" || agg == "AVG") && !is_numeric_column_type(&column_info.data_type) {
+        return Err(format!(
+            "{} requires a numeric column, but '{}' is {}",
+            agg, column, column_info.data_type
+        ));
+    }
+
+    let (table_ref, column_ref) = match db_type.as_str() {
+        "postgres" | "postgresql" => (
+            format!(
+                "\"{}\".\"{}\"",
+                escape_sql_identifier(&schema),
+                escape_sql_identifier(&table)
+            ),
+            format!("\"{}\"", escape_sql_identifier(&column)),
+        ),
+        "sqlite" | "sqlite3" => (
+            format!("\"{}\"", escape_sql_identifier(&table)),
+            format!("\"{}\"", escape_sql_identifier(&column)),
+        ),
+        "clickhouse" => (
+            format!("`{}`", table.replace('`', "``")),
+            format!("`{}`", column.replace('`', "``")),
+        ),
+        _ => {
+            return Err(format!(
+                "Column aggregates are not supported for {}",
+                db_type
+            ))
+        }
+    };
+
+    let where_clause = filter
+        .filter(|f| !f.trim().is_empty())
+        .map(|f| {
+            // Same quote normalization as the browse view
+            let normalized = f
+                .replace(['\u{2018}', '\u{2019}'], "'")
+                .replace(['\u{201C}', '\u{201D}'], "\"")
+                .replace("\\'", "'");
+            format!(" WHERE {}", normalized)
+        })
+        .unwrap_or_default();
+
+    let query = format!(
+        "SELECT {}({}) AS value FROM {}{}",
+        agg, column_ref, table_ref, where_clause
+    );
+    let result = driver.execute_query(&query).await?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+
+    Ok(result
+        .data
+        .first()
+        .and_then(|row| row.get("value").cloned())
+        .unwrap_or(serde_json::Value::Null))
+}
+
+/// Rows counted by `profile_columns` for columns without catalog statistics
+const PROFILE_SAMPLE_ROWS: i64 = 10_000;
+
+/// NULL percentage and cardinality estimate of each column in a table.
+///
+/// Planner statistics are used where the database keeps them (Postgres `pg_stats`), so
+/// profiling doesn't scan the table. Other columns are counted over the first
+/// `PROFILE_SAMPLE_ROWS` rows, which makes their distinct count a lower bound.
+#[tauri::command]
+pub async fn profile_columns(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+) -> Result<Vec<ColumnProfile>, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let structure = driver.get_table_structure(&schema, &table).await?;
+    let statistics = driver.column_statistics(&schema, &table).await?;
+    let unprofiled: Vec<&str> = structure
+        .columns
+        .iter()
+        .map(|c| c.name.as_str())
+        .filter(|name| !statistics.iter().any(|s| s.column == *name))
+        .collect();
+
+    let sampled = if unprofiled.is_empty() {
+        Vec::new()
+    } else {
+        sample_column_profiles(driver.as_ref(), &db_type, &schema, &table, &unprofiled).await?
+    };
+
+    // Report columns in table order
+    Ok(structure
+        .columns
+        .iter()
+        .filter_map(|c| {
+            statistics
+                .iter()
+                .chain(&sampled)
+                .find(|p| p.column == c.name)
+                .cloned()
+        })
+        .collect())
+}
+
+/// Count NULLs and distinct values of `columns` over a sample of the table's rows
+async fn sample_column_profiles(
+    driver: &dyn DatabaseDriver,
+    db_type: &str,
+    schema: &str,
+    table: &str,
+    columns: &[&str],
+) -> Result<Vec<ColumnProfile>, String> {
+    let (table_ref, quote): (String, fn(&str) -> String) = match db_type {
+        "postgres" | "postgresql" | "sqlite" | "sqlite3" => {
+            (row_lookup_table_ref(db_type, schema, table), |c| {
+                format!("\"{}\"", escape_sql_identifier(c))
+            })
+        }
+        "clickhouse" => (format!("`{}`", table.replace('`', "``")), |c| {
+            format!("`{}`", c.replace('`', "``"))
+        }),
+        _ => return Err(format!("Column profiling is not supported for {}", db_type)),
+    };
+    // Postgres can't compare every type (json, point) for DISTINCT, so compare as text
+    let distinct_cast = if db_type.starts_with("postgres") {
+        "::text"
+    } else {
+        ""
+    };
+
+    let counts: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            format!(
+                "COUNT({0}) AS n{1}, COUNT(DISTINCT {0}{2}) AS d{1}",
+                quote(c),
+                i,
+                distinct_cast
+            )
+        })
+        .collect();
+    let query = format!(
+        "SELECT COUNT(*) AS total, {} FROM (SELECT * FROM {} LIMIT {}) AS sample",
+        counts.join(", "),
+        table_ref,
+        PROFILE_SAMPLE_ROWS
+    );
+
+    let result = driver.execute_query(&query).await?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+    let row = result.data.first().cloned().unwrap_or_default();
+    // ClickHouse returns 64-bit counts as strings
+    let count = |key: &str| -> i64 {
+        match row.get(key) {
+            Some(serde_json::Value::Number(n)) => n.as_i64().unwrap_or(0),
+            Some(serde_json::Value::String(s)) => s.parse().unwrap_or(0),
+            _ => 0,
+        }
+    };
+
+    let total = count("total");
+    Ok(columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let non_null = count(&format!("n{}", i));
+            ColumnProfile {
+                column: c.to_string(),
+                null_fraction: if total > 0 {
+                    (total - non_null) as f64 / total as f64
+                } else {
+                    0.0
+                },
+                distinct_estimate: count(&format!("d{}", i)),
+                source: "sample".to_string(),
+            }
+        })
+        .collect())
+}
+
+/// Fetch the row(s) a foreign key value points at, for jumping to a parent record
+#[tauri::command]
+pub async fn follow_foreign_key(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    fk_column: String,
+    value: serde_json::Value,
+) -> Result<QueryResult, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let structure = driver.get_table_structure(&schema, &table).await?;
+    let foreign_key = structure
+        .foreign_keys
+        .iter()
+        .find(|fk| fk.column == fk_column)
+        .ok_or_else(|| {
+            format!(
+                "Column '{}' of table '{}' has no foreign key",
+                fk_column, table
+            )
+        })?;
+    if foreign_key.references_table.is_empty() {
+        return Err(format!(
+            "Foreign key on '{}' does not name a referenced table",
+            fk_column
+        ));
+    }
+    let target_table = foreign_key.references_table.clone();
+
+    // SQLite leaves the column empty when the key references the parent's primary key
+    let target_column = if foreign_key.references_column.is_empty() {
+        let target = driver.get_table_structure(&schema, &target_table).await?;
+        let primary_keys: Vec<String> = target
+            .columns
+            .into_iter()
+            .filter(|c| c.primary_key)
+            .map(|c| c.name)
+            .collect();
+        match primary_keys.as_slice() {
+            [column] => column.clone(),
+            _ => {
+                return Err(format!(
+                    "Cannot determine the column referenced by '{}' in table '{}'",
+                    fk_column, target_table
+                ))
+            }
+        }
+    } else {
+        foreign_key.references_column.clone()
+    };
+
+    let query = format!(
+        "SELECT * FROM {} WHERE \"{}\" = {}",
+        row_lookup_table_ref(&db_type, &schema, &target_table),
+        escape_sql_identifier(&target_column),
+        format_sql_value(&value)
+    );
+
+    let result = driver.execute_query(&query).await?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+    Ok(result)
+}
+
+/// Reload a single row by its primary key, e.g. to show values set by triggers or
+/// defaults after an edit. Fails unless exactly one row matches.
+#[tauri::command]
+pub async fn get_row_by_primary_key(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    primary_key_columns: Vec<String>,
+    primary_key_values: Vec<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    if primary_key_columns.is_empty() {
+        return Err("No primary key columns provided".to_string());
+    }
+    if primary_key_columns.len() != primary_key_values.len() {
+        return Err(format!(
+            "Expected {} primary key values, got {}",
+            primary_key_columns.len(),
+            primary_key_values.len()
+        ));
+    }
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    // Postgres and SQLite take bind parameters; other drivers get escaped literals
+    let placeholder = |index: usize| bind_placeholder(&db_type, index);
+    let binds = placeholder(0).is_some();
+    let conditions: Vec<String> = primary_key_columns
+        .iter()
+        .zip(&primary_key_values)
+        .enumerate()
+        .map(|(index, (column, value))| {
+            format!(
+                "\"{}\" = {}",
+                escape_sql_identifier(column),
+                placeholder(index).unwrap_or_else(|| format_sql_value(value))
+            )
+        })
+        .collect();
+
+    // Two rows are enough to tell that the key is not unique
+    let query = format!(
+        "SELECT * FROM {} WHERE {} LIMIT 2",
+        row_lookup_table_ref(&db_type, &schema, &table),
+        conditions.join(" AND ")
+    );
+    let result = if binds {
+        driver
+            .execute_query_with_params(&query, &primary_key_values)
+            .await?
+    } else {
+        driver.execute_query(&query).await?
+    };
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+
+    let mut rows = result.data.into_iter();
+    match (rows.next(), rows.next()) {
+        (Some(row), None) => Ok(row),
+        (None, _) => Err(format!("No row in '{}' matches the primary key", table)),
+        (Some(_), Some(_)) => Err(format!(
+            "More than one row in '{}' matches the primary key",
+            table
+        )),
+    }
+}
+
+/// Find the rows in other tables whose foreign keys reference a record, grouped by table
+#[tauri::command]
+pub async fn find_referencing_rows(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    pk_column: String,
+    pk_value: serde_json::Value,
+    limit: Option<i64>,
+) -> Result<Vec<ReferencingRows>, String> {
+    let limit = limit.unwrap_or(100).max(1);
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let overview = driver.get_schema_overview().await?;
+    // A foreign key without a referenced column (SQLite) targets the primary key
+    let is_primary_key = overview
+        .tables
+        .iter()
+        .find(|t| t.schema == schema && t.name == table)
+        .map(|t| {
+            t.columns
+                .iter()
+                .any(|c| c.name == pk_column && c.primary_key)
+        })
+        .unwrap_or(false);
+
+    // Postgres and SQLite take bind parameters; other drivers get escaped literals
+    let placeholder = bind_placeholder(&db_type, 0);
+    let mut groups = Vec::new();
+    for referencing in &overview.tables {
+        for fk in &referencing.foreign_keys {
+            let references_column = if fk.references_column.is_empty() && is_primary_key {
+                pk_column.as_str()
+            } else {
+                fk.references_column.as_str()
+            };
+            if fk.references_schema != schema
+                || fk.references_table != table
+                || references_column != pk_column
+            {
+                continue;
+            }
+
+            let query = format!(
+                "SELECT * FROM {} WHERE \"{}\" = {} LIMIT {}",
+                row_lookup_table_ref(&db_type, &referencing.schema, &referencing.name),
+                escape_sql_identifier(&fk.column),
+                placeholder
+                    .clone()
+                    .unwrap_or_else(|| format_sql_value(&pk_value)),
+                limit
+            );
+            let result = if placeholder.is_some() {
+                driver
+                    .execute_query_with_params(&query, std::slice::from_ref(&pk_value))
+                    .await?
+            } else {
+                driver.execute_query(&query).await?
+            };
+            if let Some(error) = result.error {
+                return Err(error);
+            }
+            if result.data.is_empty() {
+                continue;
+            }
+            groups.push(ReferencingRows {
+                schema: referencing.schema.clone(),
+                table: referencing.name.clone(),
+                column: fk.column.clone(),
+                row_count: result.row_count,
+                data: result.data,
+            });
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Placeholder for the bind parameter at `index` (zero-based), or `None` for drivers
+/// that take escaped literals instead
+pub(crate) fn bind_placeholder(db_type: &str, index: usize) -> Option<String> {
+    match db_type {
+        "postgres" | "postgresql" => Some(format!("${}", index + 1)),
+        "sqlite" | "sqlite3" => Some("?".to_string()),
+        _ => None,
+    }
+}
+
+/// Table reference for the row lookup queries; SQLite tables are not schema-qualified
+pub(crate) fn row_lookup_table_ref(db_type: &str, schema: &str, table: &str) -> String {
+    if db_type == "sqlite" || db_type == "sqlite3" {
+        format!("\"{}\"", escape_sql_identifier(table))
+    } else {
+        format!(
+            "\"{}\".\"{}\"",
+            escape_sql_identifier(schema),
+            escape_sql_identifier(table)
+        )
+    }
+}
+
+/// Largest sample `sample_table` returns
+const MAX_SAMPLE_ROWS: i64 = 10_000;
+
+/// Up to `n` random rows of a table, for a quick look at its data
+#[tauri::command]
+pub async fn sample_table(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    n: i64,
+) -> Result<QueryResult, String> {
+    if n <= 0 {
+        return Err("Sample size must be at least 1".to_string());
+    }
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let result = driver
+        .sample_table(&schema, &table, n.min(MAX_SAMPLE_ROWS))
+        .await?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+    Ok(result)
+}
+
+/// List Postgres sessions blocked on locks together with the sessions blocking them
+#[tauri::command]
+pub async fn list_blocking_queries(
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> Result<Vec<BlockingQuery>, String> {
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver.blocking_queries().await
+}
+
+/// Run `ANALYZE` on a Postgres table or the whole database
+#[tauri::command]
+pub async fn postgres_analyze(
+    sqlite_pool: State<'_, SqlitePool>,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    table: Option<String>,
+    connection_uuid: Option<String>,
+) -> Result<String, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver.analyze(table.as_deref()).await
+}
+
+/// Run `VACUUM` (or `VACUUM FULL`) on a Postgres table or the whole database
+#[tauri::command]
+pub async fn postgres_vacuum(
+    sqlite_pool: State<'_, SqlitePool>,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    table: Option<String>,
+    full: Option<bool>,
+    connection_uuid: Option<String>,
+) -> Result<String, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver.vacuum(table.as_deref(), full.unwrap_or(false)).await
+}
+
+/// Suggest `CREATE INDEX` statements for the filtered sequential scans in a Postgres
+/// query's plan. The query is planned with `EXPLAIN` but not run.
+#[tauri::command]
+pub async fn suggest_indexes(
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    query: String,
+) -> Result<Vec<IndexSuggestion>, String> {
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver.suggest_indexes(&query).await
+}
+
+/// Grants on a Postgres table and whether row-level security is enabled, with its
+/// policies
+#[tauri::command]
+pub async fn get_table_permissions(
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+) -> Result<TablePermissions, String> {
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver.table_permissions(&schema, &table).await
+}
+
+/// Size and a preview of a Postgres large object. `preview_bytes` defaults to 64 KiB.
+#[tauri::command]
+pub async fn read_large_object(
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    oid: i64,
+    preview_bytes: Option<i64>,
+) -> Result<LargeObject, String> {
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver
+        .read_large_object(oid, preview_bytes.unwrap_or(LARGE_OBJECT_PREVIEW_LIMIT))
+        .await
+}
+
+/// Replace a Postgres large object's contents with base64-encoded `data`, creating a
+/// new object when `oid` is omitted. Returns the object as `read_large_object` would.
+#[tauri::command]
+pub async fn write_large_object(
+    sqlite_pool: State<'_, SqlitePool>,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    oid: Option<i64>,
+    data_base64: String,
+    connection_uuid: Option<String>,
+) -> Result<LargeObject, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        "postgres",
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        None,
+    ));
+    let data = decode_base64_value(&data_base64)?;
+
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    let oid = driver.write_large_object(oid, &data).await?;
+    driver
+        .read_large_object(oid, LARGE_OBJECT_PREVIEW_LIMIT)
+        .await
+}
+
+/// Last value of the sequence behind a Postgres serial or identity column
+#[tauri::command]
+pub async fn get_sequence_value(
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    column: String,
+) -> Result<SequenceValue, String> {
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver.sequence_value(&schema, &table, &column).await
+}
+
+/// Reset the sequence behind a Postgres serial or identity column. By default `value`
+/// counts as used, so the next insert gets `value + 1`.
+#[tauri::command]
+pub async fn set_sequence_value(
+    sqlite_pool: State<'_, SqlitePool>,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    column: String,
+    value: i64,
+    is_called: Option<bool>,
+    connection_uuid: Option<String>,
+) -> Result<SequenceValue, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        "postgres",
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        None,
+    ));
+
+    let driver = PostgresDriver::new(postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    ));
+    driver
+        .set_sequence_value(&schema, &table, &column, value, is_called.unwrap_or(true))
+        .await
+}
+
+#[derive(Clone, Serialize)]
+pub struct TableChangePayload {
+    pub watch_id: String,
+    #[serde(flatten)]
+    pub change: TableChange,
+}
+
+fn table_watch_id(config: &PostgresConfig, schema: &str, table: &str) -> String {
+    format!(
+        "{}:{}/{}/{}",
+        config.host,
+        config.port,
+        config.database,
+        PostgresDriver::watch_object_name(schema, table)
+    )
+}
+
+/// Watch a Postgres table for row changes, emitting a `table-change` event for each
+/// insert, update and delete. Returns the watch id.
+#[tauri::command]
+pub async fn watch_table_changes<R: Runtime>(
+    app: AppHandle<R>,
+    sqlite_pool: State<'_, SqlitePool>,
+    watch_manager: State<'_, TableWatchManager>,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    connection_uuid: Option<String>,
+) -> Result<String, String> {
+    // Installing or removing the trigger changes the schema
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let config = postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    );
+    let watch_id = table_watch_id(&config, &schema, &table);
+    let driver = PostgresDriver::new(config);
+
+    let event_watch_id = watch_id.clone();
+    let handle = start_table_watch(&driver, &schema, &table, move |change| {
+        let _ = app.emit(
+            "table-change",
+            TableChangePayload {
+                watch_id: event_watch_id.clone(),
+                change,
+            },
+        );
+    })
+    .await?;
+
+    watch_manager.insert(watch_id.clone(), handle).await;
+    Ok(watch_id)
+}
+
+/// Stop a table watch and drop its trigger and trigger function
+#[tauri::command]
+pub async fn stop_watch_table(
+    sqlite_pool: State<'_, SqlitePool>,
+    watch_manager: State<'_, TableWatchManager>,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    connection_uuid: Option<String>,
+) -> Result<(), String> {
+    // Dropping the trigger and its function changes the schema
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let config = postgres_config_from_params(
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        socket_path,
+        session_setup_sql,
+    );
+    watch_manager
+        .stop(&table_watch_id(&config, &schema, &table))
+        .await;
+
+    PostgresDriver::new(config)
+        .remove_table_watch(&schema, &table)
+        .await
+}
+
+/// Run `VACUUM` on a SQLite database file
+#[tauri::command]
+pub async fn sqlite_vacuum(
+    sqlite_pool: State<'_, SqlitePool>,
+    file_path: String,
+    connection_uuid: Option<String>,
+) -> Result<(), String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    SqliteDriver::new(SqliteConfig {
+        file_path,
+        session_setup_sql: None,
+    })
+    .vacuum()
+    .await
+}
+
+/// Run `PRAGMA integrity_check` on a SQLite database file
+#[tauri::command]
+pub async fn sqlite_integrity_check(file_path: String) -> Result<Vec<String>, String> {
+    SqliteDriver::new(SqliteConfig {
+        file_path,
+        session_setup_sql: None,
+    })
+    .integrity_check()
+    .await
+}
+
+/// Set or clear the comment on a table
+#[tauri::command]
+pub async fn set_table_comment(
+    sqlite_pool: State<'_, SqlitePool>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    comment: Option<String>,
+    connection_uuid: Option<String>,
+) -> Result<QueryResult, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
+    let comment = comment.filter(|c| !c.is_empty());
+
+    let query = match db_type.as_str() {
+        "postgres" | "postgresql" => format!(
+            "COMMENT ON TABLE \"{}\".\"{}\" IS {}",
+            escape_sql_identifier(&schema),
+            escape_sql_identifier(&table),
+            comment
+                .map(|c| format_sql_value(&serde_json::Value::String(c)))
+                .unwrap_or_else(|| "NULL".to_string())
+        ),
+        "clickhouse" => format!(
+            "ALTER TABLE `{}`.`{}` MODIFY COMMENT '{}'",
+            schema.replace('`', "``"),
+            table.replace('`', "``"),
+            comment
+                .unwrap_or_default()
+                .replace('\\', "\\\\")
+                .replace('\'', "\\'")
+        ),
+        "sqlite" | "sqlite3" => return Err("SQLite does not support table comments".to_string()),
+        _ => return Err(format!("Table comments are not supported for {}", db_type)),
+    };
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.execute_query(&query).await
+}
+
+/// Create `dst_table` in `src_schema` with the columns, keys and indexes of `src_table`
+/// but none of its rows
+#[tauri::command]
+pub async fn clone_table_structure(
+    sqlite_pool: State<'_, SqlitePool>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    src_schema: String,
+    src_table: String,
+    dst_table: String,
+    connection_uuid: Option<String>,
+) -> Result<(), String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+    if dst_table.trim().is_empty() {
+        return Err("Target table name is required".to_string());
+    }
+    if dst_table == src_table {
+        return Err("Target table must differ from the source table".to_string());
+    }
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let statements = match db_type.as_str() {
+        "postgres" | "postgresql" => vec![format!(
+            "CREATE TABLE \"{schema}\".\"{}\" (LIKE \"{schema}\".\"{}\" INCLUDING ALL)",
+            escape_sql_identifier(&dst_table),
+            escape_sql_identifier(&src_table),
+            schema = escape_sql_identifier(&src_schema),
+        )],
+        "clickhouse" => vec![format!(
+            "CREATE TABLE `{db}`.`{}` AS `{db}`.`{}`",
+            dst_table.replace('`', "``"),
+            src_table.replace('`', "``"),
+            db = src_schema.replace('`', "``"),
+        )],
+        "sqlite" | "sqlite3" => {
+            let structure = driver.get_table_structure(&src_schema, &src_table).await?;
+            sqlite_clone_statements(&src_table, &dst_table, &structure)?
+        }
+        _ => return Err(format!("Cloning tables is not supported for {}", db_type)),
+    };
+
+    for statement in statements {
+        let result = driver.execute_query(&statement).await?;
+        if let Some(error) = result.error {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// `CREATE TABLE` and `CREATE INDEX` statements that rebuild a SQLite table's structure
+/// under a new name. Index names have the source table name swapped for the new one.
+fn sqlite_clone_statements(
+    src_table: &str,
+    dst_table: &str,
+    structure: &TableStructure,
+) -> Result<Vec<String>, String> {
+    if structure.columns.is_empty() {
+        return Err(format!("Table '{}' not found", src_table));
+    }
+    if structure.columns.iter().any(|c| c.is_generated) {
+        return Err("Tables with generated columns cannot be cloned on SQLite".to_string());
+    }
+
+    let quote = |name: &str| format!("\"{}\"", escape_sql_identifier(name));
+    let quote_all = |names: &[String]| {
+        names
+            .iter()
+            .map(|n| quote(n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    // The rowid alias keeps its inline `INTEGER PRIMARY KEY` so it stays auto-assigned
+    let rowid_alias = structure.columns.iter().find(|c| c.auto_increment);
+    let mut definitions: Vec<String> = structure
+        .columns
+        .iter()
+        .map(|column| {
+            let mut definition = format!("{} {}", quote(&column.name), column.data_type);
+            if column.auto_increment {
+                definition.push_str(" PRIMARY KEY");
+            }
+            if !column.nullable {
+                definition.push_str(" NOT NULL");
+            }
+            if let Some(default) = &column.default {
+                definition.push_str(&format!(" DEFAULT {}", default));
+            }
+            definition
+        })
+        .collect();
+
+    if rowid_alias.is_none() {
+        let primary_columns = structure
+            .indexes
+            .iter()
+            .find(|i| i.primary)
+            .map(|i| i.columns.clone())
+            .unwrap_or_else(|| {
+                structure
+                    .columns
+                    .iter()
+                    .filter(|c| c.primary_key)
+                    .map(|c| c.name.clone())
+                    .collect()
+            });
+        if !primary_columns.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", quote_all(&primary_columns)));
+        }
+    }
+
+    // Unique constraints are backed by automatic indexes, which can only be declared inline
+    for index in &structure.indexes {
+        if !index.primary && index.unique && index.name.starts_with("sqlite_autoindex_") {
+            definitions.push(format!("UNIQUE ({})", quote_all(&index.columns)));
+        }
+    }
+
+    for check in &structure.check_constraints {
+        definitions.push(format!(
+            "CONSTRAINT {} CHECK ({})",
+            quote(&check.name),
+            check.expression
+        ));
+    }
+
+    // Composite foreign keys are listed once per column under the same name
+    let mut foreign_keys: Vec<(&str, Vec<String>, &str, Vec<String>)> = Vec::new();
+    for fk in &structure.foreign_keys {
+        match foreign_keys.iter_mut().find(|(name, ..)| *name == fk.name) {
+            Some((_, columns, _, references)) => {
+                columns.push(fk.column.clone());
+                references.push(fk.references_column.clone());
+            }
+            None => foreign_keys.push((
+                &fk.name,
+                vec![fk.column.clone()],
+                &fk.references_table,
+                vec![fk.references_column.clone()],
+            )),
+        }
+    }
+    for (_, columns, table, references) in &foreign_keys {
+        definitions.push(format!(
+            "FOREIGN KEY ({}) REFERENCES {} ({})",
+            quote_all(columns),
+            quote(table),
+            quote_all(references)
+        ));
+    }
+
+    let mut statements = vec![format!(
+        "CREATE TABLE {} (\n    {}\n)",
+        quote(dst_table),
+        definitions.join(",\n    ")
+    )];
+
+    for index in &structure.indexes {
+        if index.primary || index.name.starts_with("sqlite_autoindex_") {
+            continue;
+        }
+        let name = if index.name.contains(src_table) {
+            index.name.replacen(src_table, dst_table, 1)
+        } else {
+            format!("{}_{}", dst_table, index.name)
+        };
+        statements.push(format!(
+            "CREATE {}INDEX {} ON {} ({})",
+            if index.unique { "UNIQUE " } else { "" },
+            quote(&name),
+            quote(dst_table),
+            quote_all(&index.columns)
+        ));
+    }
+
+    Ok(statements)
+}
+
+// ============================================================================
+// Row editing commands (UPDATE/DELETE)
+// ============================================================================
+
+/// Names of the columns in `schema.table` whose values the database computes.
+/// A failed lookup only loses the friendlier error; the database still rejects the write.
+async fn generated_columns(
+    driver: &dyn DatabaseDriver,
+    schema: &str,
+    table: &str,
+) -> HashSet<String> {
+    driver
+        .get_table_structure(schema, table)
+        .await
+        .map(|structure| {
+            structure
+                .columns
+                .into_iter()
+                .filter(|c| c.is_generated)
+                .map(|c| c.name)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn generated_column_error(column: &str) -> String {
+    format!(
+        "Column '{}' is a generated column and cannot be written",
+        column
+    )
+}
+
+/// Result of `update_table_row`, naming the columns it wrote so the UI can highlight them
+#[derive(Debug, Clone, Serialize)]
+pub struct RowUpdateResult {
+    #[serde(flatten)]
+    pub result: QueryResult,
+    pub changed_columns: Vec<String>,
+}
+
+/// Update a row in a table
+#[tauri::command]
+pub async fn update_table_row(
+    sqlite_pool: State<'_, SqlitePool>,
+    undo_history: State<'_, UndoHistory>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    primary_key_columns: Vec<String>,
+    primary_key_values: Vec<serde_json::Value>,
+    updates: serde_json::Map<String, serde_json::Value>,
+    connection_uuid: Option<String>,
+) -> Result<RowUpdateResult, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
+    if primary_key_columns.is_empty() || primary_key_columns.len() != primary_key_values.len() {
+        return Err("Primary key columns and values must match".to_string());
+    }
+
+    if updates.is_empty() {
+        return Err("No updates provided".to_string());
+    }
+
+    let identity = driver_identity(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    );
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    // Build the UPDATE query
+    let table_ref = if db_type == "sqlite" || db_type == "sqlite3" {
+        format!("\"{}\"", escape_sql_identifier(&table))
+    } else {
+        format!(
+            "\"{}\".\"{}\"",
+            escape_sql_identifier(&schema),
+            escape_sql_identifier(&table)
+        )
+    };
+
+    let generated = generated_columns(driver.as_ref(), &schema, &table).await;
+    if let Some(column) = updates.keys().find(|col| generated.contains(*col)) {
+        return Err(generated_column_error(column));
+    }
+
+    // Build SET clause
+    let set_parts: Vec<String> = updates
+        .iter()
+        .map(|(col, val)| {
+            let formatted_value = format_sql_value(val);
+            format!("\"{}\" = {}", escape_sql_identifier(col), formatted_value)
+        })
+        .collect();
+    let set_clause = set_parts.join(", ");
+
+    // Build WHERE clause for primary key
+    let where_parts: Vec<String> = primary_key_columns
+        .iter()
+        .zip(primary_key_values.iter())
+        .map(|(col, val)| {
+            let formatted_value = format_sql_value(val);
+            format!("\"{}\" = {}", escape_sql_identifier(col), formatted_value)
+        })
+        .collect();
+    let where_clause = where_parts.join(" AND ");
+
+    let query = format!(
+        "UPDATE {} SET {} WHERE {}",
+        table_ref, set_clause, where_clause
+    );
+
+    // Keep the values being replaced so the edit can be undone
+    let edited = EditedRow {
+        db_type: &db_type,
+        identity,
+        schema: &schema,
+        table: &table,
+        table_ref: &table_ref,
+        where_clause: &where_clause,
+    };
+    let undo_entry = match &connection_uuid {
+        Some(_) => {
+            let columns: Vec<&str> = updates.keys().map(String::as_str).collect();
+            let key = key_after_update(&primary_key_columns, &primary_key_values, &updates, &[]);
+            edited.before_update(driver.as_ref(), &columns, key).await
+        }
+        None => None,
+    };
+
+    let result = driver.execute_query(&query).await?;
+    if let (Some(uuid), Some(entry)) = (&connection_uuid, undo_entry) {
+        if result.error.is_none() {
+            undo_history.record(uuid, entry);
+        }
+    }
+    Ok(RowUpdateResult {
+        result,
+        changed_columns: updates.keys().cloned().collect(),
+    })
+}
+
+/// Update a row in a table with raw SQL support
+#[tauri::command]
+pub async fn update_table_row_with_raw_sql(
+    sqlite_pool: State<'_, SqlitePool>,
+    undo_history: State<'_, UndoHistory>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    primary_key_columns: Vec<String>,
+    primary_key_values: Vec<serde_json::Value>,
+    updates: Vec<serde_json::Value>,
+    connection_uuid: Option<String>,
+) -> Result<QueryResult, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
+    if primary_key_columns.is_empty() || primary_key_columns.len() != primary_key_values.len() {
+        return Err("Primary key columns and values must match".to_string());
+    }
+
     if updates.is_empty() {
         return Err("No updates provided".to_string());
     }
 
+    let identity = driver_identity(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    );
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
 
     // Build the UPDATE query
@@ -436,8 +2524,12 @@ pub async fn update_table_row_with_raw_sql(
         )
     };
 
+    let generated = generated_columns(driver.as_ref(), &schema, &table).await;
+
     // Extract columns and values from the updates array
     let mut set_parts: Vec<String> = Vec::new();
+    let mut written = serde_json::Map::new();
+    let mut raw_columns: Vec<&str> = Vec::new();
 
     for update_obj in updates.iter() {
         let update_map = update_obj
@@ -448,6 +2540,9 @@ pub async fn update_table_row_with_raw_sql(
             .get("column")
             .and_then(|v| v.as_str())
             .ok_or("Missing column name")?;
+        if generated.contains(column) {
+            return Err(generated_column_error(column));
+        }
         let value = update_map.get("value").ok_or("Missing value")?;
         let is_raw_sql = update_map
             .get("isRawSql")
@@ -463,9 +2558,11 @@ pub async fn update_table_row_with_raw_sql(
                 .map_err(|e| format!("Invalid raw SQL value: {}", e))?;
 
             // Use the value as-is after validation
+            raw_columns.push(column);
             raw_value.to_string()
         } else {
             // For literal values, format them properly
+            written.insert(column.to_string(), value.clone());
             format_sql_value(value)
         };
 
@@ -494,12 +2591,44 @@ pub async fn update_table_row_with_raw_sql(
         table_ref, set_clause, where_clause
     );
 
-    driver.execute_query(&query).await
+    // Keep the values being replaced so the edit can be undone
+    let edited = EditedRow {
+        db_type: &db_type,
+        identity,
+        schema: &schema,
+        table: &table,
+        table_ref: &table_ref,
+        where_clause: &where_clause,
+    };
+    let undo_entry = match &connection_uuid {
+        Some(_) => {
+            let mut columns: Vec<&str> = written.keys().map(String::as_str).collect();
+            columns.extend(&raw_columns);
+            let key = key_after_update(
+                &primary_key_columns,
+                &primary_key_values,
+                &written,
+                &raw_columns,
+            );
+            edited.before_update(driver.as_ref(), &columns, key).await
+        }
+        None => None,
+    };
+
+    let result = driver.execute_query(&query).await?;
+    if let (Some(uuid), Some(entry)) = (&connection_uuid, undo_entry) {
+        if result.error.is_none() {
+            undo_history.record(uuid, entry);
+        }
+    }
+    Ok(result)
 }
 
 /// Delete a row from a table
 #[tauri::command]
 pub async fn delete_table_row(
+    sqlite_pool: State<'_, SqlitePool>,
+    undo_history: State<'_, UndoHistory>,
     db_type: String,
     host: Option<String>,
     port: Option<i64>,
@@ -508,17 +2637,47 @@ pub async fn delete_table_row(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     schema: String,
     table: String,
     primary_key_columns: Vec<String>,
     primary_key_values: Vec<serde_json::Value>,
+    connection_uuid: Option<String>,
 ) -> Result<QueryResult, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
     if primary_key_columns.is_empty() || primary_key_columns.len() != primary_key_values.len() {
         return Err("Primary key columns and values must match".to_string());
     }
 
+    let identity = driver_identity(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    );
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
 
     // Build the DELETE query
@@ -545,12 +2704,293 @@ pub async fn delete_table_row(
 
     let query = format!("DELETE FROM {} WHERE {}", table_ref, where_clause);
 
-    driver.execute_query(&query).await
+    // Keep the whole row so the delete can be undone
+    let edited = EditedRow {
+        db_type: &db_type,
+        identity,
+        schema: &schema,
+        table: &table,
+        table_ref: &table_ref,
+        where_clause: &where_clause,
+    };
+    let undo_entry = match &connection_uuid {
+        Some(_) => edited.before_delete(driver.as_ref()).await,
+        None => None,
+    };
+
+    let result = driver.execute_query(&query).await?;
+    if let (Some(uuid), Some(entry)) = (&connection_uuid, undo_entry) {
+        if result.error.is_none() {
+            undo_history.record(uuid, entry);
+        }
+    }
+    Ok(result)
+}
+
+/// The row matched by `where_clause`, or `None` unless exactly one row matches
+async fn fetch_single_row(
+    driver: &dyn DatabaseDriver,
+    columns: &str,
+    table_ref: &str,
+    where_clause: &str,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let query = format!(
+        "SELECT {} FROM {} WHERE {}",
+        columns, table_ref, where_clause
+    );
+    let result = driver.execute_query(&query).await.ok()?;
+    if result.error.is_some() || result.data.len() != 1 {
+        return None;
+    }
+    result.data.into_iter().next()?.as_object().cloned()
+}
+
+/// A row about to be changed from the grid, read beforehand so the change can be undone
+pub(crate) struct EditedRow<'a> {
+    pub db_type: &'a str,
+    /// `driver_identity` of the database the row is in
+    pub identity: String,
+    pub schema: &'a str,
+    pub table: &'a str,
+    pub table_ref: &'a str,
+    /// Condition matching the row by its primary key
+    pub where_clause: &'a str,
+}
+
+impl EditedRow<'_> {
+    fn entry(&self, edit: RowEdit) -> UndoEntry {
+        UndoEntry {
+            db_type: self.db_type.to_string(),
+            identity: self.identity.clone(),
+            edit,
+        }
+    }
+
+    /// The values of `columns` an update is about to overwrite. `key` finds the row once
+    /// the update has run; without one, or if the row can't be read, there is no entry.
+    pub(crate) async fn before_update(
+        &self,
+        driver: &dyn DatabaseDriver,
+        columns: &[&str],
+        key: Option<Vec<(String, serde_json::Value)>>,
+    ) -> Option<UndoEntry> {
+        let primary_key = key?;
+        let columns: Vec<String> = columns
+            .iter()
+            .map(|col| format!("\"{}\"", escape_sql_identifier(col)))
+            .collect();
+        let previous = fetch_single_row(
+            driver,
+            &columns.join(", "),
+            self.table_ref,
+            self.where_clause,
+        )
+        .await?;
+        Some(self.entry(RowEdit::Update {
+            schema: self.schema.to_string(),
+            table: self.table.to_string(),
+            table_ref: self.table_ref.to_string(),
+            primary_key,
+            previous,
+        }))
+    }
+
+    /// The whole row a delete is about to remove
+    pub(crate) async fn before_delete(&self, driver: &dyn DatabaseDriver) -> Option<UndoEntry> {
+        let row = fetch_single_row(driver, "*", self.table_ref, self.where_clause).await?;
+        Some(self.entry(RowEdit::Delete {
+            schema: self.schema.to_string(),
+            table: self.table.to_string(),
+            table_ref: self.table_ref.to_string(),
+            row,
+        }))
+    }
+}
+
+/// Primary key that finds a row after an update: the written value for key columns the
+/// update set, the old one for the rest. `None` when a key column was set by raw SQL,
+/// whose result isn't known.
+pub(crate) fn key_after_update(
+    primary_key_columns: &[String],
+    primary_key_values: &[serde_json::Value],
+    written: &serde_json::Map<String, serde_json::Value>,
+    raw_columns: &[&str],
+) -> Option<Vec<(String, serde_json::Value)>> {
+    primary_key_columns
+        .iter()
+        .zip(primary_key_values)
+        .map(|(col, val)| {
+            if raw_columns.contains(&col.as_str()) {
+                return None;
+            }
+            Some((col.clone(), written.get(col).unwrap_or(val).clone()))
+        })
+        .collect()
+}
+
+/// Result of `undo_last_edit`, naming the table whose row was restored
+#[derive(Debug, Serialize)]
+pub struct UndoResult {
+    pub schema: String,
+    pub table: String,
+    #[serde(flatten)]
+    pub result: QueryResult,
+    /// Edits still left to undo on the connection
+    pub remaining: usize,
+}
+
+/// Undo the most recent row edit recorded for a connection by running its inverse:
+/// re-inserting a deleted row or writing back the values an update replaced, with the
+/// values bound as parameters where the driver takes them. The edit is only undone on
+/// the database it was made on, `identity`, and stays on the stack if the inverse fails.
+pub async fn undo_last_edit_with_driver(
+    history: &UndoHistory,
+    identity: &str,
+    driver: &dyn DatabaseDriver,
+    connection_uuid: &str,
+) -> Result<UndoResult, String> {
+    let entry = history.pop(connection_uuid).ok_or("Nothing to undo")?;
+    if entry.identity != identity {
+        history.record(connection_uuid, entry);
+        return Err(
+            "The last edit was made on a different database than this connection now uses"
+                .to_string(),
+        );
+    }
+
+    let mut params: Vec<serde_json::Value> = Vec::new();
+    let mut value_sql =
+        |value: &serde_json::Value| match bind_placeholder(&entry.db_type, params.len()) {
+            Some(placeholder) => {
+                params.push(value.clone());
+                placeholder
+            }
+            None => format_sql_value(value),
+        };
+    let (schema, table, query) = match &entry.edit {
+        RowEdit::Update {
+            schema,
+            table,
+            table_ref,
+            primary_key,
+            previous,
+        } => {
+            let set_clause = previous
+                .iter()
+                .map(|(col, val)| {
+                    format!("\"{}\" = {}", escape_sql_identifier(col), value_sql(val))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let where_clause = primary_key
+                .iter()
+                .map(|(col, val)| {
+                    format!("\"{}\" = {}", escape_sql_identifier(col), value_sql(val))
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ");
+            let query = format!(
+                "UPDATE {} SET {} WHERE {}",
+                table_ref, set_clause, where_clause
+            );
+            (schema, table, query)
+        }
+        RowEdit::Delete {
+            schema,
+            table,
+            table_ref,
+            row,
+        } => {
+            let generated = generated_columns(driver, schema, table).await;
+            let (columns, values): (Vec<String>, Vec<String>) = row
+                .iter()
+                .filter(|(col, _)| !generated.contains(*col))
+                .map(|(col, val)| {
+                    (
+                        format!("\"{}\"", escape_sql_identifier(col)),
+                        value_sql(val),
+                    )
+                })
+                .unzip();
+            let query = format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                table_ref,
+                columns.join(", "),
+                values.join(", ")
+            );
+            (schema, table, query)
+        }
+    };
+
+    let outcome = driver.execute_query_with_params(&query, &params).await;
+    let result = match outcome {
+        Ok(result) if result.error.is_none() => result,
+        Ok(result) => {
+            let error = result.error.clone().unwrap_or_default();
+            history.record(connection_uuid, entry);
+            return Err(format!("Undo failed: {}", error));
+        }
+        Err(e) => {
+            history.record(connection_uuid, entry);
+            return Err(format!("Undo failed: {}", e));
+        }
+    };
+    Ok(UndoResult {
+        schema: schema.clone(),
+        table: table.clone(),
+        result,
+        remaining: history.depth(connection_uuid),
+    })
+}
+
+/// Undo the most recent row edit made from the grid on a saved connection
+#[tauri::command]
+pub async fn undo_last_edit(
+    sqlite_pool: State<'_, SqlitePool>,
+    undo_history: State<'_, UndoHistory>,
+    connection_uuid: String,
+) -> Result<UndoResult, String> {
+    let conn: Connection = sqlx::query_as("SELECT * FROM connections WHERE uuid = ?")
+        .bind(&connection_uuid)
+        .fetch_one(sqlite_pool.inner())
+        .await
+        .map_err(|e| format!("Failed to get connection: {}", e))?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &conn.db_type,
+        Some(&conn.host),
+        Some(conn.port),
+        Some(&conn.database),
+        conn.file_path.as_deref(),
+    ));
+
+    let (driver, _tunnel) = create_driver_for_connection(sqlite_pool.inner(), &conn).await?;
+    undo_last_edit_with_driver(
+        undo_history.inner(),
+        &connection_identity(&conn),
+        driver.as_ref(),
+        &connection_uuid,
+    )
+    .await
+}
+
+/// `driver_identity` of a saved connection
+pub(crate) fn connection_identity(conn: &Connection) -> String {
+    driver_identity(
+        &conn.db_type,
+        Some(&conn.host),
+        Some(conn.port),
+        Some(&conn.database),
+        conn.file_path.as_deref(),
+    )
 }
 
-/// Insert a new row into a table
+/// Insert a new row into a table, returning the inserted row where the driver can fetch it
 #[tauri::command]
 pub async fn insert_table_row(
+    sqlite_pool: State<'_, SqlitePool>,
     db_type: String,
     host: Option<String>,
     port: Option<i64>,
@@ -559,16 +2999,39 @@ pub async fn insert_table_row(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     schema: String,
     table: String,
     values: Vec<serde_json::Value>,
+    connection_uuid: Option<String>,
 ) -> Result<QueryResult, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
     if values.is_empty() {
         return Err("No values provided".to_string());
     }
 
     let driver = create_driver(
-        &db_type, host, port, database, username, password, ssl, file_path,
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
     )?;
 
     // Build the INSERT query
@@ -582,10 +3045,13 @@ pub async fn insert_table_row(
         )
     };
 
+    let generated = generated_columns(driver.as_ref(), &schema, &table).await;
+
     // Extract columns and values from the values array
     // Each value should be an object with: column, value, isRawSql
     let mut columns: Vec<String> = Vec::new();
     let mut value_parts: Vec<String> = Vec::new();
+    let mut inserted: Vec<(String, String)> = Vec::new();
 
     for value_obj in values.iter() {
         let value_map = value_obj
@@ -602,6 +3068,14 @@ pub async fn insert_table_row(
             .and_then(|v| v.as_bool())
             .unwrap_or(false);
 
+        // Generated columns are left out when the form sends them empty or as DEFAULT
+        if generated.contains(column) {
+            if value.is_null() || (is_raw_sql && value.as_str() == Some("DEFAULT")) {
+                continue;
+            }
+            return Err(generated_column_error(column));
+        }
+
         columns.push(format!("\"{}\"", escape_sql_identifier(column)));
 
         let formatted_value = if is_raw_sql {
@@ -619,18 +3093,24 @@ pub async fn insert_table_row(
             format_sql_value(value)
         };
 
+        inserted.push((column.to_string(), formatted_value.clone()));
         value_parts.push(formatted_value);
     }
 
-    let columns_clause = columns.join(", ");
-    let values_clause = value_parts.join(", ");
-
-    let query = format!(
-        "INSERT INTO {} ({}) VALUES ({})",
-        table_ref, columns_clause, values_clause
-    );
+    let query = if columns.is_empty() {
+        format!("INSERT INTO {} DEFAULT VALUES", table_ref)
+    } else {
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table_ref,
+            columns.join(", "),
+            value_parts.join(", ")
+        )
+    };
 
-    driver.execute_query(&query).await
+    driver
+        .execute_returning(&schema, &table, &query, &inserted)
+        .await
 }
 
 /// Whitelist of allowed SQL functions/values for raw SQL injection.
@@ -820,6 +3300,353 @@ pub fn format_sql_value(value: &serde_json::Value) -> String {
     }
 }
 
+/// Replace `{{name}}` placeholders in a query with connection variable values.
+///
+/// SQL databases receive each value as an escaped string literal; Redis commands get the raw value.
+pub fn substitute_query_variables(
+    query: &str,
+    variables: &HashMap<String, String>,
+    db_type: &str,
+) -> Result<String, String> {
+    replace_query_variables(query, variables, db_type, |value| {
+        if db_type == "redis" {
+            value.to_string()
+        } else {
+            format_sql_value(&serde_json::Value::String(value.to_string()))
+        }
+    })
+}
+
+/// Resolve `{{name}}` placeholders for execution, returning the query and the values to
+/// bind. Drivers that take bind parameters get a placeholder per variable; other drivers,
+/// and scripts of several statements, get the values substituted as text.
+pub fn bind_query_variables(
+    query: &str,
+    variables: &HashMap<String, String>,
+    db_type: &str,
+) -> Result<(String, Vec<serde_json::Value>), String> {
+    if bind_placeholder(db_type, 0).is_none() || split_statements(query).len() > 1 {
+        let query = substitute_query_variables(query, variables, db_type)?;
+        return Ok((query, vec![]));
+    }
+    let mut params = Vec::new();
+    let query = replace_query_variables(query, variables, db_type, |value| {
+        params.push(serde_json::Value::String(value.to_string()));
+        bind_placeholder(db_type, params.len() - 1).unwrap_or_default()
+    })?;
+    Ok((query, params))
+}
+
+/// Replace each `{{name}}` placeholder with `render` of its value. In SQL, placeholders
+/// inside quotes, comments and Postgres dollar-quoted bodies are left as written.
+fn replace_query_variables(
+    query: &str,
+    variables: &HashMap<String, String>,
+    db_type: &str,
+    mut render: impl FnMut(&str) -> String,
+) -> Result<String, String> {
+    let sql = db_type != "redis";
+    let bytes = query.as_bytes();
+    let mut result = String::with_capacity(query.len());
+    let mut copied = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' if bytes.get(i + 1) == Some(&b'{') => {
+                let Some(len) = query[i + 2..].find("}}") else {
+                    break;
+                };
+                let name = query[i + 2..i + 2 + len].trim();
+                let value = variables
+                    .get(name)
+                    .ok_or_else(|| format!("Undefined query variable: {}", name))?;
+
+                result.push_str(&query[copied..i]);
+                result.push_str(&render(value));
+                i += 2 + len + 2;
+                copied = i;
+                continue;
+            }
+            quote @ (b'\'' | b'"' | b'`') if sql => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+            }
+            b'-' if sql && bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if sql && bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'$' if sql => {
+                if let Some(tag_len) = dollar_quote_tag_len(&bytes[i..]) {
+                    let tag = &bytes[i..i + tag_len];
+                    i += tag_len;
+                    while i < bytes.len() && !bytes[i..].starts_with(tag) {
+                        i += 1;
+                    }
+                    i += tag_len - 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    result.push_str(&query[copied..]);
+    Ok(result)
+}
+
+/// Error returned when a write is attempted on a read-only connection
+pub const READ_ONLY_ERROR: &str = "Connection is read-only";
+
+/// Restrictions and defaults a saved connection applies to the commands run against it,
+/// read from the app database by uuid rather than trusted from the caller
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionPolicy {
+    pub read_only: bool,
+    /// Default query timeout of the connection, in seconds
+    pub query_timeout_secs: Option<i64>,
+}
+
+impl ConnectionPolicy {
+    /// Policy of the saved connection `connection_uuid`. An ad hoc connection without a
+    /// uuid has no restrictions.
+    pub async fn load(pool: &SqlitePool, connection_uuid: Option<&str>) -> Result<Self, String> {
+        let Some(uuid) = connection_uuid else {
+            return Ok(Self::default());
+        };
+        let (read_only, query_timeout_secs): (i64, Option<i64>) =
+            sqlx::query_as("SELECT read_only, query_timeout_secs FROM connections WHERE uuid = ?")
+                .bind(uuid)
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| format!("Failed to get connection: {}", e))?
+                .ok_or_else(|| format!("Connection not found: {}", uuid))?;
+        Ok(Self {
+            read_only: read_only == 1,
+            query_timeout_secs,
+        })
+    }
+
+    /// Fail if the connection is read-only
+    pub fn check_write(&self) -> Result<(), String> {
+        if self.read_only {
+            return Err(READ_ONLY_ERROR.to_string());
+        }
+        Ok(())
+    }
+
+    /// Fail if the connection is read-only and `query` may write
+    pub fn check_query(&self, query: &str, db_type: &str) -> Result<(), String> {
+        if self.read_only && !is_read_only_query(query, db_type) {
+            return Err(READ_ONLY_ERROR.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// SQL keywords that modify data or schema
+const SQL_WRITE_KEYWORDS: &[&str] = &[
+    "INSERT", "UPDATE", "DELETE", "MERGE", "CREATE", "DROP", "ALTER", "TRUNCATE", "GRANT",
+    "REVOKE", "COPY", "VACUUM", "REINDEX", "CLUSTER", "REFRESH", "OPTIMIZE", "ATTACH", "DETACH",
+    "RENAME", "LOCK",
+];
+
+/// Functions that change state even when called from a SELECT
+const SQL_WRITE_FUNCTIONS: &[&str] = &[
+    "NEXTVAL",
+    "SETVAL",
+    "SET_CONFIG",
+    "PG_ADVISORY_LOCK",
+    "PG_ADVISORY_XACT_LOCK",
+    "PG_TRY_ADVISORY_LOCK",
+    "PG_TRY_ADVISORY_XACT_LOCK",
+    "PG_CANCEL_BACKEND",
+    "PG_TERMINATE_BACKEND",
+    "PG_RELOAD_CONF",
+    "PG_ROTATE_LOGFILE",
+    "PG_SWITCH_WAL",
+    "PG_CREATE_RESTORE_POINT",
+    "PG_NOTIFY",
+    "PG_STAT_RESET",
+    "PG_LOGICAL_EMIT_MESSAGE",
+    "PG_CREATE_LOGICAL_REPLICATION_SLOT",
+    "PG_CREATE_PHYSICAL_REPLICATION_SLOT",
+    "PG_DROP_REPLICATION_SLOT",
+    "LO_CREATE",
+    "LO_CREAT",
+    "LO_IMPORT",
+    "LO_EXPORT",
+    "LO_UNLINK",
+    "LO_PUT",
+    "LO_FROM_BYTEA",
+    "LOWRITE",
+    "DBLINK_EXEC",
+];
+
+/// Redis commands that only read data
+const REDIS_READ_COMMANDS: &[&str] = &[
+    "GET",
+    "MGET",
+    "STRLEN",
+    "GETRANGE",
+    "EXISTS",
+    "TYPE",
+    "TTL",
+    "PTTL",
+    "KEYS",
+    "SCAN",
+    "DBSIZE",
+    "INFO",
+    "PING",
+    "ECHO",
+    "TIME",
+    "HGET",
+    "HMGET",
+    "HGETALL",
+    "HKEYS",
+    "HVALS",
+    "HLEN",
+    "HEXISTS",
+    "HSCAN",
+    "LRANGE",
+    "LLEN",
+    "LINDEX",
+    "SMEMBERS",
+    "SCARD",
+    "SISMEMBER",
+    "SSCAN",
+    "ZRANGE",
+    "ZRANGEBYSCORE",
+    "ZREVRANGE",
+    "ZCARD",
+    "ZSCORE",
+    "ZRANK",
+    "ZCOUNT",
+    "ZSCAN",
+    "XRANGE",
+    "XLEN",
+    "MEMORY",
+    "OBJECT",
+];
+
+/// Remove leading whitespace, comments and opening parentheses from a statement
+fn strip_statement_prefix(statement: &str) -> &str {
+    let mut rest = statement.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix("--") {
+            rest = after
+                .split_once('\n')
+                .map(|(_, r)| r)
+                .unwrap_or("")
+                .trim_start();
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after
+                .split_once("*/")
+                .map(|(_, r)| r)
+                .unwrap_or("")
+                .trim_start();
+        } else if let Some(after) = rest.strip_prefix('(') {
+            rest = after.trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
+/// Whether a statement calls one of `SQL_WRITE_FUNCTIONS`, ignoring string literals and
+/// quoted identifiers
+fn calls_write_function(statement: &str) -> bool {
+    let mut unquoted = String::with_capacity(statement.len());
+    let mut quote = None;
+    for c in statement.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' || c == '`' => {
+                quote = Some(c);
+                unquoted.push(' ');
+            }
+            None => unquoted.push(c),
+        }
+    }
+
+    let upper = unquoted.to_uppercase();
+    let mut rest = upper.as_str();
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        let word_rest = &rest[start..];
+        let end = word_rest
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(word_rest.len());
+        let (word, after) = word_rest.split_at(end);
+        // A schema qualifier such as `pg_catalog.nextval(` still names the function
+        if after.trim_start().starts_with('(') && SQL_WRITE_FUNCTIONS.contains(&word) {
+            return true;
+        }
+        rest = after;
+    }
+    false
+}
+
+/// Classify a single SQL statement as read-only or not
+fn is_read_only_sql_statement(statement: &str) -> bool {
+    let words: Vec<String> = statement
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_uppercase())
+        .collect();
+    let has_write_keyword = words
+        .iter()
+        .any(|w| SQL_WRITE_KEYWORDS.contains(&w.as_str()));
+
+    if calls_write_function(statement) {
+        return false;
+    }
+
+    match words.first().map(|w| w.as_str()) {
+        // SELECT ... INTO creates a table (Postgres) or writes a file (ClickHouse)
+        Some("SELECT") => !words.iter().any(|w| w == "INTO"),
+        Some("SHOW") | Some("DESCRIBE") | Some("DESC") => true,
+        // EXPLAIN ANALYZE actually runs the statement, so the inner statement must be a read
+        Some("EXPLAIN") => {
+            let analyze = words.iter().any(|w| w == "ANALYZE" || w == "ANALYSE");
+            !analyze || !has_write_keyword
+        }
+        // CTEs are reads unless one of them is a data-modifying statement
+        Some("WITH") => !has_write_keyword,
+        _ => false,
+    }
+}
+
+/// Check whether a query only reads data.
+/// Every statement must be SELECT/SHOW/EXPLAIN/DESCRIBE (or a read-only Redis command).
+pub fn is_read_only_query(query: &str, db_type: &str) -> bool {
+    if db_type == "redis" {
+        return query
+            .split_whitespace()
+            .next()
+            .map(|cmd| REDIS_READ_COMMANDS.contains(&cmd.to_uppercase().as_str()))
+            .unwrap_or(false);
+    }
+
+    let statements: Vec<&str> = query
+        .split(';')
+        .map(strip_statement_prefix)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    !statements.is_empty() && statements.iter().all(|s| is_read_only_sql_statement(s))
+}
+
 // ============================================================================
 // Redis-specific commands
 // ============================================================================
@@ -881,8 +3708,12 @@ pub async fn redis_search_keys(
     pattern: String,
     limit: i64,
     cursor: u64,
+    db: Option<i64>,
 ) -> Result<RedisKeyListResponse, String> {
-    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (mut config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
     let driver = RedisDriver::new(config.clone());
 
     let progress_callback = {
@@ -931,6 +3762,7 @@ pub async fn redis_search_keys(
                 Some(&conn.ssh_key_path)
             },
             conn.ssh_use_key == 1,
+            conn.ssh_use_agent == 1,
         )
         .await?;
 
@@ -950,8 +3782,12 @@ pub async fn redis_get_key_details(
     sqlite_pool: State<'_, SqlitePool>,
     uuid: String,
     key: String,
+    db: Option<i64>,
 ) -> Result<RedisKeyDetails, String> {
-    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (mut config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
     let driver = RedisDriver::new(config.clone());
 
     if conn.ssh_enabled == 1 {
@@ -977,6 +3813,7 @@ pub async fn redis_get_key_details(
                 Some(&conn.ssh_key_path)
             },
             conn.ssh_use_key == 1,
+            conn.ssh_use_agent == 1,
         )
         .await?;
 
@@ -986,6 +3823,153 @@ pub async fn redis_get_key_details(
     }
 }
 
+/// Complete value of a Redis string key whose details only carried a preview
+#[tauri::command]
+pub async fn redis_get_full_value(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    db: Option<i64>,
+) -> Result<RedisStringValue, String> {
+    let (mut config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
+    let driver = RedisDriver::new(config);
+    driver.get_full_value(&key).await
+}
+
+/// Page through a Redis list by index
+#[tauri::command]
+pub async fn redis_lrange(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    start: isize,
+    stop: isize,
+    db: Option<i64>,
+) -> Result<Vec<String>, String> {
+    let (mut config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
+    let driver = RedisDriver::new(config);
+    driver.lrange(&key, start, stop).await
+}
+
+/// Scan one page of Redis set members
+#[tauri::command]
+pub async fn redis_sscan(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    cursor: u64,
+    count: Option<usize>,
+    db: Option<i64>,
+) -> Result<RedisScanPage, String> {
+    let (mut config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
+    let driver = RedisDriver::new(config);
+    driver.sscan(&key, cursor, count).await
+}
+
+/// Scan one page of Redis hash fields
+#[tauri::command]
+pub async fn redis_hscan(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    cursor: u64,
+    count: Option<usize>,
+    db: Option<i64>,
+) -> Result<RedisScanPage, String> {
+    let (mut config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
+    let driver = RedisDriver::new(config);
+    driver.hscan(&key, cursor, count).await
+}
+
+/// Page through a Redis sorted set by rank
+#[tauri::command]
+pub async fn redis_zrange(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    start: isize,
+    stop: isize,
+    withscores: Option<bool>,
+    db: Option<i64>,
+) -> Result<serde_json::Value, String> {
+    let (mut config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
+    let driver = RedisDriver::new(config);
+    driver
+        .zrange(&key, start, stop, withscores.unwrap_or(true))
+        .await
+}
+
+/// Fetch Redis sorted set members whose scores fall between `min` and `max`
+#[tauri::command]
+pub async fn redis_zrangebyscore(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    min: String,
+    max: String,
+    limit: Option<usize>,
+    db: Option<i64>,
+) -> Result<serde_json::Value, String> {
+    let (mut config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if db.is_some() {
+        config.db = db;
+    }
+    let driver = RedisDriver::new(config);
+    driver.zrangebyscore(&key, &min, &max, limit).await
+}
+
+/// Replication role and link state of a Redis server, with a cluster summary when it
+/// runs in cluster mode
+#[tauri::command]
+pub async fn redis_replication_status(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<RedisReplicationStatus, String> {
+    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let driver = RedisDriver::new(config);
+    driver.replication_status().await
+}
+
+/// Switch a saved Redis connection to another logical database. The index is checked with
+/// `SELECT`, then stored as the connection's database so later commands use it.
+pub async fn select_redis_db(sqlite_pool: &SqlitePool, uuid: &str, db: i64) -> Result<(), String> {
+    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool, uuid).await?;
+    RedisDriver::new(config).select_db(db).await?;
+
+    sqlx::query("UPDATE connections SET database = ?, updated_at = datetime('now') WHERE uuid = ?")
+        .bind(db.to_string())
+        .bind(uuid)
+        .execute(sqlite_pool)
+        .await
+        .map_err(|e| format!("Failed to save selected database: {}", e))?;
+    Ok(())
+}
+
+/// Switch a Redis connection to another logical database for all later commands
+#[tauri::command]
+pub async fn redis_select_db(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    db: i64,
+) -> Result<(), String> {
+    select_redis_db(sqlite_pool.inner(), &uuid, db).await
+}
+
 /// Delete a Redis key
 #[tauri::command]
 pub async fn redis_delete_key(
@@ -993,12 +3977,33 @@ pub async fn redis_delete_key(
     uuid: String,
     key: String,
 ) -> Result<bool, String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
     driver.delete_key(&key).await
 }
 
-/// Set a Redis key value (for string types)
+/// Delete all Redis keys matching a pattern
+#[tauri::command]
+pub async fn redis_delete_keys_by_pattern(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    pattern: String,
+    confirm: Option<bool>,
+) -> Result<u64, String> {
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+    let driver = RedisDriver::new(config);
+    driver
+        .delete_keys_by_pattern(&pattern, confirm.unwrap_or(false))
+        .await
+}
+
+/// Set a Redis key value (for string types). With `base64`, the value is decoded and stored as raw bytes
 #[tauri::command]
 pub async fn redis_set_key(
     sqlite_pool: State<'_, SqlitePool>,
@@ -1006,12 +4011,37 @@ pub async fn redis_set_key(
     key: String,
     value: String,
     ttl: Option<i64>,
+    base64: Option<bool>,
 ) -> Result<(), String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
+    if base64.unwrap_or(false) {
+        let bytes = decode_base64_value(&value)?;
+        return driver.set_key_bytes(&key, &bytes, ttl).await;
+    }
     driver.set_key(&key, &value, ttl).await
 }
 
+/// Store a JSON value as a Redis string key
+#[tauri::command]
+pub async fn redis_set_json(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    key: String,
+    value: serde_json::Value,
+    ttl: Option<i64>,
+) -> Result<(), String> {
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+    let driver = RedisDriver::new(config);
+    driver.set_json(&key, &value, ttl).await
+}
+
 /// Set a Redis list key value
 #[tauri::command]
 pub async fn redis_set_list_key(
@@ -1021,7 +4051,10 @@ pub async fn redis_set_list_key(
     values: Vec<String>,
     ttl: Option<i64>,
 ) -> Result<(), String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
     driver.set_list_key(&key, &values, ttl).await
 }
@@ -1035,7 +4068,10 @@ pub async fn redis_set_set_key(
     values: Vec<String>,
     ttl: Option<i64>,
 ) -> Result<(), String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
     driver.set_set_key(&key, &values, ttl).await
 }
@@ -1049,7 +4085,10 @@ pub async fn redis_set_hash_key(
     fields: std::collections::HashMap<String, String>,
     ttl: Option<i64>,
 ) -> Result<(), String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
     driver.set_hash_key(&key, &fields, ttl).await
 }
@@ -1063,7 +4102,10 @@ pub async fn redis_set_zset_key(
     members: Vec<(String, f64)>,
     ttl: Option<i64>,
 ) -> Result<(), String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
     driver.set_zset_key(&key, &members, ttl).await
 }
@@ -1076,11 +4118,33 @@ pub async fn redis_update_ttl(
     key: String,
     ttl: Option<i64>,
 ) -> Result<(), String> {
-    let (config, _conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
     let driver = RedisDriver::new(config);
     driver.update_ttl(&key, ttl).await
 }
 
+/// Set a TTL on all Redis keys matching a pattern
+#[tauri::command]
+pub async fn redis_expire_keys(
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+    pattern: String,
+    ttl_seconds: i64,
+    confirm: Option<bool>,
+) -> Result<u64, String> {
+    let (config, conn) = get_redis_config_from_uuid(sqlite_pool.inner(), &uuid).await?;
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+    let driver = RedisDriver::new(config);
+    driver
+        .expire_keys(&pattern, ttl_seconds, confirm.unwrap_or(false))
+        .await
+}
+
 /// Get schema overview with all tables and their structures
 #[tauri::command(rename_all = "snake_case")]
 pub async fn unified_get_schema_overview(
@@ -1092,6 +4156,8 @@ pub async fn unified_get_schema_overview(
     password: Option<String>,
     ssl: Option<bool>,
     file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
     ssh_enabled: Option<bool>,
     ssh_host: Option<String>,
     ssh_port: Option<i64>,
@@ -1099,6 +4165,7 @@ pub async fn unified_get_schema_overview(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    ssh_use_agent: Option<bool>,
 ) -> Result<SchemaOverview, String> {
     let (driver, _tunnel) = create_driver_with_ssh(
         &db_type,
@@ -1109,6 +4176,7 @@ pub async fn unified_get_schema_overview(
         password,
         ssl,
         file_path,
+        socket_path,
         ssh_enabled,
         ssh_host,
         ssh_port,
@@ -1116,8 +4184,309 @@ pub async fn unified_get_schema_overview(
         ssh_password,
         ssh_key_path,
         ssh_use_key,
+        ssh_use_agent,
+        session_setup_sql,
     )
     .await?;
 
     driver.get_schema_overview().await
 }
+
+/// Load table structures one at a time, reporting each table through `on_event` as soon as
+/// it is ready. Returns the number of tables streamed.
+pub async fn stream_schema_tables<F>(
+    driver: &dyn DatabaseDriver,
+    overview_id: &str,
+    mut on_event: F,
+) -> Result<usize, String>
+where
+    F: FnMut(SchemaStreamEvent),
+{
+    let tables = driver.list_tables().await?;
+    let total = tables.len();
+
+    for (index, table) in tables.into_iter().enumerate() {
+        let structure = match driver.get_table_structure(&table.schema, &table.name).await {
+            Ok(structure) => structure,
+            Err(e) => {
+                println!(
+                    "[Schema] Failed to load structure for {}.{}: {}",
+                    table.schema, table.name, e
+                );
+                TableStructure {
+                    columns: Vec::new(),
+                    indexes: Vec::new(),
+                    foreign_keys: Vec::new(),
+                    check_constraints: Vec::new(),
+                    unique_constraints: Vec::new(),
+                    warnings: vec![],
+                }
+            }
+        };
+
+        on_event(SchemaStreamEvent::Table(SchemaTablePayload {
+            overview_id: overview_id.to_string(),
+            index,
+            total,
+            table: TableWithStructure {
+                schema: table.schema,
+                name: table.name,
+                table_type: table.table_type,
+                comment: table.comment,
+                columns: structure.columns,
+                foreign_keys: structure.foreign_keys,
+                indexes: structure.indexes,
+            },
+        }));
+    }
+
+    on_event(SchemaStreamEvent::Complete(SchemaCompletePayload {
+        overview_id: overview_id.to_string(),
+        table_count: total,
+    }));
+
+    Ok(total)
+}
+
+/// Stream the schema overview as `schema-table` events followed by `schema-complete`
+#[tauri::command(rename_all = "snake_case")]
+pub async fn stream_schema_overview(
+    app: AppHandle,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    ssh_enabled: Option<bool>,
+    ssh_host: Option<String>,
+    ssh_port: Option<i64>,
+    ssh_user: Option<String>,
+    ssh_password: Option<String>,
+    ssh_key_path: Option<String>,
+    ssh_use_key: Option<bool>,
+    ssh_use_agent: Option<bool>,
+    overview_id: String,
+) -> Result<(), String> {
+    let (driver, _tunnel) = create_driver_with_ssh(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        ssh_enabled,
+        ssh_host,
+        ssh_port,
+        ssh_user,
+        ssh_password,
+        ssh_key_path,
+        ssh_use_key,
+        ssh_use_agent,
+        session_setup_sql,
+    )
+    .await?;
+
+    stream_schema_tables(driver.as_ref(), &overview_id, |event| {
+        let _ = match event {
+            SchemaStreamEvent::Table(payload) => app.emit("schema-table", payload),
+            SchemaStreamEvent::Complete(payload) => app.emit("schema-complete", payload),
+        };
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// List the stored functions and procedures in a schema
+#[tauri::command]
+pub async fn list_routines(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+) -> Result<Vec<RoutineInfo>, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.list_routines(&schema).await
+}
+
+/// Get the source of a stored function or procedure
+#[tauri::command]
+pub async fn get_routine_definition(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    name: String,
+) -> Result<String, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.get_routine_definition(&schema, &name).await
+}
+
+/// Table counts and estimated row totals per schema (or per database on ClickHouse),
+/// for a quick look at what a connection holds
+#[tauri::command]
+pub async fn get_server_overview(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> Result<ServerOverview, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    let namespaces = driver.namespace_overview().await?;
+    Ok(ServerOverview {
+        total_tables: namespaces.iter().map(|n| n.table_count).sum(),
+        total_estimated_rows: namespaces.iter().map(|n| n.estimated_rows).sum(),
+        namespaces,
+    })
+}
+
+/// Partitioning strategy and child partitions of a table
+#[tauri::command]
+pub async fn get_partitions(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+) -> Result<TablePartitions, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.get_partitions(&schema, &table).await
+}
+
+/// Shards and replicas of the ClickHouse clusters the server belongs to, showing where
+/// queries on Distributed tables fan out
+#[tauri::command]
+pub async fn get_cluster_info(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> Result<Vec<ClusterNode>, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.get_cluster_info().await
+}
+
+/// Character set, collation and timezone of the connected database
+#[tauri::command]
+pub async fn get_database_info(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> Result<DatabaseInfo, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    driver.get_database_info().await
+}
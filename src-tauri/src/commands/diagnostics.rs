@@ -0,0 +1,90 @@
+//! Health checks for the app's own SQLite database (saved connections, queries and
+//! settings), to help troubleshoot a corrupted or half-migrated install.
+
+use crate::db::models::{AppDbDiagnostics, AppTableCount, AppliedMigration};
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Table sqlx records applied migrations in
+const MIGRATIONS_TABLE: &str = "_sqlx_migrations";
+
+/// Check integrity, applied migrations, per-table row counts and size of the app database
+pub async fn diagnose(pool: &SqlitePool) -> Result<AppDbDiagnostics, String> {
+    let problems: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Integrity check failed: {}", e))?;
+
+    let table_names: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(|e| format!("Failed to list tables: {}", e))?;
+
+    let migrations: Vec<AppliedMigration> =
+        if table_names.iter().any(|name| name == MIGRATIONS_TABLE) {
+            sqlx::query_as(
+                "SELECT version, description, CAST(installed_on AS TEXT) AS installed_on, success \
+                 FROM _sqlx_migrations ORDER BY version",
+            )
+            .fetch_all(pool)
+            .await
+            .map_err(|e| format!("Failed to read migrations: {}", e))?
+        } else {
+            Vec::new()
+        };
+    let schema_version = migrations
+        .iter()
+        .filter(|migration| migration.success)
+        .map(|migration| migration.version)
+        .max();
+
+    let mut tables = Vec::new();
+    for table in table_names
+        .into_iter()
+        .filter(|name| name != MIGRATIONS_TABLE)
+    {
+        let rows: i64 = sqlx::query_scalar(&format!(
+            "SELECT COUNT(*) FROM \"{}\"",
+            table.replace('"', "\"\"")
+        ))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to count rows in {}: {}", table, e))?;
+        tables.push(AppTableCount { table, rows });
+    }
+
+    let files: Vec<(i64, String, String)> = sqlx::query_as("PRAGMA database_list")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| format!("Failed to locate database file: {}", e))?;
+    let file_path = files
+        .into_iter()
+        .find(|(_, name, _)| name == "main")
+        .map(|(_, _, file)| file)
+        .filter(|file| !file.is_empty());
+
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to read page count: {}", e))?;
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to read page size: {}", e))?;
+
+    Ok(AppDbDiagnostics {
+        integrity: problems.join("\n"),
+        schema_version,
+        migrations,
+        tables,
+        file_path,
+        file_size_bytes: page_count * page_size,
+    })
+}
+
+#[tauri::command]
+pub async fn diagnose_app_db(pool: State<'_, SqlitePool>) -> Result<AppDbDiagnostics, String> {
+    diagnose(pool.inner()).await
+}
@@ -0,0 +1,345 @@
+//! Query editor helpers. These are pure transforms of queries and results with no
+//! database access.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+use sqlformat::{FormatOptions, Indent, QueryParams};
+use sqlparser::ast::{
+    FromTable, ObjectName, Query, Select, SelectItem, SetExpr, Statement, TableFactor,
+};
+use sqlparser::dialect::{
+    ClickHouseDialect, Dialect, GenericDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
+use sqlparser::parser::Parser;
+
+use crate::db::models::QueryResult;
+
+/// Dialects whose queries are SQL (or close enough, like CQL) to be formatted
+const SQL_DIALECTS: &[&str] = &[
+    "postgres",
+    "postgresql",
+    "sqlite",
+    "sqlite3",
+    "clickhouse",
+    "mysql",
+    "cassandra",
+    "oracle",
+    "sql",
+];
+
+/// Dialects whose unquoted identifiers are case-sensitive. The formatter upper-cases every
+/// word it treats as reserved, which includes common column names such as `name` or
+/// `status`, so these dialects keep the query's own casing.
+const CASE_SENSITIVE_DIALECTS: &[&str] = &["clickhouse", "mysql"];
+
+/// Pretty-print a query: upper-case keywords and put each clause on its own indented line.
+/// String literals, quoted identifiers and comments are copied through unchanged.
+#[tauri::command]
+pub fn format_sql(query: String, dialect: String) -> Result<String, String> {
+    let dialect = dialect.to_lowercase();
+    if !SQL_DIALECTS.contains(&dialect.as_str()) {
+        return Err(format!("Formatting is not supported for {}", dialect));
+    }
+    if query.trim().is_empty() {
+        return Ok(String::new());
+    }
+
+    Ok(sqlformat::format(
+        &query,
+        &QueryParams::None,
+        FormatOptions {
+            indent: Indent::Spaces(2),
+            uppercase: !CASE_SENSITIVE_DIALECTS.contains(&dialect.as_str()),
+            lines_between_queries: 2,
+        },
+    ))
+}
+
+/// A potential problem found by `lint_sql`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintWarning {
+    /// Stable identifier: `syntax`, `select_star`, `missing_where` or `cartesian_join`
+    pub rule: String,
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(rule: &str, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Check a query for common mistakes without running it. A query that does not parse
+/// yields a single `syntax` warning, since the editor lints while the user is typing.
+#[tauri::command]
+pub fn lint_sql(query: String, dialect: String) -> Result<Vec<LintWarning>, String> {
+    let dialect = dialect.to_lowercase();
+    if !SQL_DIALECTS.contains(&dialect.as_str()) {
+        return Err(format!("Linting is not supported for {}", dialect));
+    }
+    let statements = match Parser::parse_sql(parser_dialect(&dialect).as_ref(), &query) {
+        Ok(statements) => statements,
+        Err(e) => return Ok(vec![LintWarning::new("syntax", e.to_string())]),
+    };
+
+    let mut warnings = Vec::new();
+    for statement in &statements {
+        match statement {
+            Statement::Query(query) => lint_query(query, &mut warnings),
+            Statement::Insert(insert) => {
+                if let Some(source) = &insert.source {
+                    lint_query(source, &mut warnings);
+                }
+            }
+            Statement::Update {
+                selection: None, ..
+            } => warnings.push(LintWarning::new(
+                "missing_where",
+                "UPDATE without WHERE changes every row",
+            )),
+            Statement::Delete(delete) if delete.selection.is_none() => warnings.push(
+                LintWarning::new("missing_where", "DELETE without WHERE removes every row"),
+            ),
+            _ => {}
+        }
+    }
+    warnings.dedup();
+    Ok(warnings)
+}
+
+fn lint_query(query: &Query, warnings: &mut Vec<LintWarning>) {
+    if let Some(with) = &query.with {
+        for cte in &with.cte_tables {
+            lint_query(&cte.query, warnings);
+        }
+    }
+    lint_set_expr(&query.body, warnings);
+}
+
+fn lint_set_expr(body: &SetExpr, warnings: &mut Vec<LintWarning>) {
+    match body {
+        SetExpr::Select(select) => lint_select(select, warnings),
+        SetExpr::Query(query) => lint_query(query, warnings),
+        SetExpr::SetOperation { left, right, .. } => {
+            lint_set_expr(left, warnings);
+            lint_set_expr(right, warnings);
+        }
+        _ => {}
+    }
+}
+
+fn lint_select(select: &Select, warnings: &mut Vec<LintWarning>) {
+    if select.projection.iter().any(|item| {
+        matches!(
+            item,
+            SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..)
+        )
+    }) {
+        warnings.push(LintWarning::new(
+            "select_star",
+            "SELECT * used; list the columns you need",
+        ));
+    }
+
+    if select.from.len() > 1 && select.selection.is_none() {
+        warnings.push(LintWarning::new(
+            "cartesian_join",
+            "Possible cartesian join: comma-separated tables without a join condition",
+        ));
+    }
+
+    for table in &select.from {
+        let factors =
+            std::iter::once(&table.relation).chain(table.joins.iter().map(|j| &j.relation));
+        for factor in factors {
+            if let TableFactor::Derived { subquery, .. } = factor {
+                lint_query(subquery, warnings);
+            }
+        }
+    }
+}
+
+fn parser_dialect(dialect: &str) -> Box<dyn Dialect> {
+    match dialect {
+        "postgres" | "postgresql" => Box::new(PostgreSqlDialect {}),
+        "sqlite" | "sqlite3" => Box::new(SQLiteDialect {}),
+        "clickhouse" => Box::new(ClickHouseDialect {}),
+        "mysql" => Box::new(MySqlDialect {}),
+        _ => Box::new(GenericDialect {}),
+    }
+}
+
+/// Tables written by the INSERT, UPDATE, DELETE, TRUNCATE and ALTER TABLE statements in
+/// `query`, in order of first appearance. Tables only read from, such as those in a
+/// subquery, are not included. Best effort: a query that does not parse, or is not SQL,
+/// yields an empty list.
+pub fn affected_tables(query: &str, dialect: &str) -> Vec<String> {
+    let dialect = dialect.to_lowercase();
+    if !SQL_DIALECTS.contains(&dialect.as_str()) {
+        return Vec::new();
+    }
+    let Ok(statements) = Parser::parse_sql(parser_dialect(&dialect).as_ref(), query) else {
+        return Vec::new();
+    };
+
+    let mut tables: Vec<String> = Vec::new();
+    for statement in &statements {
+        let targets: Vec<&ObjectName> = match statement {
+            Statement::Insert(insert) => vec![&insert.table_name],
+            Statement::Update { table, .. } => {
+                table_factor_name(&table.relation).into_iter().collect()
+            }
+            Statement::Delete(delete) if !delete.tables.is_empty() => {
+                delete.tables.iter().collect()
+            }
+            Statement::Delete(delete) => match &delete.from {
+                FromTable::WithFromKeyword(from) | FromTable::WithoutKeyword(from) => from
+                    .iter()
+                    .filter_map(|table| table_factor_name(&table.relation))
+                    .collect(),
+            },
+            Statement::Truncate { table_names, .. } => {
+                table_names.iter().map(|target| &target.name).collect()
+            }
+            Statement::AlterTable { name, .. } => vec![name],
+            _ => Vec::new(),
+        };
+        for target in targets {
+            let name = target
+                .0
+                .iter()
+                .map(|ident| ident.value.as_str())
+                .collect::<Vec<_>>()
+                .join(".");
+            if !tables.contains(&name) {
+                tables.push(name);
+            }
+        }
+    }
+    tables
+}
+
+fn table_factor_name(factor: &TableFactor) -> Option<&ObjectName> {
+    match factor {
+        TableFactor::Table { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// A row found on both sides of `compare_results` whose values differ
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangedRow {
+    pub left: Value,
+    pub right: Value,
+    /// Columns whose values differ, in the left row's column order
+    pub columns: Vec<String>,
+}
+
+/// Differences between two result sets, as reported by `compare_results`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ResultComparison {
+    /// Rows only in the left result
+    pub only_left: Vec<Value>,
+    /// Rows only in the right result
+    pub only_right: Vec<Value>,
+    pub changed: Vec<ChangedRow>,
+    /// Rows equal on both sides
+    pub unchanged: usize,
+}
+
+/// Diff two fetched results, e.g. of a query and its rewrite. Rows are matched by
+/// `key_columns` regardless of order; without key columns whole rows are matched, so
+/// only added and removed rows are reported. Rows sharing a key are paired in order.
+#[tauri::command]
+pub fn compare_results(
+    left: QueryResult,
+    right: QueryResult,
+    key_columns: Vec<String>,
+) -> Result<ResultComparison, String> {
+    for (side, result) in [("left", &left), ("right", &right)] {
+        if let Some(error) = &result.error {
+            return Err(format!("The {} result has an error: {}", side, error));
+        }
+        let missing = result.data.first().and_then(|row| {
+            key_columns
+                .iter()
+                .find(|column| row.get(column.as_str()).is_none())
+        });
+        if let Some(column) = missing {
+            return Err(format!(
+                "Key column '{}' is not in the {} result",
+                column, side
+            ));
+        }
+    }
+
+    let row_key = |row: &Value| -> String {
+        if key_columns.is_empty() {
+            return row.to_string();
+        }
+        let key: Vec<&Value> = key_columns
+            .iter()
+            .map(|column| row.get(column.as_str()).unwrap_or(&Value::Null))
+            .collect();
+        serde_json::to_string(&key).unwrap_or_default()
+    };
+
+    let mut right_by_key: HashMap<String, Vec<Value>> = HashMap::new();
+    for row in right.data.iter().rev() {
+        right_by_key
+            .entry(row_key(row))
+            .or_default()
+            .push(row.clone());
+    }
+
+    let mut comparison = ResultComparison {
+        only_left: Vec::new(),
+        only_right: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+    };
+    for row in left.data {
+        // Rows were pushed in reverse, so popping pairs duplicates in order
+        match right_by_key.get_mut(&row_key(&row)).and_then(Vec::pop) {
+            Some(other) if other == row => comparison.unchanged += 1,
+            Some(other) => {
+                let columns = changed_columns(&row, &other);
+                comparison.changed.push(ChangedRow {
+                    left: row,
+                    right: other,
+                    columns,
+                });
+            }
+            None => comparison.only_left.push(row),
+        }
+    }
+
+    // Whatever is left unmatched on the right, in its original order
+    for row in right.data {
+        if let Some(rows) = right_by_key.get_mut(&row_key(&row)) {
+            if let Some(position) = rows.iter().rposition(|r| *r == row) {
+                comparison.only_right.push(rows.remove(position));
+            }
+        }
+    }
+
+    Ok(comparison)
+}
+
+/// Columns whose values differ between two rows, including columns only one row has
+fn changed_columns(left: &Value, right: &Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let left = left.as_object().unwrap_or(&empty);
+    let right = right.as_object().unwrap_or(&empty);
+
+    left.keys()
+        .chain(right.keys().filter(|key| !left.contains_key(*key)))
+        .filter(|key| left.get(*key) != right.get(*key))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,456 @@
+//! Table export and import commands.
+
+use super::database::{
+    create_driver, escape_sql_identifier, is_read_only_query, row_lookup_table_ref,
+    ConnectionPolicy,
+};
+use crate::database::er_diagram;
+use crate::database::export::{
+    write_rows_parquet, CsvExportSummary, ExportManager, ParquetWriter, PARQUET_ROW_GROUP_SIZE,
+};
+use crate::database::import::{
+    import_pasted_data, is_gzip_input, CsvImportSummary, PastedImportSummary,
+};
+use crate::database::query_cache;
+use crate::database::DatabaseDriver;
+use sqlx::SqlitePool;
+use tauri::State;
+
+/// Export a table to a CSV file. With an `export_id`, the export can be stopped early by
+/// `cancel_export`.
+#[tauri::command]
+pub async fn unified_export_table_csv(
+    export_manager: State<'_, ExportManager>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    output_path: String,
+    export_id: Option<String>,
+) -> Result<CsvExportSummary, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let Some(export_id) = export_id else {
+        let cancel = tokio_util::sync::CancellationToken::new();
+        return driver
+            .export_table_csv(&schema, &table, &output_path, &cancel)
+            .await;
+    };
+
+    let handle = export_manager.start(&export_id).await;
+    let result = driver
+        .export_table_csv(&schema, &table, &output_path, &handle.cancel)
+        .await;
+    let rows_written = result.as_ref().ok().map(|summary| summary.rows_written);
+    export_manager
+        .finish(&export_id, handle, rows_written)
+        .await;
+    result
+}
+
+/// Stop a running export once its current batch is written, returning the number of rows
+/// in the partial file. Returns `None` if no export with that id is running.
+#[tauri::command]
+pub async fn cancel_export(
+    export_manager: State<'_, ExportManager>,
+    export_id: String,
+) -> Result<Option<u64>, String> {
+    Ok(export_manager.cancel(&export_id).await)
+}
+
+/// Run a query and write its result set to a Parquet file, returning the number of rows.
+/// The schema comes from the query's column types and rows are streamed one row group
+/// at a time; drivers that cannot describe a query fall back to inferring types from
+/// the fetched rows.
+#[tauri::command]
+pub async fn export_query_parquet(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    query: String,
+    output_path: String,
+) -> Result<usize, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    let columns = driver.describe_query(&query).await?;
+    if columns.is_empty() {
+        let result = driver.execute_query(&query).await?;
+        if let Some(error) = result.error {
+            return Err(error);
+        }
+        return tokio::task::spawn_blocking(move || write_rows_parquet(&result.data, &output_path))
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    // Encoding and file writes happen on a blocking thread; the bounded channel keeps
+    // only a couple of batches in flight
+    let (sender, receiver) = std::sync::mpsc::sync_channel::<Vec<serde_json::Value>>(2);
+    let writer = tokio::task::spawn_blocking(move || {
+        let mut writer = ParquetWriter::create(&output_path, &columns)?;
+        for rows in receiver {
+            writer.write_rows(&rows)?;
+        }
+        writer.finish()
+    });
+
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let streamed = driver
+        .stream_query(&query, PARQUET_ROW_GROUP_SIZE, &cancel, &mut |rows| {
+            // The writer only hangs up after a failed write
+            if sender.send(rows).is_err() {
+                cancel.cancel();
+            }
+        })
+        .await;
+    drop(sender);
+
+    let rows_written = writer.await.map_err(|e| e.to_string())??;
+    streamed?;
+    Ok(rows_written)
+}
+
+/// Snapshot a query's result set into a new table with `CREATE TABLE ... AS`,
+/// returning the number of rows in the created table.
+/// An existing target is an error unless `replace` is set, in which case it is replaced
+/// only once the new table has been created.
+#[tauri::command]
+pub async fn materialize_query(
+    sqlite_pool: State<'_, SqlitePool>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    query: String,
+    target_schema: String,
+    target_table: String,
+    replace: Option<bool>,
+    connection_uuid: Option<String>,
+) -> Result<i64, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
+    let query = query.trim().trim_end_matches(';').trim();
+    if !is_read_only_query(query, &db_type) {
+        return Err("Only SELECT queries can be materialized into a table".to_string());
+    }
+    if target_table.trim().is_empty() {
+        return Err("Target table name is required".to_string());
+    }
+
+    let target = match db_type.as_str() {
+        "postgres" | "postgresql" => format!(
+            "\"{}\".\"{}\"",
+            escape_sql_identifier(&target_schema),
+            escape_sql_identifier(&target_table)
+        ),
+        "sqlite" | "sqlite3" => format!("\"{}\"", escape_sql_identifier(&target_table)),
+        "clickhouse" => format!(
+            "`{}`.`{}`",
+            target_schema.replace('`', "``"),
+            target_table.replace('`', "``")
+        ),
+        _ => {
+            return Err(format!(
+                "Materializing queries is not supported for {}",
+                db_type
+            ))
+        }
+    };
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    let exists = driver.list_tables().await?.iter().any(|t| {
+        t.name == target_table && (db_type.starts_with("sqlite") || t.schema == target_schema)
+    });
+    if exists && !replace.unwrap_or(false) {
+        return Err(format!("Table '{}' already exists", target_table));
+    }
+
+    if db_type == "clickhouse" {
+        materialize_clickhouse(
+            driver.as_ref(),
+            query,
+            &target_schema,
+            &target_table,
+            exists,
+        )
+        .await?;
+    } else {
+        // DDL is transactional here, so a failed query leaves the existing table in place
+        let mut tx = driver.begin_transaction().await?;
+        if exists {
+            tx.execute(&format!("DROP TABLE {}", target)).await?;
+        }
+        tx.execute(&format!("CREATE TABLE {} AS {}", target, query))
+            .await?;
+        tx.commit().await?;
+    }
+
+    let result = driver
+        .execute_query(&format!("SELECT COUNT(*) AS row_count FROM {}", target))
+        .await?;
+    if let Some(error) = result.error {
+        return Err(error);
+    }
+    // ClickHouse quotes 64-bit integers in JSON output
+    let row_count = &result
+        .data
+        .first()
+        .ok_or("Row count query returned no rows")?["row_count"];
+    row_count
+        .as_i64()
+        .or_else(|| row_count.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| format!("Unexpected row count: {}", row_count))
+}
+
+/// ClickHouse has no transactional DDL: the result is built under a temporary name and
+/// swapped in with `EXCHANGE TABLES`, so the existing table is only replaced once the
+/// new one is complete. A plain MergeTree keeps insertion order.
+async fn materialize_clickhouse(
+    driver: &dyn DatabaseDriver,
+    query: &str,
+    target_schema: &str,
+    target_table: &str,
+    exists: bool,
+) -> Result<(), String> {
+    let quote = |table: &str| {
+        format!(
+            "`{}`.`{}`",
+            target_schema.replace('`', "``"),
+            table.replace('`', "``")
+        )
+    };
+    let target = quote(target_table);
+    let run = |sql: String| async move {
+        match driver.execute_query(&sql).await?.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    };
+    let create = |table: &str| {
+        format!(
+            "CREATE TABLE {} ENGINE = MergeTree ORDER BY tuple() AS {}",
+            table, query
+        )
+    };
+
+    if !exists {
+        return run(create(&target)).await;
+    }
+
+    let staging = quote(&format!(
+        "{}_materialize_{}",
+        target_table,
+        uuid::Uuid::new_v4().simple()
+    ));
+    if let Err(e) = run(create(&staging)).await {
+        let _ = run(format!("DROP TABLE IF EXISTS {}", staging)).await;
+        return Err(e);
+    }
+    let swapped = run(format!("EXCHANGE TABLES {} AND {}", staging, target)).await;
+    // After the swap the staging name holds the old table
+    let _ = run(format!("DROP TABLE IF EXISTS {}", staging)).await;
+    swapped
+}
+
+/// Import a CSV file with a header row into an existing table. Gzip-compressed files
+/// (`compressed`, or a `.gz` name) are decompressed while reading.
+#[tauri::command]
+pub async fn import_table_csv(
+    sqlite_pool: State<'_, SqlitePool>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    schema: String,
+    table: String,
+    csv_path: String,
+    compressed: Option<bool>,
+    connection_uuid: Option<String>,
+) -> Result<CsvImportSummary, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+
+    let table_ref = row_lookup_table_ref(&db_type, &schema, &table);
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+
+    driver
+        .import_table_csv(&table_ref, &csv_path, is_gzip_input(&csv_path, compressed))
+        .await
+}
+
+/// Create `target_table` from pasted CSV (with a header row) or a JSON array of objects,
+/// inferring each column's type from its values, and insert the rows in one transaction
+#[tauri::command]
+pub async fn import_clipboard_data(
+    sqlite_pool: State<'_, SqlitePool>,
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+    data: String,
+    format: String,
+    target_table: String,
+    connection_uuid: Option<String>,
+) -> Result<PastedImportSummary, String> {
+    ConnectionPolicy::load(sqlite_pool.inner(), connection_uuid.as_deref())
+        .await?
+        .check_write()?;
+    let _cache_guard = query_cache::write_guard(query_cache::target(
+        &db_type,
+        host.as_deref(),
+        port,
+        database.as_deref(),
+        file_path.as_deref(),
+    ));
+    if !matches!(
+        db_type.as_str(),
+        "postgres" | "postgresql" | "sqlite" | "sqlite3"
+    ) {
+        return Err(format!(
+            "Importing pasted data is not supported for {}",
+            db_type
+        ));
+    }
+    if target_table.trim().is_empty() {
+        return Err("Target table name is required".to_string());
+    }
+
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    let table_ref = format!("\"{}\"", escape_sql_identifier(&target_table));
+    import_pasted_data(driver.as_ref(), &db_type, &table_ref, &data, &format).await
+}
+
+/// Mermaid `erDiagram` of the database's tables, columns and foreign keys, ready to paste
+/// into Markdown
+#[tauri::command]
+pub async fn export_er_mermaid(
+    db_type: String,
+    host: Option<String>,
+    port: Option<i64>,
+    database: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    ssl: Option<bool>,
+    file_path: Option<String>,
+    socket_path: Option<String>,
+    session_setup_sql: Option<String>,
+) -> Result<String, String> {
+    let driver = create_driver(
+        &db_type,
+        host,
+        port,
+        database,
+        username,
+        password,
+        ssl,
+        file_path,
+        socket_path,
+        session_setup_sql,
+    )?;
+    let overview = driver.get_schema_overview().await?;
+    Ok(er_diagram::to_mermaid(&overview))
+}
@@ -1,7 +1,13 @@
 pub mod ai;
+pub mod bundle;
 pub mod connections;
 pub mod database;
+pub mod diagnostics;
+pub mod editor;
+pub mod export;
 pub mod pool;
 pub mod postgres;
 pub mod queries;
 pub mod settings;
+pub mod slow_queries;
+pub mod snippets;
@@ -2,11 +2,19 @@
 //!
 //! Commands for managing the connection pool: connect, disconnect, status, health check.
 
+use crate::commands::connections::{
+    resolve_page_size, spawn_record_connection_opened, spawn_record_query_executed,
+};
+use crate::commands::database::{is_read_only_query, substitute_query_variables, READ_ONLY_ERROR};
+use crate::commands::editor::affected_tables;
+use crate::commands::settings::{load_connection_variables, spawn_store_last_query};
+use crate::commands::slow_queries::spawn_record_if_slow;
 use crate::database::pool_manager::{ConnectionConfig, ConnectionStatus, PoolManager};
+use crate::database::{query_cache, timeouts};
 use crate::db::models::TestConnectionResult;
 use serde::{Deserialize, Serialize};
 use sqlx::SqlitePool;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 /// Response for connection status
 #[derive(Serialize, Deserialize)]
@@ -61,13 +69,20 @@ pub async fn pool_connect(
         } else {
             Some(conn.ssh_key_path)
         },
+        ssh_use_agent: conn.ssh_use_agent == 1,
+        session_setup_sql: conn.session_setup_sql,
+        max_concurrent_queries: conn.max_concurrent_queries,
+        socket_path: conn.socket_path,
     };
 
     match pool_manager.connect(&uuid, config).await {
-        Ok(_) => Ok(ConnectionStatusResponse {
-            status: ConnectionStatus::Connected,
-            error: None,
-        }),
+        Ok(_) => {
+            spawn_record_connection_opened(sqlite_pool.inner(), &uuid);
+            Ok(ConnectionStatusResponse {
+                status: ConnectionStatus::Connected,
+                error: None,
+            })
+        }
         Err(e) => Ok(ConnectionStatusResponse {
             status: ConnectionStatus::Disconnected,
             error: Some(e),
@@ -148,6 +163,10 @@ async fn get_connection_config(
         } else {
             Some(conn.ssh_key_path)
         },
+        ssh_use_agent: conn.ssh_use_agent == 1,
+        session_setup_sql: conn.session_setup_sql,
+        max_concurrent_queries: conn.max_concurrent_queries,
+        socket_path: conn.socket_path,
     })
 }
 
@@ -168,6 +187,7 @@ async fn ensure_connection(
     // Not connected, get config and connect
     let config = get_connection_config(sqlite_pool, uuid).await?;
     pool_manager.connect(uuid, config).await?;
+    spawn_record_connection_opened(sqlite_pool, uuid);
     Ok(())
 }
 
@@ -186,6 +206,7 @@ async fn reconnect(
     // Reconnect
     let config = get_connection_config(sqlite_pool, uuid).await?;
     pool_manager.connect(uuid, config).await?;
+    spawn_record_connection_opened(sqlite_pool, uuid);
     Ok(())
 }
 
@@ -214,6 +235,18 @@ pub async fn pool_list_tables(
     }
 }
 
+/// PID of a Postgres backend in the pooled connection that pooled queries run on, for
+/// `pg_stat_activity` and `pg_cancel_backend` (auto-connects if needed)
+#[tauri::command]
+pub async fn pool_get_backend_pid(
+    pool_manager: State<'_, PoolManager>,
+    sqlite_pool: State<'_, SqlitePool>,
+    uuid: String,
+) -> Result<i64, String> {
+    ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+    pool_manager.backend_pid(&uuid).await
+}
+
 /// Get table data using the pooled connection (auto-connects if needed, auto-retries on error)
 #[tauri::command]
 pub async fn pool_get_table_data(
@@ -223,11 +256,12 @@ pub async fn pool_get_table_data(
     schema: String,
     table: String,
     page: i64,
-    limit: i64,
+    limit: Option<i64>,
     filter: Option<String>,
     sort_column: Option<String>,
     sort_direction: Option<String>,
 ) -> Result<crate::db::models::TableDataResponse, String> {
+    let limit = resolve_page_size(sqlite_pool.inner(), &uuid, limit).await?;
     ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
 
     match pool_manager
@@ -280,24 +314,65 @@ pub async fn pool_get_table_structure(
 /// Execute query using the pooled connection (auto-connects if needed, auto-retries on error)
 #[tauri::command]
 pub async fn pool_execute_query(
+    app: AppHandle,
     pool_manager: State<'_, PoolManager>,
     sqlite_pool: State<'_, SqlitePool>,
     uuid: String,
     query: String,
+    use_variables: Option<bool>,
+    timeout_secs: Option<i64>,
 ) -> Result<crate::db::models::QueryResult, String> {
-    ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+    let (db_type, read_only, query_timeout_secs): (String, i64, Option<i64>) = sqlx::query_as(
+        "SELECT db_type, read_only, query_timeout_secs FROM connections WHERE uuid = ?",
+    )
+    .bind(&uuid)
+    .fetch_one(sqlite_pool.inner())
+    .await
+    .map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    spawn_store_last_query(sqlite_pool.inner(), &uuid, &query);
+
+    let query = if use_variables.unwrap_or(false) {
+        let variables = load_connection_variables(sqlite_pool.inner(), &uuid).await?;
+        substitute_query_variables(&query, &variables, &db_type)?
+    } else {
+        query
+    };
 
-    match pool_manager.execute_query(&uuid, &query).await {
-        Ok(result) => Ok(result),
-        Err(e) => {
-            println!(
-                "[Pool] execute_query failed: {}, retrying with fresh connection",
-                e
-            );
-            reconnect(&pool_manager, sqlite_pool.inner(), &uuid).await?;
-            pool_manager.execute_query(&uuid, &query).await
+    if read_only == 1 && !is_read_only_query(&query, &db_type) {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+
+    ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+    let _cache_guard = match pool_manager.get_config(&uuid).await {
+        Some(config) if !query_cache::is_cacheable(&query, &db_type) => {
+            Some(query_cache::write_guard(query_cache::target_for_config(&config)))
+        }
+        _ => None,
+    };
+    spawn_record_query_executed(sqlite_pool.inner(), &uuid);
+
+    let limit = timeouts::resolve_query_timeout(timeout_secs, query_timeout_secs);
+    let mut result = timeouts::with_timeout(limit, async {
+        match pool_manager.execute_query(&uuid, &query).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                println!(
+                    "[Pool] execute_query failed: {}, retrying with fresh connection",
+                    e
+                );
+                reconnect(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+                pool_manager.execute_query(&uuid, &query).await
+            }
         }
+    })
+    .await?;
+    if result.error.is_none() {
+        result.affected_tables = affected_tables(&query, &db_type);
     }
+
+    spawn_record_if_slow(&app, sqlite_pool.inner(), &uuid, &query, &result);
+    Ok(result)
 }
 
 /// Get schema overview using the pooled connection (auto-connects if needed, auto-retries on error)
@@ -326,13 +401,18 @@ pub async fn pool_get_schema_overview(
 // Row editing commands (UPDATE/DELETE/INSERT) using connection pool
 // ============================================================================
 
-use crate::commands::database::{escape_sql_identifier, format_sql_value, validate_raw_sql_value};
+use crate::commands::database::{
+    connection_identity, escape_sql_identifier, format_sql_value, key_after_update,
+    validate_raw_sql_value, EditedRow,
+};
+use crate::database::undo::UndoHistory;
 
 /// Update a row in a table using the pooled connection
 #[tauri::command]
 pub async fn pool_update_table_row(
     pool_manager: State<'_, PoolManager>,
     sqlite_pool: State<'_, SqlitePool>,
+    undo_history: State<'_, UndoHistory>,
     uuid: String,
     schema: String,
     table: String,
@@ -356,6 +436,10 @@ pub async fn pool_update_table_row(
             .await
             .map_err(|e| format!("Failed to get connection: {}", e))?;
 
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+
     let db_type = &conn.db_type;
 
     // Build the UPDATE query
@@ -371,6 +455,8 @@ pub async fn pool_update_table_row(
 
     // Extract columns and values from the updates array
     let mut set_parts: Vec<String> = Vec::new();
+    let mut written = serde_json::Map::new();
+    let mut raw_columns: Vec<&str> = Vec::new();
 
     for update_obj in updates.iter() {
         let update_map = update_obj
@@ -391,8 +477,10 @@ pub async fn pool_update_table_row(
             let raw_value = value.as_str().ok_or("Raw SQL value must be a string")?;
             validate_raw_sql_value(raw_value, db_type)
                 .map_err(|e| format!("Invalid raw SQL value: {}", e))?;
+            raw_columns.push(column);
             raw_value.to_string()
         } else {
+            written.insert(column.to_string(), value.clone());
             format_sql_value(value)
         };
 
@@ -422,18 +510,50 @@ pub async fn pool_update_table_row(
     );
 
     ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+    let _cache_guard = pool_manager
+        .get_config(&uuid)
+        .await
+        .map(|config| query_cache::write_guard(query_cache::target_for_config(&config)));
+
+    // Keep the values being replaced so the edit can be undone
+    let edited = EditedRow {
+        db_type,
+        identity: connection_identity(&conn),
+        schema: &schema,
+        table: &table,
+        table_ref: &table_ref,
+        where_clause: &where_clause,
+    };
+    let undo_entry = match pool_manager.get_cached(&uuid).await {
+        Some(driver) => {
+            let mut columns: Vec<&str> = written.keys().map(String::as_str).collect();
+            columns.extend(&raw_columns);
+            let key = key_after_update(
+                &primary_key_columns,
+                &primary_key_values,
+                &written,
+                &raw_columns,
+            );
+            edited.before_update(&**driver, &columns, key).await
+        }
+        None => None,
+    };
 
-    match pool_manager.execute_query(&uuid, &query).await {
-        Ok(result) => Ok(result),
+    let result = match pool_manager.execute_query(&uuid, &query).await {
+        Ok(result) => result,
         Err(e) => {
             println!(
                 "[Pool] update_table_row failed: {}, retrying with fresh connection",
                 e
             );
             reconnect(&pool_manager, sqlite_pool.inner(), &uuid).await?;
-            pool_manager.execute_query(&uuid, &query).await
+            pool_manager.execute_query(&uuid, &query).await?
         }
+    };
+    if let Some(entry) = undo_entry.filter(|_| result.error.is_none()) {
+        undo_history.record(&uuid, entry);
     }
+    Ok(result)
 }
 
 /// Delete a row from a table using the pooled connection
@@ -441,6 +561,7 @@ pub async fn pool_update_table_row(
 pub async fn pool_delete_table_row(
     pool_manager: State<'_, PoolManager>,
     sqlite_pool: State<'_, SqlitePool>,
+    undo_history: State<'_, UndoHistory>,
     uuid: String,
     schema: String,
     table: String,
@@ -459,6 +580,10 @@ pub async fn pool_delete_table_row(
             .await
             .map_err(|e| format!("Failed to get connection: {}", e))?;
 
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+
     let db_type = &conn.db_type;
 
     // Build the DELETE query
@@ -486,21 +611,43 @@ pub async fn pool_delete_table_row(
     let query = format!("DELETE FROM {} WHERE {}", table_ref, where_clause);
 
     ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+    let _cache_guard = pool_manager
+        .get_config(&uuid)
+        .await
+        .map(|config| query_cache::write_guard(query_cache::target_for_config(&config)));
+
+    // Keep the whole row so the delete can be undone
+    let edited = EditedRow {
+        db_type,
+        identity: connection_identity(&conn),
+        schema: &schema,
+        table: &table,
+        table_ref: &table_ref,
+        where_clause: &where_clause,
+    };
+    let undo_entry = match pool_manager.get_cached(&uuid).await {
+        Some(driver) => edited.before_delete(&**driver).await,
+        None => None,
+    };
 
-    match pool_manager.execute_query(&uuid, &query).await {
-        Ok(result) => Ok(result),
+    let result = match pool_manager.execute_query(&uuid, &query).await {
+        Ok(result) => result,
         Err(e) => {
             println!(
                 "[Pool] delete_table_row failed: {}, retrying with fresh connection",
                 e
             );
             reconnect(&pool_manager, sqlite_pool.inner(), &uuid).await?;
-            pool_manager.execute_query(&uuid, &query).await
+            pool_manager.execute_query(&uuid, &query).await?
         }
+    };
+    if let Some(entry) = undo_entry.filter(|_| result.error.is_none()) {
+        undo_history.record(&uuid, entry);
     }
+    Ok(result)
 }
 
-/// Insert a new row into a table using the pooled connection
+/// Insert a new row into a table using the pooled connection, returning the inserted row
 #[tauri::command]
 pub async fn pool_insert_table_row(
     pool_manager: State<'_, PoolManager>,
@@ -522,6 +669,10 @@ pub async fn pool_insert_table_row(
             .await
             .map_err(|e| format!("Failed to get connection: {}", e))?;
 
+    if conn.read_only == 1 {
+        return Err(READ_ONLY_ERROR.to_string());
+    }
+
     let db_type = &conn.db_type;
 
     // Build the INSERT query
@@ -538,6 +689,7 @@ pub async fn pool_insert_table_row(
     // Extract columns and values from the values array
     let mut columns: Vec<String> = Vec::new();
     let mut value_parts: Vec<String> = Vec::new();
+    let mut inserted: Vec<(String, String)> = Vec::new();
 
     for value_obj in values.iter() {
         let value_map = value_obj
@@ -565,6 +717,7 @@ pub async fn pool_insert_table_row(
             format_sql_value(value)
         };
 
+        inserted.push((column.to_string(), formatted_value.clone()));
         value_parts.push(formatted_value);
     }
 
@@ -577,8 +730,15 @@ pub async fn pool_insert_table_row(
     );
 
     ensure_connection(&pool_manager, sqlite_pool.inner(), &uuid).await?;
+    let _cache_guard = pool_manager
+        .get_config(&uuid)
+        .await
+        .map(|config| query_cache::write_guard(query_cache::target_for_config(&config)));
 
-    match pool_manager.execute_query(&uuid, &query).await {
+    match pool_manager
+        .execute_returning(&uuid, &schema, &table, &query, &inserted)
+        .await
+    {
         Ok(result) => Ok(result),
         Err(e) => {
             println!(
@@ -586,7 +746,9 @@ pub async fn pool_insert_table_row(
                 e
             );
             reconnect(&pool_manager, sqlite_pool.inner(), &uuid).await?;
-            pool_manager.execute_query(&uuid, &query).await
+            pool_manager
+                .execute_returning(&uuid, &schema, &table, &query, &inserted)
+                .await
         }
     }
 }
@@ -1,12 +1,29 @@
+use crate::database::postgres::section_or_warning;
+use crate::database::queries::postgres::{CHECK_CONSTRAINTS_QUERY, UNIQUE_CONSTRAINTS_QUERY};
+use crate::database::{query_cache, timeouts};
 use crate::db::models::{
-    ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, TableDataResponse, TableInfo,
-    TableStructure, TestConnectionResult,
+    CheckConstraint, ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, TableDataResponse,
+    TableInfo, TableStructure, TestConnectionResult, UniqueConstraint,
 };
 use crate::ssh_tunnel::SshTunnel;
 use serde_json::{json, Value};
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{Column, Row, TypeInfo};
 
+/// Row shape for column metadata: name, type, nullable, default, primary key, comment, enum values,
+/// auto increment, generated
+type ColumnRow = (
+    String,
+    String,
+    bool,
+    Option<String>,
+    bool,
+    Option<String>,
+    Option<Vec<String>>,
+    bool,
+    bool,
+);
+
 fn build_connection_string(
     host: &str,
     port: i64,
@@ -37,6 +54,7 @@ pub async fn test_connection(
     ssh_password: Option<String>,
     ssh_key_path: Option<String>,
     ssh_use_key: Option<bool>,
+    ssh_use_agent: Option<bool>,
 ) -> Result<TestConnectionResult, String> {
     let _tunnel: Option<SshTunnel>;
     println!(
@@ -50,6 +68,7 @@ pub async fn test_connection(
         let ssh_password_val = ssh_password.unwrap_or_default();
         let ssh_key_path_val = ssh_key_path.unwrap_or_default();
         let use_key = ssh_use_key.unwrap_or(false);
+        let use_agent = ssh_use_agent.unwrap_or(false);
 
         let key_path = if use_key && !ssh_key_path_val.is_empty() {
             Some(ssh_key_path_val.as_str())
@@ -62,15 +81,17 @@ pub async fn test_connection(
             None
         };
 
-        // Use a 20 second timeout for SSH tunnel creation (can take longer due to network/auth)
+        // SSH tunnel creation can take longer due to network/auth
+        let ssh_timeout = timeouts::ssh_tunnel_timeout();
         match tokio::time::timeout(
-            std::time::Duration::from_secs(20),
+            ssh_timeout,
             SshTunnel::new(
                 &ssh_host_val,
                 ssh_port_val,
                 &ssh_user_val,
                 password_opt,
                 key_path,
+                use_agent,
                 &host,
                 port as u16,
             ),
@@ -91,7 +112,10 @@ pub async fn test_connection(
             Err(_) => {
                 return Ok(TestConnectionResult {
                     success: false,
-                    message: "SSH tunnel connection timed out after 20 seconds".to_string(),
+                    message: format!(
+                        "SSH tunnel connection timed out after {} seconds",
+                        ssh_timeout.as_secs()
+                    ),
                 });
             }
         }
@@ -109,9 +133,10 @@ pub async fn test_connection(
         ssl,
     );
 
-    // Use a 10 second timeout for connection (longer for SSH tunnel overhead)
+    // Defaults to 15 seconds (longer for SSH tunnel overhead)
+    let connect_timeout = timeouts::connect_timeout(15);
     match tokio::time::timeout(
-        std::time::Duration::from_secs(15),
+        connect_timeout,
         PgPoolOptions::new()
             .max_connections(1)
             .acquire_timeout(std::time::Duration::from_secs(8))
@@ -139,7 +164,10 @@ pub async fn test_connection(
         }),
         Err(_) => Ok(TestConnectionResult {
             success: false,
-            message: "Connection timed out after 10 seconds".to_string(),
+            message: format!(
+                "Connection timed out after {} seconds",
+                connect_timeout.as_secs()
+            ),
         }),
     }
 }
@@ -161,18 +189,33 @@ pub async fn list_tables(
         .await
         .map_err(|e| e.to_string())?;
 
-    let tables = sqlx::query_as::<_, (String, String, String)>(
+    let tables = sqlx::query_as::<_, (String, String, String, Option<String>)>(
         r#"
-        SELECT 
+        SELECT
             table_schema as schema,
             table_name as name,
-            CASE 
-                WHEN table_type = 'BASE TABLE' THEN 'table'
-                WHEN table_type = 'VIEW' THEN 'view'
-                ELSE 'table'
-            END as type
-        FROM information_schema.tables
-        WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+            table_type as type,
+            obj_description(
+                format('%I.%I', table_schema, table_name)::regclass,
+                'pg_class'
+            ) as comment
+        FROM (
+            SELECT
+                table_schema,
+                table_name,
+                CASE
+                    WHEN table_type = 'BASE TABLE' THEN 'table'
+                    WHEN table_type = 'VIEW' THEN 'view'
+                    WHEN table_type = 'FOREIGN' THEN 'foreign_table'
+                    ELSE 'table'
+                END as table_type
+            FROM information_schema.tables
+            WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+            UNION ALL
+            SELECT schemaname, matviewname, 'materialized_view'
+            FROM pg_matviews
+            WHERE schemaname NOT IN ('pg_catalog', 'information_schema')
+        ) t
         ORDER BY table_schema, table_name
         "#,
     )
@@ -184,10 +227,12 @@ pub async fn list_tables(
 
     Ok(tables
         .into_iter()
-        .map(|(schema, name, table_type)| TableInfo {
+        .map(|(schema, name, table_type, comment)| TableInfo {
             schema,
             name,
             table_type,
+            comment,
+            engine_full: None,
         })
         .collect())
 }
@@ -345,7 +390,7 @@ pub async fn get_table_structure(
         .await
         .map_err(|e| e.to_string())?;
 
-    let columns = sqlx::query_as::<_, (String, String, bool, Option<String>, bool)>(
+    let columns = sqlx::query_as::<_, ColumnRow>(
         r#"
         SELECT
             c.column_name as name,
@@ -360,7 +405,21 @@ pub async fn get_table_structure(
                 AND tc.table_name = c.table_name
                 AND kcu.column_name = c.column_name
                 AND tc.constraint_type = 'PRIMARY KEY'
-            ) as primary_key
+            ) as primary_key,
+            col_description(
+                format('%I.%I', c.table_schema, c.table_name)::regclass,
+                c.ordinal_position::int
+            ) as comment,
+            (
+                SELECT array_agg(e.enumlabel ORDER BY e.enumsortorder)::text[]
+                FROM pg_type t
+                JOIN pg_namespace n ON n.oid = t.typnamespace
+                JOIN pg_enum e ON e.enumtypid = t.oid
+                WHERE t.typname = c.udt_name
+                AND n.nspname = c.udt_schema
+            ) as enum_values,
+            (c.is_identity = 'YES' OR COALESCE(c.column_default, '') LIKE 'nextval(%') as auto_increment,
+            c.is_generated = 'ALWAYS' as is_generated
         FROM information_schema.columns c
         WHERE c.table_schema = $1
         AND c.table_name = $2
@@ -373,6 +432,7 @@ pub async fn get_table_structure(
     .await
     .map_err(|e| e.to_string())?;
 
+    let mut warnings = Vec::new();
     let indexes = sqlx::query_as::<_, (String, Vec<String>, bool, bool)>(
         r#"
         SELECT
@@ -393,13 +453,15 @@ pub async fn get_table_structure(
     .bind(&table)
     .fetch_all(&pool)
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| e.to_string());
+    let indexes = section_or_warning("indexes", indexes, &mut warnings)?;
 
-    let foreign_keys = sqlx::query_as::<_, (String, String, String, String)>(
+    let foreign_keys = sqlx::query_as::<_, (String, String, String, String, String)>(
         r#"
         SELECT
             tc.constraint_name as name,
             kcu.column_name as column,
+            ccu.table_schema as references_schema,
             ccu.table_name as references_table,
             ccu.column_name as references_column
         FROM information_schema.table_constraints tc
@@ -416,7 +478,26 @@ pub async fn get_table_structure(
     .bind(&table)
     .fetch_all(&pool)
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(|e| e.to_string());
+    let foreign_keys = section_or_warning("foreign keys", foreign_keys, &mut warnings)?;
+
+    let check_constraints = sqlx::query_as::<_, (String, String)>(CHECK_CONSTRAINTS_QUERY)
+        .bind(&schema)
+        .bind(&table)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string());
+    let check_constraints =
+        section_or_warning("check constraints", check_constraints, &mut warnings)?;
+
+    let unique_constraints = sqlx::query_as::<_, (String, Vec<String>)>(UNIQUE_CONSTRAINTS_QUERY)
+        .bind(&schema)
+        .bind(&table)
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| e.to_string());
+    let unique_constraints =
+        section_or_warning("unique constraints", unique_constraints, &mut warnings)?;
 
     pool.close().await;
 
@@ -424,12 +505,28 @@ pub async fn get_table_structure(
         columns: columns
             .into_iter()
             .map(
-                |(name, data_type, nullable, default, primary_key)| ColumnInfo {
+                |(
                     name,
                     data_type,
                     nullable,
                     default,
                     primary_key,
+                    comment,
+                    enum_values,
+                    auto_increment,
+                    is_generated,
+                )| {
+                    ColumnInfo {
+                        name,
+                        data_type,
+                        nullable,
+                        default,
+                        primary_key,
+                        comment,
+                        enum_values,
+                        auto_increment,
+                        is_generated,
+                    }
                 },
             )
             .collect(),
@@ -445,14 +542,26 @@ pub async fn get_table_structure(
         foreign_keys: foreign_keys
             .into_iter()
             .map(
-                |(name, column, references_table, references_column)| ForeignKeyInfo {
-                    name,
-                    column,
-                    references_table,
-                    references_column,
+                |(name, column, references_schema, references_table, references_column)| {
+                    ForeignKeyInfo {
+                        name,
+                        column,
+                        references_schema,
+                        references_table,
+                        references_column,
+                    }
                 },
             )
             .collect(),
+        check_constraints: check_constraints
+            .into_iter()
+            .map(|(name, expression)| CheckConstraint { name, expression })
+            .collect(),
+        unique_constraints: unique_constraints
+            .into_iter()
+            .map(|(name, columns)| UniqueConstraint { name, columns })
+            .collect(),
+        warnings,
     })
 }
 
@@ -466,6 +575,15 @@ pub async fn execute_query(
     ssl: bool,
     query: String,
 ) -> Result<QueryResult, String> {
+    let _cache_guard = (!query_cache::is_cacheable(&query, "postgres")).then(|| {
+        query_cache::write_guard(query_cache::target(
+            "postgres",
+            Some(&host),
+            Some(port),
+            Some(&database),
+            None,
+        ))
+    });
     let start_time = std::time::Instant::now();
     let conn_str = build_connection_string(&host, port, &database, &username, &password, ssl);
 
@@ -557,6 +675,7 @@ pub async fn execute_query(
                 row_count,
                 error: None,
                 time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
             })
         }
         Err(e) => {
@@ -566,6 +685,7 @@ pub async fn execute_query(
                 row_count: 0,
                 error: Some(e.to_string()),
                 time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
             })
         }
     }
@@ -1,27 +1,38 @@
-use crate::db::models::{SavedQuery, SavedQueryFormData};
+use crate::commands::database::create_driver_for_connection;
+use crate::db::models::{Connection, SavedQuery, SavedQueryFormData};
 use sqlx::SqlitePool;
 use tauri::State;
 
-#[tauri::command]
-pub async fn get_saved_queries(
-    pool: State<'_, SqlitePool>,
-    connection_uuid: String,
-) -> Result<Vec<SavedQuery>, String> {
-    sqlx::query_as::<_, SavedQuery>(
-        "SELECT * FROM saved_queries WHERE connection_uuid = ? ORDER BY updated_at DESC",
-    )
-    .bind(&connection_uuid)
-    .fetch_all(pool.inner())
-    .await
-    .map_err(|e| e.to_string())
+/// Dry-run a query against a saved connection's database without executing it
+pub async fn validate_query_for_connection(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    query: &str,
+) -> Result<(), String> {
+    let conn: Connection = sqlx::query_as("SELECT * FROM connections WHERE uuid = ?")
+        .bind(connection_uuid)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| format!("Failed to get connection: {}", e))?;
+
+    let (driver, _tunnel) = create_driver_for_connection(pool, &conn).await?;
+    driver
+        .validate_query(query)
+        .await
+        .map_err(|e| format!("Query validation failed: {}", e))
 }
 
-#[tauri::command]
-pub async fn create_saved_query(
-    pool: State<'_, SqlitePool>,
-    connection_uuid: String,
-    data: SavedQueryFormData,
+/// Save a new query, optionally validating it against its connection first
+pub async fn insert_saved_query(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    data: &SavedQueryFormData,
+    validate: bool,
 ) -> Result<SavedQuery, String> {
+    if validate {
+        validate_query_for_connection(pool, connection_uuid, &data.query).await?;
+    }
+
     sqlx::query_as::<_, SavedQuery>(
         r#"
         INSERT INTO saved_queries (connection_uuid, name, query)
@@ -29,20 +40,31 @@ pub async fn create_saved_query(
         RETURNING *
         "#,
     )
-    .bind(&connection_uuid)
+    .bind(connection_uuid)
     .bind(&data.name)
     .bind(&data.query)
-    .fetch_one(pool.inner())
+    .fetch_one(pool)
     .await
     .map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-pub async fn update_saved_query(
-    pool: State<'_, SqlitePool>,
+/// Update a saved query, optionally validating it against its connection first
+pub async fn modify_saved_query(
+    pool: &SqlitePool,
     id: i64,
-    data: SavedQueryFormData,
+    data: &SavedQueryFormData,
+    validate: bool,
 ) -> Result<SavedQuery, String> {
+    if validate {
+        let connection_uuid: String =
+            sqlx::query_scalar("SELECT connection_uuid FROM saved_queries WHERE id = ?")
+                .bind(id)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| e.to_string())?;
+        validate_query_for_connection(pool, &connection_uuid, &data.query).await?;
+    }
+
     sqlx::query_as::<_, SavedQuery>(
         r#"
         UPDATE saved_queries
@@ -54,11 +76,51 @@ pub async fn update_saved_query(
     .bind(&data.name)
     .bind(&data.query)
     .bind(id)
-    .fetch_one(pool.inner())
+    .fetch_one(pool)
     .await
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn get_saved_queries(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+) -> Result<Vec<SavedQuery>, String> {
+    sqlx::query_as::<_, SavedQuery>(
+        "SELECT * FROM saved_queries WHERE connection_uuid = ? ORDER BY updated_at DESC",
+    )
+    .bind(&connection_uuid)
+    .fetch_all(pool.inner())
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_saved_query(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+    data: SavedQueryFormData,
+    validate: Option<bool>,
+) -> Result<SavedQuery, String> {
+    insert_saved_query(
+        pool.inner(),
+        &connection_uuid,
+        &data,
+        validate.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn update_saved_query(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    data: SavedQueryFormData,
+    validate: Option<bool>,
+) -> Result<SavedQuery, String> {
+    modify_saved_query(pool.inner(), id, &data, validate.unwrap_or(false)).await
+}
+
 #[tauri::command]
 pub async fn delete_saved_query(pool: State<'_, SqlitePool>, id: i64) -> Result<bool, String> {
     sqlx::query("DELETE FROM saved_queries WHERE id = ?")
@@ -1,3 +1,5 @@
+use crate::commands::slow_queries;
+use crate::database::{display, reachability, retry, timeouts};
 use crate::db::models::Setting;
 use sqlx::SqlitePool;
 use std::collections::HashMap;
@@ -16,6 +18,12 @@ pub async fn get_setting(pool: State<'_, SqlitePool>, key: String) -> Result<Opt
 
 #[tauri::command]
 pub async fn set_setting(pool: State<'_, SqlitePool>, key: String, value: String) -> Result<(), String> {
+    timeouts::apply_setting(&key, &value)?;
+    retry::apply_setting(&key, &value)?;
+    slow_queries::apply_setting(&key, &value)?;
+    display::apply_setting(&key, &value)?;
+    reachability::apply_setting(&key, &value)?;
+
     sqlx::query("INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)")
         .bind(&key)
         .bind(&value)
@@ -36,3 +44,133 @@ pub async fn get_all_settings(pool: State<'_, SqlitePool>) -> Result<HashMap<Str
     let map: HashMap<String, String> = settings.into_iter().map(|s| (s.key, s.value)).collect();
     Ok(map)
 }
+
+/// Settings key holding the query variables of a connection
+fn connection_variables_key(connection_uuid: &str) -> String {
+    format!("connection_variables:{}", connection_uuid)
+}
+
+/// Load the `{{var}}` values defined for a connection
+pub async fn load_connection_variables(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+) -> Result<HashMap<String, String>, String> {
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(connection_variables_key(connection_uuid))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match value {
+        Some(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// Define or remove (when `value` is `None`) a query variable for a connection
+pub async fn store_connection_variable(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    name: &str,
+    value: Option<String>,
+) -> Result<(), String> {
+    let valid_name = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_name {
+        return Err(format!("Invalid variable name: {}", name));
+    }
+
+    let mut variables = load_connection_variables(pool, connection_uuid).await?;
+    match value {
+        Some(value) => {
+            variables.insert(name.to_string(), value);
+        }
+        None => {
+            variables.remove(name);
+        }
+    }
+
+    let json = serde_json::to_string(&variables).map_err(|e| e.to_string())?;
+    sqlx::query("INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)")
+        .bind(connection_variables_key(connection_uuid))
+        .bind(&json)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn set_connection_variable(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+    name: String,
+    value: Option<String>,
+) -> Result<(), String> {
+    store_connection_variable(pool.inner(), &connection_uuid, &name, value).await
+}
+
+#[tauri::command]
+pub async fn get_connection_variables(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+) -> Result<HashMap<String, String>, String> {
+    load_connection_variables(pool.inner(), &connection_uuid).await
+}
+
+/// Settings key holding the last query executed on a connection
+fn last_query_key(connection_uuid: &str) -> String {
+    format!("last_query:{}", connection_uuid)
+}
+
+/// Remember the last query executed on a connection, replacing the previous one
+pub async fn store_last_query(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    query: &str,
+) -> Result<(), String> {
+    sqlx::query("INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)")
+        .bind(last_query_key(connection_uuid))
+        .bind(query)
+        .execute(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Remember the last query executed on a connection without delaying the caller
+pub fn spawn_store_last_query(pool: &SqlitePool, connection_uuid: &str, query: &str) {
+    let pool = pool.clone();
+    let connection_uuid = connection_uuid.to_string();
+    let query = query.to_string();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = store_last_query(&pool, &connection_uuid, &query).await {
+            println!("[Settings] Failed to save last query: {}", e);
+        }
+    });
+}
+
+/// Load the last query executed on a connection
+pub async fn load_last_query(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+) -> Result<Option<String>, String> {
+    sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(last_query_key(connection_uuid))
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_last_query(
+    pool: State<'_, SqlitePool>,
+    connection_uuid: String,
+) -> Result<Option<String>, String> {
+    load_last_query(pool.inner(), &connection_uuid).await
+}
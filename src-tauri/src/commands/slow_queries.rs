@@ -0,0 +1,137 @@
+//! Slow query log.
+//!
+//! Queries that take longer than the `slow_query_threshold_ms` setting are stored in the
+//! `slow_queries` table and announced with a `slow-query` event. Recording is off until a
+//! threshold is configured.
+
+use crate::db::models::{QueryResult, SlowQuery};
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter, Runtime, State};
+
+/// Settings key for the duration above which a query is logged, in milliseconds
+pub const SLOW_QUERY_THRESHOLD_KEY: &str = "slow_query_threshold_ms";
+
+const DEFAULT_LIMIT: i64 = 100;
+
+// 0 means "not configured"
+static THRESHOLD_MS: AtomicU64 = AtomicU64::new(0);
+
+/// Apply the slow query setting. Other keys are ignored; an empty value turns logging off.
+pub fn apply_setting(key: &str, value: &str) -> Result<(), String> {
+    if key != SLOW_QUERY_THRESHOLD_KEY {
+        return Ok(());
+    }
+    let value = value.trim();
+    let ms = if value.is_empty() {
+        0
+    } else {
+        value
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid value for {}: {}", key, value))?
+    };
+    THRESHOLD_MS.store(ms, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Load the slow query setting from the app database
+pub async fn load_from_settings(pool: &SqlitePool) -> Result<(), String> {
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(SLOW_QUERY_THRESHOLD_KEY)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match value {
+        Some(value) => apply_setting(SLOW_QUERY_THRESHOLD_KEY, &value),
+        None => Ok(()),
+    }
+}
+
+/// The configured threshold, if slow query logging is on
+pub fn threshold_ms() -> Option<u64> {
+    match THRESHOLD_MS.load(Ordering::SeqCst) {
+        0 => None,
+        ms => Some(ms),
+    }
+}
+
+/// Store a query in the slow query log when it took longer than the threshold.
+/// Returns the stored entry, or `None` when the query was fast enough.
+pub async fn record_if_slow(
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    query: &str,
+    time_taken_ms: Option<u128>,
+) -> Result<Option<SlowQuery>, String> {
+    let (Some(threshold), Some(duration_ms)) = (threshold_ms(), time_taken_ms) else {
+        return Ok(None);
+    };
+    if duration_ms <= threshold as u128 {
+        return Ok(None);
+    }
+
+    sqlx::query_as::<_, SlowQuery>(
+        r#"
+        INSERT INTO slow_queries (connection_uuid, query, duration_ms)
+        VALUES (?, ?, ?)
+        RETURNING *
+        "#,
+    )
+    .bind(connection_uuid)
+    .bind(query)
+    .bind(duration_ms.min(i64::MAX as u128) as i64)
+    .fetch_one(pool)
+    .await
+    .map(Some)
+    .map_err(|e| e.to_string())
+}
+
+/// Log a slow query and emit `slow-query` without delaying the caller
+pub fn spawn_record_if_slow<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    connection_uuid: &str,
+    query: &str,
+    result: &QueryResult,
+) {
+    let Some(threshold) = threshold_ms() else {
+        return;
+    };
+    let time_taken_ms = result.time_taken_ms;
+    if time_taken_ms.is_none_or(|ms| ms <= threshold as u128) {
+        return;
+    }
+    let app = app.clone();
+    let pool = pool.clone();
+    let connection_uuid = connection_uuid.to_string();
+    let query = query.to_string();
+    tauri::async_runtime::spawn(async move {
+        match record_if_slow(&pool, &connection_uuid, &query, time_taken_ms).await {
+            Ok(Some(entry)) => {
+                let _ = app.emit("slow-query", entry);
+            }
+            Ok(None) => {}
+            Err(e) => println!("[SlowQuery] Failed to record slow query: {}", e),
+        }
+    });
+}
+
+/// Most recent slow queries first
+pub async fn list_slow_queries(pool: &SqlitePool, limit: i64) -> Result<Vec<SlowQuery>, String> {
+    sqlx::query_as::<_, SlowQuery>(
+        "SELECT * FROM slow_queries ORDER BY created_at DESC, id DESC LIMIT ?",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_slow_queries(
+    pool: State<'_, SqlitePool>,
+    limit: Option<i64>,
+) -> Result<Vec<SlowQuery>, String> {
+    list_slow_queries(pool.inner(), limit.unwrap_or(DEFAULT_LIMIT).max(1)).await
+}
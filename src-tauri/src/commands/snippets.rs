@@ -0,0 +1,159 @@
+use crate::db::models::{Snippet, SnippetFormData};
+use sqlx::{FromRow, SqlitePool};
+use tauri::State;
+
+/// Row shape in the `snippets` table; tags are stored comma-separated
+#[derive(FromRow)]
+struct SnippetRow {
+    id: i64,
+    title: String,
+    body: String,
+    db_type: Option<String>,
+    tags: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<SnippetRow> for Snippet {
+    fn from(row: SnippetRow) -> Self {
+        Snippet {
+            id: row.id,
+            title: row.title,
+            body: row.body,
+            db_type: row.db_type,
+            tags: row
+                .tags
+                .split(',')
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+/// Lowercase, trim and de-duplicate tags, joining them for storage
+fn encode_tags(tags: &[String]) -> String {
+    let mut normalized: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_lowercase().replace(',', ""))
+        .filter(|t| !t.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized.join(",")
+}
+
+fn normalize_db_type(db_type: &Option<String>) -> Option<String> {
+    db_type
+        .as_deref()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+}
+
+/// List snippets, optionally limited to those usable with `db_type` (including
+/// dialect-agnostic snippets) and carrying every one of `tags`
+pub async fn list_snippets(
+    pool: &SqlitePool,
+    db_type: Option<&str>,
+    tags: &[String],
+) -> Result<Vec<Snippet>, String> {
+    let rows = sqlx::query_as::<_, SnippetRow>(
+        "SELECT * FROM snippets WHERE (?1 IS NULL OR db_type IS NULL OR db_type = ?1) ORDER BY title",
+    )
+    .bind(normalize_db_type(&db_type.map(str::to_string)))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let wanted: Vec<String> = tags.iter().map(|t| t.trim().to_lowercase()).collect();
+    Ok(rows
+        .into_iter()
+        .map(Snippet::from)
+        .filter(|s| wanted.iter().all(|t| s.tags.contains(t)))
+        .collect())
+}
+
+pub async fn insert_snippet(pool: &SqlitePool, data: &SnippetFormData) -> Result<Snippet, String> {
+    sqlx::query_as::<_, SnippetRow>(
+        r#"
+        INSERT INTO snippets (title, body, db_type, tags)
+        VALUES (?, ?, ?, ?)
+        RETURNING *
+        "#,
+    )
+    .bind(&data.title)
+    .bind(&data.body)
+    .bind(normalize_db_type(&data.db_type))
+    .bind(encode_tags(&data.tags))
+    .fetch_one(pool)
+    .await
+    .map(Snippet::from)
+    .map_err(|e| e.to_string())
+}
+
+pub async fn modify_snippet(
+    pool: &SqlitePool,
+    id: i64,
+    data: &SnippetFormData,
+) -> Result<Snippet, String> {
+    sqlx::query_as::<_, SnippetRow>(
+        r#"
+        UPDATE snippets
+        SET title = ?, body = ?, db_type = ?, tags = ?, updated_at = datetime('now')
+        WHERE id = ?
+        RETURNING *
+        "#,
+    )
+    .bind(&data.title)
+    .bind(&data.body)
+    .bind(normalize_db_type(&data.db_type))
+    .bind(encode_tags(&data.tags))
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .map(Snippet::from)
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_snippets(
+    pool: State<'_, SqlitePool>,
+    db_type: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<Vec<Snippet>, String> {
+    list_snippets(
+        pool.inner(),
+        db_type.as_deref(),
+        tags.as_deref().unwrap_or_default(),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn create_snippet(
+    pool: State<'_, SqlitePool>,
+    data: SnippetFormData,
+) -> Result<Snippet, String> {
+    insert_snippet(pool.inner(), &data).await
+}
+
+#[tauri::command]
+pub async fn update_snippet(
+    pool: State<'_, SqlitePool>,
+    id: i64,
+    data: SnippetFormData,
+) -> Result<Snippet, String> {
+    modify_snippet(pool.inner(), id, &data).await
+}
+
+#[tauri::command]
+pub async fn delete_snippet(pool: State<'_, SqlitePool>, id: i64) -> Result<bool, String> {
+    sqlx::query("DELETE FROM snippets WHERE id = ?")
+        .bind(id)
+        .execute(pool.inner())
+        .await
+        .map(|_| true)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,682 @@
+//! Cassandra/ScyllaDB driver built on the `scylla` crate.
+//!
+//! Enabled with the `cassandra` cargo feature. Keyspaces are exposed as schemas. The driver
+//! keeps one `scylla` session per connection, created on first use.
+
+use async_trait::async_trait;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::response::{PagingState, PagingStateResponse};
+use scylla::statement::{Consistency, Statement};
+use scylla::value::{CqlValue, Row};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::{session_setup_sql, timeouts, DatabaseDriver};
+use crate::db::models::{
+    ColumnInfo, DriverCapabilities, IndexInfo, QueryResult, SchemaOverview, TableDataResponse,
+    TableInfo, TableStructure, TableWithStructure, TestConnectionResult,
+};
+
+/// Configuration for Cassandra/ScyllaDB connections
+#[derive(Clone)]
+pub struct CassandraConfig {
+    pub host: String,
+    pub port: i64,
+    /// Keyspace to browse; when empty, tables from all non-system keyspaces are listed
+    pub keyspace: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Connect over TLS, verifying the server against the bundled web PKI roots
+    pub ssl: bool,
+    /// cqlsh-style `CONSISTENCY <level>` statements choosing the consistency level
+    pub session_setup_sql: Option<String>,
+}
+
+/// Consistency level used when the session setup doesn't choose one
+const DEFAULT_CONSISTENCY: Consistency = Consistency::One;
+
+/// Page size used when following paging state for full result sets
+const QUERY_PAGE_SIZE: i32 = 1000;
+
+/// Keyspaces managed by Cassandra itself
+const SYSTEM_KEYSPACES: &[&str] = &[
+    "system",
+    "system_auth",
+    "system_distributed",
+    "system_schema",
+    "system_traces",
+    "system_views",
+    "system_virtual_schema",
+];
+
+/// One page of rows plus the state needed to fetch the next page
+struct RowsPage {
+    rows: Vec<Value>,
+    paging_state: Option<PagingState>,
+}
+
+/// Parse a consistency level name as accepted by cqlsh, e.g. `LOCAL_QUORUM`
+fn parse_consistency(level: &str) -> Result<Consistency, String> {
+    Ok(match level.to_ascii_uppercase().as_str() {
+        "ANY" => Consistency::Any,
+        "ONE" => Consistency::One,
+        "TWO" => Consistency::Two,
+        "THREE" => Consistency::Three,
+        "QUORUM" => Consistency::Quorum,
+        "ALL" => Consistency::All,
+        "LOCAL_QUORUM" => Consistency::LocalQuorum,
+        "EACH_QUORUM" => Consistency::EachQuorum,
+        "LOCAL_ONE" => Consistency::LocalOne,
+        "SERIAL" => Consistency::Serial,
+        "LOCAL_SERIAL" => Consistency::LocalSerial,
+        _ => return Err(format!("Unknown consistency level '{}'", level)),
+    })
+}
+
+/// Consistency level from `CONSISTENCY <level>` statements; the last one wins
+fn consistency_from_setup(setup: Option<&str>) -> Result<Consistency, String> {
+    let mut consistency = DEFAULT_CONSISTENCY;
+    for statement in setup.unwrap_or_default().split([';', '\n']) {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            continue;
+        }
+        let level = statement
+            .split_once(char::is_whitespace)
+            .filter(|(keyword, _)| keyword.eq_ignore_ascii_case("CONSISTENCY"))
+            .map(|(_, level)| level.trim())
+            .ok_or_else(|| {
+                format!(
+                    "Cassandra session setup only supports CONSISTENCY statements: {}",
+                    statement
+                )
+            })?;
+        consistency = parse_consistency(level)?;
+    }
+    Ok(consistency)
+}
+
+/// Decode a two's-complement big-endian integer (CQL `varint`)
+fn varint_to_string(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0x00 };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(buf).to_string())
+}
+
+/// Place the decimal point `scale` digits from the right of an integer string
+fn apply_scale(unscaled: &str, scale: i32) -> String {
+    let (sign, digits) = match unscaled.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", unscaled),
+    };
+    if scale <= 0 {
+        return format!(
+            "{}{}{}",
+            sign,
+            digits,
+            "0".repeat(scale.unsigned_abs() as usize)
+        );
+    }
+    let scale = scale as usize;
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, int_part, frac_part)
+}
+
+/// Render a decoded value as a JSON object key
+fn value_to_key(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+fn optional_cql_to_json(value: Option<CqlValue>) -> Value {
+    value.map(cql_to_json).unwrap_or(Value::Null)
+}
+
+/// Convert a CQL value to JSON. 64-bit integers stay numbers; varints and decimals that
+/// don't fit are rendered as strings so no precision is lost.
+fn cql_to_json(value: CqlValue) -> Value {
+    match value {
+        CqlValue::Ascii(s) | CqlValue::Text(s) => Value::String(s),
+        CqlValue::Boolean(b) => json!(b),
+        CqlValue::Blob(bytes) => Value::String(format!("0x{}", hex::encode(bytes))),
+        CqlValue::Counter(counter) => json!(counter.0),
+        CqlValue::Decimal(decimal) => {
+            let (bytes, scale) = decimal.as_signed_be_bytes_slice_and_exponent();
+            match varint_to_string(bytes) {
+                Some(unscaled) => Value::String(apply_scale(&unscaled, scale)),
+                None => Value::String(format!("0x{}E-{}", hex::encode(bytes), scale)),
+            }
+        }
+        CqlValue::Date(date) => {
+            // Days since the epoch, offset by 2^31
+            let days = date.0 as i64 - (1i64 << 31);
+            chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+                .and_then(|epoch| epoch.checked_add_signed(chrono::Duration::days(days)))
+                .map(|d| json!(d.to_string()))
+                .unwrap_or_else(|| json!(days))
+        }
+        CqlValue::Double(d) => json!(d),
+        CqlValue::Duration(d) => json!(format!("{}mo{}d{}ns", d.months, d.days, d.nanoseconds)),
+        CqlValue::Empty => Value::Null,
+        CqlValue::Float(f) => json!(f),
+        CqlValue::Int(i) => json!(i),
+        CqlValue::BigInt(i) => json!(i),
+        CqlValue::SmallInt(i) => json!(i),
+        CqlValue::TinyInt(i) => json!(i),
+        CqlValue::Timestamp(ts) => chrono::DateTime::from_timestamp_millis(ts.0)
+            .map(|dt| json!(dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string()))
+            .unwrap_or_else(|| json!(ts.0)),
+        CqlValue::Time(time) => chrono::NaiveTime::from_num_seconds_from_midnight_opt(
+            (time.0 / 1_000_000_000) as u32,
+            (time.0 % 1_000_000_000) as u32,
+        )
+        .map(|t| json!(t.to_string()))
+        .unwrap_or_else(|| json!(time.0)),
+        CqlValue::Inet(addr) => json!(addr.to_string()),
+        CqlValue::Uuid(uuid) => json!(uuid.to_string()),
+        CqlValue::Timeuuid(uuid) => json!(uuid.to_string()),
+        CqlValue::Varint(varint) => {
+            let bytes = varint.as_signed_bytes_be_slice();
+            match varint_to_string(bytes) {
+                Some(text) => text
+                    .parse::<i64>()
+                    .map(|v| json!(v))
+                    .unwrap_or(Value::String(text)),
+                None => Value::String(format!("0x{}", hex::encode(bytes))),
+            }
+        }
+        CqlValue::List(items) | CqlValue::Set(items) | CqlValue::Vector(items) => {
+            Value::Array(items.into_iter().map(cql_to_json).collect())
+        }
+        CqlValue::Map(entries) => Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (value_to_key(cql_to_json(key)), cql_to_json(value)))
+                .collect(),
+        ),
+        CqlValue::UserDefinedType { fields, .. } => Value::Object(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name, optional_cql_to_json(value)))
+                .collect(),
+        ),
+        CqlValue::Tuple(items) => {
+            Value::Array(items.into_iter().map(optional_cql_to_json).collect())
+        }
+        other => Value::String(other.to_string()),
+    }
+}
+
+/// Build a TLS configuration trusting the web PKI roots
+fn tls_config() -> Result<Arc<rustls::ClientConfig>, String> {
+    let roots = rustls::RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(
+        rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| e.to_string())?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+pub struct CassandraDriver {
+    config: CassandraConfig,
+    session: OnceCell<Session>,
+    connect_timeout: Duration,
+}
+
+impl CassandraDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: false,
+        supports_foreign_keys: false,
+        supports_row_editing: false,
+        supports_schemas: true,
+        supports_explain: false,
+    };
+
+    pub fn new(config: CassandraConfig) -> Self {
+        Self {
+            config,
+            session: OnceCell::new(),
+            connect_timeout: timeouts::connect_timeout(10),
+        }
+    }
+
+    /// Session for this connection, created on first use
+    async fn session(&self) -> Result<&Session, String> {
+        self.session
+            .get_or_try_init(|| async {
+                let mut builder = SessionBuilder::new()
+                    .known_node(format!("{}:{}", self.config.host, self.config.port))
+                    .connection_timeout(self.connect_timeout);
+                if let Some(username) = &self.config.username {
+                    builder =
+                        builder.user(username, self.config.password.clone().unwrap_or_default());
+                }
+                if self.config.ssl {
+                    builder = builder.tls_context(Some(tls_config()?));
+                }
+                builder.build().await.map_err(|e| e.to_string())
+            })
+            .await
+    }
+
+    fn statement(&self, cql: &str, page_size: i32) -> Result<Statement, String> {
+        let setup = session_setup_sql(&self.config.session_setup_sql);
+        let mut statement = Statement::new(cql).with_page_size(page_size);
+        statement.set_consistency(consistency_from_setup(setup.as_deref())?);
+        Ok(statement)
+    }
+
+    /// Run a query and return one page of rows, or `None` for statements without rows
+    async fn query(
+        &self,
+        cql: &str,
+        page_size: i32,
+        paging_state: Option<PagingState>,
+    ) -> Result<Option<RowsPage>, String> {
+        let statement = self.statement(cql, page_size)?;
+        let (result, paging) = self
+            .session()
+            .await?
+            .query_single_page(statement, &[], paging_state.unwrap_or_default())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        for warning in result.warnings() {
+            tracing::warn!(target: "dbcooper::cassandra", "{}", warning);
+        }
+        if !result.is_rows() {
+            return Ok(None);
+        }
+
+        let rows_result = result.into_rows_result().map_err(|e| e.to_string())?;
+        let names: Vec<String> = rows_result
+            .column_specs()
+            .iter()
+            .map(|spec| spec.name().to_string())
+            .collect();
+        let rows = rows_result
+            .rows::<Row>()
+            .map_err(|e| e.to_string())?
+            .map(|row| {
+                row.map(|row| {
+                    let object: Map<String, Value> = names
+                        .iter()
+                        .cloned()
+                        .zip(row.columns.into_iter().map(optional_cql_to_json))
+                        .collect();
+                    Value::Object(object)
+                })
+                .map_err(|e| e.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let paging_state = match paging {
+            PagingStateResponse::HasMorePages { state } => Some(state),
+            PagingStateResponse::NoMorePages => None,
+        };
+        Ok(Some(RowsPage { rows, paging_state }))
+    }
+
+    /// Run a query and follow paging state to collect every row
+    async fn query_all(&self, cql: &str) -> Result<Option<Vec<Value>>, String> {
+        let Some(mut page) = self.query(cql, QUERY_PAGE_SIZE, None).await? else {
+            return Ok(None);
+        };
+        let mut rows = std::mem::take(&mut page.rows);
+        while let Some(state) = page.paging_state.take() {
+            page = match self.query(cql, QUERY_PAGE_SIZE, Some(state)).await? {
+                Some(next) => next,
+                None => break,
+            };
+            rows.append(&mut page.rows);
+        }
+        Ok(Some(rows))
+    }
+
+    fn quote_identifier(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn quote_literal(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    /// Keyspace for a request, falling back to the configured keyspace
+    fn keyspace_for<'a>(&'a self, schema: &'a str) -> &'a str {
+        if schema.is_empty() {
+            &self.config.keyspace
+        } else {
+            schema
+        }
+    }
+
+    /// `WHERE` clause limiting system_schema queries to the configured keyspace
+    fn keyspace_clause(&self) -> String {
+        if self.config.keyspace.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " WHERE keyspace_name = {}",
+                Self::quote_literal(&self.config.keyspace)
+            )
+        }
+    }
+
+    fn is_visible_keyspace(&self, keyspace: &str) -> bool {
+        !self.config.keyspace.is_empty() || !SYSTEM_KEYSPACES.contains(&keyspace)
+    }
+
+    /// Build columns from `system_schema.columns` rows in key order: partition key,
+    /// clustering columns, then the remaining columns by name
+    fn columns_from_rows(rows: &[Value]) -> Vec<ColumnInfo> {
+        let kind_rank = |row: &Value| match row["kind"].as_str() {
+            Some("partition_key") => 0,
+            Some("clustering") => 1,
+            _ => 2,
+        };
+
+        let mut sorted: Vec<&Value> = rows.iter().collect();
+        sorted.sort_by(|a, b| {
+            kind_rank(a)
+                .cmp(&kind_rank(b))
+                .then_with(|| {
+                    a["position"]
+                        .as_i64()
+                        .unwrap_or(0)
+                        .cmp(&b["position"].as_i64().unwrap_or(0))
+                })
+                .then_with(|| {
+                    a["column_name"]
+                        .as_str()
+                        .unwrap_or_default()
+                        .cmp(b["column_name"].as_str().unwrap_or_default())
+                })
+        });
+
+        sorted
+            .into_iter()
+            .map(|row| {
+                let primary_key = kind_rank(row) < 2;
+                ColumnInfo {
+                    name: row["column_name"].as_str().unwrap_or_default().to_string(),
+                    data_type: row["type"].as_str().unwrap_or_default().to_string(),
+                    nullable: !primary_key,
+                    default: None,
+                    primary_key,
+                    comment: None,
+                    enum_values: None,
+                    auto_increment: false,
+                    is_generated: false,
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl DatabaseDriver for CassandraDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
+    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+        let result = self
+            .query("SELECT release_version FROM system.local", 1, None)
+            .await
+            .map(|_| ());
+
+        match result {
+            Ok(()) => Ok(TestConnectionResult {
+                success: true,
+                message: "Connection successful!".to_string(),
+            }),
+            Err(e) => Ok(TestConnectionResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+            }),
+        }
+    }
+
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        let rows = self
+            .query_all(&format!(
+                "SELECT keyspace_name, table_name FROM system_schema.tables{}",
+                self.keyspace_clause()
+            ))
+            .await?
+            .unwrap_or_default();
+
+        let mut tables: Vec<TableInfo> = rows
+            .iter()
+            .filter_map(|row| {
+                let keyspace = row["keyspace_name"].as_str()?;
+                if !self.is_visible_keyspace(keyspace) {
+                    return None;
+                }
+                Some(TableInfo {
+                    schema: keyspace.to_string(),
+                    name: row["table_name"].as_str()?.to_string(),
+                    table_type: "table".to_string(),
+                    comment: None,
+                    engine_full: None,
+                })
+            })
+            .collect();
+        tables.sort_by(|a, b| (&a.schema, &a.name).cmp(&(&b.schema, &b.name)));
+
+        Ok(tables)
+    }
+
+    /// Pages are reached by following CQL paging state, since CQL has no `OFFSET`.
+    /// CQL can only order by clustering columns within a partition, so sorting is not applied.
+    async fn get_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        page: i64,
+        limit: i64,
+        filter: Option<String>,
+        _sort_column: Option<String>,
+        _sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, String> {
+        let table_ref = format!(
+            "{}.{}",
+            Self::quote_identifier(self.keyspace_for(schema)),
+            Self::quote_identifier(table)
+        );
+        let where_clause = filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(|f| format!(" WHERE {} ALLOW FILTERING", f))
+            .unwrap_or_default();
+
+        let data_query = format!("SELECT * FROM {}{}", table_ref, where_clause);
+        let page_size = limit.clamp(1, i32::MAX as i64) as i32;
+
+        let mut current = self.query(&data_query, page_size, None).await?;
+        for _ in 1..page.max(1) {
+            let state = current.as_mut().and_then(|p| p.paging_state.take());
+            current = match state {
+                Some(state) => self.query(&data_query, page_size, Some(state)).await?,
+                None => None,
+            };
+        }
+        let (data, has_more) = match current {
+            Some(page) => (page.rows, page.paging_state.is_some()),
+            None => (vec![], false),
+        };
+
+        let count_query = format!("SELECT COUNT(*) FROM {}{}", table_ref, where_clause);
+        let total = match self.query(&count_query, 1, None).await {
+            Ok(Some(count)) => count
+                .rows
+                .first()
+                .and_then(|row| row["count"].as_i64())
+                .unwrap_or(0),
+            // Counting can time out on large tables; fall back to what we have seen
+            _ => (page.max(1) - 1) * limit + data.len() as i64 + i64::from(has_more),
+        };
+
+        Ok(TableDataResponse {
+            data,
+            total,
+            page,
+            limit,
+        })
+    }
+
+    async fn get_table_structure(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<TableStructure, String> {
+        let keyspace = Self::quote_literal(self.keyspace_for(schema));
+        let table_name = Self::quote_literal(table);
+
+        let column_rows = self
+            .query_all(&format!(
+                "SELECT column_name, type, kind, position FROM system_schema.columns WHERE keyspace_name = {} AND table_name = {}",
+                keyspace, table_name
+            ))
+            .await?
+            .unwrap_or_default();
+
+        if column_rows.is_empty() {
+            return Err(format!("Table '{}' not found", table));
+        }
+
+        let index_rows = self
+            .query_all(&format!(
+                "SELECT index_name, options FROM system_schema.indexes WHERE keyspace_name = {} AND table_name = {}",
+                keyspace, table_name
+            ))
+            .await?
+            .unwrap_or_default();
+
+        let columns = Self::columns_from_rows(&column_rows);
+        let mut indexes = vec![IndexInfo {
+            name: "PRIMARY".to_string(),
+            columns: columns
+                .iter()
+                .filter(|c| c.primary_key)
+                .map(|c| c.name.clone())
+                .collect(),
+            unique: true,
+            primary: true,
+        }];
+        indexes.extend(index_rows.iter().map(|row| {
+            IndexInfo {
+                name: row["index_name"].as_str().unwrap_or_default().to_string(),
+                columns: row["options"]["target"]
+                    .as_str()
+                    .map(|t| vec![t.to_string()])
+                    .unwrap_or_default(),
+                unique: false,
+                primary: false,
+            }
+        }));
+
+        Ok(TableStructure {
+            columns,
+            indexes,
+            foreign_keys: vec![],
+            check_constraints: vec![],
+            unique_constraints: vec![],
+            warnings: vec![],
+        })
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+        let start_time = std::time::Instant::now();
+        let cql = query.trim().trim_end_matches(';').trim();
+
+        let result = self.query_all(cql).await;
+
+        match result {
+            Ok(Some(data)) => {
+                let row_count = data.len() as i64;
+                Ok(QueryResult {
+                    data,
+                    row_count,
+                    error: None,
+                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
+                })
+            }
+            Ok(None) => Ok(QueryResult {
+                data: vec![json!({"result": "Query executed successfully"})],
+                row_count: 0,
+                error: None,
+                time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
+            }),
+            Err(e) => Ok(QueryResult {
+                data: vec![],
+                row_count: 0,
+                error: Some(e),
+                time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+        let tables = self.list_tables().await?;
+
+        let column_rows = self
+            .query_all(&format!(
+                "SELECT keyspace_name, table_name, column_name, type, kind, position FROM system_schema.columns{}",
+                self.keyspace_clause()
+            ))
+            .await?
+            .unwrap_or_default();
+
+        let mut columns_by_table: BTreeMap<(String, String), Vec<Value>> = BTreeMap::new();
+        for row in column_rows {
+            let key = (
+                row["keyspace_name"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                row["table_name"].as_str().unwrap_or_default().to_string(),
+            );
+            columns_by_table.entry(key).or_default().push(row);
+        }
+
+        let tables = tables
+            .into_iter()
+            .map(|table| {
+                let rows = columns_by_table
+                    .remove(&(table.schema.clone(), table.name.clone()))
+                    .unwrap_or_default();
+                TableWithStructure {
+                    schema: table.schema,
+                    name: table.name,
+                    table_type: table.table_type,
+                    comment: None,
+                    columns: Self::columns_from_rows(&rows),
+                    foreign_keys: vec![],
+                    indexes: vec![],
+                }
+            })
+            .collect();
+
+        Ok(SchemaOverview { tables })
+    }
+}
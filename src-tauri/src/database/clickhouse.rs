@@ -2,10 +2,16 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use super::DatabaseDriver;
-use crate::database::queries::clickhouse::{COLUMNS_QUERY, INDEXES_QUERY};
+use super::{
+    like_contains_pattern, page_offset, sample_fraction, session_setup_sql, DatabaseDriver,
+    TxHandle, SAMPLE_CLAUSE_MIN_ROWS,
+};
+use crate::database::queries::clickhouse::{
+    COLUMNS_QUERY, DATABASE_TABLE_COUNTS_QUERY, INDEXES_QUERY,
+};
 use crate::db::models::{
-    ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview, TableDataResponse,
+    ClusterNode, ColumnInfo, DatabaseInfo, DriverCapabilities, ForeignKeyInfo, IndexInfo,
+    NamespaceOverview, QueryResult, ResultColumn, RoutineInfo, SchemaOverview, TableDataResponse,
     TableInfo, TableStructure, TableWithStructure, TestConnectionResult,
 };
 use std::collections::HashMap;
@@ -34,6 +40,8 @@ pub struct ClickhouseConfig {
     #[allow(dead_code)] // Reserved for future TCP protocol support
     pub protocol: ClickhouseProtocol,
     pub ssl: bool,
+    /// `SET` statements, sent as settings with every request since HTTP has no session
+    pub session_setup_sql: Option<String>,
 }
 
 pub struct ClickhouseDriver {
@@ -41,6 +49,15 @@ pub struct ClickhouseDriver {
 }
 
 impl ClickhouseDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: false,
+        supports_foreign_keys: false,
+        supports_row_editing: false,
+        supports_schemas: true,
+        supports_explain: true,
+    };
+
     pub fn new(config: ClickhouseConfig) -> Self {
         Self { config }
     }
@@ -50,10 +67,49 @@ impl ClickhouseDriver {
         format!("{}://{}:{}", scheme, self.config.host, self.config.port)
     }
 
-    /// Execute a query and return JSON results using raw HTTP
+    /// Settings from the session setup statements, sent as query parameters on each request
+    fn session_settings(&self) -> Result<Vec<(String, String)>, String> {
+        match session_setup_sql(&self.config.session_setup_sql) {
+            Some(setup) => parse_set_statements(&setup),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Server-side elapsed time from the `X-ClickHouse-Summary` header, in milliseconds
+    fn server_elapsed_ms(headers: &reqwest::header::HeaderMap) -> Option<u128> {
+        let summary: Value = headers
+            .get("X-ClickHouse-Summary")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| serde_json::from_str(h).ok())?;
+        let elapsed_ns = match &summary["elapsed_ns"] {
+            Value::String(s) => s.parse::<u128>().ok(),
+            other => other.as_u64().map(u128::from),
+        }?;
+        Some(elapsed_ns / 1_000_000)
+    }
+
+    /// Execute a query and return JSON results using raw HTTP.
     async fn execute_query_json(&self, query: &str) -> Result<Vec<Value>, String> {
+        self.execute_query_json_timed(query, false)
+            .await
+            .map(|(rows, _)| rows)
+    }
+
+    /// Execute a query and return JSON results along with the server-reported elapsed time.
+    ///
+    /// Nested types stay structured: `Map` columns become objects, `Tuple` columns arrays
+    /// (objects for named tuples). 64-bit integers are quoted to keep full precision.
+    /// `wait_end_of_query` makes the summary header reflect the whole execution, at the cost
+    /// of the server buffering the result, so it is only requested for user queries whose
+    /// timing is reported; internal metadata lookups stream as before.
+    async fn execute_query_json_timed(
+        &self,
+        query: &str,
+        wait_end_of_query: bool,
+    ) -> Result<(Vec<Value>, Option<u128>), String> {
         let url = self.build_url();
         let client = reqwest::Client::new();
+        let settings = self.session_settings()?;
 
         // Clean up the query: trim whitespace, remove trailing semicolons
         let cleaned_query = query.trim().trim_end_matches(';').trim();
@@ -69,6 +125,15 @@ impl ClickhouseDriver {
             .post(&url)
             .basic_auth(&self.config.username, Some(&self.config.password))
             .query(&[("database", &self.config.database)])
+            .query(&settings)
+            .query(&[
+                ("output_format_json_named_tuples_as_objects", "1"),
+                ("output_format_json_quote_64bit_integers", "1"),
+            ])
+            .query(&[(
+                "wait_end_of_query",
+                if wait_end_of_query { "1" } else { "0" },
+            )])
             .body(full_query)
             .send()
             .await
@@ -79,6 +144,7 @@ impl ClickhouseDriver {
             return Err(error_text);
         }
 
+        let server_time_ms = Self::server_elapsed_ms(response.headers());
         let text = response.text().await.map_err(|e| e.to_string())?;
 
         // Parse JSONEachRow format (one JSON object per line)
@@ -88,18 +154,21 @@ impl ClickhouseDriver {
             .filter_map(|line| serde_json::from_str(line).ok())
             .collect();
 
-        Ok(rows)
+        Ok((rows, server_time_ms))
     }
 
-    /// Execute a non-SELECT query
-    async fn execute_command(&self, query: &str) -> Result<(), String> {
+    /// Execute a non-SELECT query, returning the server-reported elapsed time
+    async fn execute_command(&self, query: &str) -> Result<Option<u128>, String> {
         let url = self.build_url();
         let client = reqwest::Client::new();
+        let settings = self.session_settings()?;
 
         let response = client
             .post(&url)
             .basic_auth(&self.config.username, Some(&self.config.password))
             .query(&[("database", &self.config.database)])
+            .query(&settings)
+            .query(&[("wait_end_of_query", "1")])
             .body(query.to_string())
             .send()
             .await
@@ -110,7 +179,18 @@ impl ClickhouseDriver {
             return Err(error_text);
         }
 
-        Ok(())
+        Ok(Self::server_elapsed_ms(response.headers()))
+    }
+
+    /// Table type for an engine: materialized views and dictionaries get their own
+    /// types, everything else keeps the engine name
+    fn table_type_for_engine(engine: &str) -> String {
+        match engine {
+            "MaterializedView" => "materialized_view".to_string(),
+            "Dictionary" => "dictionary".to_string(),
+            "" => "table".to_string(),
+            other => other.to_string(),
+        }
     }
 
     /// Normalize filter to handle smart quotes from macOS
@@ -124,8 +204,121 @@ impl ClickhouseDriver {
     }
 }
 
+/// `MATERIALIZED` and `ALIAS` columns are always computed and cannot be inserted into
+fn is_computed_default_kind(default_kind: &str) -> bool {
+    matches!(default_kind, "MATERIALIZED" | "ALIAS")
+}
+
+/// Parameter list of a `CREATE FUNCTION name AS (a, b) -> ...` statement, e.g. `a, b`
+fn lambda_arguments(create_query: &str) -> String {
+    let Some(as_pos) = create_query.to_ascii_uppercase().find(" AS ") else {
+        return String::new();
+    };
+    let lambda = &create_query[as_pos + 4..];
+    let Some(arrow_pos) = lambda.find("->") else {
+        return String::new();
+    };
+    lambda[..arrow_pos]
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .trim()
+        .to_string()
+}
+
+/// Turn `SET a = 1, b = 'x'; SET c = 2` into setting name/value pairs. Quoted values
+/// follow ClickHouse string literal rules: `''` and backslash escapes such as `\'` or `\n`.
+pub fn parse_set_statements(sql: &str) -> Result<Vec<(String, String)>, String> {
+    let mut settings = Vec::new();
+    for statement in split_unquoted(sql, ';') {
+        let statement = statement.trim();
+        if statement.is_empty() {
+            continue;
+        }
+        let assignments = statement
+            .split_once(char::is_whitespace)
+            .filter(|(keyword, _)| keyword.eq_ignore_ascii_case("SET"))
+            .map(|(_, rest)| rest)
+            .ok_or_else(|| {
+                format!(
+                    "ClickHouse session setup only supports SET statements: {}",
+                    statement
+                )
+            })?;
+
+        for assignment in split_unquoted(assignments, ',') {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid setting '{}'", assignment.trim()))?;
+            let value = value.trim();
+            let value = match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+                Some(quoted) => unescape_string(quoted),
+                None => value.to_string(),
+            };
+            settings.push((name.trim().to_string(), value));
+        }
+    }
+    Ok(settings)
+}
+
+/// Contents of a single-quoted ClickHouse string literal, with escapes resolved
+fn unescape_string(quoted: &str) -> String {
+    let mut value = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('r') => value.push('\r'),
+                Some('0') => value.push('\0'),
+                Some('b') => value.push('\u{8}'),
+                Some('f') => value.push('\u{c}'),
+                Some('a') => value.push('\u{7}'),
+                Some('v') => value.push('\u{b}'),
+                // Any other escaped character stands for itself
+                Some(other) => value.push(other),
+                None => value.push('\\'),
+            },
+            '\'' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                value.push('\'');
+            }
+            other => value.push(other),
+        }
+    }
+    value
+}
+
+/// Split on `separator` outside single-quoted strings. Inside a string, a backslash
+/// escapes the next character, so `\'` does not end it.
+fn split_unquoted(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && in_quotes {
+            escaped = true;
+        } else if c == '\'' {
+            in_quotes = !in_quotes;
+        } else if c == separator && !in_quotes {
+            parts.push(&text[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
 #[async_trait]
 impl DatabaseDriver for ClickhouseDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
     async fn test_connection(&self) -> Result<TestConnectionResult, String> {
         match self.execute_query_json("SELECT 1").await {
             Ok(_) => Ok(TestConnectionResult {
@@ -140,19 +333,51 @@ impl DatabaseDriver for ClickhouseDriver {
     }
 
     async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        self.list_tables_paged(None, None, None).await
+    }
+
+    async fn list_tables_paged(
+        &self,
+        name_filter: Option<&str>,
+        page: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TableInfo>, String> {
+        let name_clause = name_filter
+            .map(|f| {
+                let pattern = like_contains_pattern(f)
+                    .replace('\\', "\\\\")
+                    .replace('\'', "\\'");
+                format!(" AND name ILIKE '{}'", pattern)
+            })
+            .unwrap_or_default();
+        let limit_clause = limit
+            .map(|l| format!(" LIMIT {} OFFSET {}", l.max(0), page_offset(page, l)))
+            .unwrap_or_default();
         let query = format!(
-            "SELECT database, name, engine FROM system.tables WHERE database = '{}'  ORDER BY name",
-            self.config.database
+            "SELECT database, name, engine, engine_full, comment FROM system.tables WHERE database = '{}'{} ORDER BY name{}",
+            self.config.database, name_clause, limit_clause
         );
 
         let rows = self.execute_query_json(&query).await?;
 
         Ok(rows
             .into_iter()
-            .map(|row| TableInfo {
-                schema: row["database"].as_str().unwrap_or("").to_string(),
-                name: row["name"].as_str().unwrap_or("").to_string(),
-                table_type: row["engine"].as_str().unwrap_or("table").to_string(),
+            .map(|row| {
+                let engine = row["engine"].as_str().unwrap_or("");
+                TableInfo {
+                    schema: row["database"].as_str().unwrap_or("").to_string(),
+                    name: row["name"].as_str().unwrap_or("").to_string(),
+                    table_type: Self::table_type_for_engine(engine),
+                    comment: row["comment"]
+                        .as_str()
+                        .filter(|c| !c.is_empty())
+                        .map(|c| c.to_string()),
+                    // Only Distributed tables need the definition, to show where queries go
+                    engine_full: row["engine_full"]
+                        .as_str()
+                        .filter(|_| engine == "Distributed")
+                        .map(|e| e.to_string()),
+                }
             })
             .collect())
     }
@@ -227,7 +452,7 @@ impl DatabaseDriver for ClickhouseDriver {
     ) -> Result<TableStructure, String> {
         // Get columns
         let columns_query = format!(
-            "SELECT name, type, default_kind, default_expression, is_in_primary_key 
+            "SELECT name, type, default_kind, default_expression, is_in_primary_key, comment 
              FROM system.columns 
              WHERE database = '{}' AND table = '{}'
              ORDER BY position",
@@ -241,6 +466,7 @@ impl DatabaseDriver for ClickhouseDriver {
             .map(|col| {
                 let data_type = col["type"].as_str().unwrap_or("").to_string();
                 let nullable = data_type.starts_with("Nullable");
+                let default_kind = col["default_kind"].as_str().unwrap_or("");
                 ColumnInfo {
                     name: col["name"].as_str().unwrap_or("").to_string(),
                     data_type,
@@ -250,11 +476,17 @@ impl DatabaseDriver for ClickhouseDriver {
                         if expr.is_empty() {
                             None
                         } else {
-                            let kind = col["default_kind"].as_str().unwrap_or("");
-                            Some(format!("{} {}", kind, expr))
+                            Some(format!("{} {}", default_kind, expr))
                         }
                     },
                     primary_key: col["is_in_primary_key"].as_u64().unwrap_or(0) == 1,
+                    comment: col["comment"]
+                        .as_str()
+                        .filter(|c| !c.is_empty())
+                        .map(|c| c.to_string()),
+                    enum_values: None,
+                    auto_increment: false,
+                    is_generated: is_computed_default_kind(default_kind),
                 }
             })
             .collect();
@@ -288,9 +520,193 @@ impl DatabaseDriver for ClickhouseDriver {
             columns: column_infos,
             indexes: index_infos,
             foreign_keys,
+            check_constraints: vec![],
+            unique_constraints: vec![],
+            warnings: vec![],
+        })
+    }
+
+    async fn validate_query(&self, query: &str) -> Result<(), String> {
+        let cleaned_query = query.trim().trim_end_matches(';').trim();
+        self.execute_query_json(&format!("EXPLAIN SYNTAX {}", cleaned_query))
+            .await
+            .map(|_| ())
+    }
+
+    async fn describe_query(&self, query: &str) -> Result<Vec<ResultColumn>, String> {
+        let cleaned_query = query.trim().trim_end_matches(';').trim();
+        let rows = self
+            .execute_query_json(&format!("DESCRIBE TABLE ({})", cleaned_query))
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| ResultColumn {
+                name: row["name"].as_str().unwrap_or_default().to_string(),
+                data_type: row["type"].as_str().unwrap_or_default().to_string(),
+            })
+            .collect())
+    }
+
+    /// Large tables with a sampling key use `SAMPLE`; others use `ORDER BY rand()`
+    async fn sample_table(
+        &self,
+        _schema: &str,
+        table: &str,
+        n: i64,
+    ) -> Result<QueryResult, String> {
+        let escaped = table.replace('\\', "\\\\").replace('\'', "\\'");
+        let info = self
+            .execute_query_json(&format!(
+                "SELECT sampling_key, total_rows FROM system.tables \
+                 WHERE database = currentDatabase() AND name = '{}'",
+                escaped
+            ))
+            .await?;
+        let info = info.first();
+        let has_sampling_key = info
+            .and_then(|row| row["sampling_key"].as_str())
+            .is_some_and(|key| !key.is_empty());
+        // 64-bit integers arrive quoted in JSON output
+        let total_rows = info
+            .and_then(|row| {
+                row["total_rows"]
+                    .as_str()
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .or_else(|| row["total_rows"].as_f64())
+            })
+            .unwrap_or(0.0);
+
+        let table_ref = format!("`{}`", table.replace('`', "``"));
+        if has_sampling_key && total_rows > SAMPLE_CLAUSE_MIN_ROWS {
+            let sampled = self
+                .execute_query(&format!(
+                    "SELECT * FROM {} SAMPLE {} LIMIT {}",
+                    table_ref,
+                    sample_fraction(n, total_rows),
+                    n
+                ))
+                .await?;
+            if sampled.error.is_some() || sampled.row_count >= n {
+                return Ok(sampled);
+            }
+        }
+
+        self.execute_query(&format!(
+            "SELECT * FROM {} ORDER BY rand() LIMIT {}",
+            table_ref, n
+        ))
+        .await
+    }
+
+    async fn namespace_overview(&self) -> Result<Vec<NamespaceOverview>, String> {
+        let rows = self.execute_query_json(DATABASE_TABLE_COUNTS_QUERY).await?;
+        // 64-bit integers arrive quoted in JSON output
+        let number = |value: &Value| {
+            value
+                .as_str()
+                .and_then(|s| s.parse::<i64>().ok())
+                .or_else(|| value.as_i64())
+                .unwrap_or(0)
+        };
+        Ok(rows
+            .iter()
+            .map(|row| NamespaceOverview {
+                name: row["name"].as_str().unwrap_or_default().to_string(),
+                table_count: number(&row["table_count"]),
+                estimated_rows: number(&row["estimated_rows"]),
+            })
+            .collect())
+    }
+
+    /// ClickHouse stores strings as raw bytes, so only the effective timezone is reported
+    async fn get_database_info(&self) -> Result<DatabaseInfo, String> {
+        let rows = self
+            .execute_query_json("SELECT timezone() AS timezone")
+            .await?;
+        Ok(DatabaseInfo {
+            encoding: None,
+            collation: None,
+            timezone: rows
+                .first()
+                .and_then(|row| row["timezone"].as_str())
+                .map(|tz| tz.to_string()),
         })
     }
 
+    async fn get_cluster_info(&self) -> Result<Vec<ClusterNode>, String> {
+        let rows = self
+            .execute_query_json(
+                "SELECT cluster, shard_num, replica_num, host_name, host_address, port, is_local \
+                 FROM system.clusters ORDER BY cluster, shard_num, replica_num",
+            )
+            .await?;
+        let number = |value: &Value| {
+            value
+                .as_i64()
+                .or_else(|| value.as_str().and_then(|s| s.parse::<i64>().ok()))
+                .unwrap_or(0)
+        };
+        let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+
+        Ok(rows
+            .iter()
+            .map(|row| ClusterNode {
+                cluster: text(&row["cluster"]),
+                shard_num: number(&row["shard_num"]),
+                replica_num: number(&row["replica_num"]),
+                host_name: text(&row["host_name"]),
+                host_address: text(&row["host_address"]),
+                port: number(&row["port"]),
+                is_local: number(&row["is_local"]) == 1,
+            })
+            .collect())
+    }
+
+    /// SQL user-defined functions are global in ClickHouse, so `schema` is not used
+    /// for filtering and every routine is reported with an empty schema.
+    async fn list_routines(&self, _schema: &str) -> Result<Vec<RoutineInfo>, String> {
+        let rows = self
+            .execute_query_json(
+                "SELECT name, create_query FROM system.functions \
+                 WHERE origin = 'SQLUserDefined' ORDER BY name",
+            )
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let create_query = row["create_query"].as_str().unwrap_or_default();
+                RoutineInfo {
+                    schema: String::new(),
+                    name: row["name"].as_str().unwrap_or_default().to_string(),
+                    routine_type: "function".to_string(),
+                    return_type: None,
+                    arguments: lambda_arguments(create_query),
+                    language: Some("SQL".to_string()),
+                }
+            })
+            .collect())
+    }
+
+    async fn get_routine_definition(&self, _schema: &str, name: &str) -> Result<String, String> {
+        let rows = self
+            .execute_query_json(&format!(
+                "SELECT create_query FROM system.functions \
+                 WHERE origin = 'SQLUserDefined' AND name = '{}'",
+                name.replace('\\', "\\\\").replace('\'', "\\'")
+            ))
+            .await?;
+
+        rows.first()
+            .and_then(|row| row["create_query"].as_str())
+            .map(|q| q.to_string())
+            .ok_or_else(|| format!("Function {} not found", name))
+    }
+
+    async fn begin_transaction(&self) -> Result<TxHandle, String> {
+        Err("ClickHouse does not support transactions or savepoints".to_string())
+    }
+
     async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
         let columns_query =
             COLUMNS_QUERY.replace("currentDatabase()", &format!("'{}'", self.config.database));
@@ -337,7 +753,11 @@ impl DatabaseDriver for ClickhouseDriver {
         for col_row in columns_rows {
             let schema: String = col_row["schema"].as_str().unwrap_or("").to_string();
             let name: String = col_row["name"].as_str().unwrap_or("").to_string();
-            let table_type: String = col_row["type"].as_str().unwrap_or("table").to_string();
+            let table_type = Self::table_type_for_engine(col_row["type"].as_str().unwrap_or(""));
+            let comment = col_row["comment"]
+                .as_str()
+                .filter(|c| !c.is_empty())
+                .map(|c| c.to_string());
 
             let empty_vec = vec![];
             let columns_raw = col_row["columns_raw"].as_array().unwrap_or(&empty_vec);
@@ -351,6 +771,11 @@ impl DatabaseDriver for ClickhouseDriver {
                         let default_kind = arr[2].as_str().unwrap_or("");
                         let default_expr = arr[3].as_str().unwrap_or("");
                         let is_pk = arr[4].as_u64().unwrap_or(0) == 1;
+                        let comment = arr
+                            .get(5)
+                            .and_then(|c| c.as_str())
+                            .filter(|c| !c.is_empty())
+                            .map(|c| c.to_string());
 
                         let nullable = col_type.starts_with("Nullable");
                         let default = if default_expr.is_empty() {
@@ -365,6 +790,10 @@ impl DatabaseDriver for ClickhouseDriver {
                             nullable,
                             default,
                             primary_key: is_pk,
+                            comment,
+                            enum_values: None,
+                            auto_increment: false,
+                            is_generated: is_computed_default_kind(default_kind),
                         });
                     }
                 }
@@ -378,6 +807,7 @@ impl DatabaseDriver for ClickhouseDriver {
                 schema,
                 name,
                 table_type,
+                comment,
                 columns,
                 foreign_keys: Vec::new(),
                 indexes,
@@ -397,14 +827,16 @@ impl DatabaseDriver for ClickhouseDriver {
             || trimmed.starts_with("WITH");
 
         if is_select {
-            match self.execute_query_json(query).await {
-                Ok(rows) => {
+            match self.execute_query_json_timed(query, true).await {
+                Ok((rows, server_time_ms)) => {
                     let row_count = rows.len() as i64;
                     Ok(QueryResult {
                         data: rows,
                         row_count,
                         error: None,
                         time_taken_ms: Some(start_time.elapsed().as_millis()),
+                        server_time_ms,
+                        ..Default::default()
                     })
                 }
                 Err(e) => Ok(QueryResult {
@@ -412,24 +844,71 @@ impl DatabaseDriver for ClickhouseDriver {
                     row_count: 0,
                     error: Some(e),
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
                 }),
             }
         } else {
             // For non-SELECT queries (INSERT, ALTER, CREATE, etc.)
             match self.execute_command(query).await {
-                Ok(_) => Ok(QueryResult {
+                Ok(server_time_ms) => Ok(QueryResult {
                     data: vec![json!({"result": "Query executed successfully"})],
                     row_count: 0,
                     error: None,
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    server_time_ms,
+                    ..Default::default()
                 }),
                 Err(e) => Ok(QueryResult {
                     data: vec![],
                     row_count: 0,
                     error: Some(e),
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
                 }),
             }
         }
     }
+
+    /// ClickHouse has no `RETURNING`, so the row is read back by its primary key.
+    /// When the insert didn't supply every key column, the plain insert result is returned.
+    async fn execute_returning(
+        &self,
+        schema: &str,
+        table: &str,
+        insert_query: &str,
+        inserted: &[(String, String)],
+    ) -> Result<QueryResult, String> {
+        let result = self.execute_query(insert_query).await?;
+        if result.error.is_some() {
+            return Ok(result);
+        }
+
+        let structure = self.get_table_structure(schema, table).await?;
+        let key_columns: Vec<&str> = structure
+            .columns
+            .iter()
+            .filter(|c| c.primary_key)
+            .map(|c| c.name.as_str())
+            .collect();
+        if key_columns.is_empty() {
+            return Ok(result);
+        }
+
+        let mut conditions = Vec::with_capacity(key_columns.len());
+        for key in key_columns {
+            match inserted.iter().find(|(column, _)| column == key) {
+                Some((_, value)) => {
+                    conditions.push(format!("`{}` = {}", key.replace('`', "``"), value))
+                }
+                None => return Ok(result),
+            }
+        }
+
+        self.execute_query(&format!(
+            "SELECT * FROM `{}` WHERE {} LIMIT 1",
+            table.replace('`', "``"),
+            conditions.join(" AND ")
+        ))
+        .await
+    }
 }
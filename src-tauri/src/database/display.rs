@@ -0,0 +1,209 @@
+//! Display settings applied to query results before they are returned.
+//!
+//! Settings come from the app settings table and apply to every connection; type
+//! overrides are passed with each request.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::db::models::QueryResult;
+
+/// Settings key for the IANA timezone (e.g. `Europe/Paris`) timestamps are shown in
+pub const DISPLAY_TIMEZONE_KEY: &str = "display_timezone";
+/// Settings key for the most rows returned for one statement of a script
+pub const MAX_RESULT_ROWS_KEY: &str = "max_result_rows";
+
+// None means "not configured"
+static DISPLAY_TIMEZONE: RwLock<Option<Tz>> = RwLock::new(None);
+// 0 means "not configured"
+static MAX_RESULT_ROWS: AtomicU64 = AtomicU64::new(0);
+
+/// Apply a display setting. Other keys are ignored; an empty value resets the setting
+/// (UTC timestamps, no row cap).
+pub fn apply_setting(key: &str, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid value for {}: {}", key, value);
+    match key {
+        DISPLAY_TIMEZONE_KEY => {
+            let timezone = if value.is_empty() {
+                None
+            } else {
+                Some(value.parse::<Tz>().map_err(|_| invalid())?)
+            };
+            *DISPLAY_TIMEZONE.write().unwrap_or_else(|e| e.into_inner()) = timezone;
+        }
+        MAX_RESULT_ROWS_KEY => {
+            let rows = if value.is_empty() {
+                0
+            } else {
+                value.parse::<u64>().map_err(|_| invalid())?
+            };
+            MAX_RESULT_ROWS.store(rows, Ordering::SeqCst);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Load the display settings from the app database
+pub async fn load_from_settings(pool: &SqlitePool) -> Result<(), String> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT key, value FROM settings WHERE key IN (?, ?)")
+            .bind(DISPLAY_TIMEZONE_KEY)
+            .bind(MAX_RESULT_ROWS_KEY)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    for (key, value) in rows {
+        apply_setting(&key, &value)?;
+    }
+    Ok(())
+}
+
+/// The configured display timezone, if any
+pub fn display_timezone() -> Option<Tz> {
+    *DISPLAY_TIMEZONE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Format a timezone-aware timestamp. With a display timezone it is converted to that
+/// zone and written as ISO-8601 with its offset; otherwise it is shown in UTC.
+pub fn format_timestamptz(value: &DateTime<Utc>) -> String {
+    match display_timezone() {
+        Some(tz) => value
+            .with_timezone(&tz)
+            .to_rfc3339_opts(SecondsFormat::AutoSi, false),
+        None => value.to_string(),
+    }
+}
+
+/// The configured row cap, if any
+pub fn max_result_rows() -> Option<usize> {
+    match MAX_RESULT_ROWS.load(Ordering::SeqCst) {
+        0 => None,
+        rows => Some(rows as usize),
+    }
+}
+
+/// Cut a result's rows to the `max_result_rows` setting, returning whether any were
+/// dropped. `row_count` keeps counting every row the statement returned.
+pub fn cap_result_rows(result: &mut QueryResult) -> bool {
+    match max_result_rows() {
+        Some(cap) if result.data.len() > cap => {
+            result.data.truncate(cap);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Type a result column is forced to by a `type_overrides` entry
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TypeOverride {
+    Int,
+    Float,
+    String,
+    Bool,
+    Json,
+}
+
+impl TypeOverride {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name.trim().to_lowercase().as_str() {
+            "int" => Ok(Self::Int),
+            "float" => Ok(Self::Float),
+            "string" => Ok(Self::String),
+            "bool" => Ok(Self::Bool),
+            "json" => Ok(Self::Json),
+            other => Err(format!(
+                "Unknown type override: {} (expected int, float, string, bool or json)",
+                other
+            )),
+        }
+    }
+
+    /// Re-type one value. Values that cannot be read as the target type, and NULLs, are
+    /// left as the driver decoded them.
+    fn apply(self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (_, Value::Null) => None,
+            (Self::String, Value::String(_)) => None,
+            (Self::String, other) => Some(Value::String(other.to_string())),
+            (Self::Json, Value::String(text)) => serde_json::from_str(text).ok(),
+            (Self::Json, _) => None,
+            (Self::Int, Value::Number(n)) if n.is_f64() => {
+                n.as_f64().map(|f| Value::from(f.trunc() as i64))
+            }
+            (Self::Int, Value::Bool(b)) => Some(Value::from(*b as i64)),
+            (Self::Int, Value::String(text)) => {
+                let number = numeric_text(text);
+                number
+                    .parse::<i64>()
+                    .ok()
+                    .or_else(|| number.parse::<f64>().ok().map(|f| f.trunc() as i64))
+                    .map(Value::from)
+            }
+            (Self::Float, Value::Number(n)) => n.as_f64().map(Value::from),
+            (Self::Float, Value::Bool(b)) => Some(Value::from(*b as u8 as f64)),
+            (Self::Float, Value::String(text)) => {
+                numeric_text(text).parse::<f64>().ok().map(Value::from)
+            }
+            (Self::Bool, Value::Number(n)) => n.as_f64().map(|f| Value::Bool(f != 0.0)),
+            (Self::Bool, Value::String(text)) => match text.trim().to_lowercase().as_str() {
+                "true" | "t" | "yes" | "y" | "on" | "1" => Some(Value::Bool(true)),
+                "false" | "f" | "no" | "n" | "off" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Strip currency symbols, thousands separators and spaces from a formatted number such
+/// as `$1,234.50`, keeping a leading minus sign
+fn numeric_text(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+        .collect()
+}
+
+/// Columns forced to a type (column name to `int`, `float`, `string`, `bool` or `json`),
+/// for types the driver maps badly. Columns not listed, and values that do not convert,
+/// keep the driver's mapping.
+#[derive(Debug, Default)]
+pub struct TypeOverrides(Vec<(String, TypeOverride)>);
+
+impl TypeOverrides {
+    /// Check every type name up front, so a typo fails before the query runs
+    pub fn parse(overrides: &HashMap<String, String>) -> Result<Self, String> {
+        overrides
+            .iter()
+            .map(|(column, name)| Ok((column.clone(), TypeOverride::parse(name)?)))
+            .collect::<Result<Vec<_>, String>>()
+            .map(Self)
+    }
+
+    /// Re-type the overridden columns of every row
+    pub fn apply(&self, rows: &mut [Value]) {
+        if self.0.is_empty() {
+            return;
+        }
+        for row in rows.iter_mut() {
+            let Some(row) = row.as_object_mut() else {
+                continue;
+            };
+            for (column, target) in &self.0 {
+                if let Some(value) = row.get_mut(column) {
+                    if let Some(coerced) = target.apply(value) {
+                        *value = coerced;
+                    }
+                }
+            }
+        }
+    }
+}
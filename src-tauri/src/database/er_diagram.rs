@@ -0,0 +1,106 @@
+//! Entity-relationship diagrams of a schema in Mermaid's `erDiagram` syntax, for pasting
+//! into Markdown.
+
+use crate::db::models::{SchemaOverview, TableWithStructure};
+use std::collections::HashSet;
+
+/// Mermaid entity name for a table. The schema is included only when the overview spans
+/// several schemas, and names with characters Mermaid does not allow bare are quoted.
+fn entity_name(table: &TableWithStructure, qualify: bool) -> String {
+    let name = if qualify {
+        format!("{}.{}", table.schema, table.name)
+    } else {
+        table.name.clone()
+    };
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        name
+    } else {
+        format!("\"{}\"", name.replace('"', "'"))
+    }
+}
+
+/// Column names and types must be single words; anything else becomes `_`
+fn attribute_word(text: &str) -> String {
+    let word: String = text
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '(' | ')' | '[' | ']') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match word.chars().next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => word,
+        _ => format!("_{}", word),
+    }
+}
+
+/// Render the tables of `overview` as entities with their columns, marking primary and
+/// foreign key columns, and each foreign key as a one-to-many relationship. A foreign
+/// key column that allows NULL makes the parent side optional.
+pub fn to_mermaid(overview: &SchemaOverview) -> String {
+    let schemas: HashSet<&str> = overview.tables.iter().map(|t| t.schema.as_str()).collect();
+    let qualify = schemas.len() > 1;
+
+    let mut lines = vec!["erDiagram".to_string()];
+    for table in &overview.tables {
+        let foreign_key_columns: HashSet<&str> = table
+            .foreign_keys
+            .iter()
+            .map(|fk| fk.column.as_str())
+            .collect();
+        lines.push(format!("    {} {{", entity_name(table, qualify)));
+        for column in &table.columns {
+            let keys = match (
+                column.primary_key,
+                foreign_key_columns.contains(column.name.as_str()),
+            ) {
+                (true, true) => " PK, FK",
+                (true, false) => " PK",
+                (false, true) => " FK",
+                (false, false) => "",
+            };
+            lines.push(format!(
+                "        {} {}{}",
+                attribute_word(&column.data_type),
+                attribute_word(&column.name),
+                keys
+            ));
+        }
+        lines.push("    }".to_string());
+    }
+
+    for table in &overview.tables {
+        for fk in &table.foreign_keys {
+            // Foreign keys name the referenced table without its schema; prefer a match
+            // in the same schema
+            let Some(parent) = overview
+                .tables
+                .iter()
+                .filter(|t| t.name == fk.references_table)
+                .min_by_key(|t| t.schema != table.schema)
+            else {
+                continue;
+            };
+            let nullable = table
+                .columns
+                .iter()
+                .any(|c| c.name == fk.column && c.nullable);
+            lines.push(format!(
+                "    {} {}--o{{ {} : \"{}\"",
+                entity_name(parent, qualify),
+                if nullable { "|o" } else { "||" },
+                entity_name(table, qualify),
+                fk.column.replace('"', "'")
+            ));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
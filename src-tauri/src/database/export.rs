@@ -0,0 +1,490 @@
+//! Table export helpers shared by all drivers.
+
+use arrow_array::builder::{
+    BooleanBuilder, Date32Builder, Float64Builder, Int64Builder, StringBuilder,
+    TimestampMicrosecondBuilder,
+};
+use arrow_array::{ArrayRef, RecordBatch, RecordBatchOptions};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{oneshot, Mutex};
+use tokio_util::sync::CancellationToken;
+
+use super::DatabaseDriver;
+use crate::db::models::ResultColumn;
+
+/// Number of rows fetched per page by the batched exporter
+const EXPORT_BATCH_SIZE: i64 = 1000;
+
+/// Rows per Parquet row group; query exports stream the result in batches of this size
+pub const PARQUET_ROW_GROUP_SIZE: usize = 10_000;
+
+/// Outcome of a CSV table export
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CsvExportSummary {
+    /// Data rows written, not counting the header
+    pub rows_written: u64,
+    pub bytes_written: u64,
+    /// The export was stopped by `cancel_export`; the file holds only the rows written so far
+    pub cancelled: bool,
+}
+
+/// Cancellation handle for one running export
+pub struct ExportHandle {
+    pub cancel: CancellationToken,
+    done: oneshot::Sender<u64>,
+}
+
+struct RunningExport {
+    cancel: CancellationToken,
+    done: oneshot::Receiver<u64>,
+}
+
+/// Tracks every running export, keyed by export id, so it can be cancelled
+#[derive(Default)]
+pub struct ExportManager {
+    exports: Mutex<HashMap<String, RunningExport>>,
+}
+
+impl ExportManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new export, cancelling any export still running under the same id
+    pub async fn start(&self, export_id: &str) -> ExportHandle {
+        let cancel = CancellationToken::new();
+        let (done_tx, done_rx) = oneshot::channel();
+        if let Some(previous) = self.exports.lock().await.insert(
+            export_id.to_string(),
+            RunningExport {
+                cancel: cancel.clone(),
+                done: done_rx,
+            },
+        ) {
+            previous.cancel.cancel();
+        }
+        ExportHandle {
+            cancel,
+            done: done_tx,
+        }
+    }
+
+    /// Record that an export ended, handing `rows_written` to a pending `cancel` call.
+    /// `None` means the export failed.
+    pub async fn finish(&self, export_id: &str, handle: ExportHandle, rows_written: Option<u64>) {
+        if !handle.cancel.is_cancelled() {
+            self.exports.lock().await.remove(export_id);
+        }
+        if let Some(rows) = rows_written {
+            let _ = handle.done.send(rows);
+        }
+    }
+
+    /// Cancel a running export and wait for it to close its file. Returns the number of rows
+    /// written before it stopped, or `None` if no such export was running or it failed.
+    pub async fn cancel(&self, export_id: &str) -> Option<u64> {
+        let running = self.exports.lock().await.remove(export_id)?;
+        running.cancel.cancel();
+        running.done.await.ok()
+    }
+}
+
+/// Counts the records in a stream of CSV bytes. Newlines inside quoted fields do not end
+/// a record; doubled quotes toggle the quote state twice and so cancel out.
+#[derive(Default)]
+pub(crate) struct CsvRecordCounter {
+    in_quotes: bool,
+    records: u64,
+}
+
+impl CsvRecordCounter {
+    /// Count the records ended in `chunk`, returning the offset just past the last
+    /// record terminator if there was one
+    pub(crate) fn feed(&mut self, chunk: &[u8]) -> Option<usize> {
+        let mut last_end = None;
+        for (i, byte) in chunk.iter().enumerate() {
+            match byte {
+                b'"' => self.in_quotes = !self.in_quotes,
+                b'\n' if !self.in_quotes => {
+                    self.records += 1;
+                    last_end = Some(i + 1);
+                }
+                _ => {}
+            }
+        }
+        last_end
+    }
+
+    pub(crate) fn records(&self) -> u64 {
+        self.records
+    }
+}
+
+/// Convert a JSON cell value into its CSV text representation
+fn csv_field(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Export a table to CSV by paging through `get_table_data`.
+///
+/// Used by drivers without a native bulk export path. Cancelling stops before the next
+/// page, leaving a file of complete rows.
+pub async fn export_table_csv_batched<D: DatabaseDriver + ?Sized>(
+    driver: &D,
+    schema: &str,
+    table: &str,
+    output_path: &str,
+    cancel: &CancellationToken,
+) -> Result<CsvExportSummary, String> {
+    let structure = driver.get_table_structure(schema, table).await?;
+    let mut headers: Vec<String> = structure.columns.into_iter().map(|c| c.name).collect();
+
+    let mut file = tokio::fs::File::create(output_path)
+        .await
+        .map_err(|e| format!("Failed to create export file: {}", e))?;
+    let mut summary = CsvExportSummary::default();
+    let mut header_written = false;
+    let mut page = 1;
+
+    loop {
+        let response = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => break,
+            response = driver.get_table_data(
+                schema, table, page, EXPORT_BATCH_SIZE, None, None, None,
+            ) => response?,
+        };
+
+        if headers.is_empty() {
+            if let Some(Value::Object(first)) = response.data.first() {
+                headers = first.keys().cloned().collect();
+            }
+        }
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        if !header_written {
+            writer
+                .write_record(&headers)
+                .map_err(|e| format!("Failed to write CSV: {}", e))?;
+            header_written = true;
+        }
+
+        for row in &response.data {
+            let record: Vec<String> = headers
+                .iter()
+                .map(|h| row.get(h).map(csv_field).unwrap_or_default())
+                .collect();
+            writer
+                .write_record(&record)
+                .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        }
+
+        let chunk = writer
+            .into_inner()
+            .map_err(|e| format!("Failed to write CSV: {}", e))?;
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write export file: {}", e))?;
+        summary.bytes_written += chunk.len() as u64;
+        summary.rows_written += response.data.len() as u64;
+
+        if response.data.len() < EXPORT_BATCH_SIZE as usize
+            || page * EXPORT_BATCH_SIZE >= response.total
+        {
+            break;
+        }
+        page += 1;
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+    summary.cancelled = cancel.is_cancelled();
+    Ok(summary)
+}
+
+/// Parse the timestamp formats the drivers produce: RFC 3339, and
+/// `YYYY-MM-DD HH:MM:SS[.f]` with an optional ` UTC` suffix
+fn parse_timestamp_micros(text: &str) -> Option<i64> {
+    if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Some(ts.timestamp_micros());
+    }
+    let naive = text.strip_suffix(" UTC").unwrap_or(text);
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(naive, format).ok())
+        .map(|ts| ts.and_utc().timestamp_micros())
+}
+
+/// Parse a `YYYY-MM-DD` date into days since the Unix epoch
+fn parse_date_days(text: &str) -> Option<i32> {
+    let date = chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+    let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1)?;
+    i32::try_from((date - epoch).num_days()).ok()
+}
+
+/// Map a column's database type to an Arrow type. Exact numerics, unsigned 64-bit and wider
+/// integers, and anything unrecognized are written as text so no value loses precision.
+/// Returns `None` when the database did not report a type.
+pub fn arrow_type_for(db_type: &str) -> Option<DataType> {
+    let mut name = db_type.trim().to_ascii_lowercase();
+    for wrapper in ["nullable(", "lowcardinality("] {
+        while let Some(inner) = name.strip_prefix(wrapper).and_then(|n| n.strip_suffix(')')) {
+            name = inner.to_string();
+        }
+    }
+    let base = name.split('(').next().unwrap_or_default().trim();
+
+    let data_type = match base {
+        "" | "null" => return None,
+        "bool" | "boolean" => DataType::Boolean,
+        "int" | "int2" | "int4" | "int8" | "integer" | "smallint" | "bigint" | "tinyint"
+        | "mediumint" | "int16" | "int32" | "int64" | "uint8" | "uint16" | "uint32" => {
+            DataType::Int64
+        }
+        "real" | "float" | "float4" | "float8" | "float32" | "float64" | "double"
+        | "double precision" => DataType::Float64,
+        "timestamp" | "timestamptz" | "datetime" | "datetime64" => {
+            DataType::Timestamp(TimeUnit::Microsecond, None)
+        }
+        "date" | "date32" => DataType::Date32,
+        _ => DataType::Utf8,
+    };
+    Some(data_type)
+}
+
+/// Pick the Arrow type for a column from its non-null values. Mixed or nested
+/// values fall back to text, with non-string values written as JSON.
+fn infer_arrow_type<'a>(values: impl Iterator<Item = &'a Value> + Clone) -> DataType {
+    let mut non_null = values.filter(|v| !v.is_null()).peekable();
+    if non_null.peek().is_none() {
+        return DataType::Utf8;
+    }
+    if non_null.clone().all(Value::is_boolean) {
+        DataType::Boolean
+    } else if non_null.clone().all(Value::is_i64) {
+        DataType::Int64
+    } else if non_null.clone().all(Value::is_number) {
+        DataType::Float64
+    } else if non_null.all(|v| v.as_str().and_then(parse_timestamp_micros).is_some()) {
+        DataType::Timestamp(TimeUnit::Microsecond, None)
+    } else {
+        DataType::Utf8
+    }
+}
+
+/// Convert one non-null value for a typed column. Numbers and booleans are also
+/// accepted as strings, since drivers quote 64-bit integers and SQLite stores booleans as 0/1.
+fn convert_value<T>(
+    value: &Value,
+    name: &str,
+    data_type: &DataType,
+    convert: impl Fn(&Value) -> Option<T>,
+) -> Result<Option<T>, String> {
+    if value.is_null() {
+        return Ok(None);
+    }
+    convert(value).map(Some).ok_or_else(|| {
+        format!(
+            "Column \"{}\" value {} cannot be written as {}",
+            name, value, data_type
+        )
+    })
+}
+
+fn as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => n.as_i64().filter(|n| *n == 0 || *n == 1).map(|n| n == 1),
+        Value::String(s) => match s.as_str() {
+            "true" | "t" | "1" => Some(true),
+            "false" | "f" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+fn build_arrow_column(
+    rows: &[Value],
+    name: &str,
+    data_type: &DataType,
+) -> Result<ArrayRef, String> {
+    let values = rows.iter().map(|row| row.get(name).unwrap_or(&Value::Null));
+    let column: ArrayRef = match data_type {
+        DataType::Boolean => {
+            let mut builder = BooleanBuilder::new();
+            for v in values {
+                builder.append_option(convert_value(v, name, data_type, as_bool)?);
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Int64 => {
+            let mut builder = Int64Builder::new();
+            for v in values {
+                builder.append_option(convert_value(v, name, data_type, as_i64)?);
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Float64 => {
+            let mut builder = Float64Builder::new();
+            for v in values {
+                builder.append_option(convert_value(v, name, data_type, as_f64)?);
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Timestamp(_, _) => {
+            let mut builder = TimestampMicrosecondBuilder::new();
+            for v in values {
+                builder.append_option(convert_value(v, name, data_type, |v| {
+                    v.as_str().and_then(parse_timestamp_micros)
+                })?);
+            }
+            Arc::new(builder.finish())
+        }
+        DataType::Date32 => {
+            let mut builder = Date32Builder::new();
+            for v in values {
+                builder.append_option(convert_value(v, name, data_type, |v| {
+                    v.as_str().and_then(parse_date_days)
+                })?);
+            }
+            Arc::new(builder.finish())
+        }
+        _ => {
+            let mut builder = StringBuilder::new();
+            values.for_each(|v| match v {
+                Value::Null => builder.append_null(),
+                Value::String(s) => builder.append_value(s),
+                other => builder.append_value(other.to_string()),
+            });
+            Arc::new(builder.finish())
+        }
+    };
+    Ok(column)
+}
+
+/// Parquet file written one row group per batch, so a result never has to be held
+/// in memory at once
+pub struct ParquetWriter {
+    writer: ArrowWriter<std::fs::File>,
+    schema: SchemaRef,
+    rows_written: usize,
+}
+
+impl ParquetWriter {
+    /// Create the file with a schema built from the result's column types. Columns
+    /// without a reported type are written as text.
+    pub fn create(output_path: &str, columns: &[ResultColumn]) -> Result<Self, String> {
+        let fields: Vec<Field> = columns
+            .iter()
+            .map(|column| {
+                let data_type = arrow_type_for(&column.data_type).unwrap_or(DataType::Utf8);
+                Field::new(&column.name, data_type, true)
+            })
+            .collect();
+        Self::with_schema(output_path, Arc::new(Schema::new(fields)))
+    }
+
+    fn with_schema(output_path: &str, schema: SchemaRef) -> Result<Self, String> {
+        let file = std::fs::File::create(output_path)
+            .map_err(|e| format!("Failed to create export file: {}", e))?;
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(PARQUET_ROW_GROUP_SIZE)
+            .build();
+        let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))
+            .map_err(|e| format!("Failed to write Parquet: {}", e))?;
+        Ok(Self {
+            writer,
+            schema,
+            rows_written: 0,
+        })
+    }
+
+    /// Write a batch of rows as a row group
+    pub fn write_rows(&mut self, rows: &[Value]) -> Result<(), String> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| build_arrow_column(rows, field.name(), field.data_type()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+        let batch = RecordBatch::try_new_with_options(self.schema.clone(), columns, &options)
+            .map_err(|e| format!("Failed to build record batch: {}", e))?;
+        self.writer
+            .write(&batch)
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| format!("Failed to write Parquet: {}", e))?;
+        self.rows_written += rows.len();
+        Ok(())
+    }
+
+    /// Close the file, returning the number of rows written
+    pub fn finish(self) -> Result<usize, String> {
+        self.writer
+            .close()
+            .map_err(|e| format!("Failed to write Parquet: {}", e))?;
+        Ok(self.rows_written)
+    }
+}
+
+/// Write query result rows to a Parquet file, inferring an Arrow schema from the
+/// values. Columns keep the order in which they first appear. Returns the row count.
+/// Used when the driver cannot describe the query's columns.
+pub fn write_rows_parquet(rows: &[Value], output_path: &str) -> Result<usize, String> {
+    let mut names: Vec<String> = Vec::new();
+    for row in rows {
+        if let Value::Object(obj) = row {
+            for key in obj.keys() {
+                if !names.contains(key) {
+                    names.push(key.clone());
+                }
+            }
+        }
+    }
+
+    let fields: Vec<Field> = names
+        .iter()
+        .map(|name| {
+            let data_type = infer_arrow_type(
+                rows.iter()
+                    .map(|row| row.get(name.as_str()).unwrap_or(&Value::Null)),
+            );
+            Field::new(name, data_type, true)
+        })
+        .collect();
+
+    let mut writer = ParquetWriter::with_schema(output_path, Arc::new(Schema::new(fields)))?;
+    for chunk in rows.chunks(PARQUET_ROW_GROUP_SIZE) {
+        writer.write_rows(chunk)?;
+    }
+    writer.finish()
+}
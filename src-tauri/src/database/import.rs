@@ -0,0 +1,533 @@
+//! CSV import into existing tables, and pasted CSV or JSON into new ones, shared by all
+//! SQL drivers.
+
+use flate2::read::MultiGzDecoder;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::postgres::PgPoolCopyExt;
+use sqlx::PgPool;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use tokio::sync::mpsc;
+
+use super::DatabaseDriver;
+
+/// Number of CSV records inserted per `INSERT` statement
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Outcome of a CSV import
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct CsvImportSummary {
+    pub rows_imported: u64,
+    /// Whether all rows went in as one transaction, so a failed import left the table
+    /// unchanged. Drivers without transactions commit each batch on its own.
+    pub transactional: bool,
+}
+
+/// Whether an input should be gunzipped: `compressed` when given, otherwise a `.gz` name
+pub fn is_gzip_input(path: &str, compressed: Option<bool>) -> bool {
+    compressed.unwrap_or_else(|| path.to_ascii_lowercase().ends_with(".gz"))
+}
+
+/// Open a CSV file with a header row, decompressing gzip input as it is read
+fn open_csv(path: &str, gzip: bool) -> Result<csv::Reader<Box<dyn Read + Send>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open CSV file: {}", e))?;
+    let input: Box<dyn Read + Send> = if gzip {
+        Box::new(MultiGzDecoder::new(BufReader::new(file)))
+    } else {
+        Box::new(BufReader::new(file))
+    };
+    Ok(csv::Reader::from_reader(input))
+}
+
+fn csv_error(e: csv::Error) -> String {
+    format!("Failed to read CSV: {}", e)
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// SQL literal for a CSV field. Empty fields become NULL; everything else is a quoted
+/// string for the database to convert to the column's type.
+fn sql_literal(field: &str) -> String {
+    if field.is_empty() {
+        "NULL".to_string()
+    } else {
+        format!("'{}'", field.replace('\'', "''"))
+    }
+}
+
+/// Import a CSV file into `table_ref`, matching its header row to column names.
+///
+/// The file is read on a blocking thread and inserted in multi-row `INSERT` batches,
+/// inside one transaction when the driver supports it.
+pub async fn import_csv_batched<D: DatabaseDriver + ?Sized>(
+    driver: &D,
+    table_ref: &str,
+    csv_path: &str,
+    gzip: bool,
+) -> Result<CsvImportSummary, String> {
+    let mut reader = open_csv(csv_path, gzip)?;
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    if headers.iter().all(str::is_empty) {
+        return Err("CSV file has no header row".to_string());
+    }
+    let insert_prefix = format!(
+        "INSERT INTO {} ({}) VALUES ",
+        table_ref,
+        headers
+            .iter()
+            .map(quote_identifier)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    // Dropping the receiver on an error stops the reader at its next batch
+    let (sender, mut batches) = mpsc::channel::<Result<Vec<csv::StringRecord>, String>>(2);
+    let producer = tokio::task::spawn_blocking(move || {
+        let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+        for record in reader.into_records() {
+            match record {
+                Ok(record) => batch.push(record),
+                Err(e) => {
+                    let _ = sender.blocking_send(Err(csv_error(e)));
+                    return;
+                }
+            }
+            if batch.len() == IMPORT_BATCH_SIZE
+                && sender
+                    .blocking_send(Ok(std::mem::take(&mut batch)))
+                    .is_err()
+            {
+                return;
+            }
+        }
+        if !batch.is_empty() {
+            let _ = sender.blocking_send(Ok(batch));
+        }
+    });
+
+    // An uncommitted transaction rolls back when dropped on an early return. Only
+    // databases without transactions fall back to committing each batch; a failed BEGIN
+    // on one that has them is an error.
+    let mut transaction = if driver.capabilities().supports_transactions {
+        Some(driver.begin_transaction().await?)
+    } else {
+        None
+    };
+    let mut summary = CsvImportSummary {
+        rows_imported: 0,
+        transactional: transaction.is_some(),
+    };
+
+    while let Some(batch) = batches.recv().await {
+        let batch = batch?;
+        let rows: Vec<String> = batch
+            .iter()
+            .map(|record| {
+                let values: Vec<String> = record.iter().map(sql_literal).collect();
+                format!("({})", values.join(", "))
+            })
+            .collect();
+        let statement = format!("{}{}", insert_prefix, rows.join(", "));
+
+        match transaction.as_mut() {
+            Some(tx) => {
+                tx.execute(&statement).await?;
+            }
+            None => {
+                let result = driver.execute_query(&statement).await?;
+                if let Some(error) = result.error {
+                    return Err(error);
+                }
+            }
+        }
+        summary.rows_imported += batch.len() as u64;
+    }
+
+    producer.await.map_err(|e| e.to_string())?;
+    if let Some(tx) = transaction {
+        tx.commit().await?;
+    }
+    Ok(summary)
+}
+
+/// Import a CSV file into a Postgres table with `COPY ... FROM STDIN`.
+///
+/// Records are re-encoded as they are read, so the header row still picks the columns
+/// and gzip input works as in [`import_csv_batched`]; `FORCE_NULL` makes empty fields
+/// NULL there too. COPY is a single statement, so a failed import leaves the table
+/// unchanged.
+pub async fn copy_csv_postgres(
+    pool: &PgPool,
+    table_ref: &str,
+    csv_path: &str,
+    gzip: bool,
+) -> Result<CsvImportSummary, String> {
+    let mut reader = open_csv(csv_path, gzip)?;
+    let headers = reader.headers().map_err(csv_error)?.clone();
+    if headers.iter().all(str::is_empty) {
+        return Err("CSV file has no header row".to_string());
+    }
+    let columns = headers
+        .iter()
+        .map(quote_identifier)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let statement = format!(
+        "COPY {} ({}) FROM STDIN WITH (FORMAT csv, FORCE_NULL ({}))",
+        table_ref, columns, columns
+    );
+
+    // Dropping the receiver on an error stops the reader at its next chunk
+    let (sender, mut chunks) = mpsc::channel::<Result<Vec<u8>, String>>(2);
+    let producer = tokio::task::spawn_blocking(move || {
+        let new_writer = || csv::Writer::from_writer(Vec::new());
+        let mut writer = new_writer();
+        let mut buffered = 0;
+        for record in reader.into_records() {
+            let written = record
+                .map_err(csv_error)
+                .and_then(|record| writer.write_record(&record).map_err(csv_error));
+            if let Err(e) = written {
+                let _ = sender.blocking_send(Err(e));
+                return;
+            }
+            buffered += 1;
+            if buffered == IMPORT_BATCH_SIZE {
+                buffered = 0;
+                let chunk = std::mem::replace(&mut writer, new_writer())
+                    .into_inner()
+                    .map_err(|e| format!("Failed to encode CSV: {}", e.error()));
+                if sender.blocking_send(chunk).is_err() {
+                    return;
+                }
+            }
+        }
+        if buffered > 0 {
+            let chunk = writer
+                .into_inner()
+                .map_err(|e| format!("Failed to encode CSV: {}", e.error()));
+            let _ = sender.blocking_send(chunk);
+        }
+    });
+
+    let mut copy = pool
+        .copy_in_raw(&statement)
+        .await
+        .map_err(|e| format!("Failed to start COPY import: {}", e))?;
+    while let Some(chunk) = chunks.recv().await {
+        let sent = match chunk {
+            Ok(chunk) => copy
+                .send(chunk)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("COPY import failed: {}", e)),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            let _ = copy.abort(e.as_str()).await;
+            return Err(e);
+        }
+    }
+
+    producer.await.map_err(|e| e.to_string())?;
+    let rows_imported = copy
+        .finish()
+        .await
+        .map_err(|e| format!("COPY import failed: {}", e))?;
+    Ok(CsvImportSummary {
+        rows_imported,
+        transactional: true,
+    })
+}
+
+/// Column type inferred from pasted values by `infer_column_type`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InferredType {
+    Integer,
+    Float,
+    Boolean,
+    Timestamp,
+    String,
+}
+
+impl InferredType {
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            InferredType::Integer => is_integer(value),
+            InferredType::Float => is_float(value),
+            InferredType::Boolean => is_boolean(value),
+            InferredType::Timestamp => is_timestamp(value),
+            InferredType::String => true,
+        }
+    }
+
+    /// Column type used for the new table on `db_type` (`postgres` or `sqlite`)
+    fn sql_type(self, db_type: &str) -> &'static str {
+        let postgres = matches!(db_type, "postgres" | "postgresql");
+        match self {
+            InferredType::Integer if postgres => "BIGINT",
+            InferredType::Integer => "INTEGER",
+            InferredType::Float if postgres => "DOUBLE PRECISION",
+            InferredType::Float => "REAL",
+            InferredType::Boolean => "BOOLEAN",
+            InferredType::Timestamp => "TIMESTAMP",
+            InferredType::String => "TEXT",
+        }
+    }
+}
+
+/// A column of a table created by `import_pasted_data`
+#[derive(Clone, Debug, Serialize)]
+pub struct InferredColumn {
+    pub name: String,
+    pub inferred_type: InferredType,
+    pub sql_type: String,
+}
+
+/// Outcome of importing pasted data into a new table
+#[derive(Clone, Debug, Serialize)]
+pub struct PastedImportSummary {
+    pub columns: Vec<InferredColumn>,
+    pub rows_imported: u64,
+    /// Whether the table was created and filled in one transaction
+    pub transactional: bool,
+}
+
+/// Header and rows of pasted data; `None` is a missing value
+type PastedRows = (Vec<String>, Vec<Vec<Option<String>>>);
+
+/// Parse pasted `csv` (with a header row) or `json` (an array of objects). JSON columns
+/// are the keys in order of first appearance; nested values are kept as JSON text.
+pub fn parse_pasted_data(data: &str, format: &str) -> Result<PastedRows, String> {
+    match format.to_lowercase().as_str() {
+        "csv" => {
+            let mut reader = csv::Reader::from_reader(data.as_bytes());
+            let headers: Vec<String> = reader
+                .headers()
+                .map_err(csv_error)?
+                .iter()
+                .map(|h| h.trim().to_string())
+                .collect();
+            if headers.iter().all(String::is_empty) {
+                return Err("CSV data has no header row".to_string());
+            }
+            let rows = reader
+                .records()
+                .map(|record| {
+                    record.map_err(csv_error).map(|record| {
+                        record
+                            .iter()
+                            .map(|field| (!field.is_empty()).then(|| field.to_string()))
+                            .collect()
+                    })
+                })
+                .collect::<Result<Vec<Vec<Option<String>>>, String>>()?;
+            Ok((headers, rows))
+        }
+        "json" => {
+            let value: Value =
+                serde_json::from_str(data).map_err(|e| format!("Failed to parse JSON: {}", e))?;
+            let objects = value
+                .as_array()
+                .ok_or("JSON data must be an array of objects")?
+                .iter()
+                .map(|row| {
+                    row.as_object()
+                        .ok_or("JSON data must be an array of objects")
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut headers: Vec<String> = Vec::new();
+            for object in &objects {
+                for key in object.keys() {
+                    if !headers.contains(key) {
+                        headers.push(key.clone());
+                    }
+                }
+            }
+            let rows = objects
+                .iter()
+                .map(|object| {
+                    headers
+                        .iter()
+                        .map(|header| match object.get(header) {
+                            None | Some(Value::Null) => None,
+                            Some(Value::String(s)) => Some(s.clone()),
+                            Some(other) => Some(other.to_string()),
+                        })
+                        .collect()
+                })
+                .collect();
+            Ok((headers, rows))
+        }
+        other => Err(format!("Unsupported paste format: {}", other)),
+    }
+}
+
+/// Leading zeros, as in zip codes or ids, mean a value is not really a number
+fn has_leading_zero(value: &str) -> bool {
+    let digits = value.trim_start_matches(['-', '+']);
+    digits.len() > 1 && digits.starts_with('0') && digits.as_bytes()[1].is_ascii_digit()
+}
+
+fn is_integer(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && !has_leading_zero(value)
+        && value.parse::<i64>().is_ok()
+}
+
+fn is_float(value: &str) -> bool {
+    // Rules out `inf` and `NaN`, which `f64` would accept
+    value
+        .bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'.' | b'-' | b'+' | b'e' | b'E'))
+        && value.bytes().any(|b| b.is_ascii_digit())
+        && !has_leading_zero(value)
+        && value.parse::<f64>().is_ok()
+}
+
+fn is_boolean(value: &str) -> bool {
+    value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false")
+}
+
+fn is_timestamp(value: &str) -> bool {
+    chrono::DateTime::parse_from_rfc3339(value).is_ok()
+        || ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+            .iter()
+            .any(|format| chrono::NaiveDateTime::parse_from_str(value, format).is_ok())
+        || chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+/// Narrowest type every present value fits, trying integer, float, boolean and
+/// timestamp in turn. A column with no values is a string column.
+pub fn infer_column_type<'a>(values: impl Iterator<Item = &'a str> + Clone) -> InferredType {
+    let mut values = values.peekable();
+    if values.peek().is_none() {
+        return InferredType::String;
+    }
+    [
+        InferredType::Integer,
+        InferredType::Float,
+        InferredType::Boolean,
+        InferredType::Timestamp,
+    ]
+    .into_iter()
+    .find(|inferred| values.clone().all(|value| inferred.accepts(value)))
+    .unwrap_or(InferredType::String)
+}
+
+fn typed_literal(value: Option<&String>, inferred: InferredType) -> String {
+    match (value, inferred) {
+        (None, _) => "NULL".to_string(),
+        (Some(value), InferredType::Integer | InferredType::Float) => value.clone(),
+        (Some(value), InferredType::Boolean) => value.to_uppercase(),
+        (Some(value), _) => sql_literal(value),
+    }
+}
+
+/// Create `table_ref` with column types inferred from pasted `csv` or `json` data and
+/// insert its rows, inside one transaction when the driver supports it
+pub async fn import_pasted_data<D: DatabaseDriver + ?Sized>(
+    driver: &D,
+    db_type: &str,
+    table_ref: &str,
+    data: &str,
+    format: &str,
+) -> Result<PastedImportSummary, String> {
+    let (headers, rows) = parse_pasted_data(data, format)?;
+    if headers.is_empty() {
+        return Err("Pasted data has no columns".to_string());
+    }
+    if let Some(row) = rows.iter().find(|row| row.len() != headers.len()) {
+        return Err(format!(
+            "Expected {} values per row, found a row with {}",
+            headers.len(),
+            row.len()
+        ));
+    }
+
+    let columns: Vec<InferredColumn> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let inferred_type = infer_column_type(rows.iter().filter_map(|row| row[i].as_deref()));
+            InferredColumn {
+                name: name.clone(),
+                inferred_type,
+                sql_type: inferred_type.sql_type(db_type).to_string(),
+            }
+        })
+        .collect();
+
+    let create = format!(
+        "CREATE TABLE {} ({})",
+        table_ref,
+        columns
+            .iter()
+            .map(|c| format!("{} {}", quote_identifier(&c.name), c.sql_type))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let insert_prefix = format!(
+        "INSERT INTO {} ({}) VALUES ",
+        table_ref,
+        columns
+            .iter()
+            .map(|c| quote_identifier(&c.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let mut statements = vec![create];
+    for batch in rows.chunks(IMPORT_BATCH_SIZE) {
+        let values: Vec<String> = batch
+            .iter()
+            .map(|row| {
+                let literals: Vec<String> = row
+                    .iter()
+                    .zip(&columns)
+                    .map(|(value, column)| typed_literal(value.as_ref(), column.inferred_type))
+                    .collect();
+                format!("({})", literals.join(", "))
+            })
+            .collect();
+        statements.push(format!("{}{}", insert_prefix, values.join(", ")));
+    }
+
+    // An uncommitted transaction rolls back when dropped on an early return. Only
+    // databases without transactions fall back to committing each batch; a failed BEGIN
+    // on one that has them is an error.
+    let mut transaction = if driver.capabilities().supports_transactions {
+        Some(driver.begin_transaction().await?)
+    } else {
+        None
+    };
+    let transactional = transaction.is_some();
+    for statement in &statements {
+        match transaction.as_mut() {
+            Some(tx) => {
+                tx.execute(statement).await?;
+            }
+            None => {
+                let result = driver.execute_query(statement).await?;
+                if let Some(error) = result.error {
+                    return Err(error);
+                }
+            }
+        }
+    }
+    if let Some(tx) = transaction {
+        tx.commit().await?;
+    }
+
+    Ok(PastedImportSummary {
+        columns,
+        rows_imported: rows.len() as u64,
+        transactional,
+    })
+}
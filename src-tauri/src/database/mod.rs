@@ -1,26 +1,78 @@
 use async_trait::async_trait;
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
+#[cfg(feature = "cassandra")]
+pub mod cassandra;
 pub mod clickhouse;
+pub mod display;
+pub mod er_diagram;
+pub mod export;
+pub mod import;
+pub mod notices;
+pub mod opensearch;
+#[cfg(feature = "oracle")]
+pub mod oracle;
 pub mod pool_manager;
 pub mod postgres;
 pub mod queries;
+pub mod query_cache;
+pub mod query_stream;
+pub mod reachability;
 pub mod redis;
+pub mod retry;
+pub mod script;
 pub mod sqlite;
+pub mod table_watch;
+pub mod timeouts;
+pub mod transaction;
+pub mod undo;
 
 use crate::db::models::{
-    QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure,
-    TestConnectionResult,
+    ClusterNode, ColumnProfile, DatabaseInfo, DriverCapabilities, NamespaceOverview, QueryResult,
+    ResultColumn, RoutineInfo, SchemaOverview, TableDataResponse, TableInfo, TablePartitions,
+    TableStructure, TestConnectionResult,
 };
+pub use transaction::TxHandle;
 
 /// Common trait for all database drivers
 #[async_trait]
 pub trait DatabaseDriver: Send + Sync {
+    /// Which optional features this database supports; nothing unless the driver says so
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities::default()
+    }
+
     /// Test if the connection is valid
     async fn test_connection(&self) -> Result<TestConnectionResult, String>;
 
     /// List all tables in the database
     async fn list_tables(&self) -> Result<Vec<TableInfo>, String>;
 
+    /// List tables whose name contains `name_filter` (case-insensitive), one page at a time.
+    /// `page` is 1-based; without a `limit` all matching tables are returned.
+    async fn list_tables_paged(
+        &self,
+        name_filter: Option<&str>,
+        page: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TableInfo>, String> {
+        let needle = name_filter.map(|f| f.to_lowercase());
+        let tables = self.list_tables().await?.into_iter().filter(|t| {
+            needle
+                .as_ref()
+                .is_none_or(|n| t.name.to_lowercase().contains(n))
+        });
+
+        Ok(match limit {
+            Some(limit) => tables
+                .skip(page_offset(page, limit) as usize)
+                .take(limit.max(0) as usize)
+                .collect(),
+            None => tables.collect(),
+        })
+    }
+
     /// Get paginated data from a table
     async fn get_table_data(
         &self,
@@ -43,8 +95,228 @@ pub trait DatabaseDriver: Send + Sync {
     /// Execute a raw SQL query
     async fn execute_query(&self, query: &str) -> Result<QueryResult, String>;
 
+    /// Execute a single statement with bind parameters (`$1` for Postgres, `?` for SQLite)
+    async fn execute_query_with_params(
+        &self,
+        query: &str,
+        params: &[Value],
+    ) -> Result<QueryResult, String> {
+        if params.is_empty() {
+            return self.execute_query(query).await;
+        }
+        Err("Bind parameters are not supported for this database".to_string())
+    }
+
+    /// Run a query that can produce several result sets, such as a batch of statements
+    /// or a procedure, returning one result per set.
+    ///
+    /// Drivers without a multi-result API return the single result of `execute_query`.
+    async fn execute_multi_result(&self, query: &str) -> Result<Vec<QueryResult>, String> {
+        Ok(vec![self.execute_query(query).await?])
+    }
+
+    /// Run an `INSERT` and return the inserted row in `data`. `inserted` pairs each
+    /// written column with its SQL literal, for drivers that read the row back by key.
+    ///
+    /// Drivers that can't fetch the new row just run the insert.
+    async fn execute_returning(
+        &self,
+        _schema: &str,
+        _table: &str,
+        insert_query: &str,
+        _inserted: &[(String, String)],
+    ) -> Result<QueryResult, String> {
+        self.execute_query(insert_query).await
+    }
+
+    /// Run a query and pass its rows to `on_batch` in batches of up to `batch_size`,
+    /// stopping once `cancel` fires. Returns the number of rows delivered.
+    ///
+    /// Drivers without a row stream run the query in full and deliver it in slices.
+    async fn stream_query(
+        &self,
+        query: &str,
+        batch_size: usize,
+        cancel: &CancellationToken,
+        on_batch: &mut (dyn FnMut(Vec<Value>) + Send),
+    ) -> Result<u64, String> {
+        let result = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Ok(0),
+            result = self.execute_query(query) => result?,
+        };
+        if let Some(error) = result.error {
+            return Err(error);
+        }
+
+        let mut delivered: u64 = 0;
+        for batch in result.data.chunks(batch_size.max(1)) {
+            if cancel.is_cancelled() {
+                break;
+            }
+            delivered += batch.len() as u64;
+            on_batch(batch.to_vec());
+        }
+        Ok(delivered)
+    }
+
     /// Get schema overview with all tables and their structures (columns, foreign keys, indexes)
     async fn get_schema_overview(&self) -> Result<SchemaOverview, String>;
+
+    /// Export a table to a CSV file. Cancelling `cancel` stops the export early, leaving
+    /// the rows written so far in a cleanly closed file.
+    async fn export_table_csv(
+        &self,
+        schema: &str,
+        table: &str,
+        output_path: &str,
+        cancel: &CancellationToken,
+    ) -> Result<export::CsvExportSummary, String> {
+        export::export_table_csv_batched(self, schema, table, output_path, cancel).await
+    }
+
+    /// Import a CSV file with a header row into `table_ref`, gunzipping it when `gzip`
+    async fn import_table_csv(
+        &self,
+        table_ref: &str,
+        csv_path: &str,
+        gzip: bool,
+    ) -> Result<import::CsvImportSummary, String> {
+        import::import_csv_batched(self, table_ref, csv_path, gzip).await
+    }
+
+    /// Start an explicit transaction
+    async fn begin_transaction(&self) -> Result<TxHandle, String> {
+        Err("Transactions are not supported for this database".to_string())
+    }
+
+    /// Character set, collation and timezone, for debugging encoding issues
+    async fn get_database_info(&self) -> Result<DatabaseInfo, String> {
+        Err("Database info is not supported for this database".to_string())
+    }
+
+    /// List the stored functions and procedures in a schema
+    async fn list_routines(&self, _schema: &str) -> Result<Vec<RoutineInfo>, String> {
+        Err("Routines are not supported for this database".to_string())
+    }
+
+    /// Source of a routine; overloads sharing the name are returned together
+    async fn get_routine_definition(&self, _schema: &str, _name: &str) -> Result<String, String> {
+        Err("Routines are not supported for this database".to_string())
+    }
+
+    /// Null fraction and distinct count of each column from the catalog's planner
+    /// statistics, without scanning the table. Empty when none have been gathered.
+    async fn column_statistics(
+        &self,
+        _schema: &str,
+        _table: &str,
+    ) -> Result<Vec<ColumnProfile>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Table count and estimated rows of every schema or database on the connection
+    async fn namespace_overview(&self) -> Result<Vec<NamespaceOverview>, String> {
+        Err("Server overview is not supported for this database".to_string())
+    }
+
+    /// Partitioning strategy and child partitions of a table
+    async fn get_partitions(&self, _schema: &str, _table: &str) -> Result<TablePartitions, String> {
+        Err("Partitions are not supported for this database".to_string())
+    }
+
+    /// Shards and replicas of the clusters the server knows about
+    async fn get_cluster_info(&self) -> Result<Vec<ClusterNode>, String> {
+        Err("Clusters are not supported for this database".to_string())
+    }
+
+    /// PID of the server process serving one of the driver's pooled connections, for
+    /// use with `pg_stat_activity` and `pg_cancel_backend`
+    async fn backend_pid(&self) -> Result<i64, String> {
+        Err("Backend PID is not available for this database".to_string())
+    }
+
+    /// Up to `n` rows picked at random from a table, for a quick look at its contents
+    async fn sample_table(
+        &self,
+        _schema: &str,
+        _table: &str,
+        _n: i64,
+    ) -> Result<QueryResult, String> {
+        Err("Table sampling is not supported for this database".to_string())
+    }
+
+    /// Run a lightweight round trip (`SELECT 1`, `PING`) on the cached connection.
+    /// Drivers whose connection test clears the reachability cache override this, so a
+    /// background ping never retries a server that is known to be down.
+    async fn ping(&self) -> Result<(), String> {
+        let result = self.test_connection().await?;
+        if result.success {
+            Ok(())
+        } else {
+            Err(result.message)
+        }
+    }
+
+    /// Close the driver's server connections, e.g. when the pool manager evicts it.
+    /// Drivers without a long-lived pool have nothing to close.
+    async fn close(&self) {}
+
+    /// Check that a query parses for this database without executing it. Drivers
+    /// without a dry-run mode accept every query.
+    async fn validate_query(&self, _query: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Columns a query returns, with their database types, without running it.
+    /// Drivers that cannot describe a query return no columns.
+    async fn describe_query(&self, _query: &str) -> Result<Vec<ResultColumn>, String> {
+        Ok(Vec::new())
+    }
+}
+
+/// Row offset of a 1-based page
+pub(crate) fn page_offset(page: Option<i64>, limit: i64) -> i64 {
+    (page.unwrap_or(1).max(1) - 1) * limit.max(0)
+}
+
+/// Build a LIKE pattern matching names that contain `filter` literally
+pub(crate) fn like_contains_pattern(filter: &str) -> String {
+    let escaped = filter
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Estimated row count above which `sample_table` uses the engine's sampling clause
+/// instead of sorting the whole table randomly
+pub(crate) const SAMPLE_CLAUSE_MIN_ROWS: f64 = 100_000.0;
+
+/// Fraction of a table to sample so that `n` rows are very likely returned. Block-level
+/// sampling is uneven, so ten times the needed share is requested.
+pub(crate) fn sample_fraction(n: i64, estimated_rows: f64) -> f64 {
+    (n as f64 * 10.0 / estimated_rows.max(1.0)).clamp(0.000_001, 1.0)
+}
+
+/// Session setup statements worth running, ignoring a blank setting
+pub(crate) fn session_setup_sql(setup: &Option<String>) -> Option<String> {
+    setup
+        .as_deref()
+        .map(str::trim)
+        .filter(|sql| !sql.is_empty())
+        .map(str::to_string)
+}
+
+/// Check that a statement received exactly as many bind parameters as it has placeholders
+pub(crate) fn check_param_count(expected: usize, provided: usize) -> Result<(), String> {
+    if expected != provided {
+        return Err(format!(
+            "Query expects {} parameter(s) but {} were provided",
+            expected, provided
+        ));
+    }
+    Ok(())
 }
 
 /// Configuration for Postgres connections
@@ -56,12 +328,19 @@ pub struct PostgresConfig {
     pub username: String,
     pub password: String,
     pub ssl: bool,
+    /// Directory holding the server's Unix domain socket (e.g. `/var/run/postgresql`),
+    /// used instead of `host`
+    pub socket_path: Option<String>,
+    /// Statements run on each new session, e.g. `SET search_path TO app`
+    pub session_setup_sql: Option<String>,
 }
 
 /// Configuration for SQLite connections
 #[derive(Clone)]
 pub struct SqliteConfig {
     pub file_path: String,
+    /// Statements run each time the database is opened, e.g. `PRAGMA foreign_keys = ON`
+    pub session_setup_sql: Option<String>,
 }
 
 /// Configuration for Redis connections
@@ -75,7 +354,12 @@ pub struct RedisConfig {
 }
 
 // Re-export ClickHouse config from its module
+#[cfg(feature = "cassandra")]
+pub use cassandra::CassandraConfig;
 pub use clickhouse::{ClickhouseConfig, ClickhouseProtocol};
+pub use opensearch::OpenSearchConfig;
+#[cfg(feature = "oracle")]
+pub use oracle::{OracleConfig, OracleService};
 
 /// Database type enum for dispatching
 #[allow(dead_code)]
@@ -85,6 +369,11 @@ pub enum DatabaseType {
     Sqlite,
     Redis,
     Clickhouse,
+    OpenSearch,
+    #[cfg(feature = "cassandra")]
+    Cassandra,
+    #[cfg(feature = "oracle")]
+    Oracle,
 }
 
 impl DatabaseType {
@@ -95,7 +384,27 @@ impl DatabaseType {
             "sqlite" | "sqlite3" => Some(DatabaseType::Sqlite),
             "redis" => Some(DatabaseType::Redis),
             "clickhouse" => Some(DatabaseType::Clickhouse),
+            "opensearch" | "elasticsearch" => Some(DatabaseType::OpenSearch),
+            #[cfg(feature = "cassandra")]
+            "cassandra" | "scylla" => Some(DatabaseType::Cassandra),
+            #[cfg(feature = "oracle")]
+            "oracle" => Some(DatabaseType::Oracle),
             _ => None,
         }
     }
+
+    /// Capabilities of the driver for this database type
+    pub fn capabilities(&self) -> DriverCapabilities {
+        match self {
+            DatabaseType::Postgres => postgres::PostgresDriver::CAPABILITIES,
+            DatabaseType::Sqlite => sqlite::SqliteDriver::CAPABILITIES,
+            DatabaseType::Redis => redis::RedisDriver::CAPABILITIES,
+            DatabaseType::Clickhouse => clickhouse::ClickhouseDriver::CAPABILITIES,
+            DatabaseType::OpenSearch => opensearch::OpenSearchDriver::CAPABILITIES,
+            #[cfg(feature = "cassandra")]
+            DatabaseType::Cassandra => cassandra::CassandraDriver::CAPABILITIES,
+            #[cfg(feature = "oracle")]
+            DatabaseType::Oracle => oracle::OracleDriver::CAPABILITIES,
+        }
+    }
 }
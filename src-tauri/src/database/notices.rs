@@ -0,0 +1,65 @@
+//! Capture of Postgres NOTICE and WARNING messages.
+//!
+//! sqlx has no notice callback; it reports each `NoticeResponse` as a `tracing` event on
+//! the `sqlx::postgres::notice` target, emitted from the task that is running the query.
+//! [`NoticeLayer`], registered with the app's subscriber at startup, forwards those events
+//! into a task-local buffer, so wrapping a query future in [`collect`] yields the
+//! messages the server sent while it ran. Events on other targets are left to the other
+//! layers.
+
+use std::cell::RefCell;
+use std::future::Future;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+pub const NOTICE_TARGET: &str = "sqlx::postgres::notice";
+
+tokio::task_local! {
+    static NOTICES: RefCell<Vec<String>>;
+}
+
+/// `tracing` layer that turns sqlx notice events into query notices
+pub struct NoticeLayer;
+
+struct MessageVisitor(Option<String>);
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" && self.0.is_none() {
+            self.0 = Some(format!("{:?}", value));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for NoticeLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != NOTICE_TARGET {
+            return;
+        }
+        let mut visitor = MessageVisitor(None);
+        event.record(&mut visitor);
+        if let Some(message) = visitor.0 {
+            // Notices outside a `collect` scope are dropped
+            let _ = NOTICES.try_with(|notices| notices.borrow_mut().push(message));
+        }
+    }
+}
+
+/// Run `query`, returning its output with the notices received while it ran
+pub async fn collect<F: Future>(query: F) -> (F::Output, Vec<String>) {
+    NOTICES
+        .scope(RefCell::new(Vec::new()), async {
+            let output = query.await;
+            (output, NOTICES.with(|notices| notices.take()))
+        })
+        .await
+}
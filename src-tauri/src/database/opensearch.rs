@@ -0,0 +1,396 @@
+//! Read-only driver for OpenSearch/Elasticsearch clusters using the REST API.
+//!
+//! Indices are exposed as tables and document fields as columns. Nested objects are
+//! flattened into dotted column names (e.g. `user.name`).
+
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+
+use super::DatabaseDriver;
+use crate::db::models::{
+    ColumnInfo, DriverCapabilities, QueryResult, SchemaOverview, TableDataResponse, TableInfo,
+    TableStructure, TableWithStructure, TestConnectionResult,
+};
+
+/// Configuration for OpenSearch/Elasticsearch connections
+#[derive(Clone)]
+pub struct OpenSearchConfig {
+    pub host: String,
+    pub port: i64,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub ssl: bool,
+}
+
+pub struct OpenSearchDriver {
+    config: OpenSearchConfig,
+    client: reqwest::Client,
+}
+
+impl OpenSearchDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: false,
+        supports_foreign_keys: false,
+        supports_row_editing: false,
+        supports_schemas: false,
+        supports_explain: false,
+    };
+
+    pub fn new(config: OpenSearchConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn build_url(&self, path: &str) -> String {
+        let scheme = if self.config.ssl { "https" } else { "http" };
+        format!(
+            "{}://{}:{}/{}",
+            scheme,
+            self.config.host,
+            self.config.port,
+            path.trim_start_matches('/')
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let builder = self.client.request(method, self.build_url(path));
+        match self.config.username.as_deref().filter(|u| !u.is_empty()) {
+            Some(username) => builder.basic_auth(username, self.config.password.as_deref()),
+            None => builder,
+        }
+    }
+
+    /// Send a request and parse the JSON response, turning error statuses into errors
+    async fn send_json(&self, builder: reqwest::RequestBuilder) -> Result<Value, String> {
+        let response = builder.send().await.map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("OpenSearch returned {}: {}", status, error_text));
+        }
+
+        response.json::<Value>().await.map_err(|e| e.to_string())
+    }
+
+    /// Flatten nested `_source` objects into dotted keys. Arrays are kept as values.
+    fn flatten_source(prefix: &str, value: &Value, out: &mut Map<String, Value>) {
+        match value {
+            Value::Object(obj) => {
+                for (key, inner) in obj {
+                    let name = if prefix.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{}.{}", prefix, key)
+                    };
+                    Self::flatten_source(&name, inner, out);
+                }
+            }
+            other => {
+                out.insert(prefix.to_string(), other.clone());
+            }
+        }
+    }
+
+    /// Convert a search hit into a result row
+    fn hit_to_row(hit: &Value) -> Value {
+        let mut row = Map::new();
+        if let Some(id) = hit.get("_id") {
+            row.insert("_id".to_string(), id.clone());
+        }
+        if let Some(source) = hit.get("_source") {
+            Self::flatten_source("", source, &mut row);
+        }
+        Value::Object(row)
+    }
+
+    /// Extract the total hit count from both the object and the legacy numeric form
+    fn total_hits(response: &Value) -> i64 {
+        let total = &response["hits"]["total"];
+        total["value"]
+            .as_i64()
+            .or_else(|| total.as_i64())
+            .unwrap_or(0)
+    }
+
+    /// Derive columns from mapping `properties`, recursing into object fields
+    fn mapping_columns(prefix: &str, properties: &Map<String, Value>, out: &mut Vec<ColumnInfo>) {
+        for (field, spec) in properties {
+            let name = if prefix.is_empty() {
+                field.clone()
+            } else {
+                format!("{}.{}", prefix, field)
+            };
+
+            if let Some(nested) = spec.get("properties").and_then(|p| p.as_object()) {
+                Self::mapping_columns(&name, nested, out);
+                continue;
+            }
+
+            out.push(ColumnInfo {
+                name,
+                data_type: spec["type"].as_str().unwrap_or("object").to_string(),
+                nullable: true,
+                default: None,
+                primary_key: false,
+                comment: None,
+                enum_values: None,
+                auto_increment: false,
+                is_generated: false,
+            });
+        }
+    }
+
+    /// Columns for one index mapping, led by the document `_id`
+    fn columns_from_mapping(mapping: &Value) -> Vec<ColumnInfo> {
+        let mut columns = vec![ColumnInfo {
+            name: "_id".to_string(),
+            data_type: "keyword".to_string(),
+            nullable: false,
+            default: None,
+            primary_key: true,
+            comment: None,
+            enum_values: None,
+            auto_increment: false,
+            is_generated: false,
+        }];
+
+        if let Some(properties) = mapping["mappings"]["properties"].as_object() {
+            Self::mapping_columns("", properties, &mut columns);
+        }
+        columns
+    }
+}
+
+#[async_trait]
+impl DatabaseDriver for OpenSearchDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
+    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+        match self
+            .send_json(self.request(reqwest::Method::GET, "/"))
+            .await
+        {
+            Ok(_) => Ok(TestConnectionResult {
+                success: true,
+                message: "Connection successful!".to_string(),
+            }),
+            Err(e) => Ok(TestConnectionResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+            }),
+        }
+    }
+
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        let indices = self
+            .send_json(self.request(reqwest::Method::GET, "_cat/indices?format=json&h=index"))
+            .await?;
+
+        let mut names: Vec<String> = indices
+            .as_array()
+            .map(|rows| {
+                rows.iter()
+                    .filter_map(|row| row["index"].as_str())
+                    // Dot-prefixed indices are system/hidden indices
+                    .filter(|name| !name.starts_with('.'))
+                    .map(|name| name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+
+        Ok(names
+            .into_iter()
+            .map(|name| TableInfo {
+                schema: "opensearch".to_string(),
+                name,
+                table_type: "index".to_string(),
+                comment: None,
+                engine_full: None,
+            })
+            .collect())
+    }
+
+    async fn get_table_data(
+        &self,
+        _schema: &str,
+        table: &str,
+        page: i64,
+        limit: i64,
+        filter: Option<String>,
+        sort_column: Option<String>,
+        sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, String> {
+        let query = match filter.as_deref().map(str::trim).filter(|f| !f.is_empty()) {
+            Some(f) => json!({ "query_string": { "query": f } }),
+            None => json!({ "match_all": {} }),
+        };
+
+        let mut body = json!({
+            "from": (page - 1).max(0) * limit,
+            "size": limit,
+            "track_total_hits": true,
+            "query": query,
+        });
+
+        if let Some(column) = sort_column {
+            let order = match sort_direction.as_deref() {
+                Some(d) if d.eq_ignore_ascii_case("desc") => "desc",
+                _ => "asc",
+            };
+            body["sort"] = json!([{ column: { "order": order } }]);
+        }
+
+        let response = self
+            .send_json(
+                self.request(reqwest::Method::POST, &format!("{}/_search", table))
+                    .json(&body),
+            )
+            .await?;
+
+        let data = response["hits"]["hits"]
+            .as_array()
+            .map(|hits| hits.iter().map(Self::hit_to_row).collect())
+            .unwrap_or_default();
+
+        Ok(TableDataResponse {
+            data,
+            total: Self::total_hits(&response),
+            page,
+            limit,
+        })
+    }
+
+    async fn get_table_structure(
+        &self,
+        _schema: &str,
+        table: &str,
+    ) -> Result<TableStructure, String> {
+        let response = self
+            .send_json(self.request(reqwest::Method::GET, &format!("{}/_mapping", table)))
+            .await?;
+
+        // The response is keyed by the concrete index name, which differs for aliases
+        let mapping = response
+            .as_object()
+            .and_then(|indices| indices.values().next())
+            .ok_or_else(|| format!("No mapping found for index '{}'", table))?;
+
+        Ok(TableStructure {
+            columns: Self::columns_from_mapping(mapping),
+            indexes: vec![],
+            foreign_keys: vec![],
+            check_constraints: vec![],
+            unique_constraints: vec![],
+            warnings: vec![],
+        })
+    }
+
+    /// Run a raw query DSL body. An optional top-level `"index"` field selects the
+    /// target index (or pattern); otherwise all indices are searched.
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+        let start_time = std::time::Instant::now();
+
+        let mut body: Value = match serde_json::from_str(query) {
+            Ok(body) => body,
+            Err(e) => {
+                return Ok(QueryResult {
+                    data: vec![],
+                    row_count: 0,
+                    error: Some(format!("Invalid JSON query: {}", e)),
+                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
+                })
+            }
+        };
+
+        let index = body
+            .as_object_mut()
+            .and_then(|obj| obj.remove("index"))
+            .and_then(|v| v.as_str().map(|s| s.to_string()));
+        let path = match index {
+            Some(index) => format!("{}/_search", index),
+            None => "_search".to_string(),
+        };
+
+        match self
+            .send_json(self.request(reqwest::Method::POST, &path).json(&body))
+            .await
+        {
+            Ok(response) => {
+                let mut data: Vec<Value> = response["hits"]["hits"]
+                    .as_array()
+                    .map(|hits| {
+                        hits.iter()
+                            .map(|hit| {
+                                let mut row = Self::hit_to_row(hit);
+                                if let Some(index) = hit.get("_index") {
+                                    row["_index"] = index.clone();
+                                }
+                                row
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                // Aggregation-only searches return their buckets as a single row
+                if data.is_empty() {
+                    if let Some(aggregations) = response.get("aggregations") {
+                        data.push(json!({ "aggregations": aggregations }));
+                    }
+                }
+
+                let row_count = data.len() as i64;
+                Ok(QueryResult {
+                    data,
+                    row_count,
+                    error: None,
+                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    server_time_ms: response["took"].as_u64().map(u128::from),
+                    ..Default::default()
+                })
+            }
+            Err(e) => Ok(QueryResult {
+                data: vec![],
+                row_count: 0,
+                error: Some(e),
+                time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+        let response = self
+            .send_json(self.request(reqwest::Method::GET, "_mapping"))
+            .await?;
+
+        let mut tables: Vec<TableWithStructure> = response
+            .as_object()
+            .map(|indices| {
+                indices
+                    .iter()
+                    .filter(|(name, _)| !name.starts_with('.'))
+                    .map(|(name, mapping)| TableWithStructure {
+                        schema: "opensearch".to_string(),
+                        name: name.clone(),
+                        table_type: "index".to_string(),
+                        comment: None,
+                        columns: Self::columns_from_mapping(mapping),
+                        foreign_keys: vec![],
+                        indexes: vec![],
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(SchemaOverview { tables })
+    }
+}
@@ -0,0 +1,729 @@
+//! Oracle driver built on the `oracle` crate (ODPI-C over the Oracle client libraries).
+//!
+//! Enabled with the `oracle` cargo feature; the Oracle Instant Client must be installed at
+//! runtime. Schemas are Oracle users. The driver keeps one connection, opened on first
+//! use, and runs every call on a blocking thread since OCI calls block.
+
+use async_trait::async_trait;
+use oracle::sql_type::OracleType;
+use oracle::{Connector, SqlValue};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::{session_setup_sql, timeouts, DatabaseDriver};
+use crate::db::models::{
+    CheckConstraint, ColumnInfo, DriverCapabilities, ForeignKeyInfo, IndexInfo, QueryResult,
+    SchemaOverview, TableDataResponse, TableInfo, TableStructure, TableWithStructure,
+    TestConnectionResult, UniqueConstraint,
+};
+
+/// How the database is named in the connect descriptor
+#[derive(Clone, Debug, PartialEq)]
+pub enum OracleService {
+    ServiceName(String),
+    /// System identifier, for older setups that are not registered by service name
+    Sid(String),
+}
+
+impl OracleService {
+    /// Parse the connection's database field: `SID:<sid>` selects a SID, anything else
+    /// is a service name
+    pub fn parse(database: &str) -> Self {
+        let database = database.trim();
+        match database.split_once(':') {
+            Some((prefix, sid)) if prefix.eq_ignore_ascii_case("sid") => {
+                OracleService::Sid(sid.trim().to_string())
+            }
+            _ => OracleService::ServiceName(database.to_string()),
+        }
+    }
+}
+
+/// Configuration for Oracle connections
+#[derive(Clone)]
+pub struct OracleConfig {
+    pub host: String,
+    pub port: i64,
+    pub service: OracleService,
+    pub username: String,
+    pub password: String,
+    /// Connect over TCPS
+    pub ssl: bool,
+    /// Statements such as `ALTER SESSION SET ...`, separated by semicolons, run after
+    /// connecting
+    pub session_setup_sql: Option<String>,
+}
+
+impl OracleConfig {
+    /// Full connect descriptor, so the connect timeout and a SID work with any client
+    pub fn connect_descriptor(&self, connect_timeout: Duration) -> String {
+        let connect_data = match &self.service {
+            OracleService::ServiceName(name) => format!("(SERVICE_NAME={})", name),
+            OracleService::Sid(sid) => format!("(SID={})", sid),
+        };
+        format!(
+            "(DESCRIPTION=(CONNECT_TIMEOUT={})(ADDRESS=(PROTOCOL={})(HOST={})(PORT={}))(CONNECT_DATA={}))",
+            connect_timeout.as_secs().max(1),
+            if self.ssl { "TCPS" } else { "TCP" },
+            self.host,
+            self.port,
+            connect_data
+        )
+    }
+}
+
+/// First release with `OFFSET ... FETCH`, identity columns and `oracle_maintained`
+const OFFSET_FETCH_MIN_VERSION: i32 = 12;
+
+/// Column added by the `ROWNUM` paging query and removed from its rows
+const ROWNUM_COLUMN: &str = "DBCOOPER_RN__";
+
+/// An open connection and what was learned about the server when it was opened
+struct OracleSession {
+    conn: oracle::Connection,
+    major_version: i32,
+    current_schema: String,
+}
+
+impl OracleSession {
+    fn supports_offset_fetch(&self) -> bool {
+        self.major_version >= OFFSET_FETCH_MIN_VERSION
+    }
+
+    /// `WHERE` condition on `column` choosing the schemas to browse: every schema not
+    /// maintained by Oracle, or before 12c (which cannot tell them apart) the current one
+    fn owner_filter(&self, column: &str) -> String {
+        if self.supports_offset_fetch() {
+            format!(
+                "{} NOT IN (SELECT username FROM all_users WHERE oracle_maintained = 'Y')",
+                column
+            )
+        } else {
+            format!("{} = SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA')", column)
+        }
+    }
+
+    /// Schema for a request, falling back to the session's current schema
+    fn owner_for(&self, schema: &str) -> String {
+        if schema.is_empty() {
+            self.current_schema.clone()
+        } else {
+            schema.to_string()
+        }
+    }
+
+    /// Run a query and convert every row to a JSON object keyed by column name
+    fn query_json(
+        &self,
+        sql: &str,
+        params: &[&dyn oracle::sql_type::ToSql],
+    ) -> Result<Vec<Value>, oracle::Error> {
+        let rows = self.conn.query(sql, params)?;
+        let names: Vec<String> = rows
+            .column_info()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        rows.map(|row| {
+            let row = row?;
+            Ok(row_to_json(&names, row.sql_values()))
+        })
+        .collect()
+    }
+}
+
+fn row_to_json(names: &[String], values: &[SqlValue]) -> Value {
+    let object: Map<String, Value> = names
+        .iter()
+        .cloned()
+        .zip(values.iter().map(sql_value_to_json))
+        .collect();
+    Value::Object(object)
+}
+
+/// Convert an Oracle value to JSON. Integral `NUMBER`s that fit in 64 bits become JSON
+/// numbers; other `NUMBER`s are rendered as strings so no precision is lost.
+fn sql_value_to_json(value: &SqlValue) -> Value {
+    if value.is_null().unwrap_or(true) {
+        return Value::Null;
+    }
+    let converted = match value.oracle_type() {
+        Ok(OracleType::Number(_, _) | OracleType::Int64) => value.get::<String>().map(|text| {
+            text.parse::<i64>()
+                .map(|v| json!(v))
+                .unwrap_or(Value::String(text))
+        }),
+        Ok(OracleType::BinaryFloat | OracleType::BinaryDouble | OracleType::Float(_)) => {
+            value.get::<f64>().map(|v| json!(v))
+        }
+        Ok(OracleType::Boolean) => value.get::<bool>().map(|v| json!(v)),
+        Ok(OracleType::Date) => value
+            .get::<chrono::NaiveDateTime>()
+            .map(|dt| json!(dt.format("%Y-%m-%d %H:%M:%S").to_string())),
+        Ok(OracleType::Timestamp(_)) => value
+            .get::<chrono::NaiveDateTime>()
+            .map(|dt| json!(dt.format("%Y-%m-%d %H:%M:%S%.f").to_string())),
+        Ok(OracleType::Raw(_) | OracleType::LongRaw | OracleType::BLOB | OracleType::BFILE) => {
+            value
+                .get::<Vec<u8>>()
+                .map(|bytes| Value::String(format!("0x{}", hex::encode(bytes))))
+        }
+        _ => value.get::<String>().map(Value::String),
+    };
+    converted.unwrap_or_else(|_| Value::String(value.to_string()))
+}
+
+/// Column type as written in DDL, e.g. `NUMBER(10,2)` or `VARCHAR2(100)`
+fn column_type(row: &Value) -> String {
+    let data_type = row["DATA_TYPE"].as_str().unwrap_or_default();
+    let length = row["CHAR_LENGTH"].as_i64().filter(|l| *l > 0);
+    let precision = row["DATA_PRECISION"].as_i64();
+    let scale = row["DATA_SCALE"].as_i64();
+    match data_type {
+        "VARCHAR2" | "NVARCHAR2" | "CHAR" | "NCHAR" => match length {
+            Some(length) => format!("{}({})", data_type, length),
+            None => data_type.to_string(),
+        },
+        "RAW" => format!("RAW({})", row["DATA_LENGTH"].as_i64().unwrap_or_default()),
+        "NUMBER" => match (precision, scale) {
+            (Some(p), Some(0)) => format!("NUMBER({})", p),
+            (Some(p), Some(s)) => format!("NUMBER({},{})", p, s),
+            (None, Some(0)) => "INTEGER".to_string(),
+            _ => "NUMBER".to_string(),
+        },
+        _ => data_type.to_string(),
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Split session setup into statements, dropping the `;` Oracle does not accept
+fn setup_statements(setup: &str) -> Vec<&str> {
+    setup
+        .split(';')
+        .map(str::trim)
+        .filter(|statement| !statement.is_empty())
+        .collect()
+}
+
+/// Strip the trailing `;` from SQL statements. PL/SQL blocks keep theirs, since `END;`
+/// is part of the block.
+fn statement_text(query: &str) -> &str {
+    let query = query.trim();
+    let first_word = query
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+    if ["BEGIN", "DECLARE"]
+        .iter()
+        .any(|keyword| first_word.eq_ignore_ascii_case(keyword))
+    {
+        query
+    } else {
+        query.trim_end_matches(';').trim_end()
+    }
+}
+
+/// Wrap a query so only rows `offset + 1 ..= offset + limit` are returned, with
+/// `OFFSET ... FETCH` on 12c and later or `ROWNUM` before
+fn paged_query(query: &str, offset: i64, limit: i64, offset_fetch: bool) -> String {
+    if offset_fetch {
+        format!(
+            "{} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+            query, offset, limit
+        )
+    } else {
+        format!(
+            "SELECT * FROM (SELECT q.*, ROWNUM AS {rn} FROM ({query}) q WHERE ROWNUM <= {end}) WHERE {rn} > {offset}",
+            rn = ROWNUM_COLUMN,
+            query = query,
+            end = offset + limit,
+            offset = offset
+        )
+    }
+}
+
+const COLUMNS_QUERY: &str = "SELECT c.column_name, c.data_type, c.data_length, c.char_length, \
+     c.data_precision, c.data_scale, c.nullable, c.data_default, c.virtual_column, {identity} AS identity_column, \
+     cc.comments \
+     FROM all_tab_cols c \
+     LEFT JOIN all_col_comments cc \
+       ON cc.owner = c.owner AND cc.table_name = c.table_name AND cc.column_name = c.column_name \
+     WHERE c.owner = :1 AND c.table_name = :2 AND c.hidden_column = 'NO' \
+     ORDER BY c.column_id";
+
+const CONSTRAINTS_QUERY: &str = "SELECT con.constraint_name, con.constraint_type, col.column_name, \
+     r.owner AS r_owner, r.table_name AS r_table_name, rcol.column_name AS r_column_name \
+     FROM all_constraints con \
+     JOIN all_cons_columns col ON col.owner = con.owner AND col.constraint_name = con.constraint_name \
+     LEFT JOIN all_constraints r ON r.owner = con.r_owner AND r.constraint_name = con.r_constraint_name \
+     LEFT JOIN all_cons_columns rcol \
+       ON rcol.owner = r.owner AND rcol.constraint_name = r.constraint_name AND rcol.position = col.position \
+     WHERE con.owner = :1 AND con.table_name = :2 AND con.constraint_type IN ('P', 'U', 'R') \
+     ORDER BY con.constraint_name, col.position";
+
+const CHECKS_QUERY: &str = "SELECT constraint_name, search_condition FROM all_constraints \
+     WHERE owner = :1 AND table_name = :2 AND constraint_type = 'C' AND generated = 'USER NAME' \
+     ORDER BY constraint_name";
+
+const INDEXES_QUERY: &str = "SELECT i.index_name, i.uniqueness, ic.column_name, \
+     (SELECT COUNT(*) FROM all_constraints p \
+       WHERE p.owner = i.table_owner AND p.table_name = i.table_name \
+         AND p.constraint_type = 'P' AND p.index_name = i.index_name) AS is_primary \
+     FROM all_indexes i \
+     JOIN all_ind_columns ic ON ic.index_owner = i.owner AND ic.index_name = i.index_name \
+     WHERE i.table_owner = :1 AND i.table_name = :2 \
+     ORDER BY i.index_name, ic.column_position";
+
+fn text(row: &Value, column: &str) -> String {
+    row[column].as_str().unwrap_or_default().to_string()
+}
+
+/// Build a table's structure from the rows of the catalog queries above
+fn structure_from_rows(
+    column_rows: &[Value],
+    constraint_rows: &[Value],
+    check_rows: &[Value],
+    index_rows: &[Value],
+) -> TableStructure {
+    let primary_key: Vec<String> = constraint_rows
+        .iter()
+        .filter(|row| row["CONSTRAINT_TYPE"] == "P")
+        .map(|row| text(row, "COLUMN_NAME"))
+        .collect();
+
+    let columns = column_rows
+        .iter()
+        .map(|row| {
+            let name = text(row, "COLUMN_NAME");
+            let is_generated = row["VIRTUAL_COLUMN"] == "YES";
+            ColumnInfo {
+                primary_key: primary_key.contains(&name),
+                data_type: column_type(row),
+                nullable: row["NULLABLE"] == "Y",
+                default: row["DATA_DEFAULT"]
+                    .as_str()
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty() && !is_generated)
+                    .map(str::to_string),
+                comment: row["COMMENTS"].as_str().map(str::to_string),
+                enum_values: None,
+                auto_increment: row["IDENTITY_COLUMN"] == "YES",
+                is_generated,
+                name,
+            }
+        })
+        .collect();
+
+    let foreign_keys = constraint_rows
+        .iter()
+        .filter(|row| row["CONSTRAINT_TYPE"] == "R")
+        .map(|row| ForeignKeyInfo {
+            name: text(row, "CONSTRAINT_NAME"),
+            column: text(row, "COLUMN_NAME"),
+            references_schema: text(row, "R_OWNER"),
+            references_table: text(row, "R_TABLE_NAME"),
+            references_column: text(row, "R_COLUMN_NAME"),
+        })
+        .collect();
+
+    let mut unique_columns: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for row in constraint_rows
+        .iter()
+        .filter(|row| row["CONSTRAINT_TYPE"] == "U")
+    {
+        unique_columns
+            .entry(text(row, "CONSTRAINT_NAME"))
+            .or_default()
+            .push(text(row, "COLUMN_NAME"));
+    }
+    let unique_constraints = unique_columns
+        .into_iter()
+        .map(|(name, columns)| UniqueConstraint { name, columns })
+        .collect();
+
+    let check_constraints = check_rows
+        .iter()
+        .map(|row| CheckConstraint {
+            name: text(row, "CONSTRAINT_NAME"),
+            expression: text(row, "SEARCH_CONDITION"),
+        })
+        .collect();
+
+    let mut indexes: Vec<IndexInfo> = Vec::new();
+    for row in index_rows {
+        let name = text(row, "INDEX_NAME");
+        let column = text(row, "COLUMN_NAME");
+        match indexes.last_mut() {
+            Some(index) if index.name == name => index.columns.push(column),
+            _ => indexes.push(IndexInfo {
+                name,
+                columns: vec![column],
+                unique: row["UNIQUENESS"] == "UNIQUE",
+                primary: row["IS_PRIMARY"].as_i64().unwrap_or(0) > 0,
+            }),
+        }
+    }
+
+    TableStructure {
+        columns,
+        indexes,
+        foreign_keys,
+        check_constraints,
+        unique_constraints,
+        warnings: vec![],
+    }
+}
+
+pub struct OracleDriver {
+    config: OracleConfig,
+    session: OnceCell<Arc<OracleSession>>,
+    connect_timeout: Duration,
+}
+
+impl OracleDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: false,
+        supports_foreign_keys: true,
+        supports_row_editing: false,
+        supports_schemas: true,
+        supports_explain: false,
+    };
+
+    pub fn new(config: OracleConfig) -> Self {
+        Self {
+            config,
+            session: OnceCell::new(),
+            connect_timeout: timeouts::connect_timeout(10),
+        }
+    }
+
+    fn connect(config: &OracleConfig, connect_timeout: Duration) -> Result<OracleSession, String> {
+        let mut conn = Connector::new(
+            config.username.as_str(),
+            config.password.as_str(),
+            config.connect_descriptor(connect_timeout),
+        )
+        .connect()
+        .map_err(|e| e.to_string())?;
+        // Statements run from the query editor take effect right away, as on other databases
+        conn.set_autocommit(true);
+
+        if let Some(setup) = session_setup_sql(&config.session_setup_sql) {
+            for statement in setup_statements(&setup) {
+                conn.execute(statement, &[])
+                    .map_err(|e| format!("Session setup failed: {}", e))?;
+            }
+        }
+
+        let (version, _) = conn.server_version().map_err(|e| e.to_string())?;
+        let current_schema = conn
+            .query_row_as::<String>(
+                "SELECT SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA') FROM dual",
+                &[],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(OracleSession {
+            conn,
+            major_version: version.major(),
+            current_schema,
+        })
+    }
+
+    /// Session for this connection, opened on first use
+    async fn session(&self) -> Result<Arc<OracleSession>, String> {
+        self.session
+            .get_or_try_init(|| async {
+                let config = self.config.clone();
+                let connect_timeout = self.connect_timeout;
+                tokio::task::spawn_blocking(move || Self::connect(&config, connect_timeout))
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .map(Arc::new)
+            })
+            .await
+            .cloned()
+    }
+
+    /// Run blocking OCI calls on the session from a blocking thread
+    async fn run<T, F>(&self, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&OracleSession) -> Result<T, oracle::Error> + Send + 'static,
+    {
+        let session = self.session().await?;
+        tokio::task::spawn_blocking(move || f(&session))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl DatabaseDriver for OracleDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
+    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+        let result = self.run(|session| session.conn.ping()).await;
+
+        match result {
+            Ok(()) => Ok(TestConnectionResult {
+                success: true,
+                message: "Connection successful!".to_string(),
+            }),
+            Err(e) => Ok(TestConnectionResult {
+                success: false,
+                message: format!("Connection failed: {}", e),
+            }),
+        }
+    }
+
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        let rows = self
+            .run(|session| {
+                let sql = format!(
+                    "SELECT t.owner, t.table_name, 'table' AS table_type, c.comments \
+                     FROM all_tables t \
+                     LEFT JOIN all_tab_comments c ON c.owner = t.owner AND c.table_name = t.table_name \
+                     WHERE {} AND t.nested = 'NO' AND t.secondary = 'N' \
+                     UNION ALL \
+                     SELECT v.owner, v.view_name, 'view', c.comments \
+                     FROM all_views v \
+                     LEFT JOIN all_tab_comments c ON c.owner = v.owner AND c.table_name = v.view_name \
+                     WHERE {} \
+                     ORDER BY 1, 2",
+                    session.owner_filter("t.owner"),
+                    session.owner_filter("v.owner")
+                );
+                session.query_json(&sql, &[])
+            })
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TableInfo {
+                schema: text(row, "OWNER"),
+                name: text(row, "TABLE_NAME"),
+                table_type: text(row, "TABLE_TYPE"),
+                comment: row["COMMENTS"].as_str().map(str::to_string),
+                engine_full: None,
+            })
+            .collect())
+    }
+
+    async fn get_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        page: i64,
+        limit: i64,
+        filter: Option<String>,
+        sort_column: Option<String>,
+        sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, String> {
+        let schema = schema.to_string();
+        let table = table.to_string();
+        let where_clause = filter
+            .as_deref()
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(|f| format!(" WHERE {}", f))
+            .unwrap_or_default();
+        let order_clause = sort_column
+            .map(|column| {
+                // Validate sort_direction to prevent SQL injection
+                let direction = match sort_direction.as_deref().map(str::to_lowercase).as_deref() {
+                    Some("desc") => "DESC",
+                    _ => "ASC",
+                };
+                format!(" ORDER BY {} {}", quote_identifier(&column), direction)
+            })
+            .unwrap_or_default();
+        let offset = (page.max(1) - 1) * limit;
+
+        let (data, total) = self
+            .run(move |session| {
+                let table_ref = format!(
+                    "{}.{}",
+                    quote_identifier(&session.owner_for(&schema)),
+                    quote_identifier(&table)
+                );
+                let count = session.conn.query_row_as::<i64>(
+                    &format!("SELECT COUNT(*) FROM {}{}", table_ref, where_clause),
+                    &[],
+                )?;
+                let query = format!(
+                    "SELECT * FROM {}{}{}",
+                    table_ref, where_clause, order_clause
+                );
+                let mut data = session.query_json(
+                    &paged_query(&query, offset, limit, session.supports_offset_fetch()),
+                    &[],
+                )?;
+                for row in &mut data {
+                    if let Value::Object(object) = row {
+                        object.remove(ROWNUM_COLUMN);
+                    }
+                }
+                Ok((data, count))
+            })
+            .await?;
+
+        Ok(TableDataResponse {
+            data,
+            total,
+            page,
+            limit,
+        })
+    }
+
+    async fn get_table_structure(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<TableStructure, String> {
+        let schema = schema.to_string();
+        let table_name = table.to_string();
+
+        let (column_rows, constraint_rows, check_rows, index_rows) = self
+            .run(move |session| {
+                let owner = session.owner_for(&schema);
+                let params: [&dyn oracle::sql_type::ToSql; 2] = [&owner, &table_name];
+                let identity = if session.supports_offset_fetch() {
+                    "c.identity_column"
+                } else {
+                    "'NO'"
+                };
+                Ok((
+                    session.query_json(&COLUMNS_QUERY.replace("{identity}", identity), &params)?,
+                    session.query_json(CONSTRAINTS_QUERY, &params)?,
+                    session.query_json(CHECKS_QUERY, &params)?,
+                    session.query_json(INDEXES_QUERY, &params)?,
+                ))
+            })
+            .await?;
+
+        if column_rows.is_empty() {
+            return Err(format!("Table '{}' not found", table));
+        }
+
+        Ok(structure_from_rows(
+            &column_rows,
+            &constraint_rows,
+            &check_rows,
+            &index_rows,
+        ))
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+        let start_time = std::time::Instant::now();
+        let sql = statement_text(query).to_string();
+
+        let result = self
+            .run(move |session| {
+                let mut statement = session.conn.statement(&sql).build()?;
+                if statement.is_query() {
+                    let rows = statement.query(&[])?;
+                    let names: Vec<String> = rows
+                        .column_info()
+                        .iter()
+                        .map(|column| column.name().to_string())
+                        .collect();
+                    let data = rows
+                        .map(|row| row.map(|row| row_to_json(&names, row.sql_values())))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(Some(data))
+                } else {
+                    statement.execute(&[])?;
+                    Ok(None)
+                }
+            })
+            .await;
+
+        match result {
+            Ok(Some(data)) => {
+                let row_count = data.len() as i64;
+                Ok(QueryResult {
+                    data,
+                    row_count,
+                    error: None,
+                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
+                })
+            }
+            Ok(None) => Ok(QueryResult {
+                data: vec![json!({"result": "Query executed successfully"})],
+                row_count: 0,
+                error: None,
+                time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
+            }),
+            Err(e) => Ok(QueryResult {
+                data: vec![],
+                row_count: 0,
+                error: Some(e),
+                time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
+            }),
+        }
+    }
+
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+        let tables = self.list_tables().await?;
+
+        let column_rows = self
+            .run(|session| {
+                let sql = format!(
+                    "SELECT c.owner, c.table_name, c.column_name, c.data_type, c.data_length, \
+                     c.char_length, c.data_precision, c.data_scale, c.nullable \
+                     FROM all_tab_columns c WHERE {} ORDER BY c.owner, c.table_name, c.column_id",
+                    session.owner_filter("c.owner")
+                );
+                session.query_json(&sql, &[])
+            })
+            .await?;
+
+        let mut columns_by_table: BTreeMap<(String, String), Vec<ColumnInfo>> = BTreeMap::new();
+        for row in &column_rows {
+            columns_by_table
+                .entry((text(row, "OWNER"), text(row, "TABLE_NAME")))
+                .or_default()
+                .push(ColumnInfo {
+                    name: text(row, "COLUMN_NAME"),
+                    data_type: column_type(row),
+                    nullable: row["NULLABLE"] == "Y",
+                    default: None,
+                    primary_key: false,
+                    comment: None,
+                    enum_values: None,
+                    auto_increment: false,
+                    is_generated: false,
+                });
+        }
+
+        let tables = tables
+            .into_iter()
+            .map(|table| TableWithStructure {
+                columns: columns_by_table
+                    .remove(&(table.schema.clone(), table.name.clone()))
+                    .unwrap_or_default(),
+                schema: table.schema,
+                name: table.name,
+                table_type: table.table_type,
+                comment: table.comment,
+                foreign_keys: vec![],
+                indexes: vec![],
+            })
+            .collect();
+
+        Ok(SchemaOverview { tables })
+    }
+}
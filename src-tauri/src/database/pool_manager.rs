@@ -5,16 +5,24 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
-use std::time::Instant;
-use tokio::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, OwnedSemaphorePermit, RwLock, Semaphore};
+use tokio::task::JoinHandle;
 
+#[cfg(feature = "cassandra")]
+use super::cassandra::{CassandraConfig, CassandraDriver};
 use super::clickhouse::ClickhouseDriver;
+use super::opensearch::OpenSearchDriver;
+#[cfg(feature = "oracle")]
+use super::oracle::{OracleConfig, OracleDriver, OracleService};
 use super::postgres::PostgresDriver;
 use super::redis::RedisDriver;
 use super::sqlite::SqliteDriver;
 use super::{
-    ClickhouseConfig, ClickhouseProtocol, DatabaseDriver, PostgresConfig, RedisConfig, SqliteConfig,
+    timeouts, ClickhouseConfig, ClickhouseProtocol, DatabaseDriver, OpenSearchConfig,
+    PostgresConfig, RedisConfig, SqliteConfig,
 };
 use crate::db::models::{
     QueryResult, TableDataResponse, TableInfo, TableStructure, TestConnectionResult,
@@ -48,8 +56,19 @@ pub struct ConnectionConfig {
     pub ssh_user: Option<String>,
     pub ssh_password: Option<String>,
     pub ssh_key_path: Option<String>,
+    /// Authenticate through the running ssh-agent instead of a key file or password
+    pub ssh_use_agent: bool,
+    pub session_setup_sql: Option<String>,
+    /// Queries run at once on this connection; `None` uses `DEFAULT_MAX_CONCURRENT_QUERIES`
+    pub max_concurrent_queries: Option<i64>,
+    /// Directory of the Postgres server's Unix domain socket, used instead of `host`
+    pub socket_path: Option<String>,
 }
 
+/// Queries a pooled connection runs at once unless configured, matching the drivers'
+/// pool size so queued commands wait here instead of timing out on acquire
+pub const DEFAULT_MAX_CONCURRENT_QUERIES: usize = 5;
+
 /// Entry in the connection pool
 struct PoolEntry {
     driver: Arc<Box<dyn DatabaseDriver>>,
@@ -59,11 +78,74 @@ struct PoolEntry {
     last_error: Option<String>,
     #[allow(dead_code)]
     ssh_tunnel: Option<SshTunnel>,
+    /// Background keepalive task, stopped when the entry is evicted
+    keepalive: Option<JoinHandle<()>>,
+    /// Bounds the queries in flight; excess callers queue for a permit
+    query_slots: Arc<Semaphore>,
+}
+
+impl Drop for PoolEntry {
+    fn drop(&mut self) {
+        if let Some(handle) = self.keepalive.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// Ping `driver` every `interval` so idle connections are not dropped by the server
+/// or intermediate firewalls. The task runs until its handle is aborted.
+pub fn spawn_keepalive(driver: Arc<Box<dyn DatabaseDriver>>, interval: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            if let Err(e) = driver.ping().await {
+                println!("[PoolManager] Keepalive ping failed: {}", e);
+            }
+        }
+    })
+}
+
+/// Longest wait between idle sweeps, so a TTL set at runtime takes effect promptly
+const IDLE_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+type PoolMap = RwLock<HashMap<String, PoolEntry>>;
+
+/// Permits for a connection's query semaphore; values below one fall back to the default
+fn max_concurrent_queries(config: &ConnectionConfig) -> usize {
+    config
+        .max_concurrent_queries
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|n| *n > 0)
+        .unwrap_or(DEFAULT_MAX_CONCURRENT_QUERIES)
+}
+
+/// Remove entries unused for longer than `ttl` and close their drivers. Dropping an
+/// entry also closes its SSH tunnel and stops its keepalive.
+async fn evict_idle_entries(pools: &PoolMap, ttl: Duration) -> Vec<String> {
+    let evicted: Vec<(String, PoolEntry)> = {
+        let mut pools = pools.write().await;
+        let idle: Vec<String> = pools
+            .iter()
+            .filter(|(_, entry)| entry.last_used.elapsed() > ttl)
+            .map(|(uuid, _)| uuid.clone())
+            .collect();
+        idle.into_iter()
+            .filter_map(|uuid| pools.remove(&uuid).map(|entry| (uuid, entry)))
+            .collect()
+    };
+
+    let mut uuids = Vec::with_capacity(evicted.len());
+    for (uuid, entry) in evicted {
+        println!("[PoolManager] Evicting idle connection {}", uuid);
+        entry.driver.close().await;
+        uuids.push(uuid);
+    }
+    uuids
 }
 
 /// Connection pool manager
 pub struct PoolManager {
-    pools: RwLock<HashMap<String, PoolEntry>>,
+    pools: Arc<PoolMap>,
     /// Mutex per connection UUID to serialize connect/disconnect
     connect_locks: RwLock<HashMap<String, Arc<Mutex<()>>>>,
 }
@@ -77,7 +159,7 @@ impl Default for PoolManager {
 impl PoolManager {
     pub fn new() -> Self {
         Self {
-            pools: RwLock::new(HashMap::new()),
+            pools: Arc::new(RwLock::new(HashMap::new())),
             connect_locks: RwLock::new(HashMap::new()),
         }
     }
@@ -112,15 +194,17 @@ impl PoolManager {
             let remote_host = config.host.as_ref().ok_or("Remote host is required")?;
             let remote_port = config.port.unwrap_or(5432) as u16;
 
-            // Use a 20 second timeout for SSH tunnel creation (can take longer due to network/auth)
+            // SSH tunnel creation can take longer due to network/auth
+            let ssh_timeout = timeouts::ssh_tunnel_timeout();
             let tunnel = match tokio::time::timeout(
-                std::time::Duration::from_secs(20),
+                ssh_timeout,
                 SshTunnel::new(
                     ssh_host,
                     ssh_port,
                     ssh_user,
                     ssh_password,
                     ssh_key_path,
+                    config.ssh_use_agent,
                     remote_host,
                     remote_port,
                 ),
@@ -130,7 +214,10 @@ impl PoolManager {
                 Ok(Ok(tunnel)) => tunnel,
                 Ok(Err(e)) => return Err(format!("SSH tunnel failed: {}", e)),
                 Err(_) => {
-                    return Err("SSH tunnel connection timed out after 20 seconds".to_string())
+                    return Err(format!(
+                        "SSH tunnel connection timed out after {} seconds",
+                        ssh_timeout.as_secs()
+                    ))
                 }
             };
 
@@ -156,6 +243,8 @@ impl PoolManager {
                     username: config.username.clone().unwrap_or_default(),
                     password: config.password.clone().unwrap_or_default(),
                     ssl: config.ssl.unwrap_or(false),
+                    socket_path: config.socket_path.clone(),
+                    session_setup_sql: config.session_setup_sql.clone(),
                 };
                 Ok((Box::new(PostgresDriver::new(pg_config)), ssh_tunnel))
             }
@@ -164,7 +253,10 @@ impl PoolManager {
                     .file_path
                     .clone()
                     .ok_or("File path is required for SQLite connections")?;
-                let sqlite_config = SqliteConfig { file_path: path };
+                let sqlite_config = SqliteConfig {
+                    file_path: path,
+                    session_setup_sql: config.session_setup_sql.clone(),
+                };
                 Ok((Box::new(SqliteDriver::new(sqlite_config)), None))
             }
             "redis" => {
@@ -192,9 +284,46 @@ impl PoolManager {
                     password: config.password.clone().unwrap_or_default(),
                     protocol: ClickhouseProtocol::Http,
                     ssl: config.ssl.unwrap_or(false),
+                    session_setup_sql: config.session_setup_sql.clone(),
                 };
                 Ok((Box::new(ClickhouseDriver::new(ch_config)), ssh_tunnel))
             }
+            "opensearch" | "elasticsearch" => {
+                let os_config = OpenSearchConfig {
+                    host: effective_host,
+                    port: effective_port,
+                    username: config.username.clone(),
+                    password: config.password.clone(),
+                    ssl: config.ssl.unwrap_or(false),
+                };
+                Ok((Box::new(OpenSearchDriver::new(os_config)), ssh_tunnel))
+            }
+            #[cfg(feature = "cassandra")]
+            "cassandra" | "scylla" => {
+                let cassandra_config = CassandraConfig {
+                    host: effective_host,
+                    port: effective_port,
+                    keyspace: config.database.clone().unwrap_or_default(),
+                    username: config.username.clone(),
+                    password: config.password.clone(),
+                    ssl: config.ssl.unwrap_or(false),
+                    session_setup_sql: config.session_setup_sql.clone(),
+                };
+                Ok((Box::new(CassandraDriver::new(cassandra_config)), ssh_tunnel))
+            }
+            #[cfg(feature = "oracle")]
+            "oracle" => {
+                let oracle_config = OracleConfig {
+                    host: effective_host,
+                    port: effective_port,
+                    service: OracleService::parse(config.database.as_deref().unwrap_or_default()),
+                    username: config.username.clone().unwrap_or_default(),
+                    password: config.password.clone().unwrap_or_default(),
+                    ssl: config.ssl.unwrap_or(false),
+                    session_setup_sql: config.session_setup_sql.clone(),
+                };
+                Ok((Box::new(OracleDriver::new(oracle_config)), ssh_tunnel))
+            }
             _ => Err(format!("Unsupported database type: {}", config.db_type)),
         }
     }
@@ -207,9 +336,10 @@ impl PoolManager {
     ) -> Result<Arc<Box<dyn DatabaseDriver>>, String> {
         // Check if we have an existing connected pool
         {
-            let pools = self.pools.read().await;
-            if let Some(entry) = pools.get(uuid) {
+            let mut pools = self.pools.write().await;
+            if let Some(entry) = pools.get_mut(uuid) {
                 if entry.status == ConnectionStatus::Connected {
+                    entry.last_used = Instant::now();
                     return Ok(entry.driver.clone());
                 }
             }
@@ -246,6 +376,7 @@ impl PoolManager {
             ConnectionStatus::Disconnected
         };
 
+        let query_slots = Arc::new(Semaphore::new(max_concurrent_queries(&config)));
         let entry = PoolEntry {
             driver: driver.clone(),
             config,
@@ -257,6 +388,13 @@ impl PoolManager {
                 Some(test_result.message.clone())
             },
             ssh_tunnel,
+            query_slots,
+            keepalive: match timeouts::keepalive_interval() {
+                Some(interval) if test_result.success => {
+                    Some(spawn_keepalive(driver.clone(), interval))
+                }
+                _ => None,
+            },
         };
 
         // Store in pool
@@ -347,10 +485,60 @@ impl PoolManager {
         }
     }
 
-    /// Get a cached driver if it exists (without creating new connection)
+    /// Get a cached driver if it exists (without creating new connection).
+    /// Counts as a use of the connection for idle eviction.
     pub async fn get_cached(&self, uuid: &str) -> Option<Arc<Box<dyn DatabaseDriver>>> {
-        let pools = self.pools.read().await;
-        pools.get(uuid).map(|e| e.driver.clone())
+        let mut pools = self.pools.write().await;
+        pools.get_mut(uuid).map(|e| {
+            e.last_used = Instant::now();
+            e.driver.clone()
+        })
+    }
+
+    /// The cached driver together with a query slot, waiting in line while the
+    /// connection's `max_concurrent_queries` are all in use. Hold the permit for the
+    /// duration of the query.
+    async fn acquire(
+        &self,
+        uuid: &str,
+    ) -> Result<(Arc<Box<dyn DatabaseDriver>>, OwnedSemaphorePermit), String> {
+        let (driver, slots) = {
+            let mut pools = self.pools.write().await;
+            let entry = pools
+                .get_mut(uuid)
+                .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+            entry.last_used = Instant::now();
+            (entry.driver.clone(), entry.query_slots.clone())
+        };
+        let permit = slots
+            .acquire_owned()
+            .await
+            .map_err(|_| "Connection was closed".to_string())?;
+        Ok((driver, permit))
+    }
+
+    /// Close and remove connections unused for longer than `ttl`, returning their UUIDs.
+    /// The next pooled command on an evicted connection opens it again.
+    pub async fn evict_idle(&self, ttl: Duration) -> Vec<String> {
+        evict_idle_entries(&self.pools, ttl).await
+    }
+
+    /// Background task evicting idle connections whenever `timeouts::idle_ttl` is set.
+    /// Runs until the task is dropped.
+    pub fn idle_sweeper(&self) -> impl Future<Output = ()> + Send + 'static {
+        let pools = self.pools.clone();
+        async move {
+            loop {
+                let ttl = timeouts::idle_ttl();
+                tokio::time::sleep(
+                    ttl.map_or(IDLE_SWEEP_INTERVAL, |ttl| ttl.min(IDLE_SWEEP_INTERVAL)),
+                )
+                .await;
+                if let Some(ttl) = timeouts::idle_ttl() {
+                    evict_idle_entries(&pools, ttl).await;
+                }
+            }
+        }
     }
 
     /// Get config for a cached connection
@@ -361,10 +549,7 @@ impl PoolManager {
 
     /// List tables using the pooled connection
     pub async fn list_tables(&self, uuid: &str) -> Result<Vec<TableInfo>, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+        let (driver, _permit) = self.acquire(uuid).await?;
         driver.list_tables().await
     }
 
@@ -380,10 +565,7 @@ impl PoolManager {
         sort_column: Option<String>,
         sort_direction: Option<String>,
     ) -> Result<TableDataResponse, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+        let (driver, _permit) = self.acquire(uuid).await?;
         driver
             .get_table_data(schema, table, page, limit, filter, sort_column, sort_direction)
             .await
@@ -396,31 +578,44 @@ impl PoolManager {
         schema: &str,
         table: &str,
     ) -> Result<TableStructure, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+        let (driver, _permit) = self.acquire(uuid).await?;
         driver.get_table_structure(schema, table).await
     }
 
     /// Execute query using the pooled connection
     pub async fn execute_query(&self, uuid: &str, query: &str) -> Result<QueryResult, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+        let (driver, _permit) = self.acquire(uuid).await?;
         driver.execute_query(query).await
     }
 
+    /// PID of a server backend in the pooled connection, which stays open for the
+    /// queries run through the pool
+    pub async fn backend_pid(&self, uuid: &str) -> Result<i64, String> {
+        let (driver, _permit) = self.acquire(uuid).await?;
+        driver.backend_pid().await
+    }
+
+    /// Run an `INSERT` using the pooled connection, returning the inserted row
+    pub async fn execute_returning(
+        &self,
+        uuid: &str,
+        schema: &str,
+        table: &str,
+        query: &str,
+        inserted: &[(String, String)],
+    ) -> Result<QueryResult, String> {
+        let (driver, _permit) = self.acquire(uuid).await?;
+        driver
+            .execute_returning(schema, table, query, inserted)
+            .await
+    }
+
     /// Get schema overview using the pooled connection
     pub async fn get_schema_overview(
         &self,
         uuid: &str,
     ) -> Result<crate::db::models::SchemaOverview, String> {
-        let driver = self
-            .get_cached(uuid)
-            .await
-            .ok_or_else(|| "Connection not found. Please connect first.".to_string())?;
+        let (driver, _permit) = self.acquire(uuid).await?;
 
         driver.get_schema_overview().await
     }
@@ -1,65 +1,201 @@
 use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use futures_util::StreamExt;
 use serde_json::{json, Value};
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{Column, Row, TypeInfo};
+use sqlx::postgres::types::{Oid, PgHstore, PgRange};
+use sqlx::postgres::{PgArguments, PgListener, PgPoolOptions, PgTypeInfo, PgValueFormat};
+use sqlx::query::Query;
+use sqlx::{Column, Either, Executor, Postgres, Row, Statement, TypeInfo, ValueRef};
+use std::ops::Bound;
+use std::str::FromStr;
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
 
-use super::{DatabaseDriver, PostgresConfig};
-use crate::database::queries::postgres::SCHEMA_OVERVIEW_QUERY;
+use super::display;
+use super::export::{CsvExportSummary, CsvRecordCounter};
+use super::import::{self, CsvImportSummary};
+use super::notices;
+use super::query_stream::drain_rows;
+use super::reachability;
+use super::retry::RetryPolicy;
+use super::{
+    check_param_count, like_contains_pattern, page_offset, sample_fraction, session_setup_sql,
+    timeouts, DatabaseDriver, PostgresConfig, TxHandle, SAMPLE_CLAUSE_MIN_ROWS,
+};
+use crate::database::queries::postgres::{
+    BLOCKING_QUERIES_QUERY, CHECK_CONSTRAINTS_QUERY, COLUMN_STATISTICS_QUERY, DATABASE_INFO_QUERY,
+    INDEXED_LEADING_COLUMNS_QUERY, PARTITIONS_QUERY, PARTITION_STRATEGY_QUERY,
+    RELATION_EXISTS_QUERY, ROUTINES_QUERY, ROUTINE_DEFINITION_QUERY, ROW_SECURITY_POLICIES_QUERY,
+    ROW_SECURITY_QUERY, SCHEMA_OVERVIEW_QUERY, SCHEMA_TABLE_COUNTS_QUERY, SERIAL_SEQUENCE_QUERY,
+    TABLE_GRANTS_QUERY, TABLE_ROW_ESTIMATE_QUERY, UNIQUE_CONSTRAINTS_QUERY,
+};
 use crate::db::models::{
-    ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview, TableDataResponse,
-    TableInfo, TableStructure, TableWithStructure, TestConnectionResult,
+    BlockingQuery, CheckConstraint, ColumnInfo, ColumnProfile, DatabaseInfo, DriverCapabilities,
+    ForeignKeyInfo, IndexInfo, IndexSuggestion, LargeObject, NamespaceOverview, PartitionInfo,
+    QueryResult, ResultColumn, RoutineInfo, RowSecurityPolicy, SchemaOverview, SequenceValue,
+    TableDataResponse, TableGrant, TableInfo, TablePartitions, TablePermissions, TableStructure,
+    TableWithStructure, TestConnectionResult, UniqueConstraint,
 };
 
+/// `refcursor` has no built-in sqlx type, so it is recognized by its catalog OID
+const REFCURSOR_OID: Oid = Oid(1790);
+
+/// Rows of each result set `query` produces, in order, with sets made only of
+/// `refcursor` columns replaced by the rows of each cursor they name
+async fn fetch_result_sets(
+    conn: &mut sqlx::PgConnection,
+    query: &str,
+) -> Result<Vec<Vec<sqlx::postgres::PgRow>>, sqlx::Error> {
+    let mut sets = Vec::new();
+    let mut current = Vec::new();
+    {
+        let mut stream = conn.fetch_many(sqlx::raw_sql(query));
+        while let Some(item) = stream.next().await {
+            match item? {
+                Either::Left(_) => sets.push(std::mem::take(&mut current)),
+                Either::Right(row) => current.push(row),
+            }
+        }
+    }
+
+    let mut expanded = Vec::new();
+    for rows in sets {
+        let cursors = rows.first().is_some_and(|row| {
+            !row.columns().is_empty()
+                && row
+                    .columns()
+                    .iter()
+                    .all(|column| column.type_info().oid() == Some(REFCURSOR_OID))
+        });
+        if !cursors {
+            expanded.push(rows);
+            continue;
+        }
+        for row in &rows {
+            for index in 0..row.columns().len() {
+                let Some(cursor) = row.try_get_unchecked::<Option<String>, _>(index)? else {
+                    continue;
+                };
+                let fetch = format!("FETCH ALL FROM \"{}\"", cursor.replace('"', "\"\""));
+                let fetched = conn.fetch_all(sqlx::raw_sql(&fetch)).await?;
+                expanded.push(fetched);
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+/// Row shape for column metadata: name, type, nullable, default, primary key, comment, enum values,
+/// auto increment, generated
+type ColumnRow = (
+    String,
+    String,
+    bool,
+    Option<String>,
+    bool,
+    Option<String>,
+    Option<Vec<String>>,
+    bool,
+    bool,
+);
+
 pub struct PostgresDriver {
     config: PostgresConfig,
     pool: Arc<RwLock<Option<sqlx::PgPool>>>,
+    connect_timeout: std::time::Duration,
 }
 
 impl PostgresDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: true,
+        supports_foreign_keys: true,
+        supports_row_editing: true,
+        supports_schemas: true,
+        supports_explain: true,
+    };
+
     pub fn new(config: PostgresConfig) -> Self {
         Self {
             config,
             pool: Arc::new(RwLock::new(None)),
+            connect_timeout: timeouts::connect_timeout(15),
         }
     }
 
-    fn build_connection_string(&self) -> String {
+    /// Connection URL for the configured server. A socket directory, given as
+    /// `socket_path` or as a `host` starting with `/` like libpq, is percent-encoded in
+    /// place of the host.
+    pub fn build_connection_string(&self) -> Result<String, String> {
         let ssl_mode = if self.config.ssl {
             "require"
         } else {
             "disable"
         };
-        format!(
+        let host = self.config.host.trim();
+        let socket = match self.config.socket_path.as_deref().map(str::trim) {
+            Some(socket) if !socket.is_empty() => {
+                if !host.is_empty() && host != socket {
+                    return Err(format!(
+                        "Both host '{}' and socket path '{}' are set; use one or the other",
+                        host, socket
+                    ));
+                }
+                Some(socket)
+            }
+            _ => host.starts_with('/').then_some(host),
+        };
+
+        let host = match socket {
+            Some(socket) => {
+                if !socket.starts_with('/') {
+                    return Err(format!(
+                        "Socket path '{}' must be an absolute directory",
+                        socket
+                    ));
+                }
+                percent_encode(socket)
+            }
+            None => host.to_string(),
+        };
+        Ok(format!(
             "postgres://{}:{}@{}:{}/{}?sslmode={}",
             self.config.username,
             self.config.password,
-            self.config.host,
+            host,
             self.config.port,
             self.config.database,
             ssl_mode
-        )
+        ))
     }
 
     async fn create_pool(&self) -> Result<sqlx::PgPool, String> {
-        let conn_str = self.build_connection_string();
-
-        // Use a 15 second timeout for connection (longer for SSH tunnel overhead)
-        match tokio::time::timeout(
-            std::time::Duration::from_secs(15),
-            PgPoolOptions::new()
-                .max_connections(5)
-                .acquire_timeout(std::time::Duration::from_secs(30))
-                .idle_timeout(std::time::Duration::from_secs(600))
-                .test_before_acquire(false)
-                .connect(&conn_str),
-        )
-        .await
-        {
+        let conn_str = self.build_connection_string()?;
+
+        let mut options = PgPoolOptions::new()
+            .max_connections(5)
+            .acquire_timeout(std::time::Duration::from_secs(30))
+            .idle_timeout(std::time::Duration::from_secs(600))
+            .test_before_acquire(false);
+        if let Some(setup) = session_setup_sql(&self.config.session_setup_sql) {
+            // Sent as a simple query, so several statements may be given
+            options = options.after_connect(move |conn, _meta| {
+                let setup = setup.clone();
+                Box::pin(async move { conn.execute(setup.as_str()).await.map(|_| ()) })
+            });
+        }
+
+        // Defaults to 15 seconds (longer for SSH tunnel overhead)
+        match tokio::time::timeout(self.connect_timeout, options.connect(&conn_str)).await {
             Ok(Ok(pool)) => Ok(pool),
-            Ok(Err(e)) => Err(format!("Failed to connect to PostgreSQL: {}", e)),
-            Err(_) => Err("Connection timed out after 15 seconds".to_string()),
+            Ok(Err(e)) => Err(connect_error_message(&e)),
+            Err(_) => Err(format!(
+                "Connection timed out after {} seconds",
+                self.connect_timeout.as_secs()
+            )),
         }
     }
 
@@ -90,14 +226,485 @@ impl PostgresDriver {
         Ok(())
     }
 
+    /// Key for this server and login in the failed-connection cache
+    fn reachability_target(&self) -> String {
+        format!(
+            "postgres://{}@{}:{}/{}?ssl={}",
+            self.config.username,
+            self.config
+                .socket_path
+                .as_deref()
+                .unwrap_or(&self.config.host),
+            self.config.port,
+            self.config.database,
+            self.config.ssl
+        )
+    }
+
     async fn get_pool_with_retry(&self) -> Result<sqlx::PgPool, String> {
-        match self.get_pool().await {
-            Ok(pool) => Ok(pool),
-            Err(e) => {
-                println!("[Postgres] Pool initialization failed: {}, resetting...", e);
+        let target = self.reachability_target();
+        let policy = RetryPolicy::current();
+        let retry = policy.run(|attempt| async move {
+            if attempt > 1 {
                 self.reset_pool().await?;
-                self.get_pool().await
             }
+            self.get_pool().await.inspect_err(|e| {
+                println!(
+                    "[Postgres] Pool initialization failed (attempt {}): {}",
+                    attempt, e
+                );
+            })
+        });
+        reachability::guard(&target, retry).await
+    }
+
+    /// Sessions waiting on a lock, each paired with a session holding a conflicting lock
+    pub async fn blocking_queries(&self) -> Result<Vec<BlockingQuery>, String> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::query_as::<_, BlockingQuery>(BLOCKING_QUERIES_QUERY)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Name of a table as stored in the catalog. Postgres folds unquoted identifiers to
+    /// lowercase, so a name that does not exist as given is retried lowercased. The name is
+    /// returned unchanged when neither form exists.
+    pub async fn resolve_table_name(&self, schema: &str, table: &str) -> Result<String, String> {
+        let lowercase = table.to_lowercase();
+        if lowercase == table {
+            return Ok(table.to_string());
+        }
+
+        let pool = self.get_pool_with_retry().await?;
+        for candidate in [table, lowercase.as_str()] {
+            let exists: bool = sqlx::query_scalar(RELATION_EXISTS_QUERY)
+                .bind(schema)
+                .bind(candidate)
+                .fetch_one(&pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            if exists {
+                return Ok(candidate.to_string());
+            }
+        }
+        Ok(table.to_string())
+    }
+
+    /// Name of the sequence feeding a serial or identity column, already quoted
+    async fn serial_sequence(
+        &self,
+        pool: &sqlx::PgPool,
+        schema: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<String, String> {
+        let sequence: Option<String> = sqlx::query_scalar(SERIAL_SEQUENCE_QUERY)
+            .bind(schema)
+            .bind(table)
+            .bind(column)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        sequence.ok_or_else(|| {
+            format!(
+                "Column '{}' of {}.{} is not backed by a sequence",
+                column, schema, table
+            )
+        })
+    }
+
+    /// Last value handed out by the sequence behind a serial or identity column
+    pub async fn sequence_value(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+    ) -> Result<SequenceValue, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let sequence = self.serial_sequence(&pool, schema, table, column).await?;
+        // The name comes back from the catalog already quoted
+        sqlx::query_as::<_, SequenceValue>(&format!(
+            "SELECT $1::text AS sequence_name, last_value, is_called FROM {}",
+            sequence
+        ))
+        .bind(&sequence)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Move the sequence behind a serial or identity column with `setval`. With
+    /// `is_called`, the next insert gets `value + 1`; otherwise it gets `value`.
+    pub async fn set_sequence_value(
+        &self,
+        schema: &str,
+        table: &str,
+        column: &str,
+        value: i64,
+        is_called: bool,
+    ) -> Result<SequenceValue, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let sequence = self.serial_sequence(&pool, schema, table, column).await?;
+        sqlx::query("SELECT setval($1::regclass, $2, $3)")
+            .bind(&sequence)
+            .bind(value)
+            .bind(is_called)
+            .execute(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(SequenceValue {
+            sequence_name: sequence,
+            last_value: value,
+            is_called,
+        })
+    }
+
+    /// Grants on a table plus its row-level security state and policies
+    pub async fn table_permissions(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<TablePermissions, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let (rls_enabled, rls_forced): (bool, bool) = sqlx::query_as(ROW_SECURITY_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let grants = sqlx::query_as::<_, TableGrant>(TABLE_GRANTS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let policies = sqlx::query_as::<_, RowSecurityPolicy>(ROW_SECURITY_POLICIES_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(TablePermissions {
+            grants,
+            rls_enabled,
+            rls_forced,
+            policies,
+        })
+    }
+
+    /// Plan `query` with `EXPLAIN` (without running it) and suggest an index for each
+    /// sequential scan whose filter uses columns that lead no existing index. This is a
+    /// heuristic: columns only compared inside function calls are skipped.
+    pub async fn suggest_indexes(&self, query: &str) -> Result<Vec<IndexSuggestion>, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let plan: Value = sqlx::query_scalar(&format!(
+            "EXPLAIN (FORMAT JSON, VERBOSE) {}",
+            query.trim().trim_end_matches(';')
+        ))
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut scans = Vec::new();
+        if let Some(root) = plan.get(0).and_then(|p| p.get("Plan")) {
+            collect_filtered_seq_scans(root, &mut scans);
+        }
+
+        let mut suggestions: Vec<IndexSuggestion> = Vec::new();
+        for scan in scans {
+            let indexed: Vec<String> = sqlx::query_scalar(INDEXED_LEADING_COLUMNS_QUERY)
+                .bind(&scan.schema)
+                .bind(&scan.table)
+                .fetch_all(&pool)
+                .await
+                .map_err(|e| e.to_string())?;
+            let columns: Vec<String> = filter_columns(&scan.filter, &scan.alias)
+                .into_iter()
+                .filter(|column| !indexed.contains(column))
+                .collect();
+            if columns.is_empty()
+                || suggestions.iter().any(|s| {
+                    s.schema == scan.schema && s.table == scan.table && s.columns == columns
+                })
+            {
+                continue;
+            }
+
+            let create_statement = format!(
+                "CREATE INDEX ON {}.{} ({});",
+                Self::quote_identifier(&scan.schema),
+                Self::quote_identifier(&scan.table),
+                columns
+                    .iter()
+                    .map(|c| Self::quote_identifier(c))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            suggestions.push(IndexSuggestion {
+                schema: scan.schema,
+                table: scan.table,
+                columns,
+                filter: scan.filter,
+                create_statement,
+            });
+        }
+        Ok(suggestions)
+    }
+
+    /// Size of a large object and its first `preview_bytes` bytes. The size comes from
+    /// seeking to the end of a read descriptor, so the rest of the object is not read.
+    pub async fn read_large_object(
+        &self,
+        oid: i64,
+        preview_bytes: i64,
+    ) -> Result<LargeObject, String> {
+        let pool = self.get_pool_with_retry().await?;
+        // The descriptor lasts until the statement's implicit transaction ends
+        let (size, preview): (i64, Vec<u8>) = sqlx::query_as(
+            "SELECT lo_lseek64(lo_open($1::bigint::oid, $3), 0, 2), \
+             lo_get($1::bigint::oid, 0, $2::int)",
+        )
+        .bind(oid)
+        .bind(preview_bytes.clamp(0, i32::MAX as i64))
+        .bind(LO_INV_READ)
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let text = match std::str::from_utf8(&preview) {
+            Ok(text) => Some(text.to_string()),
+            // A multi-byte character cut by the preview boundary
+            Err(e) if e.error_len().is_none() => {
+                Some(String::from_utf8_lossy(&preview[..e.valid_up_to()]).into_owned())
+            }
+            Err(_) => None,
+        };
+        Ok(LargeObject {
+            oid,
+            size,
+            preview: text,
+            preview_base64: BASE64.encode(&preview),
+            truncated: (preview.len() as i64) < size,
+        })
+    }
+
+    /// Replace the contents of a large object, or create one when `oid` is `None`.
+    /// Returns the object's OID.
+    pub async fn write_large_object(&self, oid: Option<i64>, data: &[u8]) -> Result<i64, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let Some(oid) = oid else {
+            return sqlx::query_scalar("SELECT lo_from_bytea(0, $1)::bigint")
+                .bind(data)
+                .fetch_one(&pool)
+                .await
+                .map_err(|e| e.to_string());
+        };
+
+        // Truncating through a descriptor keeps the object's owner and permissions,
+        // which unlinking and recreating it would lose. Descriptors last until commit.
+        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+        sqlx::query("SELECT lo_truncate(lo_open($1::bigint::oid, $2), 0)")
+            .bind(oid)
+            .bind(LO_INV_WRITE)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        sqlx::query("SELECT lo_put($1::bigint::oid, 0, $2)")
+            .bind(oid)
+            .bind(data)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+        tx.commit().await.map_err(|e| e.to_string())?;
+        Ok(oid)
+    }
+
+    /// Quote a table name for maintenance statements, accepting an optional
+    /// `schema.table` qualifier
+    fn quote_table_ref(table: &str) -> String {
+        table
+            .splitn(2, '.')
+            .map(|part| format!("\"{}\"", part.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    fn quote_identifier(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    /// Run a maintenance statement over the simple query protocol. `VACUUM` refuses
+    /// to run inside a transaction block, so this avoids prepared statements.
+    async fn run_maintenance(&self, statement: &str) -> Result<String, String> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::raw_sql(statement)
+            .execute(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!("{} completed successfully", statement))
+    }
+
+    /// Run `ANALYZE` on a table, or on the whole database when `table` is `None`
+    pub async fn analyze(&self, table: Option<&str>) -> Result<String, String> {
+        let statement = match table {
+            Some(table) => format!("ANALYZE {}", Self::quote_table_ref(table)),
+            None => "ANALYZE".to_string(),
+        };
+        self.run_maintenance(&statement).await
+    }
+
+    /// Run `VACUUM` (optionally `FULL`) on a table, or on the whole database
+    pub async fn vacuum(&self, table: Option<&str>, full: bool) -> Result<String, String> {
+        let mut statement = if full {
+            "VACUUM FULL".to_string()
+        } else {
+            "VACUUM".to_string()
+        };
+        if let Some(table) = table {
+            statement.push(' ');
+            statement.push_str(&Self::quote_table_ref(table));
+        }
+        self.run_maintenance(&statement).await
+    }
+
+    /// Deterministic name shared by the trigger, trigger function and notification
+    /// channel that watch `schema.table`. Kept within Postgres' 63-byte identifier limit.
+    pub fn watch_object_name(schema: &str, table: &str) -> String {
+        let sanitized: String = table
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .take(38)
+            .collect();
+
+        // FNV-1a keeps the suffix stable across builds and distinguishes
+        // tables whose sanitized names collide
+        let hash = format!("{}.{}", schema, table)
+            .bytes()
+            .fold(0x811c9dc5u32, |h, b| {
+                (h ^ b as u32).wrapping_mul(0x01000193)
+            });
+
+        format!("dbcooper_watch_{}_{:08x}", sanitized, hash)
+    }
+
+    /// Create the trigger and trigger function that publish row changes on
+    /// `schema.table` to a notification channel. Returns the channel name.
+    pub async fn install_table_watch(&self, schema: &str, table: &str) -> Result<String, String> {
+        let name = Self::watch_object_name(schema, table);
+        let function = format!("{}.\"{}\"", Self::quote_identifier(schema), name);
+        let target = format!(
+            "{}.{}",
+            Self::quote_identifier(schema),
+            Self::quote_identifier(table)
+        );
+
+        let statements = format!(
+            r#"CREATE OR REPLACE FUNCTION {function}() RETURNS trigger AS $dbcooper_watch$
+DECLARE
+    row_data json;
+    payload text;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        row_data := row_to_json(OLD);
+    ELSE
+        row_data := row_to_json(NEW);
+    END IF;
+    payload := json_build_object(
+        'operation', TG_OP, 'schema', TG_TABLE_SCHEMA, 'table', TG_TABLE_NAME, 'row', row_data
+    )::text;
+    -- NOTIFY payloads are capped at 8000 bytes
+    IF octet_length(payload) > 7900 THEN
+        payload := json_build_object(
+            'operation', TG_OP, 'schema', TG_TABLE_SCHEMA, 'table', TG_TABLE_NAME, 'row', NULL
+        )::text;
+    END IF;
+    PERFORM pg_notify('{name}', payload);
+    RETURN NULL;
+END;
+$dbcooper_watch$ LANGUAGE plpgsql;
+DROP TRIGGER IF EXISTS "{name}" ON {target};
+CREATE TRIGGER "{name}" AFTER INSERT OR UPDATE OR DELETE ON {target}
+    FOR EACH ROW EXECUTE FUNCTION {function}();"#
+        );
+
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::raw_sql(&statements)
+            .execute(&pool)
+            .await
+            .map_err(|e| format!("Failed to install table watch: {}", e))?;
+        Ok(name)
+    }
+
+    /// Drop the trigger and trigger function created by [`Self::install_table_watch`]
+    pub async fn remove_table_watch(&self, schema: &str, table: &str) -> Result<(), String> {
+        let name = Self::watch_object_name(schema, table);
+        let statements = format!(
+            "DROP TRIGGER IF EXISTS \"{name}\" ON {schema}.{table};\n\
+             DROP FUNCTION IF EXISTS {schema}.\"{name}\"();",
+            schema = Self::quote_identifier(schema),
+            table = Self::quote_identifier(table),
+        );
+
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::raw_sql(&statements)
+            .execute(&pool)
+            .await
+            .map_err(|e| format!("Failed to remove table watch: {}", e))?;
+        Ok(())
+    }
+
+    /// Open a dedicated connection listening on `channel`
+    pub async fn listen(&self, channel: &str) -> Result<PgListener, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let mut listener = PgListener::connect_with(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        listener.listen(channel).await.map_err(|e| e.to_string())?;
+        Ok(listener)
+    }
+
+    /// Wrap fetched rows in a successful result
+    fn rows_result(rows: &[sqlx::postgres::PgRow], start_time: std::time::Instant) -> QueryResult {
+        let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
+        let row_count = data.len() as i64;
+        QueryResult {
+            data,
+            row_count,
+            error: None,
+            time_taken_ms: Some(start_time.elapsed().as_millis()),
+            ..Default::default()
+        }
+    }
+
+    /// Report a failed query, resetting the pool if the connection itself was lost
+    async fn error_result(&self, error_str: String, start_time: std::time::Instant) -> QueryResult {
+        let should_reset = error_str.contains("Connection reset by peer")
+            || error_str.contains("broken pipe")
+            || error_str.contains("connection closed")
+            || error_str.contains("server closed the connection");
+
+        if should_reset {
+            println!(
+                "[Postgres] Connection error detected, resetting pool: {}",
+                error_str
+            );
+            let _ = self.reset_pool().await;
+        }
+
+        QueryResult {
+            data: vec![],
+            row_count: 0,
+            error: Some(error_str),
+            time_taken_ms: Some(start_time.elapsed().as_millis()),
+            ..Default::default()
         }
     }
 
@@ -143,7 +750,7 @@ impl PostgresDriver {
                     .map(|v| json!(v.to_string()))
                     .or_else(|_| {
                         row.try_get::<chrono::DateTime<chrono::Utc>, _>(i)
-                            .map(|v| json!(v.to_string()))
+                            .map(|v| json!(display::format_timestamptz(&v)))
                     })
                     .unwrap_or(Value::Null),
                 "DATE" => row
@@ -161,6 +768,19 @@ impl PostgresDriver {
                     .try_get::<Vec<u8>, _>(i)
                     .map(|v| json!(format!("\\x{}", hex::encode(&v))))
                     .unwrap_or(Value::Null),
+                "hstore" => row
+                    .try_get::<PgHstore, _>(i)
+                    .map(|v| json!(v.0))
+                    .unwrap_or(Value::Null),
+                "INT4RANGE" => range_to_json::<i32>(row, i, |v| json!(v)),
+                "INT8RANGE" => range_to_json::<i64>(row, i, |v| json!(v)),
+                "DATERANGE" => range_to_json::<chrono::NaiveDate>(row, i, |v| json!(v.to_string())),
+                "TSRANGE" => {
+                    range_to_json::<chrono::NaiveDateTime>(row, i, |v| json!(v.to_string()))
+                }
+                "TSTZRANGE" => range_to_json::<chrono::DateTime<chrono::Utc>>(row, i, |v| {
+                    json!(display::format_timestamptz(&v))
+                }),
                 _ => row
                     .try_get::<String, _>(i)
                     .map(|v| json!(v))
@@ -172,10 +792,317 @@ impl PostgresDriver {
     }
 }
 
+/// `INV_WRITE` mode flag for `lo_open`
+const LO_INV_WRITE: i32 = 0x20000;
+/// `INV_READ` mode flag for `lo_open`
+const LO_INV_READ: i32 = 0x40000;
+
+/// Bytes of a large object returned as a preview unless the caller asks for more
+pub const LARGE_OBJECT_PREVIEW_LIMIT: i64 = 64 * 1024;
+
+/// A sequential scan that filters rows, taken from an `EXPLAIN (FORMAT JSON, VERBOSE)` plan
+struct FilteredSeqScan {
+    schema: String,
+    table: String,
+    alias: String,
+    filter: String,
+}
+
+/// Walk a plan tree collecting the sequential scans that have a `Filter`
+fn collect_filtered_seq_scans(node: &Value, scans: &mut Vec<FilteredSeqScan>) {
+    let text = |key: &str| node.get(key).and_then(Value::as_str).map(str::to_string);
+    if node.get("Node Type").and_then(Value::as_str) == Some("Seq Scan") {
+        if let (Some(schema), Some(table), Some(filter)) =
+            (text("Schema"), text("Relation Name"), text("Filter"))
+        {
+            let alias = text("Alias").unwrap_or_else(|| table.clone());
+            scans.push(FilteredSeqScan {
+                schema,
+                table,
+                alias,
+                filter,
+            });
+        }
+    }
+    for child in node
+        .get("Plans")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        collect_filtered_seq_scans(child, scans);
+    }
+}
+
+/// Columns a verbose plan filter compares directly, in order of appearance. Verbose
+/// plans qualify every column with the scan's alias (`s.email`); references that are
+/// arguments to a function call, such as `lower(s.email)`, are skipped because a plain
+/// index would not serve them.
+fn filter_columns(filter: &str, alias: &str) -> Vec<String> {
+    // Identifiers, with the character before each and whether a `.` follows
+    let chars: Vec<char> = filter.chars().collect();
+    let mut idents: Vec<(String, usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        match chars[i] {
+            '\'' => {
+                i += 1;
+                while i < chars.len() {
+                    if chars[i] == '\'' && chars.get(i + 1) == Some(&'\'') {
+                        i += 2;
+                    } else if chars[i] == '\'' {
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+                i += 1;
+            }
+            '"' => {
+                let mut name = String::new();
+                i += 1;
+                while i < chars.len() {
+                    if chars[i] == '"' && chars.get(i + 1) == Some(&'"') {
+                        name.push('"');
+                        i += 2;
+                    } else if chars[i] == '"' {
+                        break;
+                    } else {
+                        name.push(chars[i]);
+                        i += 1;
+                    }
+                }
+                i += 1;
+                idents.push((name, start, i));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$')
+                {
+                    i += 1;
+                }
+                idents.push((chars[start..i].iter().collect(), start, i));
+            }
+            _ => i += 1,
+        }
+    }
+
+    let mut columns: Vec<String> = Vec::new();
+    for pair in idents.windows(2) {
+        let (qualifier, q_start, q_end) = &pair[0];
+        let (column, c_start, _) = &pair[1];
+        if qualifier != alias || *c_start != q_end + 1 || chars[*q_end] != '.' {
+            continue;
+        }
+        let in_call = *q_start >= 2
+            && chars[q_start - 1] == '('
+            && (chars[q_start - 2].is_alphanumeric() || chars[q_start - 2] == '_');
+        if !in_call && !columns.contains(column) {
+            columns.push(column.clone());
+        }
+    }
+    columns
+}
+
+/// Describe a failed connection attempt, telling a rejected password apart from an
+/// authentication method the client cannot perform.
+///
+/// SCRAM-SHA-256 is negotiated by sqlx; when the server also offers SCRAM-SHA-256-PLUS the
+/// client declines channel binding, which servers accept since only clients can require it.
+fn connect_error_message(error: &sqlx::Error) -> String {
+    match error {
+        // invalid_password
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("28P01") => format!(
+            "Authentication failed: {}. Check the username and password.",
+            db.message()
+        ),
+        // invalid_authorization_specification, e.g. no pg_hba.conf entry or unknown role
+        sqlx::Error::Database(db) if db.code().as_deref() == Some("28000") => {
+            format!("Authentication rejected by the server: {}", db.message())
+        }
+        sqlx::Error::Protocol(message) if message.contains("unsupported") => format!(
+            "Authentication failed: the server requires an authentication method \
+             this client does not support ({})",
+            message
+        ),
+        _ => format!("Failed to connect to PostgreSQL: {}", error),
+    }
+}
+
+/// Percent-encode everything but ASCII letters and digits, for a socket path in a URL
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() {
+                (b as char).to_string()
+            } else {
+                format!("%{:02X}", b)
+            }
+        })
+        .collect()
+}
+
+/// Render a range as `{lower, upper, bounds}`, where `bounds` is the Postgres bracket pair
+/// such as `[)`. Unbounded ends are `null`; an empty range is the string `"empty"`.
+fn range_to_json<T>(row: &sqlx::postgres::PgRow, index: usize, element: fn(T) -> Value) -> Value
+where
+    T: sqlx::Type<Postgres> + for<'r> sqlx::Decode<'r, Postgres>,
+    PgRange<T>: sqlx::Type<Postgres>,
+{
+    // The first byte of a binary range holds its flags; 0x01 marks an empty range
+    let is_empty = row
+        .try_get_raw(index)
+        .ok()
+        .filter(|raw| !raw.is_null() && raw.format() == PgValueFormat::Binary)
+        .and_then(|raw| raw.as_bytes().ok().and_then(|bytes| bytes.first().copied()))
+        .is_some_and(|flags| flags & 0x01 != 0);
+    if is_empty {
+        return json!("empty");
+    }
+
+    let Ok(Some(range)) = row.try_get::<Option<PgRange<T>>, _>(index) else {
+        return Value::Null;
+    };
+    let (lower, lower_bracket) = match range.start {
+        Bound::Included(v) => (element(v), '['),
+        Bound::Excluded(v) => (element(v), '('),
+        Bound::Unbounded => (Value::Null, '('),
+    };
+    let (upper, upper_bracket) = match range.end {
+        Bound::Included(v) => (element(v), ']'),
+        Bound::Excluded(v) => (element(v), ')'),
+        Bound::Unbounded => (Value::Null, ')'),
+    };
+    json!({
+        "lower": lower,
+        "upper": upper,
+        "bounds": format!("{}{}", lower_bracket, upper_bracket),
+    })
+}
+
+/// Use an empty table structure section when reading it failed for lack of privileges,
+/// noting the failure in `warnings`. Other errors fail the whole structure fetch.
+pub fn section_or_warning<T>(
+    section: &str,
+    result: Result<Vec<T>, String>,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<T>, String> {
+    match result {
+        Err(e) if e.contains("permission denied") => {
+            warnings.push(format!("Could not read {}: {}", section, e));
+            Ok(vec![])
+        }
+        other => other,
+    }
+}
+
+/// Bind a JSON value as the type Postgres inferred for placeholder `$index`.
+/// Values are parsed for numeric, boolean, uuid and date/time placeholders;
+/// other placeholder types receive the value as text.
+fn bind_param<'q>(
+    query: Query<'q, Postgres, PgArguments>,
+    value: &Value,
+    type_info: &PgTypeInfo,
+    index: usize,
+) -> Result<Query<'q, Postgres, PgArguments>, String> {
+    let type_name = type_info.name();
+    Ok(match type_name {
+        "INT2" => query.bind(parse_param::<i16>(value, type_name, index)?),
+        "INT4" => query.bind(parse_param::<i32>(value, type_name, index)?),
+        "INT8" => query.bind(parse_param::<i64>(value, type_name, index)?),
+        "FLOAT4" => query.bind(parse_param::<f32>(value, type_name, index)?),
+        "FLOAT8" => query.bind(parse_param::<f64>(value, type_name, index)?),
+        // Exact, so a value such as "0.1" or a 30-digit amount is not rounded through a float
+        "NUMERIC" => query.bind(parse_param::<sqlx::types::BigDecimal>(
+            value, type_name, index,
+        )?),
+        "BOOL" => query.bind(parse_param::<bool>(value, type_name, index)?),
+        "UUID" => query.bind(parse_param::<uuid::Uuid>(value, type_name, index)?),
+        "DATE" => query.bind(parse_param::<chrono::NaiveDate>(value, type_name, index)?),
+        "TIMESTAMP" => query.bind(parse_param::<chrono::NaiveDateTime>(
+            &iso_timestamp(value),
+            type_name,
+            index,
+        )?),
+        "TIMESTAMPTZ" => query.bind(parse_param::<chrono::DateTime<chrono::Utc>>(
+            value, type_name, index,
+        )?),
+        "JSON" | "JSONB" => {
+            query.bind((!value.is_null()).then(|| sqlx::types::Json(value.clone())))
+        }
+        _ => query.bind(match value {
+            Value::Null => None,
+            Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }),
+    })
+}
+
+/// Rows show timestamps as `2024-01-02 03:04:05`, which `NaiveDateTime` only parses
+/// with a `T` between date and time; switch the separator so a value read back binds
+fn iso_timestamp(value: &Value) -> Value {
+    match value {
+        Value::String(s) if s.trim().as_bytes().get(10) == Some(&b' ') => {
+            let s = s.trim();
+            Value::String(format!("{}T{}", &s[..10], &s[11..]))
+        }
+        other => other.clone(),
+    }
+}
+
+/// Parse a JSON scalar for a typed placeholder; `null` binds SQL NULL
+fn parse_param<T: FromStr>(
+    value: &Value,
+    type_name: &str,
+    index: usize,
+) -> Result<Option<T>, String> {
+    let text = match value {
+        Value::Null => return Ok(None),
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(_) | Value::Bool(_) => value.to_string(),
+        Value::Array(_) | Value::Object(_) => String::new(),
+    };
+    text.parse().map(Some).map_err(|_| {
+        format!(
+            "Parameter ${} expects {}, got {}",
+            index,
+            type_name.to_lowercase(),
+            value
+        )
+    })
+}
+
 #[async_trait]
 impl DatabaseDriver for PostgresDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
+    async fn backend_pid(&self) -> Result<i64, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(pid as i64)
+    }
+
+    async fn ping(&self) -> Result<(), String> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::query("SELECT 1")
+            .fetch_one(&pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
     async fn test_connection(&self) -> Result<TestConnectionResult, String> {
-        match self.get_pool().await {
+        // An explicit test always tries again, even if a recent attempt failed
+        let target = self.reachability_target();
+        reachability::clear(&target);
+        match reachability::guard(&target, self.get_pool()).await {
             Ok(pool) => {
                 let result = sqlx::query("SELECT 1").fetch_one(&pool).await;
                 match result {
@@ -197,23 +1124,52 @@ impl DatabaseDriver for PostgresDriver {
     }
 
     async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        self.list_tables_paged(None, None, None).await
+    }
+
+    async fn list_tables_paged(
+        &self,
+        name_filter: Option<&str>,
+        page: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TableInfo>, String> {
         let pool = self.get_pool_with_retry().await?;
 
-        let tables = sqlx::query_as::<_, (String, String, String)>(
+        let tables = sqlx::query_as::<_, (String, String, String, Option<String>)>(
             r#"
-            SELECT 
+            SELECT
                 table_schema as schema,
                 table_name as name,
-                CASE 
-                    WHEN table_type = 'BASE TABLE' THEN 'table'
-                    WHEN table_type = 'VIEW' THEN 'view'
-                    ELSE 'table'
-                END as type
-            FROM information_schema.tables
-            WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+                table_type as type,
+                obj_description(
+                    format('%I.%I', table_schema, table_name)::regclass,
+                    'pg_class'
+                ) as comment
+            FROM (
+                SELECT
+                    table_schema,
+                    table_name,
+                    CASE
+                        WHEN table_type = 'BASE TABLE' THEN 'table'
+                        WHEN table_type = 'VIEW' THEN 'view'
+                        WHEN table_type = 'FOREIGN' THEN 'foreign_table'
+                        ELSE 'table'
+                    END as table_type
+                FROM information_schema.tables
+                WHERE table_schema NOT IN ('pg_catalog', 'information_schema')
+                UNION ALL
+                SELECT schemaname, matviewname, 'materialized_view'
+                FROM pg_matviews
+                WHERE schemaname NOT IN ('pg_catalog', 'information_schema')
+            ) t
+            WHERE ($1::text IS NULL OR table_name ILIKE $1)
             ORDER BY table_schema, table_name
+            LIMIT $2 OFFSET $3
             "#,
         )
+        .bind(name_filter.map(like_contains_pattern))
+        .bind(limit)
+        .bind(limit.map(|l| page_offset(page, l)).unwrap_or(0))
         .fetch_all(&pool)
         .await
         .map_err(|e| {
@@ -229,10 +1185,12 @@ impl DatabaseDriver for PostgresDriver {
 
         Ok(tables
             .into_iter()
-            .map(|(schema, name, table_type)| TableInfo {
+            .map(|(schema, name, table_type, comment)| TableInfo {
                 schema,
                 name,
                 table_type,
+                comment,
+                engine_full: None,
             })
             .collect())
     }
@@ -247,6 +1205,14 @@ impl DatabaseDriver for PostgresDriver {
         sort_column: Option<String>,
         sort_direction: Option<String>,
     ) -> Result<TableDataResponse, String> {
+        let resolved = self.resolve_table_name(schema, table).await?;
+        if resolved != table {
+            println!(
+                "[Postgres] Table \"{}\" resolved to \"{}\"",
+                table, resolved
+            );
+        }
+        let table = resolved.as_str();
         let pool = self.get_pool_with_retry().await?;
 
         let offset = (page - 1) * limit;
@@ -338,9 +1304,18 @@ impl DatabaseDriver for PostgresDriver {
         schema: &str,
         table: &str,
     ) -> Result<TableStructure, String> {
+        let resolved = self.resolve_table_name(schema, table).await?;
+        let mut warnings = Vec::new();
+        if resolved != table {
+            warnings.push(format!(
+                "Table \"{}\" not found; showing \"{}\"",
+                table, resolved
+            ));
+        }
+        let table = resolved.as_str();
         let pool = self.get_pool_with_retry().await?;
 
-        let columns = sqlx::query_as::<_, (String, String, bool, Option<String>, bool)>(
+        let columns = sqlx::query_as::<_, ColumnRow>(
             r#"
             SELECT
                 c.column_name as name,
@@ -355,7 +1330,21 @@ impl DatabaseDriver for PostgresDriver {
                     AND tc.table_name = c.table_name
                     AND kcu.column_name = c.column_name
                     AND tc.constraint_type = 'PRIMARY KEY'
-                ) as primary_key
+                ) as primary_key,
+                col_description(
+                    format('%I.%I', c.table_schema, c.table_name)::regclass,
+                    c.ordinal_position::int
+                ) as comment,
+                (
+                    SELECT array_agg(e.enumlabel ORDER BY e.enumsortorder)::text[]
+                    FROM pg_type t
+                    JOIN pg_namespace n ON n.oid = t.typnamespace
+                    JOIN pg_enum e ON e.enumtypid = t.oid
+                    WHERE t.typname = c.udt_name
+                    AND n.nspname = c.udt_schema
+                ) as enum_values,
+                (c.is_identity = 'YES' OR COALESCE(c.column_default, '') LIKE 'nextval(%') as auto_increment,
+                c.is_generated = 'ALWAYS' as is_generated
             FROM information_schema.columns c
             WHERE c.table_schema = $1
             AND c.table_name = $2
@@ -406,13 +1395,15 @@ impl DatabaseDriver for PostgresDriver {
                 println!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
             }
             error_str
-        })?;
+        });
+        let indexes = section_or_warning("indexes", indexes, &mut warnings)?;
 
-        let foreign_keys = sqlx::query_as::<_, (String, String, String, String)>(
+        let foreign_keys = sqlx::query_as::<_, (String, String, String, String, String)>(
             r#"
             SELECT
                 tc.constraint_name as name,
                 kcu.column_name as column,
+                ccu.table_schema as references_schema,
                 ccu.table_name as references_table,
                 ccu.column_name as references_column
             FROM information_schema.table_constraints tc
@@ -438,18 +1429,54 @@ impl DatabaseDriver for PostgresDriver {
                 println!("[Postgres] Connection error in get_table_structure, will reset pool on next access: {}", error_str);
             }
             error_str
-        })?;
+        });
+        let foreign_keys = section_or_warning("foreign keys", foreign_keys, &mut warnings)?;
+
+        let check_constraints = sqlx::query_as::<_, (String, String)>(CHECK_CONSTRAINTS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string());
+        let check_constraints =
+            section_or_warning("check constraints", check_constraints, &mut warnings)?;
+
+        let unique_constraints =
+            sqlx::query_as::<_, (String, Vec<String>)>(UNIQUE_CONSTRAINTS_QUERY)
+                .bind(schema)
+                .bind(table)
+                .fetch_all(&pool)
+                .await
+                .map_err(|e| e.to_string());
+        let unique_constraints =
+            section_or_warning("unique constraints", unique_constraints, &mut warnings)?;
 
         Ok(TableStructure {
             columns: columns
                 .into_iter()
                 .map(
-                    |(name, data_type, nullable, default, primary_key)| ColumnInfo {
+                    |(
                         name,
                         data_type,
                         nullable,
                         default,
                         primary_key,
+                        comment,
+                        enum_values,
+                        auto_increment,
+                        is_generated,
+                    )| {
+                        ColumnInfo {
+                            name,
+                            data_type,
+                            nullable,
+                            default,
+                            primary_key,
+                            comment,
+                            enum_values,
+                            auto_increment,
+                            is_generated,
+                        }
                     },
                 )
                 .collect(),
@@ -465,14 +1492,26 @@ impl DatabaseDriver for PostgresDriver {
             foreign_keys: foreign_keys
                 .into_iter()
                 .map(
-                    |(name, column, references_table, references_column)| ForeignKeyInfo {
-                        name,
-                        column,
-                        references_table,
-                        references_column,
+                    |(name, column, references_schema, references_table, references_column)| {
+                        ForeignKeyInfo {
+                            name,
+                            column,
+                            references_schema,
+                            references_table,
+                            references_column,
+                        }
                     },
                 )
                 .collect(),
+            check_constraints: check_constraints
+                .into_iter()
+                .map(|(name, expression)| CheckConstraint { name, expression })
+                .collect(),
+            unique_constraints: unique_constraints
+                .into_iter()
+                .map(|(name, columns)| UniqueConstraint { name, columns })
+                .collect(),
+            warnings,
         })
     }
 
@@ -480,40 +1519,269 @@ impl DatabaseDriver for PostgresDriver {
         let start_time = std::time::Instant::now();
         let pool = self.get_pool_with_retry().await?;
 
-        match sqlx::query(query).fetch_all(&pool).await {
-            Ok(rows) => {
-                let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
-                let row_count = data.len() as i64;
-                Ok(QueryResult {
-                    data,
-                    row_count,
-                    error: None,
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                })
-            }
-            Err(e) => {
-                let error_str = e.to_string();
-                let should_reset = error_str.contains("Connection reset by peer")
-                    || error_str.contains("broken pipe")
-                    || error_str.contains("connection closed")
-                    || error_str.contains("server closed the connection");
+        let (rows, notices) = notices::collect(sqlx::query(query).fetch_all(&pool)).await;
+        let mut result = match rows {
+            Ok(rows) => Self::rows_result(&rows, start_time),
+            Err(e) => self.error_result(e.to_string(), start_time).await,
+        };
+        result.notices = notices;
+        Ok(result)
+    }
 
-                if should_reset {
-                    println!(
-                        "[Postgres] Connection error detected, resetting pool: {}",
-                        error_str
-                    );
-                    let _ = self.reset_pool().await;
-                }
+    async fn execute_returning(
+        &self,
+        _schema: &str,
+        _table: &str,
+        insert_query: &str,
+        _inserted: &[(String, String)],
+    ) -> Result<QueryResult, String> {
+        self.execute_query(&format!("{} RETURNING *", insert_query))
+            .await
+    }
 
-                Ok(QueryResult {
-                    data: vec![],
-                    row_count: 0,
-                    error: Some(error_str),
-                    time_taken_ms: Some(start_time.elapsed().as_millis()),
-                })
+    /// Statements run over the simple query protocol in one transaction, each giving a
+    /// result. A result made only of `refcursor` columns, as returned by a function
+    /// that opens cursors, is replaced by the rows of each cursor it names.
+    async fn execute_multi_result(&self, query: &str) -> Result<Vec<QueryResult>, String> {
+        let start_time = std::time::Instant::now();
+        let pool = self.get_pool_with_retry().await?;
+        let run = async {
+            // Cursors are closed at the end of the transaction that opened them
+            let mut tx = pool.begin().await?;
+            let sets = fetch_result_sets(&mut tx, query).await?;
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(sets)
+        };
+
+        let (results, notices) = notices::collect(run).await;
+        let mut results = match results {
+            Ok(sets) => sets
+                .iter()
+                .map(|rows| Self::rows_result(rows, start_time))
+                .collect(),
+            Err(e) => vec![self.error_result(e.to_string(), start_time).await],
+        };
+        if let Some(first) = results.first_mut() {
+            first.notices = notices;
+        }
+        Ok(results)
+    }
+
+    async fn execute_query_with_params(
+        &self,
+        query: &str,
+        params: &[Value],
+    ) -> Result<QueryResult, String> {
+        let start_time = std::time::Instant::now();
+        let pool = self.get_pool_with_retry().await?;
+
+        // Describe the statement first to check the placeholder count and learn
+        // which type Postgres expects for each parameter
+        let parameter_types: Vec<PgTypeInfo> = match (&pool).prepare(query).await {
+            Ok(statement) => match statement.parameters() {
+                Some(Either::Left(types)) => types.to_vec(),
+                _ => vec![],
+            },
+            Err(e) => return Ok(self.error_result(e.to_string(), start_time).await),
+        };
+        check_param_count(parameter_types.len(), params.len())?;
+
+        let mut bound = sqlx::query(query);
+        for (index, (value, type_info)) in params.iter().zip(&parameter_types).enumerate() {
+            bound = bind_param(bound, value, type_info, index + 1)?;
+        }
+
+        let (rows, notices) = notices::collect(bound.fetch_all(&pool)).await;
+        let mut result = match rows {
+            Ok(rows) => Self::rows_result(&rows, start_time),
+            Err(e) => self.error_result(e.to_string(), start_time).await,
+        };
+        result.notices = notices;
+        Ok(result)
+    }
+
+    async fn stream_query(
+        &self,
+        query: &str,
+        batch_size: usize,
+        cancel: &CancellationToken,
+        on_batch: &mut (dyn FnMut(Vec<Value>) + Send),
+    ) -> Result<u64, String> {
+        let pool = self.get_pool_with_retry().await?;
+        // Dropping the row stream on cancel returns the connection to the pool
+        let rows = sqlx::query(query).fetch(&pool);
+        drain_rows(rows, Self::row_to_json, batch_size, cancel, on_batch).await
+    }
+
+    /// Large tables are sampled with `TABLESAMPLE SYSTEM`, which reads only some pages;
+    /// small tables, or samples that come back short, use `ORDER BY random()`
+    async fn sample_table(&self, schema: &str, table: &str, n: i64) -> Result<QueryResult, String> {
+        let table = self.resolve_table_name(schema, table).await?;
+        let table_ref = format!(
+            "{}.{}",
+            Self::quote_identifier(schema),
+            Self::quote_identifier(&table)
+        );
+
+        let pool = self.get_pool_with_retry().await?;
+        let estimated_rows: Option<f32> = sqlx::query_scalar(TABLE_ROW_ESTIMATE_QUERY)
+            .bind(schema)
+            .bind(&table)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let estimated_rows = estimated_rows.unwrap_or(0.0) as f64;
+        if estimated_rows > SAMPLE_CLAUSE_MIN_ROWS {
+            let percent = sample_fraction(n, estimated_rows) * 100.0;
+            let sampled = self
+                .execute_query(&format!(
+                    "SELECT * FROM {} TABLESAMPLE SYSTEM ({}) LIMIT {}",
+                    table_ref, percent, n
+                ))
+                .await?;
+            if sampled.error.is_some() || sampled.row_count >= n {
+                return Ok(sampled);
             }
         }
+
+        self.execute_query(&format!(
+            "SELECT * FROM {} ORDER BY random() LIMIT {}",
+            table_ref, n
+        ))
+        .await
+    }
+
+    async fn get_database_info(&self) -> Result<DatabaseInfo, String> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::query_as::<_, DatabaseInfo>(DATABASE_INFO_QUERY)
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn list_routines(&self, schema: &str) -> Result<Vec<RoutineInfo>, String> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::query_as::<_, RoutineInfo>(ROUTINES_QUERY)
+            .bind(schema)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn get_routine_definition(&self, schema: &str, name: &str) -> Result<String, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let definitions: Vec<String> = sqlx::query_scalar(ROUTINE_DEFINITION_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if definitions.is_empty() {
+            return Err(format!("Routine {}.{} not found", schema, name));
+        }
+        Ok(definitions.join("\n\n"))
+    }
+
+    async fn namespace_overview(&self) -> Result<Vec<NamespaceOverview>, String> {
+        let pool = self.get_pool_with_retry().await?;
+        sqlx::query_as::<_, NamespaceOverview>(SCHEMA_TABLE_COUNTS_QUERY)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    async fn column_statistics(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ColumnProfile>, String> {
+        let table = self.resolve_table_name(schema, table).await?;
+        let pool = self.get_pool_with_retry().await?;
+        let rows: Vec<(String, f64, f64)> = sqlx::query_as(COLUMN_STATISTICS_QUERY)
+            .bind(schema)
+            .bind(&table)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(column, null_fraction, distinct)| ColumnProfile {
+                column,
+                null_fraction,
+                distinct_estimate: distinct.round() as i64,
+                source: "statistics".to_string(),
+            })
+            .collect())
+    }
+
+    async fn get_partitions(&self, schema: &str, table: &str) -> Result<TablePartitions, String> {
+        let table = self.resolve_table_name(schema, table).await?;
+        let pool = self.get_pool_with_retry().await?;
+        let strategy: Option<(Option<String>, String)> = sqlx::query_as(PARTITION_STRATEGY_QUERY)
+            .bind(schema)
+            .bind(&table)
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let Some((strategy, partition_key)) = strategy else {
+            return Ok(TablePartitions {
+                strategy: None,
+                partition_key: None,
+                partitions: Vec::new(),
+            });
+        };
+
+        let partitions = sqlx::query_as::<_, PartitionInfo>(PARTITIONS_QUERY)
+            .bind(schema)
+            .bind(&table)
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(TablePartitions {
+            strategy,
+            partition_key: Some(partition_key),
+            partitions,
+        })
+    }
+
+    async fn validate_query(&self, query: &str) -> Result<(), String> {
+        // Parse and describe the statement server-side, like PREPARE, without running it
+        let pool = self.get_pool_with_retry().await?;
+        (&pool)
+            .prepare(query)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn describe_query(&self, query: &str) -> Result<Vec<ResultColumn>, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let statement = (&pool).prepare(query).await.map_err(|e| e.to_string())?;
+        Ok(statement
+            .columns()
+            .iter()
+            .map(|column| ResultColumn {
+                name: column.name().to_string(),
+                data_type: column.type_info().name().to_string(),
+            })
+            .collect())
+    }
+
+    async fn begin_transaction(&self) -> Result<TxHandle, String> {
+        let pool = self.get_pool_with_retry().await?;
+        pool.begin()
+            .await
+            .map(TxHandle::Postgres)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn close(&self) {
+        let _ = self.reset_pool().await;
     }
 
     async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
@@ -542,6 +1810,7 @@ impl DatabaseDriver for PostgresDriver {
             let schema: String = row.try_get("schema").map_err(|e| e.to_string())?;
             let name: String = row.try_get("name").map_err(|e| e.to_string())?;
             let table_type: String = row.try_get("type").map_err(|e| e.to_string())?;
+            let comment: Option<String> = row.try_get("comment").map_err(|e| e.to_string())?;
 
             let columns_json: Value = row.try_get("columns").map_err(|e| e.to_string())?;
             let columns: Vec<ColumnInfo> = serde_json::from_value(columns_json)
@@ -560,6 +1829,7 @@ impl DatabaseDriver for PostgresDriver {
                 schema,
                 name,
                 table_type,
+                comment,
                 columns,
                 foreign_keys,
                 indexes,
@@ -568,4 +1838,86 @@ impl DatabaseDriver for PostgresDriver {
 
         Ok(SchemaOverview { tables })
     }
+
+    /// Loads the file with `COPY FROM STDIN` instead of `INSERT` batches
+    async fn import_table_csv(
+        &self,
+        table_ref: &str,
+        csv_path: &str,
+        gzip: bool,
+    ) -> Result<CsvImportSummary, String> {
+        let pool = self.get_pool_with_retry().await?;
+        import::copy_csv_postgres(&pool, table_ref, csv_path, gzip).await
+    }
+
+    /// Cancelling mid-`COPY` truncates the file after the last complete row and closes
+    /// the connection, since it can no longer be reused
+    async fn export_table_csv(
+        &self,
+        schema: &str,
+        table: &str,
+        output_path: &str,
+        cancel: &CancellationToken,
+    ) -> Result<CsvExportSummary, String> {
+        let pool = self.get_pool_with_retry().await?;
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+
+        // Let the server format the CSV so NULLs and quoting follow Postgres semantics
+        let statement = format!(
+            "COPY (SELECT * FROM \"{}\".\"{}\") TO STDOUT WITH CSV HEADER",
+            schema.replace('"', "\"\""),
+            table.replace('"', "\"\"")
+        );
+
+        let mut file = tokio::fs::File::create(output_path)
+            .await
+            .map_err(|e| format!("Failed to create export file: {}", e))?;
+        let mut summary = CsvExportSummary::default();
+        let mut records = CsvRecordCounter::default();
+        // Bytes up to the end of the last complete record, where a cancelled export is cut
+        let mut complete_bytes: u64 = 0;
+
+        {
+            let mut stream = conn
+                .copy_out_raw(&statement)
+                .await
+                .map_err(|e| format!("Failed to start COPY export: {}", e))?;
+
+            loop {
+                let chunk = tokio::select! {
+                    biased;
+                    _ = cancel.cancelled() => break,
+                    chunk = stream.next() => chunk,
+                };
+                let Some(chunk) = chunk else { break };
+                let chunk = chunk.map_err(|e| format!("COPY export failed: {}", e))?;
+                file.write_all(&chunk)
+                    .await
+                    .map_err(|e| format!("Failed to write export file: {}", e))?;
+                if let Some(end) = records.feed(&chunk) {
+                    complete_bytes = summary.bytes_written + end as u64;
+                }
+                summary.bytes_written += chunk.len() as u64;
+            }
+        }
+
+        if cancel.is_cancelled() {
+            conn.close_on_drop();
+            if summary.bytes_written != complete_bytes {
+                file.set_len(complete_bytes)
+                    .await
+                    .map_err(|e| format!("Failed to write export file: {}", e))?;
+                summary.bytes_written = complete_bytes;
+            }
+            summary.cancelled = true;
+        }
+
+        file.flush()
+            .await
+            .map_err(|e| format!("Failed to write export file: {}", e))?;
+
+        // The first record is the header
+        summary.rows_written = records.records().saturating_sub(1);
+        Ok(summary)
+    }
 }
@@ -3,18 +3,20 @@ SELECT
     c.database as schema,
     c.table as name,
     t.engine as type,
+    t.comment as comment,
     groupArray(tuple(
         c.name,
         c.type,
         c.default_kind,
         c.default_expression,
-        c.is_in_primary_key
+        c.is_in_primary_key,
+        c.comment
     )) as columns_raw
 FROM system.columns c
 JOIN system.tables t ON c.database = t.database AND c.table = t.name
 WHERE c.database = currentDatabase()
     AND c.database NOT IN ('system', 'INFORMATION_SCHEMA', 'information_schema')
-GROUP BY c.database, c.table, t.engine
+GROUP BY c.database, c.table, t.engine, t.comment
 ORDER BY c.database, c.table;
 "#;
 
@@ -27,3 +29,16 @@ FROM system.data_skipping_indices
 WHERE database = currentDatabase()
 GROUP BY database, table;
 "#;
+
+pub const DATABASE_TABLE_COUNTS_QUERY: &str = r#"
+SELECT
+    database AS name,
+    count() AS table_count,
+    sum(ifNull(total_rows, 0)) AS estimated_rows
+FROM system.tables
+WHERE database NOT IN ('system', 'INFORMATION_SCHEMA', 'information_schema')
+    AND NOT is_temporary
+    AND engine NOT LIKE '%View'
+GROUP BY database
+ORDER BY database
+"#;
@@ -8,7 +8,21 @@ WITH columns_data AS (
             'type', c.data_type,
             'nullable', c.is_nullable = 'YES',
             'default', c.column_default,
-            'primary_key', CASE WHEN pk.column_name IS NOT NULL THEN true ELSE false END
+            'primary_key', CASE WHEN pk.column_name IS NOT NULL THEN true ELSE false END,
+            'comment', col_description(
+                format('%I.%I', c.table_schema, c.table_name)::regclass,
+                c.ordinal_position::int
+            ),
+            'enum_values', (
+                SELECT json_agg(e.enumlabel ORDER BY e.enumsortorder)
+                FROM pg_type t
+                JOIN pg_namespace n ON n.oid = t.typnamespace
+                JOIN pg_enum e ON e.enumtypid = t.oid
+                WHERE t.typname = c.udt_name
+                AND n.nspname = c.udt_schema
+            ),
+            'auto_increment', c.is_identity = 'YES' OR COALESCE(c.column_default, '') LIKE 'nextval(%',
+            'is_generated', c.is_generated = 'ALWAYS'
         ) ORDER BY c.ordinal_position) as columns
     FROM information_schema.columns c
     LEFT JOIN (
@@ -31,6 +45,7 @@ foreign_keys_data AS (
         json_agg(json_build_object(
             'name', tc.constraint_name,
             'column', kcu.column_name,
+            'references_schema', ccu.table_schema,
             'references_table', ccu.table_name,
             'references_column', ccu.column_name
         )) as foreign_keys
@@ -64,6 +79,10 @@ SELECT
     cd.table_schema as schema,
     cd.table_name as name,
     'table' as type,
+    obj_description(
+        format('%I.%I', cd.table_schema, cd.table_name)::regclass,
+        'pg_class'
+    ) as comment,
     cd.columns,
     COALESCE(fk.foreign_keys, '[]'::json) as foreign_keys,
     COALESCE(idx.indexes, '[]'::json) as indexes
@@ -76,3 +95,221 @@ LEFT JOIN indexes_data idx
     AND cd.table_name = idx.table_name
 ORDER BY cd.table_schema, cd.table_name;
 "#;
+
+pub const BLOCKING_QUERIES_QUERY: &str = r#"
+SELECT DISTINCT
+    blocked.pid::bigint AS blocked_pid,
+    blocked.usename::text AS blocked_user,
+    blocked.query AS blocked_query,
+    (EXTRACT(EPOCH FROM (now() - blocked.query_start)) * 1000)::float8 AS blocked_duration_ms,
+    blocking.pid::bigint AS blocking_pid,
+    blocking.usename::text AS blocking_user,
+    blocking.query AS blocking_query,
+    blocking.state AS blocking_state,
+    blocked_locks.locktype AS lock_type,
+    blocked_locks.mode AS lock_mode
+FROM pg_locks blocked_locks
+JOIN pg_stat_activity blocked ON blocked.pid = blocked_locks.pid
+JOIN pg_locks blocking_locks
+    ON blocking_locks.locktype = blocked_locks.locktype
+    AND blocking_locks.database IS NOT DISTINCT FROM blocked_locks.database
+    AND blocking_locks.relation IS NOT DISTINCT FROM blocked_locks.relation
+    AND blocking_locks.page IS NOT DISTINCT FROM blocked_locks.page
+    AND blocking_locks.tuple IS NOT DISTINCT FROM blocked_locks.tuple
+    AND blocking_locks.virtualxid IS NOT DISTINCT FROM blocked_locks.virtualxid
+    AND blocking_locks.transactionid IS NOT DISTINCT FROM blocked_locks.transactionid
+    AND blocking_locks.classid IS NOT DISTINCT FROM blocked_locks.classid
+    AND blocking_locks.objid IS NOT DISTINCT FROM blocked_locks.objid
+    AND blocking_locks.objsubid IS NOT DISTINCT FROM blocked_locks.objsubid
+    AND blocking_locks.pid <> blocked_locks.pid
+JOIN pg_stat_activity blocking ON blocking.pid = blocking_locks.pid
+WHERE NOT blocked_locks.granted
+    AND blocking_locks.granted
+ORDER BY blocked_duration_ms DESC NULLS LAST;
+"#;
+
+pub const CHECK_CONSTRAINTS_QUERY: &str = r#"
+SELECT
+    con.conname AS name,
+    pg_get_expr(con.conbin, con.conrelid) AS expression
+FROM pg_constraint con
+JOIN pg_class c ON c.oid = con.conrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE con.contype = 'c'
+AND n.nspname = $1
+AND c.relname = $2
+ORDER BY con.conname
+"#;
+
+pub const UNIQUE_CONSTRAINTS_QUERY: &str = r#"
+SELECT
+    con.conname AS name,
+    array_agg(a.attname ORDER BY k.ord)::text[] AS columns
+FROM pg_constraint con
+JOIN pg_class c ON c.oid = con.conrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+CROSS JOIN LATERAL unnest(con.conkey) WITH ORDINALITY AS k(attnum, ord)
+JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+WHERE con.contype = 'u'
+AND n.nspname = $1
+AND c.relname = $2
+GROUP BY con.conname
+ORDER BY con.conname
+"#;
+
+pub const DATABASE_INFO_QUERY: &str = r#"
+SELECT
+    pg_encoding_to_char(d.encoding)::text AS encoding,
+    d.datcollate::text AS collation,
+    current_setting('TimeZone') AS timezone
+FROM pg_database d
+WHERE d.datname = current_database()
+"#;
+
+pub const ROUTINES_QUERY: &str = r#"
+SELECT
+    r.routine_schema::text AS schema,
+    r.routine_name::text AS name,
+    lower(r.routine_type) AS routine_type,
+    CASE WHEN p.prokind = 'p' THEN NULL ELSE pg_get_function_result(p.oid) END AS return_type,
+    pg_get_function_arguments(p.oid) AS arguments,
+    r.external_language::text AS language
+FROM information_schema.routines r
+JOIN pg_proc p ON r.specific_name = p.proname || '_' || p.oid
+WHERE r.routine_schema = $1
+AND r.routine_type IN ('FUNCTION', 'PROCEDURE')
+ORDER BY r.routine_name, arguments
+"#;
+
+pub const ROUTINE_DEFINITION_QUERY: &str = r#"
+SELECT pg_get_functiondef(p.oid)
+FROM pg_proc p
+JOIN pg_namespace n ON n.oid = p.pronamespace
+WHERE n.nspname = $1
+AND p.proname = $2
+AND p.prokind IN ('f', 'p')
+ORDER BY pg_get_function_arguments(p.oid)
+"#;
+
+/// Sequence owned by a serial or identity column, NULL when the column has none
+pub const SERIAL_SEQUENCE_QUERY: &str = r#"
+SELECT pg_get_serial_sequence(format('%I.%I', $1, $2), $3)
+"#;
+
+/// Columns that lead at least one index of a table
+pub const INDEXED_LEADING_COLUMNS_QUERY: &str = r#"
+SELECT a.attname::text
+FROM pg_index i
+JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = i.indkey[0]
+WHERE i.indrelid = format('%I.%I', $1, $2)::regclass
+"#;
+
+/// Privileges granted on a table whose grantor or grantee is a role the current user has
+pub const TABLE_GRANTS_QUERY: &str = r#"
+SELECT
+    grantor::text,
+    grantee::text,
+    privilege_type::text,
+    is_grantable = 'YES' AS is_grantable
+FROM information_schema.role_table_grants
+WHERE table_schema = $1 AND table_name = $2
+ORDER BY grantee, privilege_type
+"#;
+
+pub const ROW_SECURITY_QUERY: &str = r#"
+SELECT c.relrowsecurity, c.relforcerowsecurity
+FROM pg_class c
+WHERE c.oid = format('%I.%I', $1, $2)::regclass
+"#;
+
+pub const ROW_SECURITY_POLICIES_QUERY: &str = r#"
+SELECT
+    policyname::text AS name,
+    cmd::text AS command,
+    permissive = 'PERMISSIVE' AS permissive,
+    roles::text[] AS roles,
+    qual AS using_expression,
+    with_check AS check_expression
+FROM pg_policies
+WHERE schemaname = $1 AND tablename = $2
+ORDER BY policyname
+"#;
+
+pub const RELATION_EXISTS_QUERY: &str = r#"
+SELECT EXISTS (
+    SELECT 1
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE n.nspname = $1
+    AND c.relname = $2
+)
+"#;
+
+pub const TABLE_ROW_ESTIMATE_QUERY: &str = r#"
+SELECT c.reltuples
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = $1
+AND c.relname = $2
+"#;
+
+pub const PARTITION_STRATEGY_QUERY: &str = r#"
+SELECT
+    CASE pt.partstrat WHEN 'r' THEN 'range' WHEN 'l' THEN 'list' WHEN 'h' THEN 'hash' END AS strategy,
+    pg_get_partkeydef(c.oid) AS partition_key
+FROM pg_partitioned_table pt
+JOIN pg_class c ON c.oid = pt.partrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = $1
+AND c.relname = $2
+"#;
+
+pub const PARTITIONS_QUERY: &str = r#"
+SELECT
+    cn.nspname::text AS schema,
+    c.relname::text AS name,
+    pg_get_expr(c.relpartbound, c.oid) AS bound,
+    c.relkind = 'p' AS is_partitioned
+FROM pg_inherits i
+JOIN pg_class c ON c.oid = i.inhrelid
+JOIN pg_namespace cn ON cn.oid = c.relnamespace
+JOIN pg_class p ON p.oid = i.inhparent
+JOIN pg_namespace pn ON pn.oid = p.relnamespace
+WHERE pn.nspname = $1
+AND p.relname = $2
+AND c.relispartition
+ORDER BY c.relname
+"#;
+
+/// Per-column planner statistics; a negative `n_distinct` is a fraction of the row count.
+/// Parents of partitioned or inherited tables prefer the statistics covering their children.
+pub const COLUMN_STATISTICS_QUERY: &str = r#"
+SELECT DISTINCT ON (s.attname)
+    s.attname::text AS column_name,
+    s.null_frac::float8 AS null_fraction,
+    (CASE
+        WHEN s.n_distinct >= 0 THEN s.n_distinct
+        ELSE -s.n_distinct * GREATEST(c.reltuples, 0)
+    END)::float8 AS distinct_estimate
+FROM pg_stats s
+JOIN pg_namespace n ON n.nspname = s.schemaname
+JOIN pg_class c ON c.relnamespace = n.oid AND c.relname = s.tablename
+WHERE s.schemaname = $1
+AND s.tablename = $2
+ORDER BY s.attname, s.inherited DESC
+"#;
+
+/// Tables and planner row estimates per user schema; partitions count towards rows only
+pub const SCHEMA_TABLE_COUNTS_QUERY: &str = r#"
+SELECT
+    n.nspname::text AS name,
+    COUNT(c.oid) FILTER (WHERE NOT c.relispartition) AS table_count,
+    COALESCE(SUM(GREATEST(c.reltuples, 0)), 0)::int8 AS estimated_rows
+FROM pg_namespace n
+LEFT JOIN pg_class c ON c.relnamespace = n.oid AND c.relkind IN ('r', 'p')
+WHERE n.nspname NOT IN ('pg_catalog', 'information_schema')
+AND n.nspname NOT LIKE 'pg\_toast%'
+AND n.nspname NOT LIKE 'pg\_temp\_%'
+GROUP BY n.nspname
+ORDER BY n.nspname
+"#;
@@ -11,10 +11,11 @@ SELECT
     p.type as data_type,
     p."notnull" as not_null,
     p."dflt_value" as default_value,
-    p.pk as primary_key
+    p.pk as primary_key,
+    p.hidden IN (2, 3) as is_generated
 FROM sqlite_master m
-CROSS JOIN pragma_table_info(m.name) p
-WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'
+CROSS JOIN pragma_table_xinfo(m.name) p
+WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%' AND p.hidden <> 1
 ORDER BY m.name, p.cid;
 "#;
 
@@ -24,7 +25,7 @@ SELECT
     f.id as fk_id,
     f."from" as column_name,
     f."table" as references_table,
-    f."to" as references_column
+    COALESCE(f."to", '') as references_column
 FROM sqlite_master m
 CROSS JOIN pragma_foreign_key_list(m.name) f
 WHERE m.type = 'table' AND m.name NOT LIKE 'sqlite_%'
@@ -0,0 +1,196 @@
+//! Short-lived in-memory cache for SELECT results.
+//!
+//! Entries are keyed by the database they were read from, the login used, and normalized
+//! query text. Each entry expires after the TTL requested when it was stored, or as soon
+//! as a write to the same database finishes.
+
+use moka::future::Cache;
+use moka::Expiry;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use super::pool_manager::ConnectionConfig;
+use crate::commands::database::is_read_only_query;
+use crate::db::models::QueryResult;
+
+/// Upper bound on the number of cached results
+const MAX_ENTRIES: u64 = 256;
+
+#[derive(Clone)]
+struct CachedResult {
+    result: QueryResult,
+    ttl: Duration,
+}
+
+/// Expires each entry after its own TTL
+struct PerEntryTtl;
+
+impl Expiry<String, CachedResult> for PerEntryTtl {
+    fn expire_after_create(
+        &self,
+        _key: &String,
+        value: &CachedResult,
+        _created_at: Instant,
+    ) -> Option<Duration> {
+        Some(value.ttl)
+    }
+}
+
+fn cache() -> &'static Cache<String, CachedResult> {
+    static CACHE: OnceLock<Cache<String, CachedResult>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        Cache::builder()
+            .max_capacity(MAX_ENTRIES)
+            .expire_after(PerEntryTtl)
+            .support_invalidation_closures()
+            .build()
+    })
+}
+
+/// Collapse whitespace and drop trailing semicolons so trivially different
+/// spellings of a query share an entry. Quoted strings and identifiers are kept as is.
+pub fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut quote = None;
+    let mut pending_space = false;
+    for c in query.trim().chars() {
+        match quote {
+            Some(q) => {
+                if c == q {
+                    quote = None;
+                }
+            }
+            None if c.is_whitespace() => {
+                pending_space = true;
+                continue;
+            }
+            None => {
+                if c == '\'' || c == '"' || c == '`' {
+                    quote = Some(c);
+                }
+            }
+        }
+        if pending_space {
+            normalized.push(' ');
+            pending_space = false;
+        }
+        normalized.push(c);
+    }
+    if quote.is_none() {
+        let trimmed = normalized.trim_end_matches(';').trim_end().len();
+        normalized.truncate(trimmed);
+    }
+    normalized
+}
+
+/// Only SELECT statements without side effects are cached: `SELECT ... INTO`, row-locking
+/// `FOR UPDATE`/`FOR SHARE` and calls such as `nextval()` must reach the database each time.
+pub fn is_cacheable(query: &str, db_type: &str) -> bool {
+    let normalized = normalize_query(query);
+    let is_select = normalized
+        .get(..6)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("SELECT"));
+    if !is_select || !is_read_only_query(&normalized, db_type) {
+        return false;
+    }
+
+    let words: Vec<String> = normalized
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_uppercase())
+        .collect();
+    !words.windows(2).any(|pair| {
+        pair[0] == "FOR" && matches!(pair[1].as_str(), "UPDATE" | "SHARE" | "NO" | "KEY")
+    })
+}
+
+/// Database a result is read from. Writes invalidate every cached result for their target,
+/// whichever login read it.
+pub fn target(
+    db_type: &str,
+    host: Option<&str>,
+    port: Option<i64>,
+    database: Option<&str>,
+    file_path: Option<&str>,
+) -> String {
+    format!(
+        "{}|{:?}|{:?}|{:?}|{:?}",
+        db_type, host, port, database, file_path
+    )
+}
+
+/// Cache target of a pooled connection
+pub fn target_for_config(config: &ConnectionConfig) -> String {
+    target(
+        &config.db_type,
+        config.host.as_deref(),
+        config.port,
+        config.database.as_deref(),
+        config.file_path.as_deref(),
+    )
+}
+
+/// Credentials and session setup a result was read with; different logins may see
+/// different rows, and setup such as a `search_path` changes what a query reads
+pub fn login(
+    username: Option<&str>,
+    password: Option<&str>,
+    ssl: Option<bool>,
+    session_setup_sql: Option<&str>,
+) -> String {
+    format!(
+        "{:?}|{:?}|{:?}|{:?}",
+        username, password, ssl, session_setup_sql
+    )
+}
+
+fn cache_key(target: &str, login: &str, query: &str) -> String {
+    format!("{}\u{0}{}\u{0}{}", target, login, normalize_query(query))
+}
+
+/// Look up a cached result, marking it as served from cache
+pub async fn get(target: &str, login: &str, query: &str) -> Option<QueryResult> {
+    cache()
+        .get(&cache_key(target, login, query))
+        .await
+        .map(|cached| QueryResult {
+            from_cache: true,
+            ..cached.result
+        })
+}
+
+/// Store a result for `ttl_secs` seconds
+pub async fn insert(target: &str, login: &str, query: &str, result: QueryResult, ttl_secs: u64) {
+    cache()
+        .insert(
+            cache_key(target, login, query),
+            CachedResult {
+                result,
+                ttl: Duration::from_secs(ttl_secs),
+            },
+        )
+        .await;
+}
+
+/// Drop every cached result read from `target`
+pub fn invalidate(target: &str) {
+    let prefix = format!("{}\u{0}", target);
+    // Fails only when the cache was built without invalidation closures
+    let _ = cache().invalidate_entries_if(move |key, _| key.starts_with(&prefix));
+}
+
+/// Invalidates a target's cached results when dropped, so a command holding it clears the
+/// cache once its write has finished, whether it succeeded or not
+#[must_use]
+pub struct WriteGuard(String);
+
+impl Drop for WriteGuard {
+    fn drop(&mut self) {
+        invalidate(&self.0);
+    }
+}
+
+/// Guard for a command about to write to `target`
+pub fn write_guard(target: String) -> WriteGuard {
+    WriteGuard(target)
+}
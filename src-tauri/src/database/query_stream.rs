@@ -0,0 +1,102 @@
+//! Streamed query results with cancellation.
+//!
+//! A streamed query hands its rows to a callback in batches instead of collecting them all.
+//! Each stream owns a cancellation token; cancelling it stops the fetch loop at the next row
+//! and drops the row stream, which hands the connection back to its pool.
+
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Tracks the cancellation token of every running stream, keyed by stream id
+#[derive(Default)]
+pub struct QueryStreamManager {
+    streams: Mutex<HashMap<String, CancellationToken>>,
+}
+
+impl QueryStreamManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new stream, cancelling any stream still running under the same id
+    pub async fn start(&self, stream_id: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        if let Some(previous) = self
+            .streams
+            .lock()
+            .await
+            .insert(stream_id.to_string(), token.clone())
+        {
+            previous.cancel();
+        }
+        token
+    }
+
+    /// Cancel a running stream. Returns `false` if it was not running.
+    pub async fn cancel(&self, stream_id: &str) -> bool {
+        match self.streams.lock().await.remove(stream_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget a stream that ended on its own. A stream replaced by a newer one under the
+    /// same id was cancelled by [`QueryStreamManager::start`], so the newer entry is kept.
+    pub async fn finish(&self, stream_id: &str, token: &CancellationToken) {
+        if !token.is_cancelled() {
+            self.streams.lock().await.remove(stream_id);
+        }
+    }
+}
+
+/// Pull rows from `rows` into batches of `batch_size` until the stream ends or `cancel` fires.
+/// Returns the number of rows passed to `on_batch`.
+pub async fn drain_rows<S, R, E>(
+    mut rows: S,
+    to_json: fn(&R) -> Value,
+    batch_size: usize,
+    cancel: &CancellationToken,
+    on_batch: &mut (dyn FnMut(Vec<Value>) + Send),
+) -> Result<u64, String>
+where
+    S: Stream<Item = Result<R, E>> + Unpin,
+    E: std::fmt::Display,
+{
+    let batch_size = batch_size.max(1);
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut delivered: u64 = 0;
+
+    loop {
+        let next = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Ok(delivered),
+            next = rows.next() => next,
+        };
+        match next {
+            Some(Ok(row)) => {
+                batch.push(to_json(&row));
+                if batch.len() == batch_size {
+                    delivered += batch.len() as u64;
+                    on_batch(std::mem::replace(
+                        &mut batch,
+                        Vec::with_capacity(batch_size),
+                    ));
+                }
+            }
+            Some(Err(e)) => return Err(e.to_string()),
+            None => break,
+        }
+    }
+
+    if !batch.is_empty() && !cancel.is_cancelled() {
+        delivered += batch.len() as u64;
+        on_batch(batch);
+    }
+    Ok(delivered)
+}
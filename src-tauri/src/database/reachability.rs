@@ -0,0 +1,152 @@
+//! Short-lived cache of failed connection attempts.
+//!
+//! When a server is down, every command would otherwise wait out the connect timeout and
+//! its retries. After an attempt fails because the server could not be reached, the error
+//! is remembered for a few seconds and further attempts to the same target fail at once
+//! with it. Other failures, such as a wrong password or a TLS mismatch, are not cached, so
+//! fixing the connection settings takes effect on the next attempt. A successful connect,
+//! or an explicit connection test, clears the entry.
+
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Settings key for how long a failed connection attempt is remembered, in seconds.
+/// `0` turns the cache off.
+pub const REACHABILITY_CACHE_KEY: &str = "reachability_cache_secs";
+
+const DEFAULT_CACHE_SECS: u64 = 5;
+const NOT_CONFIGURED: u64 = u64::MAX;
+
+static CACHE_SECS: AtomicU64 = AtomicU64::new(NOT_CONFIGURED);
+
+fn failures() -> &'static Mutex<HashMap<String, (Instant, String)>> {
+    static FAILURES: OnceLock<Mutex<HashMap<String, (Instant, String)>>> = OnceLock::new();
+    FAILURES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// How long failures are remembered
+pub fn cache_duration() -> Duration {
+    match CACHE_SECS.load(Ordering::SeqCst) {
+        NOT_CONFIGURED => Duration::from_secs(DEFAULT_CACHE_SECS),
+        secs => Duration::from_secs(secs),
+    }
+}
+
+/// Apply the reachability cache setting. Other keys are ignored; an empty value restores
+/// the default.
+pub fn apply_setting(key: &str, value: &str) -> Result<(), String> {
+    if key != REACHABILITY_CACHE_KEY {
+        return Ok(());
+    }
+    let value = value.trim();
+    let secs = if value.is_empty() {
+        NOT_CONFIGURED
+    } else {
+        value
+            .parse::<u64>()
+            .ok()
+            .filter(|secs| *secs != NOT_CONFIGURED)
+            .ok_or_else(|| format!("Invalid value for {}: {}", key, value))?
+    };
+    CACHE_SECS.store(secs, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Load the reachability cache setting from the app database
+pub async fn load_from_settings(pool: &SqlitePool) -> Result<(), String> {
+    let value: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(REACHABILITY_CACHE_KEY)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| e.to_string())?;
+    match value {
+        Some(value) => apply_setting(REACHABILITY_CACHE_KEY, &value),
+        None => Ok(()),
+    }
+}
+
+/// The cached error for `target`, if an attempt failed within the cache duration
+pub fn cached_failure(target: &str) -> Option<String> {
+    let mut failures = failures().lock().unwrap_or_else(|e| e.into_inner());
+    let (failed_at, error) = failures.get(target)?;
+    let remaining = cache_duration().checked_sub(failed_at.elapsed());
+    match remaining {
+        Some(remaining) if !remaining.is_zero() => Some(format!(
+            "{} (cached; retrying in {}s)",
+            error,
+            remaining.as_secs().max(1)
+        )),
+        _ => {
+            failures.remove(target);
+            None
+        }
+    }
+}
+
+/// Lower-case fragments of errors raised when a server cannot be reached at all
+const UNREACHABLE_MARKERS: &[&str] = &[
+    "connection refused",
+    "connection reset",
+    "connection aborted",
+    "timed out",
+    "timeout",
+    "host is unreachable",
+    "network is unreachable",
+    "no route to host",
+    "failed to lookup address",
+    "name or service not known",
+    "no such host",
+    "temporary failure in name resolution",
+    "nodename nor servname",
+];
+
+/// Whether `error` says the server could not be reached, as opposed to rejecting the
+/// connection once reached
+pub fn is_unreachable(error: &str) -> bool {
+    let error = error.to_lowercase();
+    UNREACHABLE_MARKERS
+        .iter()
+        .any(|marker| error.contains(marker))
+}
+
+/// Remember that connecting to `target` failed
+pub fn record_failure(target: &str, error: &str) {
+    if cache_duration().is_zero() {
+        return;
+    }
+    failures()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(target.to_string(), (Instant::now(), error.to_string()));
+}
+
+/// Forget any failure recorded for `target`
+pub fn clear(target: &str) {
+    failures()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(target);
+}
+
+/// Run a connection attempt to `target`, failing at once with the cached error if a
+/// recent attempt failed. Success clears the cache; only failures to reach the server
+/// are recorded.
+pub async fn guard<T, Fut>(target: &str, connect: Fut) -> Result<T, String>
+where
+    Fut: Future<Output = Result<T, String>>,
+{
+    if let Some(error) = cached_failure(target) {
+        return Err(error);
+    }
+    let result = connect.await;
+    match &result {
+        Ok(_) => clear(target),
+        Err(e) if is_unreachable(e) => record_failure(target, e),
+        Err(_) => {}
+    }
+    result
+}
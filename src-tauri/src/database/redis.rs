@@ -1,15 +1,32 @@
 use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use redis::AsyncCommands;
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::{DatabaseDriver, RedisConfig};
+use super::retry::RetryPolicy;
+use super::{reachability, timeouts, DatabaseDriver, RedisConfig};
 use crate::db::models::{
-    QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure, TestConnectionResult,
+    DriverCapabilities, QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure,
+    TestConnectionResult,
 };
 use crate::ssh_tunnel::SshTunnel;
 
+/// Maximum number of list, set, sorted set or hash entries fetched eagerly by
+/// `get_key_details`; larger collections are read with the paged accessors
+pub const COLLECTION_PREVIEW_LIMIT: usize = 500;
+
+/// Bytes of a string value returned by `get_key_details`; longer values are cut to this
+/// preview and read in full with `get_full_value`
+pub const STRING_PREVIEW_LIMIT: usize = 64 * 1024;
+
+/// Default `COUNT` hint for `SSCAN`/`HSCAN` pages
+const SCAN_PAGE_COUNT: usize = 500;
+
 /// Redis-specific types for key values
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct RedisKeyInfo {
@@ -29,6 +46,32 @@ pub struct RedisKeyDetails {
     pub encoding: Option<String>,
     pub size: Option<usize>,
     pub length: Option<usize>,
+    /// Structured form of a string value that holds a JSON object or array
+    pub parsed_json: Option<serde_json::Value>,
+    /// Base64 of the raw bytes when a string value is not valid UTF-8
+    pub value_base64: Option<String>,
+    /// Whether `value` holds only the first members of a larger collection, or only the
+    /// start of a string longer than [`STRING_PREVIEW_LIMIT`]
+    pub truncated: bool,
+}
+
+/// Complete value of a string key, as returned by `get_full_value`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RedisStringValue {
+    pub key: String,
+    pub value: serde_json::Value,
+    /// Base64 of the raw bytes when the value is not valid UTF-8
+    pub value_base64: Option<String>,
+    pub length: usize,
+}
+
+/// One page of an `SSCAN` or `HSCAN` over a collection key. `value` is an array of
+/// members for sets and an object of fields for hashes; a `cursor` of 0 ends the scan.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RedisScanPage {
+    pub value: serde_json::Value,
+    pub cursor: u64,
+    pub scan_complete: bool,
 }
 
 /// Result of a Redis pattern search
@@ -41,19 +84,250 @@ pub struct RedisKeyListResponse {
     pub scan_complete: bool,
 }
 
+/// Replication state from the `Replication` section of `INFO`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RedisReplicationStatus {
+    /// `master` or `slave`
+    pub role: String,
+    pub connected_slaves: i64,
+    /// `up` or `down` on a replica; `None` on a master
+    pub master_link_status: Option<String>,
+    pub master_repl_offset: i64,
+    /// Cluster summary when the server runs in cluster mode
+    pub cluster: Option<RedisClusterStatus>,
+}
+
+/// Summary of `CLUSTER INFO` and `CLUSTER NODES`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RedisClusterStatus {
+    /// `ok` or `fail`
+    pub state: String,
+    pub slots_assigned: i64,
+    pub slots_ok: i64,
+    pub known_nodes: i64,
+    /// Number of master nodes serving at least one slot
+    pub size: i64,
+    pub nodes: Vec<RedisClusterNode>,
+}
+
+/// One line of `CLUSTER NODES`
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RedisClusterNode {
+    pub id: String,
+    /// `ip:port`, without the cluster bus port
+    pub address: String,
+    /// e.g. `myself`, `master`, `slave`, `fail?`, `fail`
+    pub flags: Vec<String>,
+    /// Master this node replicates; `None` for masters
+    pub master_id: Option<String>,
+    /// `connected` or `disconnected`
+    pub link_state: String,
+    /// Slot ranges served by the node, e.g. `0-5460`
+    pub slots: Vec<String>,
+}
+
+/// `field:value` pairs of an `INFO` or `CLUSTER INFO` reply, skipping section headers
+pub fn parse_info_fields(info: &str) -> HashMap<String, String> {
+    info.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .map(|(field, value)| (field.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Nodes of a `CLUSTER NODES` reply; malformed lines are skipped
+pub fn parse_cluster_nodes(nodes: &str) -> Vec<RedisClusterNode> {
+    nodes
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() < 8 {
+                return None;
+            }
+            Some(RedisClusterNode {
+                id: parts[0].to_string(),
+                address: parts[1]
+                    .split(['@', ','])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+                flags: parts[2].split(',').map(str::to_string).collect(),
+                master_id: Some(parts[3])
+                    .filter(|master| *master != "-")
+                    .map(str::to_string),
+                link_state: parts[7].to_string(),
+                slots: parts[8..].iter().map(|slot| slot.to_string()).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Parse a Redis string value as JSON when it holds an object or array
+fn parse_json_string(value: &Value) -> Option<Value> {
+    let text = value.as_str()?.trim_start();
+    if !text.starts_with('{') && !text.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str(text).ok()
+}
+
+/// JSON form of a string value; bytes that are not UTF-8 get a size placeholder
+fn string_value_to_json(bytes: Option<&[u8]>) -> Value {
+    match bytes {
+        Some(bytes) => match std::str::from_utf8(bytes) {
+            Ok(s) => json!(s),
+            Err(_) => json!(format!("<binary data: {} bytes>", bytes.len())),
+        },
+        None => json!(null),
+    }
+}
+
+/// JSON form of sorted set members as `[{"member", "score"}, ...]`, in score order
+fn zset_members_to_json(members: Vec<(String, f64)>) -> Value {
+    Value::Array(
+        members
+            .into_iter()
+            .map(|(member, score)| json!({ "member": member, "score": score }))
+            .collect(),
+    )
+}
+
+/// Whether a list, set, sorted set or hash preview is missing entries of the key: the
+/// collection is larger than the preview, or its length could not be read. A scan that
+/// failed part way also leaves the preview short, so this does not rely on the limit.
+fn is_partial_preview(value: &Value, length: Option<usize>) -> bool {
+    let loaded = match value {
+        Value::Array(items) => items.len(),
+        Value::Object(entries) => entries.len(),
+        _ => 0,
+    };
+    length.is_none_or(|l| l > loaded)
+}
+
+/// Read the first `COLLECTION_PREVIEW_LIMIT` entries of a set or hash with `SSCAN` or
+/// `HSCAN`, stopping as soon as enough have been collected
+async fn scan_preview<T: redis::FromRedisValue>(
+    conn: &mut redis::aio::MultiplexedConnection,
+    command: &str,
+    key: &str,
+) -> Vec<T> {
+    let mut items = Vec::new();
+    let mut cursor: u64 = 0;
+    loop {
+        let Ok((next, batch)) = redis::cmd(command)
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(SCAN_PAGE_COUNT)
+            .query_async::<(u64, Vec<T>)>(conn)
+            .await
+        else {
+            break;
+        };
+        items.extend(batch);
+        cursor = next;
+        if cursor == 0 || items.len() >= COLLECTION_PREVIEW_LIMIT {
+            break;
+        }
+    }
+    items.truncate(COLLECTION_PREVIEW_LIMIT);
+    items
+}
+
+/// Read a string key's bytes and its full length in bytes. Values longer than
+/// [`STRING_PREVIEW_LIMIT`] are read only up to the limit, so a huge value is not
+/// transferred just to be displayed.
+async fn read_string_preview(
+    conn: &mut redis::aio::MultiplexedConnection,
+    key: &str,
+) -> (Option<Vec<u8>>, Option<usize>) {
+    let Ok(length) = conn.strlen::<_, usize>(key).await else {
+        return (None, None);
+    };
+    let bytes: Option<Vec<u8>> = if length > STRING_PREVIEW_LIMIT {
+        conn.getrange::<_, Vec<u8>>(key, 0, STRING_PREVIEW_LIMIT as isize - 1)
+            .await
+            .ok()
+            .map(trim_partial_utf8)
+    } else {
+        conn.get(key).await.unwrap_or(None)
+    };
+    (bytes, Some(length))
+}
+
+/// Drop a multi-byte UTF-8 character cut off at the end of a preview, so that text
+/// values stay readable. Bytes that are not UTF-8 anyway are left alone.
+fn trim_partial_utf8(mut bytes: Vec<u8>) -> Vec<u8> {
+    if let Err(e) = std::str::from_utf8(&bytes) {
+        if e.error_len().is_none() {
+            bytes.truncate(e.valid_up_to());
+        }
+    }
+    bytes
+}
+
+/// Base64 of a string value's bytes, only when they are not valid UTF-8
+fn binary_string_base64(bytes: Option<&[u8]>) -> Option<String> {
+    bytes
+        .filter(|b| std::str::from_utf8(b).is_err())
+        .map(|b| BASE64.encode(b))
+}
+
+/// Decode a base64 value sent for a binary string key
+pub fn decode_base64_value(value: &str) -> Result<Vec<u8>, String> {
+    BASE64
+        .decode(value.trim())
+        .map_err(|e| format!("Invalid base64 value: {}", e))
+}
+
 pub struct RedisDriver {
     config: RedisConfig,
     connection: Arc<RwLock<Option<redis::aio::MultiplexedConnection>>>,
+    db: Arc<AtomicI64>,
+    connect_timeout: std::time::Duration,
 }
 
 impl RedisDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: false,
+        supports_foreign_keys: false,
+        supports_row_editing: false,
+        supports_schemas: false,
+        supports_explain: false,
+    };
+
     pub fn new(config: RedisConfig) -> Self {
+        let db = config.db.unwrap_or(0);
         Self {
             config,
             connection: Arc::new(RwLock::new(None)),
+            db: Arc::new(AtomicI64::new(db)),
+            connect_timeout: timeouts::connect_timeout(10),
         }
     }
 
+    /// The logical database currently selected on this driver
+    pub fn current_db(&self) -> i64 {
+        self.db.load(Ordering::SeqCst)
+    }
+
+    /// Switch the cached connection to another logical database.
+    /// Reconnects keep using the selected index.
+    pub async fn select_db(&self, index: i64) -> Result<(), String> {
+        let mut conn = self.get_connection_with_retry().await?;
+
+        redis::cmd("SELECT")
+            .arg(index)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "select_db"))?;
+
+        self.db.store(index, Ordering::SeqCst);
+        Ok(())
+    }
+
     /// Build Redis connection string
     fn build_connection_string(&self) -> String {
         let mut auth = String::new();
@@ -63,7 +337,7 @@ impl RedisDriver {
             }
         }
 
-        let db = self.config.db.unwrap_or(0);
+        let db = self.current_db();
         let scheme = if self.config.tls { "rediss" } else { "redis" };
 
         format!(
@@ -77,16 +351,18 @@ impl RedisDriver {
         let client = redis::Client::open(self.build_connection_string())
             .map_err(|e| format!("Failed to create Redis client: {}", e))?;
 
-        // Use a 10 second timeout for connection
         match tokio::time::timeout(
-            std::time::Duration::from_secs(10),
+            self.connect_timeout,
             client.get_multiplexed_async_connection(),
         )
         .await
         {
             Ok(Ok(conn)) => Ok(conn),
             Ok(Err(e)) => Err(format!("Failed to connect to Redis: {}", e)),
-            Err(_) => Err("Connection timed out after 10 seconds".to_string()),
+            Err(_) => Err(format!(
+                "Connection timed out after {} seconds",
+                self.connect_timeout.as_secs()
+            )),
         }
     }
 
@@ -118,16 +394,27 @@ impl RedisDriver {
         Ok(())
     }
 
+    /// Key for this server in the failed-connection cache
+    fn reachability_target(&self) -> String {
+        format!(
+            "redis://{}:{}?tls={}",
+            self.config.host, self.config.port, self.config.tls
+        )
+    }
+
     /// Get connection with retry on failure
     async fn get_connection_with_retry(&self) -> Result<redis::aio::MultiplexedConnection, String> {
-        match self.get_connection().await {
-            Ok(conn) => Ok(conn),
-            Err(e) => {
-                println!("[Redis] Connection failed: {}, resetting...", e);
+        let target = self.reachability_target();
+        let policy = RetryPolicy::current();
+        let retry = policy.run(|attempt| async move {
+            if attempt > 1 {
                 self.reset_connection().await?;
-                self.get_connection().await
             }
-        }
+            self.get_connection().await.inspect_err(|e| {
+                println!("[Redis] Connection failed (attempt {}): {}", attempt, e);
+            })
+        });
+        reachability::guard(&target, retry).await
     }
 
     /// Check if error is a connection error and handle reset if needed
@@ -157,7 +444,7 @@ impl RedisDriver {
             }
         }
 
-        let db = self.config.db.unwrap_or(0);
+        let db = self.current_db();
         let scheme = if self.config.tls { "rediss" } else { "redis" };
 
         format!("{}://{}{}:{}/{}", scheme, auth, host, port, db)
@@ -220,6 +507,63 @@ impl RedisDriver {
         }
     }
 
+    /// Split a command reply into grid rows.
+    ///
+    /// Field/value replies (`CONFIG GET`, `HGETALL`, RESP3 maps) become one row per pair,
+    /// other arrays one row per element and `CLIENT LIST` one row per client.
+    /// Scalar replies stay a single row.
+    fn reply_to_rows(parts: &[&str], value: &redis::Value) -> Vec<Value> {
+        let command = parts.first().map(|p| p.to_uppercase()).unwrap_or_default();
+        let subcommand = parts.get(1).map(|p| p.to_uppercase());
+        let is_pair_reply = matches!(
+            (command.as_str(), subcommand.as_deref()),
+            ("CONFIG", Some("GET")) | ("HGETALL", _)
+        );
+        let is_client_list = command == "CLIENT" && subcommand.as_deref() == Some("LIST");
+
+        match value {
+            redis::Value::Map(map) => map
+                .iter()
+                .map(|(k, v)| {
+                    json!({
+                        "field": Self::redis_value_to_json(k, "unknown"),
+                        "value": Self::redis_value_to_json(v, "unknown"),
+                    })
+                })
+                .collect(),
+            redis::Value::Array(items) | redis::Value::Set(items)
+                if is_pair_reply && items.len() % 2 == 0 =>
+            {
+                items
+                    .chunks(2)
+                    .map(|pair| {
+                        json!({
+                            "field": Self::redis_value_to_json(&pair[0], "unknown"),
+                            "value": Self::redis_value_to_json(&pair[1], "unknown"),
+                        })
+                    })
+                    .collect()
+            }
+            redis::Value::Array(items) | redis::Value::Set(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| json!({"index": i, "value": Self::redis_value_to_json(v, "unknown")}))
+                .collect(),
+            redis::Value::BulkString(bytes) if is_client_list => String::from_utf8_lossy(bytes)
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| {
+                    let fields = line
+                        .split_whitespace()
+                        .filter_map(|field| field.split_once('='))
+                        .map(|(k, v)| (k.to_string(), json!(v)));
+                    Value::Object(serde_json::Map::from_iter(fields))
+                })
+                .collect(),
+            _ => vec![Self::redis_value_to_json(value, "unknown")],
+        }
+    }
+
     /// Get the size/length of a Redis value
     #[allow(dead_code)]
     fn get_value_length(value: &redis::Value, _key_type: &str) -> Option<usize> {
@@ -235,7 +579,22 @@ impl RedisDriver {
 
 #[async_trait]
 impl DatabaseDriver for RedisDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
+    async fn ping(&self) -> Result<(), String> {
+        let mut conn = self.get_connection_with_retry().await?;
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map(|_| ())
+            .map_err(|e| format!("Redis PING failed: {}", e))
+    }
+
     async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+        // An explicit test always tries again, even if a recent attempt failed
+        reachability::clear(&self.reachability_target());
         match self.get_connection_with_retry().await {
             Ok(mut conn) => match redis::cmd("PING").query_async::<String>(&mut conn).await {
                 Ok(_) => Ok(TestConnectionResult {
@@ -260,6 +619,8 @@ impl DatabaseDriver for RedisDriver {
             schema: "redis".to_string(),
             name: "keys".to_string(),
             table_type: "keyspace".to_string(),
+            comment: None,
+            engine_full: None,
         }])
     }
 
@@ -292,6 +653,9 @@ impl DatabaseDriver for RedisDriver {
             columns: vec![],
             indexes: vec![],
             foreign_keys: vec![],
+            check_constraints: vec![],
+            unique_constraints: vec![],
+            warnings: vec![],
         })
     }
 
@@ -315,6 +679,7 @@ impl DatabaseDriver for RedisDriver {
                         row_count: 1,
                         error: None,
                         time_taken_ms: Some(start_time.elapsed().as_millis()),
+                        ..Default::default()
                     });
                 }
                 Err(e) => {
@@ -324,6 +689,7 @@ impl DatabaseDriver for RedisDriver {
                         row_count: 0,
                         error: Some(error_msg),
                         time_taken_ms: Some(start_time.elapsed().as_millis()),
+                        ..Default::default()
                     });
                 }
             }
@@ -337,6 +703,7 @@ impl DatabaseDriver for RedisDriver {
                 row_count: 0,
                 error: Some("Empty query".to_string()),
                 time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
             });
         }
 
@@ -347,12 +714,14 @@ impl DatabaseDriver for RedisDriver {
 
         match cmd.query_async(&mut conn).await {
             Ok(value) => {
-                let json_value = Self::redis_value_to_json(&value, "unknown");
+                let data = Self::reply_to_rows(&parts, &value);
+                let row_count = data.len() as i64;
                 Ok(QueryResult {
-                    data: vec![json_value],
-                    row_count: 1,
+                    data,
+                    row_count,
                     error: None,
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
                 })
             }
             Err(e) => {
@@ -362,6 +731,7 @@ impl DatabaseDriver for RedisDriver {
                     row_count: 0,
                     error: Some(error_msg),
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
                 })
             }
         }
@@ -502,27 +872,42 @@ impl RedisDriver {
         let ttl: i64 = conn.ttl(key).await.unwrap_or(-1);
 
         // Get value based on type
+        // Read strings as bytes so values that are not UTF-8 survive
+        let (string_bytes, string_length) = if key_type == "string" {
+            read_string_preview(&mut conn, key).await
+        } else {
+            (None, None)
+        };
+
         let value = match key_type.as_str() {
-            "string" => {
-                let val: Option<String> = conn.get(key).await.unwrap_or(None);
-                json!(val)
-            }
+            "string" => string_value_to_json(string_bytes.as_deref()),
             "list" => {
-                let val: Vec<String> = conn.lrange(key, 0, -1).await.unwrap_or_default();
+                let val: Vec<String> = conn
+                    .lrange(key, 0, COLLECTION_PREVIEW_LIMIT as isize - 1)
+                    .await
+                    .unwrap_or_default();
                 json!(val)
             }
             "set" => {
-                let val: Vec<String> = conn.smembers(key).await.unwrap_or_default();
+                let mut val: Vec<String> = scan_preview(&mut conn, "SSCAN", key).await;
+                // SSCAN may return a member more than once
+                let mut seen = std::collections::HashSet::new();
+                val.retain(|member| seen.insert(member.clone()));
                 json!(val)
             }
             "zset" => {
-                let val: Vec<(String, f64)> =
-                    conn.zrange_withscores(key, 0, -1).await.unwrap_or_default();
-                json!(val)
+                let val: Vec<(String, f64)> = conn
+                    .zrange_withscores(key, 0, COLLECTION_PREVIEW_LIMIT as isize - 1)
+                    .await
+                    .unwrap_or_default();
+                zset_members_to_json(val)
             }
             "hash" => {
                 let val: std::collections::HashMap<String, String> =
-                    conn.hgetall(key).await.unwrap_or_default();
+                    scan_preview::<(String, String)>(&mut conn, "HSCAN", key)
+                        .await
+                        .into_iter()
+                        .collect();
                 json!(val)
             }
             "stream" => {
@@ -543,10 +928,7 @@ impl RedisDriver {
 
         // Get length/size based on type
         let length = match key_type.as_str() {
-            "string" => {
-                let val: Option<String> = conn.get(key).await.unwrap_or(None);
-                val.map(|v| v.len())
-            }
+            "string" => string_length,
             "list" => conn.llen(key).await.ok(),
             "set" => conn.scard(key).await.ok().map(|c: usize| c),
             "zset" => conn.zcard(key).await.ok().map(|c: usize| c),
@@ -562,10 +944,19 @@ impl RedisDriver {
             .await
             .ok();
 
+        let truncated = match key_type.as_str() {
+            "string" => length.is_some_and(|l| l > STRING_PREVIEW_LIMIT),
+            "list" | "set" | "zset" | "hash" => is_partial_preview(&value, length),
+            _ => false,
+        };
+
         Ok(RedisKeyDetails {
             key: key.to_string(),
             key_type,
             ttl,
+            parsed_json: parse_json_string(&value),
+            value_base64: binary_string_base64(string_bytes.as_deref()),
+            truncated,
             value,
             encoding,
             size,
@@ -587,6 +978,16 @@ impl RedisDriver {
 
     /// Set a key value (for string types)
     pub async fn set_key(&self, key: &str, value: &str, ttl: Option<i64>) -> Result<(), String> {
+        self.set_key_bytes(key, value.as_bytes(), ttl).await
+    }
+
+    /// Set a string key to raw bytes, which need not be valid UTF-8
+    pub async fn set_key_bytes(
+        &self,
+        key: &str,
+        value: &[u8],
+        ttl: Option<i64>,
+    ) -> Result<(), String> {
         let mut conn = self.get_connection_with_retry().await?;
 
         let result: Result<String, redis::RedisError> = if let Some(expiry) = ttl {
@@ -601,6 +1002,13 @@ impl RedisDriver {
         }
     }
 
+    /// Serialize a JSON value and store it as a string key
+    pub async fn set_json(&self, key: &str, value: &Value, ttl: Option<i64>) -> Result<(), String> {
+        let serialized =
+            serde_json::to_string(value).map_err(|e| format!("Failed to serialize JSON: {}", e))?;
+        self.set_key(key, &serialized, ttl).await
+    }
+
     /// Set a list key value.
     ///
     /// This method creates or replaces a Redis list key with the provided values.
@@ -752,6 +1160,153 @@ impl RedisDriver {
         Ok(())
     }
 
+    /// Complete value of a string key, for values `get_key_details` only previews
+    pub async fn get_full_value(&self, key: &str) -> Result<RedisStringValue, String> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let key_type: String = conn
+            .key_type(key)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "get_full_value (key_type)"))?;
+        match key_type.as_str() {
+            "string" => {}
+            "none" => return Err(format!("Key '{}' does not exist", key)),
+            other => return Err(format!("Key '{}' holds a {}, not a string", key, other)),
+        }
+
+        let bytes: Vec<u8> = conn
+            .get(key)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "get_full_value"))?;
+        Ok(RedisStringValue {
+            key: key.to_string(),
+            value: string_value_to_json(Some(&bytes)),
+            value_base64: binary_string_base64(Some(&bytes)),
+            length: bytes.len(),
+        })
+    }
+
+    /// Elements of a list from `start` to `stop` inclusive (negative indexes count from
+    /// the end)
+    pub async fn lrange(
+        &self,
+        key: &str,
+        start: isize,
+        stop: isize,
+    ) -> Result<Vec<String>, String> {
+        let mut conn = self.get_connection_with_retry().await?;
+        conn.lrange(key, start, stop)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "lrange"))
+    }
+
+    /// One `SSCAN` page of set members starting at `cursor`. As with any Redis scan,
+    /// a member may appear in more than one page.
+    pub async fn sscan(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: Option<usize>,
+    ) -> Result<RedisScanPage, String> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let (cursor, members): (u64, Vec<String>) = redis::cmd("SSCAN")
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(count.unwrap_or(SCAN_PAGE_COUNT))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "sscan"))?;
+
+        Ok(RedisScanPage {
+            value: json!(members),
+            cursor,
+            scan_complete: cursor == 0,
+        })
+    }
+
+    /// One `HSCAN` page of hash fields starting at `cursor`
+    pub async fn hscan(
+        &self,
+        key: &str,
+        cursor: u64,
+        count: Option<usize>,
+    ) -> Result<RedisScanPage, String> {
+        let mut conn = self.get_connection_with_retry().await?;
+        let (cursor, fields): (u64, Vec<(String, String)>) = redis::cmd("HSCAN")
+            .arg(key)
+            .arg(cursor)
+            .arg("COUNT")
+            .arg(count.unwrap_or(SCAN_PAGE_COUNT))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "hscan"))?;
+
+        Ok(RedisScanPage {
+            value: json!(fields
+                .into_iter()
+                .collect::<std::collections::HashMap<String, String>>()),
+            cursor,
+            scan_complete: cursor == 0,
+        })
+    }
+
+    /// Members of a sorted set by rank, from `start` to `stop` inclusive (negative
+    /// indexes count from the end). With `withscores` the result is
+    /// `[{"member", "score"}, ...]`, otherwise a plain array of members.
+    pub async fn zrange(
+        &self,
+        key: &str,
+        start: isize,
+        stop: isize,
+        withscores: bool,
+    ) -> Result<Value, String> {
+        let mut conn = self.get_connection_with_retry().await?;
+
+        if withscores {
+            let members: Vec<(String, f64)> = conn
+                .zrange_withscores(key, start, stop)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, "zrange"))?;
+            Ok(zset_members_to_json(members))
+        } else {
+            let members: Vec<String> = conn
+                .zrange(key, start, stop)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, "zrange"))?;
+            Ok(json!(members))
+        }
+    }
+
+    /// Members of a sorted set with scores between `min` and `max`, as
+    /// `[{"member", "score"}, ...]`.
+    ///
+    /// Bounds use Redis syntax, so `-inf`, `+inf` and exclusive bounds such as `(1.5`
+    /// are accepted. `limit` caps the number of members returned.
+    pub async fn zrangebyscore(
+        &self,
+        key: &str,
+        min: &str,
+        max: &str,
+        limit: Option<usize>,
+    ) -> Result<Value, String> {
+        if min.trim().is_empty() || max.trim().is_empty() {
+            return Err("Score bounds must not be empty".to_string());
+        }
+
+        let mut conn = self.get_connection_with_retry().await?;
+
+        let members: Vec<(String, f64)> = match limit {
+            Some(count) => {
+                conn.zrangebyscore_limit_withscores(key, min, max, 0, count as isize)
+                    .await
+            }
+            None => conn.zrangebyscore_withscores(key, min, max).await,
+        }
+        .map_err(|e| self.handle_connection_error(&e, "zrangebyscore"))?;
+
+        Ok(zset_members_to_json(members))
+    }
+
     /// Set a sorted set key value.
     ///
     /// This method creates or replaces a Redis sorted set (zset) key with the provided
@@ -825,6 +1380,193 @@ impl RedisDriver {
 
         Ok(())
     }
+
+    /// Apply a TTL to every key matching `pattern`, returning the number of keys updated.
+    ///
+    /// Keys are found with `SCAN` and expired in one pipeline per batch. Patterns made only
+    /// of wildcards (e.g. `*`) would touch the whole database and require `confirm`.
+    pub async fn expire_keys(
+        &self,
+        pattern: &str,
+        ttl_seconds: i64,
+        confirm: bool,
+    ) -> Result<u64, String> {
+        if ttl_seconds <= 0 {
+            return Err("TTL must be a positive number of seconds".to_string());
+        }
+        Self::check_bulk_pattern(pattern, confirm)?;
+
+        let mut conn = self.get_connection_with_retry().await?;
+        let mut cursor: u64 = 0;
+        let mut updated: u64 = 0;
+
+        loop {
+            let (new_cursor, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, "expire_keys (scan)"))?;
+
+            if !batch.is_empty() {
+                let mut pipe = redis::pipe();
+                for key in &batch {
+                    pipe.expire(key, ttl_seconds);
+                }
+                let results: Vec<i64> = pipe
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(|e| self.handle_connection_error(&e, "expire_keys"))?;
+                updated += results.iter().filter(|r| **r == 1).count() as u64;
+            }
+
+            cursor = new_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+
+        Ok(updated)
+    }
+
+    /// Delete every key matching `pattern`, returning the number of keys removed.
+    ///
+    /// Uses `UNLINK` so large values are freed in the background, falling back to `DEL`
+    /// on servers older than Redis 4. Wildcard-only patterns require `confirm`.
+    pub async fn delete_keys_by_pattern(
+        &self,
+        pattern: &str,
+        confirm: bool,
+    ) -> Result<u64, String> {
+        Self::check_bulk_pattern(pattern, confirm)?;
+
+        let mut conn = self.get_connection_with_retry().await?;
+        let mut cursor: u64 = 0;
+        let mut deleted: u64 = 0;
+        let mut use_unlink = true;
+
+        loop {
+            let (new_cursor, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(500)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, "delete_keys_by_pattern (scan)"))?;
+
+            if !batch.is_empty() {
+                if use_unlink {
+                    match redis::cmd("UNLINK")
+                        .arg(&batch)
+                        .query_async::<u64>(&mut conn)
+                        .await
+                    {
+                        Ok(count) => deleted += count,
+                        Err(e) if e.kind() == redis::ErrorKind::ResponseError => {
+                            use_unlink = false;
+                        }
+                        Err(e) => {
+                            return Err(self.handle_connection_error(&e, "delete_keys_by_pattern"))
+                        }
+                    }
+                }
+                if !use_unlink {
+                    deleted += redis::cmd("DEL")
+                        .arg(&batch)
+                        .query_async::<u64>(&mut conn)
+                        .await
+                        .map_err(|e| self.handle_connection_error(&e, "delete_keys_by_pattern"))?;
+                }
+            }
+
+            cursor = new_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+
+        Ok(deleted)
+    }
+
+    /// Validate a pattern for bulk operations; patterns made only of wildcards
+    /// touch the whole database and need explicit confirmation
+    fn check_bulk_pattern(pattern: &str, confirm: bool) -> Result<(), String> {
+        if pattern.trim().is_empty() {
+            return Err("Pattern must not be empty".to_string());
+        }
+        if !confirm && pattern.chars().all(|c| c == '*' || c == '?') {
+            return Err(format!(
+                "Pattern '{}' matches every key; confirmation is required",
+                pattern
+            ));
+        }
+        Ok(())
+    }
+
+    /// Role, replica links and replication offset from `INFO replication`, plus a
+    /// cluster summary when cluster mode is enabled
+    pub async fn replication_status(&self) -> Result<RedisReplicationStatus, String> {
+        let mut conn = self.get_connection_with_retry().await?;
+
+        let replication: String = redis::cmd("INFO")
+            .arg("replication")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "replication_status"))?;
+        let replication = parse_info_fields(&replication);
+        let number = |fields: &HashMap<String, String>, field: &str| {
+            fields
+                .get(field)
+                .and_then(|value| value.parse::<i64>().ok())
+                .unwrap_or(0)
+        };
+
+        let cluster_info: String = redis::cmd("INFO")
+            .arg("cluster")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| self.handle_connection_error(&e, "replication_status (cluster)"))?;
+        let cluster_enabled = parse_info_fields(&cluster_info)
+            .get("cluster_enabled")
+            .is_some_and(|enabled| enabled == "1");
+
+        let cluster = if cluster_enabled {
+            let summary: String = redis::cmd("CLUSTER")
+                .arg("INFO")
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, "CLUSTER INFO"))?;
+            let nodes: String = redis::cmd("CLUSTER")
+                .arg("NODES")
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| self.handle_connection_error(&e, "CLUSTER NODES"))?;
+            let summary = parse_info_fields(&summary);
+            Some(RedisClusterStatus {
+                state: summary.get("cluster_state").cloned().unwrap_or_default(),
+                slots_assigned: number(&summary, "cluster_slots_assigned"),
+                slots_ok: number(&summary, "cluster_slots_ok"),
+                known_nodes: number(&summary, "cluster_known_nodes"),
+                size: number(&summary, "cluster_size"),
+                nodes: parse_cluster_nodes(&nodes),
+            })
+        } else {
+            None
+        };
+
+        Ok(RedisReplicationStatus {
+            role: replication.get("role").cloned().unwrap_or_default(),
+            connected_slaves: number(&replication, "connected_slaves"),
+            master_link_status: replication.get("master_link_status").cloned(),
+            master_repl_offset: number(&replication, "master_repl_offset"),
+            cluster,
+        })
+    }
 }
 
 /// Redis driver with SSH tunnel support
@@ -838,6 +1580,7 @@ impl RedisDriver {
         ssh_password: Option<&str>,
         ssh_key_path: Option<&str>,
         ssh_use_key: bool,
+        ssh_use_agent: bool,
     ) -> Result<(Self, SshTunnel), String> {
         let driver = Self::new(config.clone());
 
@@ -864,7 +1607,11 @@ impl RedisDriver {
             None
         };
 
-        let password_opt = if !ssh_use_key { ssh_password } else { None };
+        let password_opt = if !ssh_use_key && !ssh_use_agent {
+            ssh_password
+        } else {
+            None
+        };
 
         let tunnel = SshTunnel::new(
             ssh_host,
@@ -872,6 +1619,7 @@ impl RedisDriver {
             ssh_user,
             password_opt,
             key_path.as_deref(),
+            ssh_use_agent,
             &config.host,
             config.port as u16,
         )
@@ -1001,27 +1749,42 @@ impl RedisDriver {
         let key_type: String = conn.key_type(key).await.map_err(|e| e.to_string())?;
         let ttl: i64 = conn.ttl(key).await.unwrap_or(-1);
 
+        // Read strings as bytes so values that are not UTF-8 survive
+        let (string_bytes, string_length) = if key_type == "string" {
+            read_string_preview(&mut conn, key).await
+        } else {
+            (None, None)
+        };
+
         let value = match key_type.as_str() {
-            "string" => {
-                let val: Option<String> = conn.get(key).await.unwrap_or(None);
-                json!(val)
-            }
+            "string" => string_value_to_json(string_bytes.as_deref()),
             "list" => {
-                let val: Vec<String> = conn.lrange(key, 0, -1).await.unwrap_or_default();
+                let val: Vec<String> = conn
+                    .lrange(key, 0, COLLECTION_PREVIEW_LIMIT as isize - 1)
+                    .await
+                    .unwrap_or_default();
                 json!(val)
             }
             "set" => {
-                let val: Vec<String> = conn.smembers(key).await.unwrap_or_default();
+                let mut val: Vec<String> = scan_preview(&mut conn, "SSCAN", key).await;
+                // SSCAN may return a member more than once
+                let mut seen = std::collections::HashSet::new();
+                val.retain(|member| seen.insert(member.clone()));
                 json!(val)
             }
             "zset" => {
-                let val: Vec<(String, f64)> =
-                    conn.zrange_withscores(key, 0, -1).await.unwrap_or_default();
-                json!(val)
+                let val: Vec<(String, f64)> = conn
+                    .zrange_withscores(key, 0, COLLECTION_PREVIEW_LIMIT as isize - 1)
+                    .await
+                    .unwrap_or_default();
+                zset_members_to_json(val)
             }
             "hash" => {
                 let val: std::collections::HashMap<String, String> =
-                    conn.hgetall(key).await.unwrap_or_default();
+                    scan_preview::<(String, String)>(&mut conn, "HSCAN", key)
+                        .await
+                        .into_iter()
+                        .collect();
                 json!(val)
             }
             _ => json!(null),
@@ -1036,10 +1799,7 @@ impl RedisDriver {
             .map(|s| s as usize);
 
         let length = match key_type.as_str() {
-            "string" => {
-                let val: Option<String> = conn.get(key).await.unwrap_or(None);
-                val.map(|v| v.len())
-            }
+            "string" => string_length,
             "list" => conn.llen(key).await.ok(),
             "set" => conn.scard(key).await.ok().map(|c: usize| c),
             "zset" => conn.zcard(key).await.ok().map(|c: usize| c),
@@ -1054,10 +1814,19 @@ impl RedisDriver {
             .await
             .ok();
 
+        let truncated = match key_type.as_str() {
+            "string" => length.is_some_and(|l| l > STRING_PREVIEW_LIMIT),
+            "list" | "set" | "zset" | "hash" => is_partial_preview(&value, length),
+            _ => false,
+        };
+
         Ok(RedisKeyDetails {
             key: key.to_string(),
             key_type,
             ttl,
+            parsed_json: parse_json_string(&value),
+            value_base64: binary_string_base64(string_bytes.as_deref()),
+            truncated,
             value,
             encoding,
             size,
@@ -0,0 +1,162 @@
+//! Retry policy for re-establishing driver connections.
+//!
+//! Values come from the app settings table, like the timeouts. Unset values keep the
+//! defaults: two attempts, 200ms apart, doubling on each further attempt.
+
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Settings key for the total number of connection attempts
+pub const RETRY_MAX_ATTEMPTS_KEY: &str = "retry_max_attempts";
+/// Settings key for the delay before the first retry, in milliseconds
+pub const RETRY_BASE_DELAY_KEY: &str = "retry_base_delay_ms";
+/// Settings key for the backoff strategy (`fixed` or `exponential`)
+pub const RETRY_BACKOFF_KEY: &str = "retry_backoff";
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 2;
+const DEFAULT_BASE_DELAY_MS: u64 = 200;
+/// Upper bound on a single delay so exponential backoff cannot stall a command
+const MAX_DELAY_MS: u64 = 30_000;
+
+// 0 means "not configured"
+static MAX_ATTEMPTS: AtomicU64 = AtomicU64::new(0);
+static BASE_DELAY_MS: AtomicU64 = AtomicU64::new(0);
+// 0 = default (exponential), 1 = fixed, 2 = exponential
+static BACKOFF: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backoff {
+    /// Wait `base_delay_ms` between every attempt
+    Fixed,
+    /// Double the delay after each failed retry
+    Exponential,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first one
+    pub max_attempts: u32,
+    pub base_delay_ms: u64,
+    pub backoff: Backoff,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay_ms: DEFAULT_BASE_DELAY_MS,
+            backoff: Backoff::Exponential,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The policy configured in settings
+    pub fn current() -> Self {
+        let defaults = Self::default();
+        let max_attempts = match MAX_ATTEMPTS.load(Ordering::SeqCst) {
+            0 => defaults.max_attempts,
+            n => n.min(u32::MAX as u64) as u32,
+        };
+        let base_delay_ms = match BASE_DELAY_MS.load(Ordering::SeqCst) {
+            0 => defaults.base_delay_ms,
+            ms => ms,
+        };
+        let backoff = match BACKOFF.load(Ordering::SeqCst) {
+            1 => Backoff::Fixed,
+            2 => Backoff::Exponential,
+            _ => defaults.backoff,
+        };
+        Self {
+            max_attempts,
+            base_delay_ms,
+            backoff,
+        }
+    }
+
+    /// Delay before attempt number `attempt` (1-based; the first attempt has none)
+    pub fn delay_before(&self, attempt: u32) -> Duration {
+        if attempt <= 1 {
+            return Duration::ZERO;
+        }
+        let ms = match self.backoff {
+            Backoff::Fixed => self.base_delay_ms,
+            Backoff::Exponential => self
+                .base_delay_ms
+                .saturating_mul(1u64 << (attempt - 2).min(32)),
+        };
+        Duration::from_millis(ms.min(MAX_DELAY_MS))
+    }
+
+    /// Run `op` until it succeeds or the attempts run out, sleeping between attempts.
+    /// `op` receives the 1-based attempt number; the last error is returned on failure.
+    pub async fn run<T, F, Fut>(&self, mut op: F) -> Result<T, String>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T, String>>,
+    {
+        let max_attempts = self.max_attempts.max(1);
+        let mut attempt = 1;
+        loop {
+            tokio::time::sleep(self.delay_before(attempt)).await;
+            match op(attempt).await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt >= max_attempts => return Err(e),
+                Err(_) => attempt += 1,
+            }
+        }
+    }
+}
+
+/// Apply a retry setting. Keys that are not retry settings are ignored;
+/// an empty value resets the setting to its default.
+pub fn apply_setting(key: &str, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    match key {
+        RETRY_MAX_ATTEMPTS_KEY | RETRY_BASE_DELAY_KEY => {
+            let parsed = if value.is_empty() {
+                0
+            } else {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid value for {}: {}", key, value))?
+            };
+            let slot = if key == RETRY_MAX_ATTEMPTS_KEY {
+                &MAX_ATTEMPTS
+            } else {
+                &BASE_DELAY_MS
+            };
+            slot.store(parsed, Ordering::SeqCst);
+        }
+        RETRY_BACKOFF_KEY => {
+            let code = match value.to_lowercase().as_str() {
+                "" => 0,
+                "fixed" => 1,
+                "exponential" => 2,
+                _ => return Err(format!("Invalid value for {}: {}", key, value)),
+            };
+            BACKOFF.store(code, Ordering::SeqCst);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Load all retry settings from the app database
+pub async fn load_from_settings(pool: &SqlitePool) -> Result<(), String> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT key, value FROM settings WHERE key IN (?, ?, ?)")
+            .bind(RETRY_MAX_ATTEMPTS_KEY)
+            .bind(RETRY_BASE_DELAY_KEY)
+            .bind(RETRY_BACKOFF_KEY)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    for (key, value) in rows {
+        apply_setting(&key, &value)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//! Running multi-statement scripts one statement at a time.
+
+use serde::Serialize;
+
+use super::{display, timeouts, DatabaseDriver};
+use crate::db::models::QueryResult;
+
+/// Outcome of one statement of a script
+#[derive(Debug, Clone, Serialize)]
+pub struct StatementResult {
+    pub statement: String,
+    #[serde(flatten)]
+    pub result: QueryResult,
+    /// Whether `data` was cut to the `max_result_rows` setting
+    pub truncated: bool,
+}
+
+/// Split a script into statements on `;`, ignoring semicolons inside quotes, comments and
+/// Postgres dollar-quoted bodies. Empty and comment-only statements are dropped.
+pub fn split_statements(script: &str) -> Vec<String> {
+    let bytes = script.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut has_code = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+                has_code = true;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'$' => {
+                if let Some(tag_len) = dollar_quote_tag_len(&bytes[i..]) {
+                    let tag = &bytes[i..i + tag_len];
+                    i += tag_len;
+                    while i < bytes.len() && !bytes[i..].starts_with(tag) {
+                        i += 1;
+                    }
+                    i += tag_len - 1;
+                }
+                has_code = true;
+            }
+            b';' => {
+                if has_code {
+                    statements.push(script[start..i].trim().to_string());
+                }
+                start = i + 1;
+                has_code = false;
+            }
+            b if !b.is_ascii_whitespace() => has_code = true,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if has_code {
+        statements.push(script[start..].trim().to_string());
+    }
+    statements
+}
+
+/// Length of the `$tag$` opening a dollar-quoted string at the start of `rest`, or `None`
+/// when the `$` starts something else, such as a `$1` parameter
+pub(crate) fn dollar_quote_tag_len(rest: &[u8]) -> Option<usize> {
+    let close = rest[1..].iter().position(|&b| b == b'$')? + 1;
+    let tag = &rest[1..close];
+    let valid = !tag.first().is_some_and(u8::is_ascii_digit)
+        && tag.iter().all(|&b| b.is_ascii_alphanumeric() || b == b'_');
+    valid.then_some(close + 1)
+}
+
+/// Run statements in order, stopping after the first one that fails. Each result is cut
+/// to the `max_result_rows` setting so one large `SELECT` cannot bloat the response.
+pub async fn run_statements(
+    driver: &dyn DatabaseDriver,
+    statements: Vec<String>,
+) -> Result<Vec<StatementResult>, String> {
+    let mut results = Vec::with_capacity(statements.len());
+    for statement in statements {
+        let mut result =
+            timeouts::with_timeout(timeouts::query_timeout(), driver.execute_query(&statement))
+                .await?;
+        let truncated = display::cap_result_rows(&mut result);
+        let failed = result.error.is_some();
+        results.push(StatementResult {
+            statement,
+            result,
+            truncated,
+        });
+        if failed {
+            break;
+        }
+    }
+    Ok(results)
+}
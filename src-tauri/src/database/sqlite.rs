@@ -1,40 +1,248 @@
 use async_trait::async_trait;
 use serde_json::{json, Value};
-use sqlx::sqlite::SqlitePoolOptions;
-use sqlx::{Column, Row, TypeInfo};
-
-use super::{DatabaseDriver, SqliteConfig};
+use sqlx::query::Query;
+use sqlx::sqlite::{SqliteArguments, SqlitePoolOptions};
+use sqlx::{Column, Either, Executor, Row, Sqlite, Statement, TypeInfo};
+
+use super::query_stream::drain_rows;
+use super::{
+    check_param_count, like_contains_pattern, page_offset, session_setup_sql, DatabaseDriver,
+    SqliteConfig, TxHandle,
+};
 use crate::database::queries::sqlite::{
     COLUMNS_QUERY, FOREIGN_KEYS_QUERY, INDEXES_QUERY, TABLES_QUERY,
 };
 use crate::db::models::{
-    ColumnInfo, ForeignKeyInfo, IndexInfo, QueryResult, SchemaOverview, TableDataResponse,
-    TableInfo, TableStructure, TableWithStructure, TestConnectionResult,
+    CheckConstraint, ColumnInfo, DatabaseInfo, DriverCapabilities, ForeignKeyInfo, IndexInfo,
+    NamespaceOverview, QueryResult, ResultColumn, SchemaOverview, TableDataResponse, TableInfo,
+    TableStructure, TableWithStructure, TestConnectionResult,
 };
 use std::collections::HashMap;
+use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
+
+/// File path that selects a throwaway in-memory database
+pub const IN_MEMORY_PATH: &str = ":memory:";
 
 pub struct SqliteDriver {
     config: SqliteConfig,
+    /// Kept open for in-memory databases, which vanish when their last connection closes
+    memory_pool: OnceCell<sqlx::SqlitePool>,
 }
 
 impl SqliteDriver {
+    /// What this driver supports, independent of any connection
+    pub const CAPABILITIES: DriverCapabilities = DriverCapabilities {
+        supports_transactions: true,
+        supports_foreign_keys: true,
+        supports_row_editing: true,
+        supports_schemas: false,
+        supports_explain: true,
+    };
+
     pub fn new(config: SqliteConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            memory_pool: OnceCell::new(),
+        }
+    }
+
+    /// Whether this driver points at an in-memory database
+    pub fn is_in_memory(&self) -> bool {
+        self.config.file_path == IN_MEMORY_PATH
     }
 
     fn connection_string(&self) -> String {
         format!("sqlite:{}?mode=rwc", self.config.file_path)
     }
 
+    /// Pool options that run the session setup statements on every opened connection
+    fn pool_options(&self) -> SqlitePoolOptions {
+        let options = SqlitePoolOptions::new();
+        match session_setup_sql(&self.config.session_setup_sql) {
+            Some(setup) => options.after_connect(move |conn, _meta| {
+                let setup = setup.clone();
+                Box::pin(async move { conn.execute(setup.as_str()).await.map(|_| ()) })
+            }),
+            None => options,
+        }
+    }
+
     async fn get_pool(&self) -> Result<sqlx::SqlitePool, String> {
+        if self.is_in_memory() {
+            return self
+                .memory_pool
+                .get_or_try_init(|| async {
+                    self.pool_options()
+                        .max_connections(1)
+                        .min_connections(1)
+                        .idle_timeout(None)
+                        .max_lifetime(None)
+                        .connect("sqlite::memory:")
+                        .await
+                        .map_err(|e| e.to_string())
+                })
+                .await
+                .cloned();
+        }
+
         let conn_str = self.connection_string();
-        SqlitePoolOptions::new()
+        self.pool_options()
             .max_connections(1)
             .connect(&conn_str)
             .await
             .map_err(|e| e.to_string())
     }
 
+    /// Close a pool after use, keeping the shared in-memory pool alive
+    async fn release_pool(&self, pool: sqlx::SqlitePool) {
+        if !self.is_in_memory() {
+            pool.close().await;
+        }
+    }
+
+    /// Rebuild the database file to reclaim unused space
+    pub async fn vacuum(&self) -> Result<(), String> {
+        let pool = self.get_pool().await?;
+        let result = sqlx::query("VACUUM")
+            .execute(&pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        self.release_pool(pool).await;
+        result
+    }
+
+    /// Run `PRAGMA integrity_check`, returning `["ok"]` for a healthy database or
+    /// one entry per problem found
+    pub async fn integrity_check(&self) -> Result<Vec<String>, String> {
+        let pool = self.get_pool().await?;
+        let result = sqlx::query_scalar::<_, String>("PRAGMA integrity_check")
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string());
+        self.release_pool(pool).await;
+        result
+    }
+
+    /// Flag the rowid alias column (a sole `INTEGER PRIMARY KEY`, with or without
+    /// `AUTOINCREMENT`), which SQLite fills in automatically
+    fn mark_auto_increment(columns: &mut [ColumnInfo]) {
+        let pk_count = columns.iter().filter(|c| c.primary_key).count();
+        for column in columns.iter_mut() {
+            column.auto_increment = pk_count == 1
+                && column.primary_key
+                && column.data_type.eq_ignore_ascii_case("INTEGER");
+        }
+    }
+
+    /// Index just past the quoted token starting at `start`; doubled closing
+    /// characters are escapes
+    fn skip_quoted(chars: &[char], start: usize, close: char) -> usize {
+        let mut i = start + 1;
+        while i < chars.len() {
+            if chars[i] == close {
+                if chars.get(i + 1) == Some(&close) && close != ']' {
+                    i += 2;
+                    continue;
+                }
+                return i + 1;
+            }
+            i += 1;
+        }
+        chars.len()
+    }
+
+    /// Extract the `CHECK (...)` clauses of a `CREATE TABLE` statement. SQLite keeps no
+    /// catalog of constraints, so checks without a `CONSTRAINT name` are numbered in order.
+    fn parse_check_constraints(create_sql: &str) -> Vec<CheckConstraint> {
+        let chars: Vec<char> = create_sql.chars().collect();
+        let mut constraints = Vec::new();
+        // The two most recent words, with whether each was quoted
+        let mut recent: Vec<(String, bool)> = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            if matches!(c, '\'' | '"' | '`' | '[') {
+                let close = if c == '[' { ']' } else { c };
+                let end = Self::skip_quoted(&chars, i, close);
+                let inner: String = chars[i + 1..end.saturating_sub(1).max(i + 1)]
+                    .iter()
+                    .collect();
+                recent.push((
+                    inner.replace(&format!("{}{}", close, close), &close.to_string()),
+                    true,
+                ));
+                i = end;
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if !word.eq_ignore_ascii_case("CHECK") {
+                    recent.push((word, false));
+                    continue;
+                }
+
+                let mut open = i;
+                while open < chars.len() && chars[open].is_whitespace() {
+                    open += 1;
+                }
+                if chars.get(open) != Some(&'(') {
+                    continue;
+                }
+
+                let mut depth = 0;
+                let mut j = open;
+                while j < chars.len() {
+                    match chars[j] {
+                        '(' => depth += 1,
+                        ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        q @ ('\'' | '"' | '`') => {
+                            j = Self::skip_quoted(&chars, j, q);
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    j += 1;
+                }
+
+                let expression: String = chars[open + 1..j.min(chars.len())].iter().collect();
+                let name = match recent.as_slice() {
+                    [.., (keyword, false), (name, _)]
+                        if keyword.eq_ignore_ascii_case("CONSTRAINT") =>
+                    {
+                        name.clone()
+                    }
+                    _ => format!("check_{}", constraints.len() + 1),
+                };
+                constraints.push(CheckConstraint {
+                    name,
+                    expression: expression.trim().to_string(),
+                });
+                recent.clear();
+                i = j + 1;
+            } else {
+                if !c.is_whitespace() {
+                    recent.clear();
+                }
+                i += 1;
+            }
+            if recent.len() > 2 {
+                recent.remove(0);
+            }
+        }
+
+        constraints
+    }
+
     fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> Value {
         let mut obj = serde_json::Map::new();
         for (i, col) in row.columns().iter().enumerate() {
@@ -101,13 +309,34 @@ impl SqliteDriver {
     }
 }
 
+/// Bind a JSON value using SQLite's matching storage class; arrays and objects bind as JSON text
+fn bind_param<'q>(
+    query: Query<'q, Sqlite, SqliteArguments<'q>>,
+    value: &Value,
+) -> Query<'q, Sqlite, SqliteArguments<'q>> {
+    match value {
+        Value::Null => query.bind(None::<String>),
+        Value::Bool(b) => query.bind(*b),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => query.bind(i),
+            None => query.bind(n.as_f64()),
+        },
+        Value::String(s) => query.bind(s.clone()),
+        other => query.bind(other.to_string()),
+    }
+}
+
 #[async_trait]
 impl DatabaseDriver for SqliteDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        Self::CAPABILITIES
+    }
+
     async fn test_connection(&self) -> Result<TestConnectionResult, String> {
         match self.get_pool().await {
             Ok(pool) => {
                 let result = sqlx::query("SELECT 1").fetch_one(&pool).await;
-                pool.close().await;
+                self.release_pool(pool).await;
                 match result {
                     Ok(_) => Ok(TestConnectionResult {
                         success: true,
@@ -127,6 +356,15 @@ impl DatabaseDriver for SqliteDriver {
     }
 
     async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        self.list_tables_paged(None, None, None).await
+    }
+
+    async fn list_tables_paged(
+        &self,
+        name_filter: Option<&str>,
+        page: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TableInfo>, String> {
         let pool = self.get_pool().await?;
 
         // SQLite doesn't have schemas, so we use "main" as the default schema
@@ -138,14 +376,19 @@ impl DatabaseDriver for SqliteDriver {
             FROM sqlite_master
             WHERE type IN ('table', 'view')
             AND name NOT LIKE 'sqlite_%'
+            AND (?1 IS NULL OR name LIKE ?1 ESCAPE '\')
             ORDER BY name
+            LIMIT ?2 OFFSET ?3
             "#,
         )
+        .bind(name_filter.map(like_contains_pattern))
+        .bind(limit.unwrap_or(-1))
+        .bind(limit.map(|l| page_offset(page, l)).unwrap_or(0))
         .fetch_all(&pool)
         .await
         .map_err(|e| e.to_string())?;
 
-        pool.close().await;
+        self.release_pool(pool).await;
 
         Ok(tables
             .into_iter()
@@ -153,6 +396,8 @@ impl DatabaseDriver for SqliteDriver {
                 schema: "main".to_string(),
                 name,
                 table_type,
+                comment: None,
+                engine_full: None,
             })
             .collect())
     }
@@ -224,7 +469,7 @@ impl DatabaseDriver for SqliteDriver {
             .await
             .map_err(|e| e.to_string())?;
 
-        pool.close().await;
+        self.release_pool(pool).await;
 
         let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
 
@@ -243,15 +488,17 @@ impl DatabaseDriver for SqliteDriver {
     ) -> Result<TableStructure, String> {
         let pool = self.get_pool().await?;
 
-        // Get columns using PRAGMA
-        let pragma_query = format!("PRAGMA table_info(\"{}\")", table);
+        // Get columns using PRAGMA; table_xinfo also lists generated columns
+        // (hidden = 2 for virtual, 3 for stored) and hidden virtual-table columns (1)
+        let pragma_query = format!("PRAGMA table_xinfo(\"{}\")", table);
         let columns_raw = sqlx::query(&pragma_query)
             .fetch_all(&pool)
             .await
             .map_err(|e| e.to_string())?;
 
-        let columns: Vec<ColumnInfo> = columns_raw
+        let mut columns: Vec<ColumnInfo> = columns_raw
             .iter()
+            .filter(|row| row.try_get::<i32, _>("hidden").unwrap_or(0) != 1)
             .map(|row| {
                 let name: String = row.try_get("name").unwrap_or_default();
                 let data_type: String = row
@@ -261,6 +508,7 @@ impl DatabaseDriver for SqliteDriver {
                 let notnull: i32 = row.try_get("notnull").unwrap_or(0);
                 let default: Option<String> = row.try_get("dflt_value").ok();
                 let pk: i32 = row.try_get("pk").unwrap_or(0);
+                let hidden: i32 = row.try_get("hidden").unwrap_or(0);
 
                 ColumnInfo {
                     name,
@@ -268,9 +516,14 @@ impl DatabaseDriver for SqliteDriver {
                     nullable: notnull == 0,
                     default,
                     primary_key: pk > 0,
+                    comment: None,
+                    enum_values: None,
+                    auto_increment: false,
+                    is_generated: hidden == 2 || hidden == 3,
                 }
             })
             .collect();
+        Self::mark_auto_increment(&mut columns);
 
         // Get indexes using PRAGMA
         let index_list_query = format!("PRAGMA index_list(\"{}\")", table);
@@ -323,18 +576,34 @@ impl DatabaseDriver for SqliteDriver {
                 ForeignKeyInfo {
                     name: format!("fk_{}", id),
                     column: from_col,
+                    references_schema: "main".to_string(),
                     references_table: to_table,
                     references_column: to_col,
                 }
             })
             .collect();
 
-        pool.close().await;
+        let create_sql: Option<String> =
+            sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+                .bind(table)
+                .fetch_optional(&pool)
+                .await
+                .map_err(|e| e.to_string())?
+                .flatten();
+        let check_constraints = create_sql
+            .as_deref()
+            .map(Self::parse_check_constraints)
+            .unwrap_or_default();
+
+        self.release_pool(pool).await;
 
         Ok(TableStructure {
             columns,
             indexes,
             foreign_keys,
+            check_constraints,
+            unique_constraints: vec![],
+            warnings: vec![],
         })
     }
 
@@ -344,7 +613,7 @@ impl DatabaseDriver for SqliteDriver {
 
         match sqlx::query(query).fetch_all(&pool).await {
             Ok(rows) => {
-                pool.close().await;
+                self.release_pool(pool).await;
                 let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
                 let row_count = data.len() as i64;
                 Ok(QueryResult {
@@ -352,20 +621,205 @@ impl DatabaseDriver for SqliteDriver {
                     row_count,
                     error: None,
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
                 })
             }
             Err(e) => {
-                pool.close().await;
+                self.release_pool(pool).await;
                 Ok(QueryResult {
                     data: vec![],
                     row_count: 0,
                     error: Some(e.to_string()),
                     time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
                 })
             }
         }
     }
 
+    async fn execute_returning(
+        &self,
+        _schema: &str,
+        _table: &str,
+        insert_query: &str,
+        _inserted: &[(String, String)],
+    ) -> Result<QueryResult, String> {
+        self.execute_query(&format!("{} RETURNING *", insert_query))
+            .await
+    }
+
+    async fn execute_query_with_params(
+        &self,
+        query: &str,
+        params: &[Value],
+    ) -> Result<QueryResult, String> {
+        let start_time = std::time::Instant::now();
+        let pool = self.get_pool().await?;
+
+        let expected = match (&pool).prepare(query).await {
+            Ok(statement) => match statement.parameters() {
+                Some(Either::Right(count)) => count,
+                Some(Either::Left(types)) => types.len(),
+                None => 0,
+            },
+            Err(e) => {
+                self.release_pool(pool).await;
+                return Ok(QueryResult {
+                    data: vec![],
+                    row_count: 0,
+                    error: Some(e.to_string()),
+                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
+                });
+            }
+        };
+        if let Err(e) = check_param_count(expected, params.len()) {
+            self.release_pool(pool).await;
+            return Err(e);
+        }
+
+        let bound = params.iter().fold(sqlx::query(query), bind_param);
+        let result = bound.fetch_all(&pool).await;
+        self.release_pool(pool).await;
+
+        Ok(match result {
+            Ok(rows) => {
+                let data: Vec<Value> = rows.iter().map(Self::row_to_json).collect();
+                let row_count = data.len() as i64;
+                QueryResult {
+                    data,
+                    row_count,
+                    error: None,
+                    time_taken_ms: Some(start_time.elapsed().as_millis()),
+                    ..Default::default()
+                }
+            }
+            Err(e) => QueryResult {
+                data: vec![],
+                row_count: 0,
+                error: Some(e.to_string()),
+                time_taken_ms: Some(start_time.elapsed().as_millis()),
+                ..Default::default()
+            },
+        })
+    }
+
+    async fn stream_query(
+        &self,
+        query: &str,
+        batch_size: usize,
+        cancel: &CancellationToken,
+        on_batch: &mut (dyn FnMut(Vec<Value>) + Send),
+    ) -> Result<u64, String> {
+        let pool = self.get_pool().await?;
+        let result = {
+            let rows = sqlx::query(query).fetch(&pool);
+            drain_rows(rows, Self::row_to_json, batch_size, cancel, on_batch).await
+        };
+        self.release_pool(pool).await;
+        result
+    }
+
+    /// SQLite has no sampling clause, so rows are picked with `ORDER BY RANDOM()`
+    async fn sample_table(
+        &self,
+        _schema: &str,
+        table: &str,
+        n: i64,
+    ) -> Result<QueryResult, String> {
+        self.execute_query(&format!(
+            "SELECT * FROM \"{}\" ORDER BY RANDOM() LIMIT {}",
+            table.replace('"', "\"\""),
+            n
+        ))
+        .await
+    }
+
+    /// SQLite keeps no row estimates without `ANALYZE`, so rows are counted; the file is
+    /// local, which keeps this affordable
+    async fn namespace_overview(&self) -> Result<Vec<NamespaceOverview>, String> {
+        let pool = self.get_pool().await?;
+        let result = async {
+            let tables: Vec<String> = sqlx::query_scalar(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )
+            .fetch_all(&pool)
+            .await?;
+
+            let mut estimated_rows = 0i64;
+            for table in &tables {
+                let count: i64 = sqlx::query_scalar(&format!(
+                    "SELECT COUNT(*) FROM \"{}\"",
+                    table.replace('"', "\"\"")
+                ))
+                .fetch_one(&pool)
+                .await?;
+                estimated_rows += count;
+            }
+
+            Ok::<_, sqlx::Error>(NamespaceOverview {
+                name: "main".to_string(),
+                table_count: tables.len() as i64,
+                estimated_rows,
+            })
+        }
+        .await
+        .map_err(|e| e.to_string());
+        self.release_pool(pool).await;
+
+        Ok(vec![result?])
+    }
+
+    /// SQLite compares text with the BINARY collation unless a column says otherwise,
+    /// and has no timezone of its own (date functions work in UTC)
+    async fn get_database_info(&self) -> Result<DatabaseInfo, String> {
+        let pool = self.get_pool().await?;
+        let encoding = sqlx::query_scalar::<_, String>("PRAGMA encoding")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| e.to_string());
+        self.release_pool(pool).await;
+
+        Ok(DatabaseInfo {
+            encoding: Some(encoding?),
+            collation: Some("BINARY".to_string()),
+            timezone: None,
+        })
+    }
+
+    async fn validate_query(&self, query: &str) -> Result<(), String> {
+        // EXPLAIN compiles the statement without running it
+        let pool = self.get_pool().await?;
+        sqlx::query(&format!("EXPLAIN {}", query.trim().trim_end_matches(';')))
+            .fetch_all(&pool)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Expression columns whose type SQLite cannot tell report `NULL`
+    async fn describe_query(&self, query: &str) -> Result<Vec<ResultColumn>, String> {
+        let pool = self.get_pool().await?;
+        let statement = (&pool).prepare(query).await.map_err(|e| e.to_string())?;
+        Ok(statement
+            .columns()
+            .iter()
+            .map(|column| ResultColumn {
+                name: column.name().to_string(),
+                data_type: column.type_info().name().to_string(),
+            })
+            .collect())
+    }
+
+    async fn begin_transaction(&self) -> Result<TxHandle, String> {
+        // The transaction keeps its connection (and with it the pool) alive until it ends
+        let pool = self.get_pool().await?;
+        pool.begin()
+            .await
+            .map(TxHandle::Sqlite)
+            .map_err(|e| e.to_string())
+    }
+
     async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
         let pool = self.get_pool().await?;
 
@@ -386,6 +840,7 @@ impl DatabaseDriver for SqliteDriver {
                     schema: "main".to_string(),
                     name,
                     table_type,
+                    comment: None,
                     columns: Vec::new(),
                     foreign_keys: Vec::new(),
                     indexes: Vec::new(),
@@ -408,6 +863,7 @@ impl DatabaseDriver for SqliteDriver {
             let not_null: i32 = row.try_get("not_null").unwrap_or(0);
             let default_value: Option<String> = row.try_get("default_value").ok();
             let primary_key: i32 = row.try_get("primary_key").unwrap_or(0);
+            let is_generated: bool = row.try_get("is_generated").unwrap_or(false);
 
             if let Some(table) = tables_map.get_mut(&table_name) {
                 table.columns.push(ColumnInfo {
@@ -416,10 +872,18 @@ impl DatabaseDriver for SqliteDriver {
                     nullable: not_null == 0,
                     default: default_value,
                     primary_key: primary_key > 0,
+                    comment: None,
+                    enum_values: None,
+                    auto_increment: false,
+                    is_generated,
                 });
             }
         }
 
+        for table in tables_map.values_mut() {
+            Self::mark_auto_increment(&mut table.columns);
+        }
+
         let foreign_keys_rows = sqlx::query(FOREIGN_KEYS_QUERY)
             .fetch_all(&pool)
             .await
@@ -438,6 +902,7 @@ impl DatabaseDriver for SqliteDriver {
                 table.foreign_keys.push(ForeignKeyInfo {
                     name: format!("fk_{}_{}", table_name, column_name),
                     column: column_name,
+                    references_schema: "main".to_string(),
                     references_table,
                     references_column,
                 });
@@ -493,7 +958,7 @@ impl DatabaseDriver for SqliteDriver {
             }
         }
 
-        pool.close().await;
+        self.release_pool(pool).await;
 
         let tables: Vec<TableWithStructure> = tables_map.into_values().collect();
 
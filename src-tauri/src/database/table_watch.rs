@@ -0,0 +1,92 @@
+//! Live row-change watches on Postgres tables.
+//!
+//! A watch installs a trigger that publishes each insert, update and delete through
+//! `pg_notify`, and a background task that forwards those notifications to a callback.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::postgres::PostgresDriver;
+
+/// A single row change published by a watch trigger
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TableChange {
+    /// `INSERT`, `UPDATE` or `DELETE`
+    pub operation: String,
+    pub schema: String,
+    pub table: String,
+    /// The new row (the old row for deletes); `null` when too large to notify
+    pub row: Value,
+}
+
+/// Tracks the listener task of every active watch, keyed by watch id
+#[derive(Default)]
+pub struct TableWatchManager {
+    watches: Mutex<HashMap<String, JoinHandle<()>>>,
+}
+
+impl TableWatchManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a listener task, replacing any previous task for the same watch
+    pub async fn insert(&self, watch_id: String, handle: JoinHandle<()>) {
+        if let Some(previous) = self.watches.lock().await.insert(watch_id, handle) {
+            previous.abort();
+        }
+    }
+
+    /// Stop the listener task for a watch. Returns `false` if it was not running.
+    pub async fn stop(&self, watch_id: &str) -> bool {
+        match self.watches.lock().await.remove(watch_id) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Install the watch trigger on `schema.table` and spawn a task that calls `on_change`
+/// for every change. The trigger stays in place until
+/// [`PostgresDriver::remove_table_watch`] is called.
+pub async fn start_table_watch<F>(
+    driver: &PostgresDriver,
+    schema: &str,
+    table: &str,
+    mut on_change: F,
+) -> Result<JoinHandle<()>, String>
+where
+    F: FnMut(TableChange) + Send + 'static,
+{
+    let channel = driver.install_table_watch(schema, table).await?;
+    let mut listener = match driver.listen(&channel).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            let _ = driver.remove_table_watch(schema, table).await;
+            return Err(e);
+        }
+    };
+
+    Ok(tokio::spawn(async move {
+        loop {
+            match listener.recv().await {
+                Ok(notification) => {
+                    match serde_json::from_str::<TableChange>(notification.payload()) {
+                        Ok(change) => on_change(change),
+                        Err(e) => println!("[TableWatch] Ignoring malformed payload: {}", e),
+                    }
+                }
+                Err(e) => {
+                    println!("[TableWatch] Listener on {} stopped: {}", channel, e);
+                    break;
+                }
+            }
+        }
+    }))
+}
@@ -0,0 +1,160 @@
+//! Global timeout settings shared by all drivers.
+//!
+//! Values come from the app settings table. A value of `0` means "not configured", in which
+//! case each call site keeps its built-in default.
+
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::db::models::QueryResult;
+
+/// Settings key for the database connect timeout
+pub const CONNECT_TIMEOUT_KEY: &str = "connect_timeout_secs";
+/// Settings key for the SSH tunnel setup timeout
+pub const SSH_TUNNEL_TIMEOUT_KEY: &str = "ssh_tunnel_timeout_secs";
+/// Settings key for the query execution timeout
+pub const QUERY_TIMEOUT_KEY: &str = "query_timeout_secs";
+/// Settings key for the interval between keepalive pings on pooled connections
+pub const KEEPALIVE_INTERVAL_KEY: &str = "keepalive_interval_secs";
+/// Settings key for how long a pooled connection may sit unused before it is closed
+pub const IDLE_TTL_KEY: &str = "idle_connection_ttl_secs";
+
+/// Default SSH tunnel setup timeout in seconds
+const DEFAULT_SSH_TUNNEL_TIMEOUT_SECS: u64 = 20;
+
+static CONNECT_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(0);
+static SSH_TUNNEL_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(0);
+static QUERY_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(0);
+static KEEPALIVE_INTERVAL_SECS: AtomicU64 = AtomicU64::new(0);
+static IDLE_TTL_SECS: AtomicU64 = AtomicU64::new(0);
+
+fn setting_slot(key: &str) -> Option<&'static AtomicU64> {
+    match key {
+        CONNECT_TIMEOUT_KEY => Some(&CONNECT_TIMEOUT_SECS),
+        SSH_TUNNEL_TIMEOUT_KEY => Some(&SSH_TUNNEL_TIMEOUT_SECS),
+        QUERY_TIMEOUT_KEY => Some(&QUERY_TIMEOUT_SECS),
+        KEEPALIVE_INTERVAL_KEY => Some(&KEEPALIVE_INTERVAL_SECS),
+        IDLE_TTL_KEY => Some(&IDLE_TTL_SECS),
+        _ => None,
+    }
+}
+
+/// Apply a timeout setting. Keys that are not timeout settings are ignored;
+/// an empty value resets the timeout to its default.
+pub fn apply_setting(key: &str, value: &str) -> Result<(), String> {
+    let Some(slot) = setting_slot(key) else {
+        return Ok(());
+    };
+
+    let secs = if value.trim().is_empty() {
+        0
+    } else {
+        value
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| format!("Invalid value for {}: {}", key, value))?
+    };
+    slot.store(secs, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Load all timeout settings from the app database
+pub async fn load_from_settings(pool: &SqlitePool) -> Result<(), String> {
+    let rows: Vec<(String, String)> =
+        sqlx::query_as("SELECT key, value FROM settings WHERE key IN (?, ?, ?, ?, ?)")
+            .bind(CONNECT_TIMEOUT_KEY)
+            .bind(SSH_TUNNEL_TIMEOUT_KEY)
+            .bind(QUERY_TIMEOUT_KEY)
+            .bind(KEEPALIVE_INTERVAL_KEY)
+            .bind(IDLE_TTL_KEY)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    for (key, value) in rows {
+        apply_setting(&key, &value)?;
+    }
+    Ok(())
+}
+
+fn configured(slot: &AtomicU64) -> Option<u64> {
+    match slot.load(Ordering::SeqCst) {
+        0 => None,
+        secs => Some(secs),
+    }
+}
+
+/// Connect timeout, falling back to the caller's default when unset
+pub fn connect_timeout(default_secs: u64) -> Duration {
+    Duration::from_secs(configured(&CONNECT_TIMEOUT_SECS).unwrap_or(default_secs))
+}
+
+/// Timeout for establishing an SSH tunnel
+pub fn ssh_tunnel_timeout() -> Duration {
+    Duration::from_secs(
+        configured(&SSH_TUNNEL_TIMEOUT_SECS).unwrap_or(DEFAULT_SSH_TUNNEL_TIMEOUT_SECS),
+    )
+}
+
+/// Query timeout, if one is configured
+pub fn query_timeout() -> Option<Duration> {
+    configured(&QUERY_TIMEOUT_SECS).map(Duration::from_secs)
+}
+
+/// Interval between keepalive pings; keepalive is off unless configured
+pub fn keepalive_interval() -> Option<Duration> {
+    configured(&KEEPALIVE_INTERVAL_SECS).map(Duration::from_secs)
+}
+
+/// How long a pooled connection may go unused before it is evicted; eviction is off
+/// unless configured
+pub fn idle_ttl() -> Option<Duration> {
+    configured(&IDLE_TTL_SECS).map(Duration::from_secs)
+}
+
+/// Query timeout for a call: a per-call override wins over the connection's default,
+/// which wins over the global setting. Values of `0` or less are treated as unset.
+pub fn resolve_query_timeout(
+    override_secs: Option<i64>,
+    connection_secs: Option<i64>,
+) -> Option<Duration> {
+    override_secs
+        .filter(|secs| *secs > 0)
+        .or(connection_secs.filter(|secs| *secs > 0))
+        .map(|secs| Duration::from_secs(secs as u64))
+        .or_else(query_timeout)
+}
+
+/// Prefix of `QueryResult.error` when a query is cut off by its timeout
+pub const TIMEOUT_ERROR_PREFIX: &str = "TIMEOUT:";
+
+/// Run a query future under `limit`, or without a limit when `None`.
+///
+/// A query that runs out of time is not an `Err`: it yields a result whose error starts with
+/// [`TIMEOUT_ERROR_PREFIX`] and whose `time_taken_ms` is the time spent before giving up.
+pub async fn with_timeout<F>(limit: Option<Duration>, query: F) -> Result<QueryResult, String>
+where
+    F: Future<Output = Result<QueryResult, String>>,
+{
+    let Some(limit) = limit else {
+        return query.await;
+    };
+
+    let started = Instant::now();
+    match tokio::time::timeout(limit, query).await {
+        Ok(result) => result,
+        Err(_) => Ok(QueryResult {
+            data: vec![],
+            row_count: 0,
+            error: Some(format!(
+                "{} Query timed out after {} seconds",
+                TIMEOUT_ERROR_PREFIX,
+                limit.as_secs()
+            )),
+            time_taken_ms: Some(started.elapsed().as_millis()),
+            ..Default::default()
+        }),
+    }
+}
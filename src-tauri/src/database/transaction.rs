@@ -0,0 +1,65 @@
+//! Explicit transactions for staging a batch of statements.
+//!
+//! A [`TxHandle`] holds a single connection for its lifetime. Dropping it without calling
+//! [`TxHandle::commit`] rolls the transaction back.
+
+use sqlx::{Executor, Postgres, Sqlite, Transaction};
+
+/// An open transaction on a Postgres or SQLite connection
+pub enum TxHandle {
+    Postgres(Transaction<'static, Postgres>),
+    Sqlite(Transaction<'static, Sqlite>),
+}
+
+impl TxHandle {
+    fn quote_savepoint(name: &str) -> Result<String, String> {
+        if name.trim().is_empty() {
+            return Err("Savepoint name must not be empty".to_string());
+        }
+        Ok(format!("\"{}\"", name.replace('"', "\"\"")))
+    }
+
+    /// Run a statement inside the transaction, returning the number of rows affected
+    pub async fn execute(&mut self, sql: &str) -> Result<u64, String> {
+        // Going through `Executor` rather than `RawSql::execute` keeps the future `Send`
+        match self {
+            TxHandle::Postgres(tx) => (&mut **tx)
+                .execute(sqlx::raw_sql(sql))
+                .await
+                .map(|r| r.rows_affected()),
+            TxHandle::Sqlite(tx) => (&mut **tx)
+                .execute(sqlx::raw_sql(sql))
+                .await
+                .map(|r| r.rows_affected()),
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    /// Mark a point that later statements can be rolled back to
+    pub async fn savepoint(&mut self, name: &str) -> Result<(), String> {
+        let sql = format!("SAVEPOINT {}", Self::quote_savepoint(name)?);
+        self.execute(&sql).await.map(|_| ())
+    }
+
+    /// Undo everything executed since `name` was set, keeping the transaction open
+    pub async fn rollback_to_savepoint(&mut self, name: &str) -> Result<(), String> {
+        let sql = format!("ROLLBACK TO SAVEPOINT {}", Self::quote_savepoint(name)?);
+        self.execute(&sql).await.map(|_| ())
+    }
+
+    pub async fn commit(self) -> Result<(), String> {
+        match self {
+            TxHandle::Postgres(tx) => tx.commit().await,
+            TxHandle::Sqlite(tx) => tx.commit().await,
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    pub async fn rollback(self) -> Result<(), String> {
+        match self {
+            TxHandle::Postgres(tx) => tx.rollback().await,
+            TxHandle::Sqlite(tx) => tx.rollback().await,
+        }
+        .map_err(|e| e.to_string())
+    }
+}
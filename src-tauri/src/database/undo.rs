@@ -0,0 +1,106 @@
+//! In-memory undo history for row edits made from the table grid.
+//!
+//! Each saved connection keeps its own stack of the values an edit replaced, newest
+//! last. The history lives in Tauri managed state and is lost when the app restarts.
+
+use serde_json::{Map, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Most edits remembered per connection; older ones are dropped first
+pub const UNDO_STACK_LIMIT: usize = 50;
+
+/// What an edit replaced, enough to put it back
+#[derive(Debug, Clone)]
+pub enum RowEdit {
+    /// An `UPDATE`: the previous values of the columns it wrote, and the primary key
+    /// that identifies the row after the edit
+    Update {
+        schema: String,
+        table: String,
+        table_ref: String,
+        primary_key: Vec<(String, Value)>,
+        previous: Map<String, Value>,
+    },
+    /// A `DELETE`: every column of the removed row
+    Delete {
+        schema: String,
+        table: String,
+        table_ref: String,
+        row: Map<String, Value>,
+    },
+}
+
+/// An edit and the database it was made on
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    /// Database type of the driver that made the edit, which decides how its inverse
+    /// binds values
+    pub db_type: String,
+    /// `driver_identity` of the database the edit was made on
+    pub identity: String,
+    pub edit: RowEdit,
+}
+
+/// Identifies the database behind a driver: the file for SQLite, otherwise the server
+/// and database name. Empty fields count as missing, so a saved connection and the
+/// arguments of a command naming the same database agree.
+pub fn driver_identity(
+    db_type: &str,
+    host: Option<&str>,
+    port: Option<i64>,
+    database: Option<&str>,
+    file_path: Option<&str>,
+) -> String {
+    fn present(value: Option<&str>) -> &str {
+        value.filter(|v| !v.is_empty()).unwrap_or_default()
+    }
+    match db_type {
+        "sqlite" | "sqlite3" => format!("sqlite|{}", present(file_path)),
+        _ => format!(
+            "{}|{}|{}|{}",
+            db_type,
+            present(host),
+            port.map(|p| p.to_string()).unwrap_or_default(),
+            present(database)
+        ),
+    }
+}
+
+/// Undo stacks of every connection, keyed by connection uuid
+#[derive(Default)]
+pub struct UndoHistory {
+    stacks: Mutex<HashMap<String, VecDeque<UndoEntry>>>,
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn stacks(&self) -> std::sync::MutexGuard<'_, HashMap<String, VecDeque<UndoEntry>>> {
+        self.stacks.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Remember an edit made on a connection
+    pub fn record(&self, connection_uuid: &str, entry: UndoEntry) {
+        let mut stacks = self.stacks();
+        let stack = stacks.entry(connection_uuid.to_string()).or_default();
+        if stack.len() >= UNDO_STACK_LIMIT {
+            stack.pop_front();
+        }
+        stack.push_back(entry);
+    }
+
+    /// Take the most recent edit made on a connection
+    pub fn pop(&self, connection_uuid: &str) -> Option<UndoEntry> {
+        self.stacks()
+            .get_mut(connection_uuid)
+            .and_then(VecDeque::pop_back)
+    }
+
+    /// Number of edits that can still be undone on a connection
+    pub fn depth(&self, connection_uuid: &str) -> usize {
+        self.stacks().get(connection_uuid).map_or(0, VecDeque::len)
+    }
+}
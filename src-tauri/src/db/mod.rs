@@ -1,3 +1,4 @@
+use sqlx::migrate::Migrator;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 use std::path::PathBuf;
 use thiserror::Error;
@@ -16,6 +17,16 @@ pub enum DbError {
 
 pub type DbResult<T> = Result<T, DbError>;
 
+/// Numbered files in `migrations/`. Applied versions are tracked in `_sqlx_migrations`,
+/// so schema changes go in a new file rather than an edit to a shipped one.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Bring the app database schema up to date
+pub async fn migrate(pool: &SqlitePool) -> DbResult<()> {
+    MIGRATOR.run(pool).await?;
+    Ok(())
+}
+
 fn get_db_path() -> DbResult<PathBuf> {
     let data_dir = dirs::data_local_dir().ok_or(DbError::DataDir)?;
     let app_dir = data_dir.join("dbcooper");
@@ -49,7 +60,7 @@ pub async fn init_pool() -> DbResult<SqlitePool> {
         .connect(&db_url)
         .await?;
 
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    migrate(&pool).await?;
 
     Ok(pool)
 }
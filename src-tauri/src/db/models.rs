@@ -17,6 +17,8 @@ pub struct Connection {
     pub ssl: i64,
     pub db_type: String,
     pub file_path: Option<String>,
+    /// Directory of the server's Unix domain socket, used instead of `host`
+    pub socket_path: Option<String>,
     pub ssh_enabled: i64,
     pub ssh_host: String,
     pub ssh_port: i64,
@@ -24,6 +26,19 @@ pub struct Connection {
     pub ssh_password: String,
     pub ssh_key_path: String,
     pub ssh_use_key: i64,
+    /// Authenticate the SSH tunnel through the running ssh-agent
+    pub ssh_use_agent: i64,
+    pub read_only: i64,
+    /// Default query timeout for this connection; `None` uses the global setting
+    pub query_timeout_secs: Option<i64>,
+    /// Statements run once on each new session before it serves queries
+    pub session_setup_sql: Option<String>,
+    /// Rows per page when browsing tables; `None` uses the app default
+    pub default_page_size: Option<i64>,
+    /// Queries run at once on the pooled connection; `None` uses the app default
+    pub max_concurrent_queries: Option<i64>,
+    pub last_connected_at: Option<String>,
+    pub query_count: i64,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -44,6 +59,8 @@ pub struct ConnectionFormData {
     #[serde(default)]
     pub file_path: Option<String>,
     #[serde(default)]
+    pub socket_path: Option<String>,
+    #[serde(default)]
     pub ssh_enabled: bool,
     #[serde(default)]
     pub ssh_host: String,
@@ -57,6 +74,18 @@ pub struct ConnectionFormData {
     pub ssh_key_path: String,
     #[serde(default)]
     pub ssh_use_key: bool,
+    #[serde(default)]
+    pub ssh_use_agent: bool,
+    #[serde(default)]
+    pub read_only: bool,
+    #[serde(default)]
+    pub query_timeout_secs: Option<i64>,
+    #[serde(default)]
+    pub session_setup_sql: Option<String>,
+    #[serde(default)]
+    pub default_page_size: Option<i64>,
+    #[serde(default)]
+    pub max_concurrent_queries: Option<i64>,
 }
 
 fn default_db_type() -> String {
@@ -83,12 +112,78 @@ pub struct SavedQueryFormData {
     pub query: String,
 }
 
+/// A query that ran longer than the slow query threshold
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SlowQuery {
+    pub id: i64,
+    pub connection_uuid: String,
+    pub query: String,
+    pub duration_ms: i64,
+    pub created_at: String,
+}
+
+/// A migration recorded in the app database
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: String,
+    pub success: bool,
+}
+
+/// Number of rows in one table of the app database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppTableCount {
+    pub table: String,
+    pub rows: i64,
+}
+
+/// Health report for the app's own SQLite database, as returned by `diagnose_app_db`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDbDiagnostics {
+    /// `ok`, or the problems `PRAGMA integrity_check` found, one per line
+    pub integrity: String,
+    /// Newest successfully applied migration, `None` if migrations never ran
+    pub schema_version: Option<i64>,
+    pub migrations: Vec<AppliedMigration>,
+    pub tables: Vec<AppTableCount>,
+    /// `None` for an in-memory database
+    pub file_path: Option<String>,
+    pub file_size_bytes: i64,
+}
+
+/// A reusable SQL template. `db_type` is `None` for snippets that work on any database.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    pub id: i64,
+    pub title: String,
+    pub body: String,
+    pub db_type: Option<String>,
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnippetFormData {
+    pub title: String,
+    pub body: String,
+    pub db_type: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableInfo {
     pub schema: String,
     pub name: String,
     #[serde(rename = "type")]
     pub table_type: String,
+    pub comment: Option<String>,
+    /// Full engine definition of ClickHouse `Distributed` tables, naming the cluster
+    /// and the local table that queries fan out to
+    #[serde(default)]
+    pub engine_full: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +194,14 @@ pub struct ColumnInfo {
     pub nullable: bool,
     pub default: Option<String>,
     pub primary_key: bool,
+    pub comment: Option<String>,
+    pub enum_values: Option<Vec<String>>,
+    /// Value is generated by the database (identity, serial, autoincrement)
+    #[serde(default)]
+    pub auto_increment: bool,
+    /// Value is computed by the database from an expression and cannot be written
+    #[serde(default)]
+    pub is_generated: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -113,15 +216,147 @@ pub struct IndexInfo {
 pub struct ForeignKeyInfo {
     pub name: String,
     pub column: String,
+    /// Schema of the referenced table
+    #[serde(default)]
+    pub references_schema: String,
     pub references_table: String,
     pub references_column: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckConstraint {
+    pub name: String,
+    pub expression: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableStructure {
     pub columns: Vec<ColumnInfo>,
     pub indexes: Vec<IndexInfo>,
     pub foreign_keys: Vec<ForeignKeyInfo>,
+    #[serde(default)]
+    pub check_constraints: Vec<CheckConstraint>,
+    /// Named `UNIQUE` constraints; their backing indexes are also listed in `indexes`
+    #[serde(default)]
+    pub unique_constraints: Vec<UniqueConstraint>,
+    /// Sections that could not be read (e.g. for lack of privileges) and were left empty
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+/// Features a database driver supports, so the UI can hide actions that would fail
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriverCapabilities {
+    /// Explicit transactions through `begin_transaction`
+    pub supports_transactions: bool,
+    /// Foreign key constraints, and so following and finding referencing rows
+    pub supports_foreign_keys: bool,
+    /// Inserting, updating and deleting rows from the table view
+    pub supports_row_editing: bool,
+    /// Tables grouped under schemas (or keyspaces/databases) on one connection
+    pub supports_schemas: bool,
+    /// Showing a query plan with `EXPLAIN`
+    pub supports_explain: bool,
+}
+
+/// Character set, collation and timezone of the connected database
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow)]
+pub struct DatabaseInfo {
+    pub encoding: Option<String>,
+    pub collation: Option<String>,
+    pub timezone: Option<String>,
+}
+
+/// Rows of one table whose foreign key references a given record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferencingRows {
+    pub schema: String,
+    pub table: String,
+    /// The foreign key column holding the referenced value
+    pub column: String,
+    pub data: Vec<serde_json::Value>,
+    pub row_count: i64,
+}
+
+/// A child partition of a partitioned table
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PartitionInfo {
+    pub schema: String,
+    pub name: String,
+    /// Partition bound, e.g. `FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')`
+    pub bound: String,
+    /// Whether the partition is itself partitioned
+    pub is_partitioned: bool,
+}
+
+/// How a table is partitioned; `strategy` is `None` for ordinary tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TablePartitions {
+    /// `range`, `list` or `hash`
+    pub strategy: Option<String>,
+    /// Partition key definition, e.g. `RANGE (created_at)`
+    pub partition_key: Option<String>,
+    pub partitions: Vec<PartitionInfo>,
+}
+
+/// One replica of a ClickHouse cluster, from `system.clusters`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterNode {
+    pub cluster: String,
+    pub shard_num: i64,
+    pub replica_num: i64,
+    pub host_name: String,
+    pub host_address: String,
+    pub port: i64,
+    /// Whether this replica is the server the connection talks to
+    pub is_local: bool,
+}
+
+/// Cheap per-column statistics for profiling a table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    pub column: String,
+    /// Share of rows where the column is NULL, from 0.0 to 1.0
+    pub null_fraction: f64,
+    pub distinct_estimate: i64,
+    /// `statistics` when read from the planner's catalog, `sample` when counted over a sample
+    pub source: String,
+}
+
+/// Table count and estimated row total of one schema (Postgres, SQLite) or database
+/// (ClickHouse)
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct NamespaceOverview {
+    pub name: String,
+    pub table_count: i64,
+    pub estimated_rows: i64,
+}
+
+/// What a connection holds, at a glance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerOverview {
+    pub namespaces: Vec<NamespaceOverview>,
+    pub total_tables: i64,
+    pub total_estimated_rows: i64,
+}
+
+/// A stored function or procedure
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RoutineInfo {
+    pub schema: String,
+    pub name: String,
+    /// `function` or `procedure`
+    pub routine_type: String,
+    /// `None` for procedures
+    pub return_type: Option<String>,
+    pub arguments: String,
+    pub language: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -132,7 +367,14 @@ pub struct TableDataResponse {
     pub limit: i64,
 }
 
+/// Name and database type of one column in a query's result set
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultColumn {
+    pub name: String,
+    pub data_type: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueryResult {
     pub data: Vec<serde_json::Value>,
     pub row_count: i64,
@@ -140,6 +382,19 @@ pub struct QueryResult {
     pub error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub time_taken_ms: Option<u128>,
+    /// Execution time reported by the server, excluding network round-trip
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_time_ms: Option<u128>,
+    /// Result was served from the query cache
+    #[serde(default)]
+    pub from_cache: bool,
+    /// NOTICE and WARNING messages the server sent while running the query
+    #[serde(default)]
+    pub notices: Vec<String>,
+    /// Tables an INSERT, UPDATE, DELETE, TRUNCATE or ALTER writes to, so the UI knows
+    /// what to refresh. Empty for reads and for queries that do not parse.
+    #[serde(default)]
+    pub affected_tables: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,6 +403,20 @@ pub struct TestConnectionResult {
     pub message: String,
 }
 
+/// Outcome of `test_connection_deep`. Each step runs only if the previous one passed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepTestConnectionResult {
+    pub can_connect: bool,
+    /// The tables could be listed
+    pub can_list: bool,
+    /// A row could be read from the first listed table
+    pub can_read: bool,
+    /// `schema.table` that was read from, if any table was listed
+    pub probed_table: Option<String>,
+    /// Why the first failing step failed, or a summary if all passed
+    pub message: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Setting {
     pub key: String,
@@ -160,6 +429,7 @@ pub struct TableWithStructure {
     pub name: String,
     #[serde(rename = "type")]
     pub table_type: String,
+    pub comment: Option<String>,
     pub columns: Vec<ColumnInfo>,
     pub foreign_keys: Vec<ForeignKeyInfo>,
     pub indexes: Vec<IndexInfo>,
@@ -169,3 +439,86 @@ pub struct TableWithStructure {
 pub struct SchemaOverview {
     pub tables: Vec<TableWithStructure>,
 }
+
+/// A Postgres large object, with the start of its contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeObject {
+    pub oid: i64,
+    /// Total size in bytes
+    pub size: i64,
+    /// The preview as text, when it is valid UTF-8
+    pub preview: Option<String>,
+    pub preview_base64: String,
+    /// Whether the preview stops before the end of the object
+    pub truncated: bool,
+}
+
+/// Current state of the sequence feeding a serial or identity column
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SequenceValue {
+    pub sequence_name: String,
+    pub last_value: i64,
+    /// Whether `last_value` has been handed out; if not, the next insert uses it as is
+    pub is_called: bool,
+}
+
+/// A privilege granted on a table, from `information_schema.role_table_grants`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TableGrant {
+    pub grantor: String,
+    pub grantee: String,
+    /// `SELECT`, `INSERT`, `UPDATE`, `DELETE`, `TRUNCATE`, `REFERENCES` or `TRIGGER`
+    pub privilege_type: String,
+    /// Whether the grantee may grant the privilege on to others
+    pub is_grantable: bool,
+}
+
+/// A row-level security policy on a table
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RowSecurityPolicy {
+    pub name: String,
+    /// `ALL`, `SELECT`, `INSERT`, `UPDATE` or `DELETE`
+    pub command: String,
+    /// `false` for restrictive policies
+    pub permissive: bool,
+    pub roles: Vec<String>,
+    pub using_expression: Option<String>,
+    pub check_expression: Option<String>,
+}
+
+/// Who can do what on a Postgres table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TablePermissions {
+    pub grants: Vec<TableGrant>,
+    pub rls_enabled: bool,
+    /// RLS also applies to the table owner
+    pub rls_forced: bool,
+    pub policies: Vec<RowSecurityPolicy>,
+}
+
+/// An index that would let the planner avoid a filtered sequential scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexSuggestion {
+    pub schema: String,
+    pub table: String,
+    /// Filtered columns that lead no existing index, in the order the filter uses them
+    pub columns: Vec<String>,
+    /// The scan's filter as the planner printed it
+    pub filter: String,
+    pub create_statement: String,
+}
+
+/// A Postgres session waiting on a lock, paired with the session holding it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BlockingQuery {
+    pub blocked_pid: i64,
+    pub blocked_user: Option<String>,
+    pub blocked_query: Option<String>,
+    pub blocked_duration_ms: Option<f64>,
+    pub blocking_pid: i64,
+    pub blocking_user: Option<String>,
+    pub blocking_query: Option<String>,
+    pub blocking_state: Option<String>,
+    pub lock_type: String,
+    pub lock_mode: Option<String>,
+}
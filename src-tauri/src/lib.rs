@@ -4,19 +4,36 @@ pub mod db;
 mod ssh_tunnel;
 
 use commands::ai::{generate_sql, select_tables_for_query};
+use commands::bundle::{export_bundle, import_bundle};
 use commands::connections::{
     create_connection, delete_connection, export_connection, get_connection_by_uuid,
-    get_connections, import_connections, update_connection,
+    get_connections, import_connections, update_connection, validate_connection,
 };
 use commands::database::{
-    delete_table_row, insert_table_row, redis_delete_key, redis_get_key_details, redis_search_keys,
-    redis_set_hash_key, redis_set_key, redis_set_list_key, redis_set_set_key, redis_set_zset_key,
-    redis_update_ttl, unified_execute_query, unified_get_schema_overview, unified_get_table_data,
+    cancel_query_stream, clone_table_structure, column_aggregate, delete_table_row,
+    execute_multi_result, execute_script, find_referencing_rows, follow_foreign_key,
+    get_cluster_info, get_database_info, get_driver_capabilities, get_partitions,
+    get_routine_definition, get_row_by_primary_key, get_sequence_value, get_server_overview,
+    get_table_permissions, insert_table_row, list_blocking_queries, list_routines,
+    postgres_analyze, postgres_vacuum, profile_columns, read_large_object, redis_delete_key,
+    redis_delete_keys_by_pattern, redis_expire_keys, redis_get_full_value, redis_get_key_details,
+    redis_hscan, redis_lrange, redis_replication_status, redis_search_keys, redis_select_db,
+    redis_set_hash_key, redis_set_json, redis_set_key, redis_set_list_key, redis_set_set_key,
+    redis_set_zset_key, redis_sscan, redis_update_ttl, redis_zrange, redis_zrangebyscore,
+    sample_table, set_sequence_value, set_table_comment, sqlite_integrity_check, sqlite_vacuum,
+    stop_watch_table, stream_query, stream_schema_overview, suggest_indexes, test_connection_deep,
+    undo_last_edit, unified_execute_query, unified_get_schema_overview, unified_get_table_data,
     unified_get_table_structure, unified_list_tables, unified_test_connection, update_table_row,
-    update_table_row_with_raw_sql,
+    update_table_row_with_raw_sql, watch_table_changes, write_large_object,
+};
+use commands::diagnostics::diagnose_app_db;
+use commands::editor::{compare_results, format_sql, lint_sql};
+use commands::export::{
+    cancel_export, export_er_mermaid, export_query_parquet, import_clipboard_data,
+    import_table_csv, materialize_query, unified_export_table_csv,
 };
 use commands::pool::{
-    pool_connect, pool_delete_table_row, pool_disconnect, pool_execute_query,
+    pool_connect, pool_delete_table_row, pool_disconnect, pool_execute_query, pool_get_backend_pid,
     pool_get_schema_overview, pool_get_status, pool_get_table_data, pool_get_table_structure,
     pool_health_check, pool_insert_table_row, pool_list_tables, pool_update_table_row,
 };
@@ -26,13 +43,39 @@ use commands::postgres::{
 use commands::queries::{
     create_saved_query, delete_saved_query, get_saved_queries, update_saved_query,
 };
-use commands::settings::{get_all_settings, get_setting, set_setting};
+use commands::settings::{
+    get_all_settings, get_connection_variables, get_last_query, get_setting,
+    set_connection_variable, set_setting,
+};
+use commands::slow_queries::get_slow_queries;
+use commands::snippets::{create_snippet, delete_snippet, get_snippets, update_snippet};
+use database::export::ExportManager;
+use database::notices::{NoticeLayer, NOTICE_TARGET};
 use database::pool_manager::PoolManager;
+use database::query_stream::QueryStreamManager;
+use database::table_watch::TableWatchManager;
+use database::undo::UndoHistory;
 use tauri::menu::{AboutMetadata, Menu, PredefinedMenuItem, Submenu};
 use tauri::Manager;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::Layer;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Postgres notices are returned with query results; other warnings go to stderr
+    tracing_subscriber::registry()
+        .with(NoticeLayer)
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(filter_fn(|metadata| {
+                    metadata.target() != NOTICE_TARGET && *metadata.level() <= tracing::Level::WARN
+                })),
+        )
+        .init();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -101,10 +144,39 @@ pub fn run() {
             let pool = rt
                 .block_on(db::init_pool())
                 .expect("Failed to initialize database");
+            if let Err(e) = rt.block_on(database::timeouts::load_from_settings(&pool)) {
+                eprintln!("Failed to load timeout settings: {}", e);
+            }
+            if let Err(e) = rt.block_on(database::retry::load_from_settings(&pool)) {
+                eprintln!("Failed to load retry settings: {}", e);
+            }
+            if let Err(e) = rt.block_on(commands::slow_queries::load_from_settings(&pool)) {
+                eprintln!("Failed to load slow query settings: {}", e);
+            }
+            if let Err(e) = rt.block_on(database::display::load_from_settings(&pool)) {
+                eprintln!("Failed to load display settings: {}", e);
+            }
+            if let Err(e) = rt.block_on(database::reachability::load_from_settings(&pool)) {
+                eprintln!("Failed to load reachability cache settings: {}", e);
+            }
             app.manage(pool);
 
-            // Initialize connection pool manager
-            app.manage(PoolManager::new());
+            // Initialize connection pool manager, closing connections left idle past the TTL
+            let pool_manager = PoolManager::new();
+            tauri::async_runtime::spawn(pool_manager.idle_sweeper());
+            app.manage(pool_manager);
+
+            // Track live table watches
+            app.manage(TableWatchManager::new());
+
+            // Track streamed queries so they can be cancelled
+            app.manage(QueryStreamManager::new());
+
+            // Track running exports so they can be cancelled
+            app.manage(ExportManager::new());
+
+            // Remember row edits made from the grid so they can be undone
+            app.manage(UndoHistory::new());
 
             Ok(())
         })
@@ -113,45 +185,114 @@ pub fn run() {
             get_connection_by_uuid,
             create_connection,
             update_connection,
+            validate_connection,
             delete_connection,
             export_connection,
             import_connections,
+            export_bundle,
+            import_bundle,
             test_connection,
             list_tables,
             get_table_data,
             get_table_structure,
             execute_query,
             unified_test_connection,
+            test_connection_deep,
+            get_driver_capabilities,
             unified_list_tables,
             unified_get_table_data,
             unified_get_table_structure,
             unified_execute_query,
+            execute_script,
+            execute_multi_result,
+            stream_query,
+            cancel_query_stream,
             unified_get_schema_overview,
+            stream_schema_overview,
+            unified_export_table_csv,
+            cancel_export,
+            export_query_parquet,
+            import_table_csv,
+            import_clipboard_data,
+            export_er_mermaid,
+            materialize_query,
+            format_sql,
+            lint_sql,
+            compare_results,
+            set_table_comment,
+            clone_table_structure,
+            column_aggregate,
+            profile_columns,
+            follow_foreign_key,
+            get_row_by_primary_key,
+            find_referencing_rows,
+            sample_table,
+            list_blocking_queries,
+            list_routines,
+            get_routine_definition,
+            get_partitions,
+            get_server_overview,
+            get_database_info,
+            get_cluster_info,
+            postgres_analyze,
+            postgres_vacuum,
+            get_sequence_value,
+            set_sequence_value,
+            read_large_object,
+            write_large_object,
+            suggest_indexes,
+            get_table_permissions,
+            watch_table_changes,
+            stop_watch_table,
+            sqlite_vacuum,
+            sqlite_integrity_check,
             redis_search_keys,
             redis_get_key_details,
+            redis_get_full_value,
             redis_delete_key,
+            redis_delete_keys_by_pattern,
+            redis_select_db,
+            redis_expire_keys,
             redis_set_key,
+            redis_set_json,
             redis_set_list_key,
             redis_set_set_key,
             redis_set_hash_key,
             redis_set_zset_key,
+            redis_lrange,
+            redis_sscan,
+            redis_hscan,
+            redis_zrange,
+            redis_zrangebyscore,
+            redis_replication_status,
             redis_update_ttl,
             update_table_row,
             update_table_row_with_raw_sql,
             delete_table_row,
+            undo_last_edit,
             insert_table_row,
             get_saved_queries,
             create_saved_query,
             update_saved_query,
             delete_saved_query,
+            get_snippets,
+            create_snippet,
+            update_snippet,
+            delete_snippet,
             get_setting,
             set_setting,
             get_all_settings,
+            set_connection_variable,
+            get_connection_variables,
+            get_last_query,
+            get_slow_queries,
+            diagnose_app_db,
             generate_sql,
             pool_connect,
             pool_disconnect,
             pool_get_status,
             pool_health_check,
+            pool_get_backend_pid,
             pool_list_tables,
             pool_get_table_data,
             pool_get_table_structure,
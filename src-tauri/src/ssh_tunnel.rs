@@ -9,13 +9,34 @@ pub struct SshTunnel {
     _shutdown_tx: oneshot::Sender<()>,
 }
 
+/// Check that an ssh-agent can be reached before trying to authenticate with it. On Unix
+/// the agent is found through `SSH_AUTH_SOCK`; elsewhere libssh2 locates it itself.
+fn check_agent_available() -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        let socket = std::env::var_os("SSH_AUTH_SOCK")
+            .filter(|path| !path.is_empty())
+            .ok_or("SSH agent is not available: SSH_AUTH_SOCK is not set")?;
+        if !std::path::Path::new(&socket).exists() {
+            return Err(format!(
+                "SSH agent is not available: socket {} does not exist",
+                socket.to_string_lossy()
+            ));
+        }
+    }
+    Ok(())
+}
+
 impl SshTunnel {
+    /// Open a tunnel to `remote_host:remote_port` through the SSH server. With
+    /// `ssh_use_agent`, the running ssh-agent authenticates instead of a key file or password.
     pub async fn new(
         ssh_host: &str,
         ssh_port: u16,
         ssh_user: &str,
         ssh_password: Option<&str>,
         ssh_key_path: Option<&str>,
+        ssh_use_agent: bool,
         remote_host: &str,
         remote_port: u16,
     ) -> Result<Self, String> {
@@ -23,6 +44,9 @@ impl SshTunnel {
             "[SSH] Creating tunnel to {}:{} -> {}:{}",
             ssh_host, ssh_port, remote_host, remote_port
         );
+        if ssh_use_agent {
+            check_agent_available()?;
+        }
 
         let ssh_addr: SocketAddr = format!("{}:{}", ssh_host, ssh_port)
             .parse()
@@ -51,7 +75,13 @@ impl SshTunnel {
 
         println!("[SSH] Handshake complete, authenticating...");
 
-        if let Some(key_path) = ssh_key_path {
+        if ssh_use_agent {
+            println!("[SSH] Attempting ssh-agent authentication");
+            session
+                .userauth_agent_with_try_next(ssh_user)
+                .await
+                .map_err(|e| format!("SSH agent authentication failed: {}", e))?;
+        } else if let Some(key_path) = ssh_key_path {
             if !key_path.is_empty() {
                 let expanded_path = if key_path.starts_with("~") {
                     if let Some(home) = dirs::home_dir() {
@@ -5,7 +5,23 @@
 //!
 //! Run with: cargo test --test app_data_tests -- --test-threads=1
 
-use dbcooper_lib::db::models::{Connection, SavedQuery, Setting};
+use dbcooper_lib::commands::bundle::{read_bundle, write_bundle};
+use dbcooper_lib::commands::connections::{
+    resolve_page_size, validate_connection, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
+use dbcooper_lib::commands::diagnostics::diagnose;
+use dbcooper_lib::commands::queries::{insert_saved_query, modify_saved_query};
+use dbcooper_lib::commands::settings::{
+    load_connection_variables, load_last_query, store_connection_variable, store_last_query,
+};
+use dbcooper_lib::commands::slow_queries::{self, SLOW_QUERY_THRESHOLD_KEY};
+use dbcooper_lib::commands::snippets::{insert_snippet, list_snippets, modify_snippet};
+use dbcooper_lib::database::sqlite::SqliteDriver;
+use dbcooper_lib::database::{DatabaseDriver, SqliteConfig};
+use dbcooper_lib::db::migrate;
+use dbcooper_lib::db::models::{
+    Connection, ConnectionFormData, SavedQuery, SavedQueryFormData, Setting, SnippetFormData,
+};
 use sqlx::sqlite::SqlitePoolOptions;
 use tempfile::NamedTempFile;
 
@@ -21,67 +37,24 @@ async fn create_test_pool() -> (sqlx::SqlitePool, NamedTempFile) {
         .await
         .expect("Failed to create pool");
 
-    // Create schema
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS connections (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            uuid TEXT NOT NULL UNIQUE,
-            type TEXT NOT NULL DEFAULT 'postgres',
-            name TEXT NOT NULL,
-            host TEXT NOT NULL,
-            port INTEGER NOT NULL,
-            database TEXT NOT NULL,
-            username TEXT NOT NULL,
-            password TEXT NOT NULL,
-            ssl INTEGER NOT NULL DEFAULT 0,
-            db_type TEXT NOT NULL DEFAULT 'postgres',
-            file_path TEXT,
-            ssh_enabled INTEGER NOT NULL DEFAULT 0,
-            ssh_host TEXT NOT NULL DEFAULT '',
-            ssh_port INTEGER NOT NULL DEFAULT 22,
-            ssh_user TEXT NOT NULL DEFAULT '',
-            ssh_password TEXT NOT NULL DEFAULT '',
-            ssh_key_path TEXT NOT NULL DEFAULT '',
-            ssh_use_key INTEGER NOT NULL DEFAULT 0,
-            created_at TEXT NOT NULL DEFAULT (datetime('now')),
-            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
+    migrate(&pool)
+        .await
+        .expect("Failed to migrate app database");
 
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS saved_queries (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            connection_uuid TEXT NOT NULL,
-            name TEXT NOT NULL,
-            query TEXT NOT NULL,
-            created_at TEXT NOT NULL DEFAULT (datetime('now')),
-            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
-        )
-        "#,
-    )
-    .execute(&pool)
-    .await
-    .unwrap();
+    (pool, temp_file)
+}
 
+/// Save a connection for rows that reference one, returning its uuid
+async fn insert_test_connection(pool: &sqlx::SqlitePool) -> String {
+    let uuid = uuid::Uuid::new_v4().to_string();
     sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS settings (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL
-        )
-        "#,
+        "INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type) VALUES (?, 'postgres', 'Queries', 'localhost', 5432, 'db', 'user', 'pass', 'postgres')",
     )
-    .execute(&pool)
+    .bind(&uuid)
+    .execute(pool)
     .await
     .unwrap();
-
-    (pool, temp_file)
+    uuid
 }
 
 // ============================================================================
@@ -254,6 +227,137 @@ async fn test_delete_connection() {
     assert_eq!(count.0, 0, "Connection should be deleted");
 }
 
+#[tokio::test]
+async fn test_connection_default_page_size() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let uuid = uuid::Uuid::new_v4().to_string();
+
+    sqlx::query(
+        "INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type) VALUES (?, 'postgres', 'Paged', 'localhost', 5432, 'db', 'user', 'pass', 'postgres')",
+    )
+    .bind(&uuid)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    // Without a configured size the app default applies
+    assert_eq!(
+        resolve_page_size(&pool, &uuid, None).await.unwrap(),
+        DEFAULT_PAGE_SIZE
+    );
+
+    let set_page_size = |size: i64| {
+        sqlx::query("UPDATE connections SET default_page_size = ? WHERE uuid = ?")
+            .bind(size)
+            .bind(&uuid)
+            .execute(&pool)
+    };
+
+    set_page_size(25).await.unwrap();
+    assert_eq!(resolve_page_size(&pool, &uuid, None).await.unwrap(), 25);
+    // An explicit limit wins over the connection default
+    assert_eq!(resolve_page_size(&pool, &uuid, Some(10)).await.unwrap(), 10);
+
+    set_page_size(1_000_000).await.unwrap();
+    assert_eq!(
+        resolve_page_size(&pool, &uuid, None).await.unwrap(),
+        MAX_PAGE_SIZE
+    );
+}
+
+#[test]
+fn test_validate_connection_fields() {
+    let form =
+        |value: serde_json::Value| -> ConnectionFormData { serde_json::from_value(value).unwrap() };
+
+    let errors = validate_connection(form(serde_json::json!({
+        "type": "postgres",
+        "name": "No host",
+        "host": "",
+        "port": 70000,
+        "database": "db",
+        "username": "user",
+        "password": "pass",
+        "ssl": false,
+        "db_type": "postgres",
+    })));
+    let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+    assert_eq!(fields, vec!["host", "port"], "{:?}", errors);
+
+    let errors = validate_connection(form(serde_json::json!({
+        "type": "sqlite",
+        "name": "Local",
+        "host": "",
+        "port": 0,
+        "database": "",
+        "username": "",
+        "password": "",
+        "ssl": false,
+        "db_type": "sqlite",
+        "file_path": "/tmp/app.db",
+    })));
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let errors = validate_connection(form(serde_json::json!({
+        "type": "sqlite",
+        "name": "Local",
+        "host": "",
+        "port": 0,
+        "database": "",
+        "username": "",
+        "password": "",
+        "ssl": false,
+        "db_type": "sqlite",
+    })));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field, "file_path");
+
+    let ssh_form = |use_agent: bool| {
+        form(serde_json::json!({
+            "type": "postgres",
+            "name": "Bastion",
+            "host": "db.internal",
+            "port": 5432,
+            "database": "db",
+            "username": "user",
+            "password": "pass",
+            "ssl": false,
+            "db_type": "postgres",
+            "ssh_enabled": true,
+            "ssh_host": "bastion.example.com",
+            "ssh_port": 22,
+            "ssh_user": "deploy",
+            "ssh_key_path": "",
+            "ssh_use_key": true,
+            "ssh_use_agent": use_agent,
+        }))
+    };
+    let errors = validate_connection(ssh_form(false));
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].field, "ssh_key_path");
+    assert!(validate_connection(ssh_form(true)).is_empty());
+
+    let socket_form = |socket_path: &str| {
+        form(serde_json::json!({
+            "type": "postgres",
+            "name": "Local socket",
+            "host": "",
+            "port": 5432,
+            "database": "db",
+            "username": "user",
+            "password": "",
+            "ssl": false,
+            "db_type": "postgres",
+            "socket_path": socket_path,
+        }))
+    };
+    // A socket directory replaces the host
+    assert!(validate_connection(socket_form("/var/run/postgresql")).is_empty());
+    let errors = validate_connection(socket_form("run/postgresql"));
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].field, "socket_path");
+}
+
 // ============================================================================
 // Saved Query CRUD Tests
 // ============================================================================
@@ -261,7 +365,7 @@ async fn test_delete_connection() {
 #[tokio::test]
 async fn test_create_saved_query() {
     let (pool, _temp_file) = create_test_pool().await;
-    let connection_uuid = uuid::Uuid::new_v4().to_string();
+    let connection_uuid = insert_test_connection(&pool).await;
 
     let result: SavedQuery = sqlx::query_as(
         r#"
@@ -285,8 +389,8 @@ async fn test_create_saved_query() {
 #[tokio::test]
 async fn test_get_saved_queries_by_connection() {
     let (pool, _temp_file) = create_test_pool().await;
-    let conn1 = uuid::Uuid::new_v4().to_string();
-    let conn2 = uuid::Uuid::new_v4().to_string();
+    let conn1 = insert_test_connection(&pool).await;
+    let conn2 = insert_test_connection(&pool).await;
 
     // Create queries for conn1
     for i in 1..=3 {
@@ -322,7 +426,7 @@ async fn test_get_saved_queries_by_connection() {
 #[tokio::test]
 async fn test_update_saved_query() {
     let (pool, _temp_file) = create_test_pool().await;
-    let connection_uuid = uuid::Uuid::new_v4().to_string();
+    let connection_uuid = insert_test_connection(&pool).await;
 
     // Create query
     let query: SavedQuery = sqlx::query_as(
@@ -357,7 +461,7 @@ async fn test_update_saved_query() {
 #[tokio::test]
 async fn test_delete_saved_query() {
     let (pool, _temp_file) = create_test_pool().await;
-    let connection_uuid = uuid::Uuid::new_v4().to_string();
+    let connection_uuid = insert_test_connection(&pool).await;
 
     // Create query
     let query: SavedQuery = sqlx::query_as(
@@ -387,6 +491,91 @@ async fn test_delete_saved_query() {
     assert_eq!(count.0, 0);
 }
 
+/// Insert a SQLite connection whose database has a `users` table
+async fn create_sqlite_connection(pool: &sqlx::SqlitePool, db_file: &NamedTempFile) -> String {
+    let db_path = db_file.path().to_string_lossy().to_string();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: db_path.clone(),
+        session_setup_sql: None,
+    });
+    driver
+        .execute_query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+
+    let uuid = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        r#"
+        INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type, file_path)
+        VALUES (?, 'sqlite', 'SQLite DB', '', 0, '', '', '', 'sqlite', ?)
+        "#,
+    )
+    .bind(&uuid)
+    .bind(&db_path)
+    .execute(pool)
+    .await
+    .unwrap();
+    uuid
+}
+
+#[tokio::test]
+async fn test_create_saved_query_with_validation() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let db_file = NamedTempFile::new().unwrap();
+    let connection_uuid = create_sqlite_connection(&pool, &db_file).await;
+
+    let data = SavedQueryFormData {
+        name: "All users".to_string(),
+        query: "SELECT * FROM users".to_string(),
+    };
+    let saved = insert_saved_query(&pool, &connection_uuid, &data, true)
+        .await
+        .expect("Valid query should be saved");
+    assert_eq!(saved.query, "SELECT * FROM users");
+
+    // Validation parses the statement without executing it
+    let data = SavedQueryFormData {
+        name: "Wipe users".to_string(),
+        query: "DELETE FROM users".to_string(),
+    };
+    modify_saved_query(&pool, saved.id, &data, true)
+        .await
+        .expect("Valid update should be saved");
+}
+
+#[tokio::test]
+async fn test_create_saved_query_rejects_invalid_sql() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let db_file = NamedTempFile::new().unwrap();
+    let connection_uuid = create_sqlite_connection(&pool, &db_file).await;
+
+    let data = SavedQueryFormData {
+        name: "Typo".to_string(),
+        query: "SELEC * FRM users".to_string(),
+    };
+    let err = insert_saved_query(&pool, &connection_uuid, &data, true)
+        .await
+        .unwrap_err();
+    assert!(
+        err.contains("Query validation failed"),
+        "Unexpected error: {}",
+        err
+    );
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM saved_queries WHERE connection_uuid = ?")
+            .bind(&connection_uuid)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(count, 0, "Invalid query should not be saved");
+
+    // Without validation the query is stored as-is
+    insert_saved_query(&pool, &connection_uuid, &data, false)
+        .await
+        .unwrap();
+}
+
 // ============================================================================
 // Settings CRUD Tests
 // ============================================================================
@@ -460,6 +649,10 @@ async fn test_update_setting() {
 #[tokio::test]
 async fn test_get_all_settings() {
     let (pool, _temp_file) = create_test_pool().await;
+    let seeded: Vec<Setting> = sqlx::query_as("SELECT key, value FROM settings")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
 
     // Create multiple settings
     for (key, value) in [
@@ -480,7 +673,152 @@ async fn test_get_all_settings() {
         .await
         .unwrap();
 
-    assert_eq!(settings.len(), 3);
+    // The migrations seed default settings
+    assert_eq!(settings.len(), seeded.len() + 3);
+}
+
+#[tokio::test]
+async fn test_connection_variables_round_trip() {
+    let (pool, _temp_file) = create_test_pool().await;
+    let uuid = "conn-vars";
+
+    store_connection_variable(&pool, uuid, "tenant_id", Some("42".to_string()))
+        .await
+        .unwrap();
+    store_connection_variable(&pool, uuid, "region", Some("eu".to_string()))
+        .await
+        .unwrap();
+
+    let variables = load_connection_variables(&pool, uuid).await.unwrap();
+    assert_eq!(variables.get("tenant_id").map(String::as_str), Some("42"));
+    assert_eq!(variables.len(), 2);
+
+    store_connection_variable(&pool, uuid, "region", None)
+        .await
+        .unwrap();
+    let variables = load_connection_variables(&pool, uuid).await.unwrap();
+    assert!(!variables.contains_key("region"));
+
+    let other = load_connection_variables(&pool, "other-conn")
+        .await
+        .unwrap();
+    assert!(other.is_empty());
+
+    assert!(store_connection_variable(&pool, uuid, "bad name", None)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_last_query_per_connection() {
+    let (pool, _temp_file) = create_test_pool().await;
+
+    assert_eq!(load_last_query(&pool, "conn-a").await.unwrap(), None);
+
+    store_last_query(&pool, "conn-a", "SELECT 1").await.unwrap();
+    store_last_query(&pool, "conn-b", "SELECT * FROM users")
+        .await
+        .unwrap();
+    store_last_query(&pool, "conn-a", "SELECT 2").await.unwrap();
+
+    assert_eq!(
+        load_last_query(&pool, "conn-a").await.unwrap().as_deref(),
+        Some("SELECT 2")
+    );
+    assert_eq!(
+        load_last_query(&pool, "conn-b").await.unwrap().as_deref(),
+        Some("SELECT * FROM users")
+    );
+}
+
+// ============================================================================
+// Snippet Tests
+// ============================================================================
+
+fn snippet(title: &str, db_type: Option<&str>, tags: &[&str]) -> SnippetFormData {
+    SnippetFormData {
+        title: title.to_string(),
+        body: format!("-- {}", title),
+        db_type: db_type.map(str::to_string),
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+    }
+}
+
+#[tokio::test]
+async fn test_create_snippet() {
+    let (pool, _temp) = create_test_pool().await;
+
+    let created = insert_snippet(
+        &pool,
+        &snippet(
+            "Find duplicate rows",
+            Some("Postgres"),
+            &["Cleanup", "dedupe", "cleanup"],
+        ),
+    )
+    .await
+    .unwrap();
+
+    assert!(created.id > 0);
+    assert_eq!(created.title, "Find duplicate rows");
+    assert_eq!(created.db_type.as_deref(), Some("postgres"));
+    assert_eq!(created.tags, vec!["cleanup", "dedupe"]);
+
+    let updated = modify_snippet(&pool, created.id, &snippet("Find dupes", None, &[]))
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Find dupes");
+    assert!(updated.db_type.is_none());
+    assert!(updated.tags.is_empty());
+}
+
+#[tokio::test]
+async fn test_filter_snippets_by_db_type_and_tag() {
+    let (pool, _temp) = create_test_pool().await;
+
+    for data in [
+        snippet("Postgres dupes", Some("postgres"), &["dedupe"]),
+        snippet("Postgres bloat", Some("postgres"), &["maintenance"]),
+        snippet("SQLite dupes", Some("sqlite"), &["dedupe"]),
+        snippet("Count rows", None, &["dedupe"]),
+    ] {
+        insert_snippet(&pool, &data).await.unwrap();
+    }
+
+    let all = list_snippets(&pool, None, &[]).await.unwrap();
+    assert_eq!(all.len(), 4);
+
+    // Dialect-agnostic snippets apply to every database type
+    let postgres = list_snippets(&pool, Some("postgres"), &[]).await.unwrap();
+    let titles: Vec<&str> = postgres.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec!["Count rows", "Postgres bloat", "Postgres dupes"]
+    );
+
+    let dedupe = list_snippets(&pool, None, &["Dedupe".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(dedupe.len(), 3);
+    assert!(dedupe
+        .iter()
+        .all(|s| s.tags.contains(&"dedupe".to_string())));
+
+    let sqlite_dedupe = list_snippets(&pool, Some("sqlite"), &["dedupe".to_string()])
+        .await
+        .unwrap();
+    let titles: Vec<&str> = sqlite_dedupe.iter().map(|s| s.title.as_str()).collect();
+    assert_eq!(titles, vec!["Count rows", "SQLite dupes"]);
+}
+
+// ============================================================================
+// Slow Query Log Tests
+// ============================================================================
+
+#[test]
+fn test_slow_query_threshold_rejects_invalid_value() {
+    let err = slow_queries::apply_setting(SLOW_QUERY_THRESHOLD_KEY, "soon").unwrap_err();
+    assert!(err.contains(SLOW_QUERY_THRESHOLD_KEY), "{}", err);
 }
 
 // ============================================================================
@@ -698,3 +1036,259 @@ async fn test_import_multiple_name_conflicts() {
 
     assert_eq!(final_name, "Production (3)");
 }
+
+// ============================================================================
+// Encrypted Bundle Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_bundle_round_trip_and_wrong_passphrase() {
+    let (source, _source_file) = create_test_pool().await;
+    let uuid = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type) VALUES (?, 'postgres', 'Shared DB', 'db.internal', 5432, 'app', 'team', 'secret', 'postgres')",
+    )
+    .bind(&uuid)
+    .execute(&source)
+    .await
+    .unwrap();
+    insert_saved_query(
+        &source,
+        &uuid,
+        &SavedQueryFormData {
+            name: "Active users".to_string(),
+            query: "SELECT * FROM users WHERE active".to_string(),
+        },
+        false,
+    )
+    .await
+    .unwrap();
+    insert_snippet(&source, &snippet("Count rows", None, &["stats"]))
+        .await
+        .unwrap();
+
+    let bundle_file = NamedTempFile::new().unwrap();
+    let bundle_path = bundle_file.path().to_str().unwrap();
+    let exported = write_bundle(&source, bundle_path, "correct horse")
+        .await
+        .unwrap();
+    assert_eq!(
+        (
+            exported.connections,
+            exported.saved_queries,
+            exported.snippets
+        ),
+        (1, 1, 1)
+    );
+
+    // Credentials must not be readable in the file
+    let raw = std::fs::read_to_string(bundle_path).unwrap();
+    assert!(!raw.contains("secret"));
+    assert!(!raw.contains("Shared DB"));
+
+    let (target, _target_file) = create_test_pool().await;
+    let err = read_bundle(&target, bundle_path, "wrong horse")
+        .await
+        .unwrap_err();
+    assert!(err.contains("wrong passphrase"), "got: {}", err);
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM connections")
+        .fetch_one(&target)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    let imported = read_bundle(&target, bundle_path, "correct horse")
+        .await
+        .unwrap();
+    assert_eq!(
+        (
+            imported.connections,
+            imported.saved_queries,
+            imported.snippets
+        ),
+        (1, 1, 1)
+    );
+
+    let conn: Connection = sqlx::query_as("SELECT * FROM connections")
+        .fetch_one(&target)
+        .await
+        .unwrap();
+    assert_eq!(conn.name, "Shared DB");
+    assert_eq!(conn.password, "secret");
+    assert_ne!(conn.uuid, uuid);
+    let queries: Vec<SavedQuery> = sqlx::query_as("SELECT * FROM saved_queries")
+        .fetch_all(&target)
+        .await
+        .unwrap();
+    assert_eq!(queries.len(), 1);
+    assert_eq!(queries[0].connection_uuid, conn.uuid);
+
+    // Importing the same bundle again merges instead of duplicating
+    let again = read_bundle(&target, bundle_path, "correct horse")
+        .await
+        .unwrap();
+    assert_eq!(
+        (again.connections, again.saved_queries, again.snippets),
+        (0, 0, 0)
+    );
+    assert_eq!(again.skipped, 3);
+}
+
+// ============================================================================
+// Schema Migration Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_migrations_create_full_schema_on_empty_db() {
+    let (pool, _temp_file) = create_test_pool().await;
+
+    let expected: &[(&str, &[&str])] = &[
+        (
+            "connections",
+            &[
+                "id",
+                "uuid",
+                "type",
+                "name",
+                "host",
+                "port",
+                "database",
+                "username",
+                "password",
+                "ssl",
+                "created_at",
+                "updated_at",
+                "ssh_enabled",
+                "ssh_host",
+                "ssh_port",
+                "ssh_user",
+                "ssh_password",
+                "ssh_key_path",
+                "ssh_use_key",
+                "db_type",
+                "file_path",
+                "read_only",
+                "last_connected_at",
+                "query_count",
+                "query_timeout_secs",
+                "session_setup_sql",
+                "default_page_size",
+                "ssh_use_agent",
+                "max_concurrent_queries",
+                "socket_path",
+            ],
+        ),
+        (
+            "saved_queries",
+            &[
+                "id",
+                "connection_uuid",
+                "name",
+                "query",
+                "created_at",
+                "updated_at",
+            ],
+        ),
+        ("settings", &["key", "value"]),
+        (
+            "snippets",
+            &[
+                "id",
+                "title",
+                "body",
+                "db_type",
+                "tags",
+                "created_at",
+                "updated_at",
+            ],
+        ),
+        (
+            "slow_queries",
+            &[
+                "id",
+                "connection_uuid",
+                "query",
+                "duration_ms",
+                "created_at",
+            ],
+        ),
+    ];
+    for (table, columns) in expected {
+        let actual: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_info(?) ORDER BY cid")
+                .bind(table)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(&actual, columns, "columns of {}", table);
+    }
+
+    // Running again on an up-to-date database is a no-op
+    migrate(&pool).await.unwrap();
+    let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        applied as usize,
+        std::fs::read_dir("migrations").unwrap().count()
+    );
+}
+
+// ============================================================================
+// App Database Diagnostics Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_diagnose_app_db_after_seeding() {
+    let (pool, temp_file) = create_test_pool().await;
+
+    let default_settings: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM settings")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    for name in ["Primary", "Replica"] {
+        sqlx::query(
+            "INSERT INTO connections (uuid, type, name, host, port, database, username, password) VALUES (?, 'postgres', ?, 'localhost', 5432, 'app', 'user', 'pass')",
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(name)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    sqlx::query("INSERT INTO settings (key, value) VALUES ('diagnostics_test', '1')")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let report = diagnose(&pool).await.unwrap();
+    assert_eq!(report.integrity, "ok");
+
+    let migration_files = std::fs::read_dir("migrations").unwrap().count();
+    assert_eq!(report.migrations.len(), migration_files);
+    assert!(report.migrations.iter().all(|m| m.success));
+    assert_eq!(
+        report.schema_version,
+        report.migrations.iter().map(|m| m.version).max()
+    );
+
+    let rows = |table: &str| {
+        report
+            .tables
+            .iter()
+            .find(|t| t.table == table)
+            .unwrap_or_else(|| panic!("{} missing from {:?}", table, report.tables))
+            .rows
+    };
+    assert_eq!(rows("connections"), 2);
+    assert_eq!(rows("saved_queries"), 0);
+    assert_eq!(rows("settings"), default_settings + 1);
+    assert!(!report.tables.iter().any(|t| t.table == "_sqlx_migrations"));
+
+    assert!(report.file_path.is_some());
+    assert_eq!(
+        report.file_size_bytes as u64,
+        std::fs::metadata(temp_file.path()).unwrap().len()
+    );
+}
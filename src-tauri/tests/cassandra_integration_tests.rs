@@ -0,0 +1,223 @@
+//! Integration tests for the Cassandra database driver
+//!
+//! These tests verify the Cassandra driver implementation of the DatabaseDriver trait.
+//! Requires the `cassandra` feature and a running Cassandra instance at localhost:9042
+//! (use docker-compose up -d cassandra)
+//!
+//! Run with: cargo test --features cassandra --test cassandra_integration_tests -- --test-threads=1
+
+#![cfg(feature = "cassandra")]
+
+use dbcooper_lib::database::cassandra::{CassandraConfig, CassandraDriver};
+use dbcooper_lib::database::DatabaseDriver;
+
+const TEST_KEYSPACE: &str = "dbcooper_test";
+
+fn create_test_config() -> CassandraConfig {
+    CassandraConfig {
+        host: "localhost".to_string(),
+        port: 9042,
+        keyspace: TEST_KEYSPACE.to_string(),
+        username: None,
+        password: None,
+        ssl: false,
+        session_setup_sql: None,
+    }
+}
+
+/// Helper function to create a test Cassandra driver
+fn create_test_driver() -> CassandraDriver {
+    CassandraDriver::new(create_test_config())
+}
+
+/// Generate a unique test table name to avoid conflicts
+fn test_table_name(prefix: &str) -> String {
+    format!("test_{}_{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+/// Create the test keyspace and a users table with `rows` rows
+async fn create_users_table(driver: &CassandraDriver, table: &str, rows: i32) {
+    let result = driver
+        .execute_query(&format!(
+            "CREATE KEYSPACE IF NOT EXISTS {} WITH replication = {{'class': 'SimpleStrategy', 'replication_factor': 1}}",
+            TEST_KEYSPACE
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    let result = driver
+        .execute_query(&format!(
+            "CREATE TABLE {}.{} (id int PRIMARY KEY, name text, tags list<text>)",
+            TEST_KEYSPACE, table
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    for id in 1..=rows {
+        driver
+            .execute_query(&format!(
+                "INSERT INTO {}.{} (id, name, tags) VALUES ({}, 'user{}', ['a', 'b'])",
+                TEST_KEYSPACE, table, id, id
+            ))
+            .await
+            .unwrap();
+    }
+}
+
+/// Helper to clean up a test table
+async fn drop_table(driver: &CassandraDriver, table: &str) {
+    let _ = driver
+        .execute_query(&format!("DROP TABLE IF EXISTS {}.{}", TEST_KEYSPACE, table))
+        .await;
+}
+
+// ============================================================================
+// Connection Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_connection_success() {
+    let driver = create_test_driver();
+
+    let test_result = driver.test_connection().await.unwrap();
+    assert!(
+        test_result.success,
+        "Connection should succeed. Make sure Cassandra is running (docker-compose up -d cassandra). Message: {}",
+        test_result.message
+    );
+}
+
+#[tokio::test]
+async fn test_connection_failure() {
+    let config = CassandraConfig {
+        host: "localhost".to_string(),
+        port: 19042, // Wrong port
+        keyspace: TEST_KEYSPACE.to_string(),
+        username: None,
+        password: None,
+        ssl: false,
+        session_setup_sql: None,
+    };
+    let driver = CassandraDriver::new(config);
+
+    let test_result = driver.test_connection().await.unwrap();
+    assert!(
+        !test_result.success,
+        "Connection should fail with wrong port"
+    );
+    assert!(test_result.message.contains("Connection failed"));
+}
+
+#[tokio::test]
+async fn test_session_setup_consistency() {
+    let driver = CassandraDriver::new(CassandraConfig {
+        session_setup_sql: Some("CONSISTENCY QUORUM".to_string()),
+        ..create_test_config()
+    });
+    let result = driver
+        .execute_query("SELECT release_version FROM system.local")
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    let driver = CassandraDriver::new(CassandraConfig {
+        session_setup_sql: Some("CONSISTENCY SOMETIMES".to_string()),
+        ..create_test_config()
+    });
+    let result = driver
+        .execute_query("SELECT release_version FROM system.local")
+        .await
+        .unwrap();
+    assert!(result
+        .error
+        .unwrap()
+        .contains("Unknown consistency level 'SOMETIMES'"));
+}
+
+// ============================================================================
+// Table Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_tables_and_structure() {
+    let driver = create_test_driver();
+    let table = test_table_name("structure");
+    create_users_table(&driver, &table, 0).await;
+
+    let tables = driver.list_tables().await.unwrap();
+    assert!(
+        tables
+            .iter()
+            .any(|t| t.name == table && t.schema == TEST_KEYSPACE),
+        "Created table should be listed"
+    );
+
+    let structure = driver
+        .get_table_structure(TEST_KEYSPACE, &table)
+        .await
+        .unwrap();
+    assert_eq!(structure.columns[0].name, "id");
+    assert!(structure.columns[0].primary_key);
+    let tags = structure.columns.iter().find(|c| c.name == "tags").unwrap();
+    assert_eq!(tags.data_type, "list<text>");
+
+    drop_table(&driver, &table).await;
+}
+
+#[tokio::test]
+async fn test_get_table_data_paging() {
+    let driver = create_test_driver();
+    let table = test_table_name("paging");
+    create_users_table(&driver, &table, 5).await;
+
+    let page1 = driver
+        .get_table_data(TEST_KEYSPACE, &table, 1, 2, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(page1.total, 5);
+    assert_eq!(page1.data.len(), 2);
+
+    let page3 = driver
+        .get_table_data(TEST_KEYSPACE, &table, 3, 2, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(page3.data.len(), 1);
+
+    drop_table(&driver, &table).await;
+}
+
+// ============================================================================
+// Execute Query Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execute_query_select() {
+    let driver = create_test_driver();
+    let table = test_table_name("select");
+    create_users_table(&driver, &table, 1).await;
+
+    let result = driver
+        .execute_query(&format!(
+            "SELECT id, name, tags FROM {}.{} WHERE id = 1",
+            TEST_KEYSPACE, table
+        ))
+        .await
+        .unwrap();
+
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["name"], "user1");
+    assert_eq!(result.data[0]["tags"], serde_json::json!(["a", "b"]));
+
+    drop_table(&driver, &table).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_syntax_error() {
+    let driver = create_test_driver();
+
+    let result = driver.execute_query("SELEC nothing").await.unwrap();
+    assert!(result.error.is_some());
+}
@@ -5,7 +5,9 @@
 //!
 //! Run with: cargo test --test clickhouse_integration_tests -- --test-threads=1
 
-use dbcooper_lib::database::clickhouse::{ClickhouseConfig, ClickhouseDriver, ClickhouseProtocol};
+use dbcooper_lib::database::clickhouse::{
+    parse_set_statements, ClickhouseConfig, ClickhouseDriver, ClickhouseProtocol,
+};
 use dbcooper_lib::database::DatabaseDriver;
 
 /// Helper function to create a test ClickHouse driver
@@ -18,6 +20,7 @@ fn create_test_driver() -> ClickhouseDriver {
         password: "clickhouse".to_string(),
         protocol: ClickhouseProtocol::Http,
         ssl: false,
+        session_setup_sql: None,
     };
     ClickhouseDriver::new(config)
 }
@@ -64,6 +67,7 @@ async fn test_connection_failure() {
         password: "clickhouse".to_string(),
         protocol: ClickhouseProtocol::Http,
         ssl: false,
+        session_setup_sql: None,
     };
     let driver = ClickhouseDriver::new(config);
 
@@ -112,6 +116,89 @@ async fn test_list_tables() {
     drop_table(&driver, &table_name).await;
 }
 
+#[tokio::test]
+async fn test_list_tables_paged_with_filter() {
+    let driver = create_test_driver();
+    let prefix = test_table_name("paged");
+    let tables: Vec<String> = (0..6).map(|i| format!("{}_{}", prefix, i)).collect();
+
+    for table in &tables {
+        driver
+            .execute_query(&format!(
+                "CREATE TABLE `{}` (id UInt32) ENGINE = Memory",
+                table
+            ))
+            .await
+            .unwrap();
+    }
+
+    let first = driver
+        .list_tables_paged(Some(&prefix), Some(1), Some(4))
+        .await
+        .unwrap();
+    assert_eq!(first.len(), 4);
+    assert_eq!(first[0].name, tables[0]);
+
+    let second = driver
+        .list_tables_paged(Some(&prefix.to_uppercase()), Some(2), Some(4))
+        .await
+        .unwrap();
+    let names: Vec<&str> = second.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec![tables[4].as_str(), tables[5].as_str()]);
+
+    // Cleanup
+    for table in &tables {
+        drop_table(&driver, table).await;
+    }
+}
+
+#[tokio::test]
+async fn test_list_tables_reports_distributed_engine() {
+    let driver = create_test_driver();
+
+    // Needs a server with a cluster in `remote_servers`
+    let clusters = driver.get_cluster_info().await.unwrap();
+    let Some(node) = clusters.first() else {
+        eprintln!("No cluster configured on the ClickHouse server; skipping");
+        return;
+    };
+    assert!(node.shard_num >= 1);
+    assert!(node.port > 0);
+
+    let local = test_table_name("local");
+    let distributed = test_table_name("dist");
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE `{}` (id UInt64) ENGINE = MergeTree ORDER BY id",
+            local
+        ))
+        .await
+        .unwrap();
+    let created = driver
+        .execute_query(&format!(
+            "CREATE TABLE `{}` AS `{}` ENGINE = Distributed('{}', 'default', '{}', rand())",
+            distributed, local, node.cluster, local
+        ))
+        .await
+        .unwrap();
+    assert!(created.error.is_none(), "{:?}", created.error);
+
+    let tables = driver.list_tables().await.unwrap();
+    let dist_table = tables.iter().find(|t| t.name == distributed).unwrap();
+    assert_eq!(dist_table.table_type, "Distributed");
+    let engine_full = dist_table.engine_full.as_deref().unwrap();
+    assert!(engine_full.contains(&node.cluster), "{}", engine_full);
+    assert!(engine_full.contains(&local), "{}", engine_full);
+
+    // Other engines carry no definition
+    let local_table = tables.iter().find(|t| t.name == local).unwrap();
+    assert!(local_table.engine_full.is_none());
+
+    // Cleanup
+    drop_table(&driver, &distributed).await;
+    drop_table(&driver, &local).await;
+}
+
 // ============================================================================
 // Get Table Data Tests
 // ============================================================================
@@ -254,7 +341,15 @@ async fn test_get_table_data_with_filter() {
         .unwrap();
 
     let result = driver
-        .get_table_data("default", &table_name, 1, 10, Some("age > 25".to_string()), None, None)
+        .get_table_data(
+            "default",
+            &table_name,
+            1,
+            10,
+            Some("age > 25".to_string()),
+            None,
+            None,
+        )
         .await;
     assert!(result.is_ok());
 
@@ -358,6 +453,37 @@ async fn test_execute_query_select() {
     drop_table(&driver, &table_name).await;
 }
 
+#[tokio::test]
+async fn test_execute_query_reports_server_time() {
+    let driver = create_test_driver();
+
+    let result = driver
+        .execute_query("SELECT count() FROM numbers(100000)")
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "Should not have error");
+
+    let server_time = result
+        .server_time_ms
+        .expect("server_time_ms should come from X-ClickHouse-Summary");
+    assert!(
+        server_time <= result.time_taken_ms.unwrap(),
+        "Server time should not exceed round-trip time"
+    );
+}
+
+#[tokio::test]
+async fn test_transactions_not_supported() {
+    let driver = create_test_driver();
+
+    let result = driver.begin_transaction().await;
+    assert!(result.is_err());
+    assert!(result
+        .err()
+        .unwrap()
+        .contains("does not support transactions"));
+}
+
 #[tokio::test]
 async fn test_execute_query_insert() {
     let driver = create_test_driver();
@@ -541,6 +667,75 @@ async fn test_various_data_types() {
     drop_table(&driver, &table_name).await;
 }
 
+#[tokio::test]
+async fn test_map_and_tuple_types_preserved() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("nested");
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE `{}` (
+                id UInt32,
+                map_col Map(String, UInt64),
+                tuple_col Tuple(String, UInt64)
+            ) ENGINE = Memory",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    driver
+        .execute_query(&format!(
+            "INSERT INTO `{}` VALUES (1, {{'a': 1, 'b': 2}}, ('x', 7))",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver
+        .execute_query(&format!("SELECT * FROM `{}`", table_name))
+        .await
+        .unwrap();
+    let row = &result.data[0];
+
+    let map = row["map_col"].as_object().expect("Map should be an object");
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], "1");
+    assert_eq!(map["b"], "2");
+
+    let tuple = row["tuple_col"]
+        .as_array()
+        .expect("Tuple should be an array");
+    assert_eq!(tuple.len(), 2);
+    assert_eq!(tuple[0], "x");
+    assert_eq!(tuple[1], "7");
+
+    let page = driver
+        .get_table_data("default", &table_name, 1, 10, None, None, None)
+        .await
+        .unwrap();
+    assert!(page.data[0]["map_col"].is_object());
+    assert!(page.data[0]["tuple_col"].is_array());
+
+    let structure = driver
+        .get_table_structure("default", &table_name)
+        .await
+        .unwrap();
+    let column_type = |name: &str| {
+        structure
+            .columns
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.data_type.clone())
+            .unwrap()
+    };
+    assert_eq!(column_type("map_col"), "Map(String, UInt64)");
+    assert_eq!(column_type("tuple_col"), "Tuple(String, UInt64)");
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
 // ============================================================================
 // Update/Delete Isolation Tests
 // ============================================================================
@@ -732,3 +927,26 @@ async fn test_alter_delete_only_affects_targeted_row() {
     // Cleanup
     drop_table(&driver, &table_name).await;
 }
+
+// ============================================================================
+// Session Setup Tests
+// ============================================================================
+
+#[test]
+fn test_parse_set_statements_unescapes_values() {
+    let settings = parse_set_statements(
+        r"SET a = 'it\'s, ok', b = 'x\\y'; SET c = 'tab\there', d = 'o''k', e = 2",
+    )
+    .unwrap();
+
+    assert_eq!(
+        settings,
+        vec![
+            ("a".to_string(), "it's, ok".to_string()),
+            ("b".to_string(), "x\\y".to_string()),
+            ("c".to_string(), "tab\there".to_string()),
+            ("d".to_string(), "o'k".to_string()),
+            ("e".to_string(), "2".to_string()),
+        ]
+    );
+}
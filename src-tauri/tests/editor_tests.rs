@@ -0,0 +1,249 @@
+//! Tests for the query editor helpers
+//!
+//! These are pure transforms of queries and results and need no external services.
+//!
+//! Run with: cargo test --test editor_tests
+
+use dbcooper_lib::commands::editor::{affected_tables, compare_results, format_sql, lint_sql};
+use dbcooper_lib::db::models::QueryResult;
+use serde_json::{json, Value};
+
+// ============================================================================
+// format_sql Tests
+// ============================================================================
+
+#[test]
+fn test_format_sql_reformats_one_line_query() {
+    let formatted = format_sql(
+        "select id,name from users where active=true and age>21 order by name".to_string(),
+        "postgres".to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        formatted,
+        "SELECT\n  id,\n  name\nFROM\n  users\nWHERE\n  active = TRUE\n  AND age > 21\nORDER BY\n  name"
+    );
+}
+
+#[test]
+fn test_format_sql_preserves_literals_and_quoted_identifiers() {
+    let formatted = format_sql(
+        r#"select "Select Count", `from` from "My Table" where note = 'select  *  from x' -- where
+ and code = 'It''s'"#
+            .to_string(),
+        "sqlite".to_string(),
+    )
+    .unwrap();
+
+    assert!(formatted.contains("\"Select Count\""), "{}", formatted);
+    assert!(formatted.contains("`from`"), "{}", formatted);
+    assert!(formatted.contains("\"My Table\""), "{}", formatted);
+    assert!(formatted.contains("'select  *  from x'"), "{}", formatted);
+    assert!(formatted.contains("-- where"), "{}", formatted);
+    assert!(formatted.contains("'It''s'"), "{}", formatted);
+    assert!(formatted.starts_with("SELECT\n"));
+}
+
+#[test]
+fn test_format_sql_keeps_case_for_clickhouse() {
+    let formatted = format_sql(
+        "select name, toDate(created) from events where status = 1".to_string(),
+        "clickhouse".to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        formatted,
+        "select\n  name,\n  toDate(created)\nfrom\n  events\nwhere\n  status = 1"
+    );
+}
+
+#[test]
+fn test_format_sql_rejects_non_sql_dialects() {
+    let err = format_sql("GET user:1".to_string(), "redis".to_string()).unwrap_err();
+    assert!(err.contains("not supported for redis"));
+}
+
+// ============================================================================
+// lint_sql Tests
+// ============================================================================
+
+/// Rules reported for `query` on Postgres
+fn lint_rules(query: &str) -> Vec<String> {
+    lint_sql(query.to_string(), "postgres".to_string())
+        .unwrap()
+        .into_iter()
+        .map(|w| w.rule)
+        .collect()
+}
+
+#[test]
+fn test_lint_sql_select_star() {
+    assert_eq!(
+        lint_rules("SELECT * FROM users WHERE id = 1"),
+        vec!["select_star"]
+    );
+    assert_eq!(
+        lint_rules("SELECT u.* FROM users u WHERE u.id = 1"),
+        vec!["select_star"]
+    );
+    assert_eq!(
+        lint_rules("WITH recent AS (SELECT * FROM orders) SELECT id FROM recent"),
+        vec!["select_star"]
+    );
+    assert!(lint_rules("SELECT COUNT(*) FROM users").is_empty());
+}
+
+#[test]
+fn test_lint_sql_missing_where() {
+    assert_eq!(
+        lint_rules("UPDATE users SET active = false"),
+        vec!["missing_where"]
+    );
+    assert_eq!(lint_rules("DELETE FROM users"), vec!["missing_where"]);
+    assert!(lint_rules("UPDATE users SET active = false WHERE id = 1").is_empty());
+    assert!(lint_rules("DELETE FROM users WHERE id = 1").is_empty());
+}
+
+#[test]
+fn test_lint_sql_cartesian_join() {
+    assert_eq!(
+        lint_rules("SELECT u.id, o.id FROM users u, orders o"),
+        vec!["cartesian_join"]
+    );
+    assert!(
+        lint_rules("SELECT u.id, o.id FROM users u, orders o WHERE o.user_id = u.id").is_empty()
+    );
+    assert!(
+        lint_rules("SELECT u.id, o.id FROM users u JOIN orders o ON o.user_id = u.id").is_empty()
+    );
+}
+
+#[test]
+fn test_lint_sql_clean_query_has_no_warnings() {
+    let warnings = lint_sql(
+        "SELECT id, name FROM users WHERE active = true ORDER BY name LIMIT 10".to_string(),
+        "sqlite".to_string(),
+    )
+    .unwrap();
+    assert!(warnings.is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn test_lint_sql_reports_syntax_errors() {
+    let warnings = lint_sql("SELEC id FROM".to_string(), "postgres".to_string()).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].rule, "syntax");
+}
+
+// ============================================================================
+// affected_tables Tests
+// ============================================================================
+
+#[test]
+fn test_affected_tables_update() {
+    assert_eq!(
+        affected_tables("UPDATE users SET active = false WHERE id = 1", "postgres"),
+        vec!["users"]
+    );
+    assert_eq!(
+        affected_tables("UPDATE public.users SET active = false", "postgres"),
+        vec!["public.users"]
+    );
+}
+
+#[test]
+fn test_affected_tables_delete_with_subquery() {
+    assert_eq!(
+        affected_tables(
+            "DELETE FROM orders WHERE customer_id IN (SELECT id FROM customers WHERE banned)",
+            "postgres"
+        ),
+        vec!["orders"]
+    );
+}
+
+#[test]
+fn test_affected_tables_multiple_statements() {
+    let tables = affected_tables(
+        "INSERT INTO audit (note) VALUES ('x'); TRUNCATE sessions; \
+         ALTER TABLE audit ADD COLUMN at TEXT; SELECT * FROM users",
+        "sqlite",
+    );
+    assert_eq!(tables, vec!["audit", "sessions"]);
+}
+
+#[test]
+fn test_affected_tables_empty_for_reads_and_unparsable_queries() {
+    assert!(affected_tables("SELECT * FROM users", "postgres").is_empty());
+    assert!(affected_tables("UPDATE users SET", "postgres").is_empty());
+    assert!(affected_tables("SET mykey 1", "redis").is_empty());
+}
+
+// ============================================================================
+// compare_results Tests
+// ============================================================================
+
+fn result(rows: Vec<Value>) -> QueryResult {
+    QueryResult {
+        row_count: rows.len() as i64,
+        data: rows,
+        error: None,
+        time_taken_ms: None,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_compare_results_reports_added_removed_and_changed_rows() {
+    let left = result(vec![
+        json!({"id": 1, "name": "alice", "total": 10}),
+        json!({"id": 2, "name": "bob", "total": 20}),
+        json!({"id": 3, "name": "carol", "total": 30}),
+    ]);
+    // Same rows in another order, with bob removed, carol changed and dave added
+    let right = result(vec![
+        json!({"id": 4, "name": "dave", "total": 40}),
+        json!({"id": 3, "name": "carol", "total": 35}),
+        json!({"id": 1, "name": "alice", "total": 10}),
+    ]);
+
+    let comparison = compare_results(left, right, vec!["id".to_string()]).unwrap();
+
+    assert_eq!(comparison.unchanged, 1);
+    assert_eq!(
+        comparison.only_left,
+        vec![json!({"id": 2, "name": "bob", "total": 20})]
+    );
+    assert_eq!(
+        comparison.only_right,
+        vec![json!({"id": 4, "name": "dave", "total": 40})]
+    );
+    assert_eq!(comparison.changed.len(), 1);
+    assert_eq!(comparison.changed[0].left["total"], json!(30));
+    assert_eq!(comparison.changed[0].right["total"], json!(35));
+    assert_eq!(comparison.changed[0].columns, vec!["total".to_string()]);
+}
+
+#[test]
+fn test_compare_results_without_keys_matches_whole_rows() {
+    let left = result(vec![json!({"n": 1}), json!({"n": 1}), json!({"n": 2})]);
+    let right = result(vec![json!({"n": 2}), json!({"n": 1}), json!({"n": 3})]);
+
+    let comparison = compare_results(left, right, vec![]).unwrap();
+
+    assert_eq!(comparison.unchanged, 2);
+    assert_eq!(comparison.only_left, vec![json!({"n": 1})]);
+    assert_eq!(comparison.only_right, vec![json!({"n": 3})]);
+    assert!(comparison.changed.is_empty());
+}
+
+#[test]
+fn test_compare_results_rejects_unknown_key_column() {
+    let left = result(vec![json!({"id": 1})]);
+    let right = result(vec![json!({"id": 1})]);
+
+    let err = compare_results(left, right, vec!["uuid".to_string()]).unwrap_err();
+    assert!(err.contains("'uuid'"), "{}", err);
+}
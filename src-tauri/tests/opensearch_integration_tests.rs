@@ -0,0 +1,258 @@
+//! Integration tests for the OpenSearch database driver
+//!
+//! These tests verify the OpenSearch driver implementation of the DatabaseDriver trait.
+//! Requires a running OpenSearch instance at localhost:9200 (use docker-compose up -d opensearch)
+//!
+//! Run with: cargo test --test opensearch_integration_tests -- --test-threads=1
+
+use dbcooper_lib::database::opensearch::{OpenSearchConfig, OpenSearchDriver};
+use dbcooper_lib::database::DatabaseDriver;
+use serde_json::{json, Value};
+
+const BASE_URL: &str = "http://localhost:9200";
+
+/// Helper function to create a test OpenSearch driver
+fn create_test_driver() -> OpenSearchDriver {
+    let config = OpenSearchConfig {
+        host: "localhost".to_string(),
+        port: 9200,
+        username: None,
+        password: None,
+        ssl: false,
+    };
+    OpenSearchDriver::new(config)
+}
+
+/// Generate a unique test index name to avoid conflicts
+fn test_index_name(prefix: &str) -> String {
+    format!("test_{}_{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+/// Create an index with the given documents; the driver itself is read-only
+async fn seed_index(index: &str, docs: &[Value]) {
+    let client = reqwest::Client::new();
+    client
+        .put(format!("{}/{}", BASE_URL, index))
+        .send()
+        .await
+        .expect("Failed to create index");
+
+    for (i, doc) in docs.iter().enumerate() {
+        client
+            .put(format!(
+                "{}/{}/_doc/{}?refresh=true",
+                BASE_URL,
+                index,
+                i + 1
+            ))
+            .json(doc)
+            .send()
+            .await
+            .expect("Failed to index document");
+    }
+}
+
+/// Helper to clean up a test index
+async fn delete_index(index: &str) {
+    let _ = reqwest::Client::new()
+        .delete(format!("{}/{}", BASE_URL, index))
+        .send()
+        .await;
+}
+
+// ============================================================================
+// Connection Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_connection_success() {
+    let driver = create_test_driver();
+
+    let test_result = driver.test_connection().await.unwrap();
+    assert!(
+        test_result.success,
+        "Connection should succeed. Make sure OpenSearch is running (docker-compose up -d opensearch). Message: {}",
+        test_result.message
+    );
+    assert_eq!(test_result.message, "Connection successful!");
+}
+
+#[tokio::test]
+async fn test_connection_failure() {
+    let config = OpenSearchConfig {
+        host: "localhost".to_string(),
+        port: 19200, // Wrong port
+        username: None,
+        password: None,
+        ssl: false,
+    };
+    let driver = OpenSearchDriver::new(config);
+
+    let test_result = driver.test_connection().await.unwrap();
+    assert!(
+        !test_result.success,
+        "Connection should fail with wrong port"
+    );
+    assert!(test_result.message.contains("Connection failed"));
+}
+
+// ============================================================================
+// List Tables Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_tables_includes_index() {
+    let driver = create_test_driver();
+    let index = test_index_name("list");
+    seed_index(&index, &[json!({ "name": "a" })]).await;
+
+    let tables = driver.list_tables().await.unwrap();
+    let found = tables.iter().find(|t| t.name == index);
+    assert!(found.is_some(), "Seeded index should be listed");
+    assert_eq!(found.unwrap().table_type, "index");
+    assert!(
+        tables.iter().all(|t| !t.name.starts_with('.')),
+        "Hidden system indices should not be listed"
+    );
+
+    delete_index(&index).await;
+}
+
+// ============================================================================
+// Table Data Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_table_data_flattens_source() {
+    let driver = create_test_driver();
+    let index = test_index_name("data");
+    seed_index(
+        &index,
+        &[json!({ "name": "Alice", "address": { "city": "Paris" } })],
+    )
+    .await;
+
+    let result = driver
+        .get_table_data("opensearch", &index, 1, 10, None, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.total, 1);
+    assert_eq!(result.data.len(), 1);
+    let row = &result.data[0];
+    assert_eq!(row["_id"], "1");
+    assert_eq!(row["name"], "Alice");
+    assert_eq!(row["address.city"], "Paris");
+
+    delete_index(&index).await;
+}
+
+#[tokio::test]
+async fn test_get_table_data_pagination() {
+    let driver = create_test_driver();
+    let index = test_index_name("paging");
+    let docs: Vec<Value> = (1..=5).map(|i| json!({ "n": i })).collect();
+    seed_index(&index, &docs).await;
+
+    let page1 = driver
+        .get_table_data(
+            "opensearch",
+            &index,
+            1,
+            2,
+            None,
+            Some("n".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(page1.total, 5);
+    assert_eq!(page1.data.len(), 2);
+    assert_eq!(page1.data[0]["n"], 1);
+
+    let page3 = driver
+        .get_table_data(
+            "opensearch",
+            &index,
+            3,
+            2,
+            None,
+            Some("n".to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(page3.data.len(), 1);
+    assert_eq!(page3.data[0]["n"], 5);
+
+    delete_index(&index).await;
+}
+
+// ============================================================================
+// Table Structure Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_table_structure_from_mapping() {
+    let driver = create_test_driver();
+    let index = test_index_name("structure");
+    seed_index(
+        &index,
+        &[json!({ "name": "Alice", "age": 30, "address": { "city": "Paris" } })],
+    )
+    .await;
+
+    let structure = driver
+        .get_table_structure("opensearch", &index)
+        .await
+        .unwrap();
+
+    let id_col = structure.columns.iter().find(|c| c.name == "_id").unwrap();
+    assert!(id_col.primary_key);
+
+    let age_col = structure.columns.iter().find(|c| c.name == "age").unwrap();
+    assert_eq!(age_col.data_type, "long");
+    assert!(
+        structure.columns.iter().any(|c| c.name == "address.city"),
+        "Object fields should be flattened"
+    );
+
+    delete_index(&index).await;
+}
+
+// ============================================================================
+// Execute Query Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execute_query_dsl() {
+    let driver = create_test_driver();
+    let index = test_index_name("query");
+    seed_index(
+        &index,
+        &[json!({ "name": "Alice" }), json!({ "name": "Bob" })],
+    )
+    .await;
+
+    let query = json!({
+        "index": index,
+        "query": { "match": { "name": "Bob" } }
+    });
+    let result = driver.execute_query(&query.to_string()).await.unwrap();
+
+    assert!(result.error.is_none(), "Query failed: {:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["name"], "Bob");
+    assert_eq!(result.data[0]["_index"], index);
+
+    delete_index(&index).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_invalid_json() {
+    let driver = create_test_driver();
+
+    let result = driver.execute_query("{ not json").await.unwrap();
+    assert!(result.error.is_some());
+    assert!(result.error.unwrap().contains("Invalid JSON query"));
+}
@@ -0,0 +1,322 @@
+//! Integration tests for the Oracle database driver
+//!
+//! These tests verify the Oracle driver implementation of the DatabaseDriver trait.
+//! Requires the `oracle` feature, the Oracle Instant Client and a running Oracle XE instance
+//! at localhost:1521 with the XEPDB1 service (use docker-compose up -d oracle)
+//!
+//! Run with: cargo test --features oracle --test oracle_integration_tests -- --test-threads=1
+
+#![cfg(feature = "oracle")]
+
+use dbcooper_lib::database::oracle::{OracleConfig, OracleDriver, OracleService};
+use dbcooper_lib::database::DatabaseDriver;
+use std::time::Duration;
+
+fn create_test_config() -> OracleConfig {
+    OracleConfig {
+        host: "localhost".to_string(),
+        port: 1521,
+        service: OracleService::ServiceName("XEPDB1".to_string()),
+        username: "dbcooper".to_string(),
+        password: "dbcooper".to_string(),
+        ssl: false,
+        session_setup_sql: None,
+    }
+}
+
+/// Helper function to create a test Oracle driver
+fn create_test_driver() -> OracleDriver {
+    OracleDriver::new(create_test_config())
+}
+
+/// Generate a unique test table name to avoid conflicts. Oracle folds unquoted names to
+/// upper case, so the name is upper case too.
+fn test_table_name(prefix: &str) -> String {
+    format!(
+        "TEST_{}_{}",
+        prefix.to_uppercase(),
+        &uuid::Uuid::new_v4().simple().to_string()[..12].to_uppercase()
+    )
+}
+
+/// Helper to clean up a test table
+async fn drop_table(driver: &OracleDriver, table: &str) {
+    let _ = driver
+        .execute_query(&format!("DROP TABLE {} CASCADE CONSTRAINTS PURGE", table))
+        .await;
+}
+
+// ============================================================================
+// Configuration Tests
+// ============================================================================
+
+#[test]
+fn test_service_parse_accepts_service_name_or_sid() {
+    assert_eq!(
+        OracleService::parse("XEPDB1"),
+        OracleService::ServiceName("XEPDB1".to_string())
+    );
+    assert_eq!(
+        OracleService::parse("sid:XE"),
+        OracleService::Sid("XE".to_string())
+    );
+    assert_eq!(
+        OracleService::parse(" SID: ORCL "),
+        OracleService::Sid("ORCL".to_string())
+    );
+}
+
+#[test]
+fn test_connect_descriptor() {
+    let mut config = create_test_config();
+    assert_eq!(
+        config.connect_descriptor(Duration::from_secs(10)),
+        "(DESCRIPTION=(CONNECT_TIMEOUT=10)(ADDRESS=(PROTOCOL=TCP)(HOST=localhost)(PORT=1521))(CONNECT_DATA=(SERVICE_NAME=XEPDB1)))"
+    );
+
+    config.service = OracleService::Sid("XE".to_string());
+    config.ssl = true;
+    assert_eq!(
+        config.connect_descriptor(Duration::from_secs(5)),
+        "(DESCRIPTION=(CONNECT_TIMEOUT=5)(ADDRESS=(PROTOCOL=TCPS)(HOST=localhost)(PORT=1521))(CONNECT_DATA=(SID=XE)))"
+    );
+}
+
+// ============================================================================
+// Connection Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_connection_success() {
+    let driver = create_test_driver();
+
+    let result = driver.test_connection().await.unwrap();
+    assert!(
+        result.success,
+        "Connection should succeed. Make sure Oracle is running (docker-compose up -d oracle). Message: {}",
+        result.message
+    );
+}
+
+#[tokio::test]
+async fn test_connection_failure() {
+    let mut config = create_test_config();
+    config.port = 11521; // Wrong port
+    let driver = OracleDriver::new(config);
+
+    let result = driver.test_connection().await.unwrap();
+    assert!(!result.success, "Connection should fail with wrong port");
+    assert!(result.message.contains("Connection failed"));
+}
+
+#[tokio::test]
+async fn test_session_setup_runs_each_statement() {
+    let mut config = create_test_config();
+    config.session_setup_sql = Some(
+        "ALTER SESSION SET NLS_NUMERIC_CHARACTERS = ',.';\nALTER SESSION SET TIME_ZONE = '+02:00';"
+            .to_string(),
+    );
+    let driver = OracleDriver::new(config);
+
+    let result = driver
+        .execute_query("SELECT TO_CHAR(1.5) AS v, SESSIONTIMEZONE AS tz FROM dual")
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.data[0]["V"], "1,5");
+    assert_eq!(result.data[0]["TZ"], "+02:00");
+}
+
+// ============================================================================
+// Table Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_list_tables_and_structure() {
+    let driver = create_test_driver();
+    let parent = test_table_name("parent");
+    let child = test_table_name("child");
+
+    for statement in [
+        format!("CREATE TABLE {} (id NUMBER(10) PRIMARY KEY)", parent),
+        format!(
+            "CREATE TABLE {} (\
+               id NUMBER GENERATED ALWAYS AS IDENTITY PRIMARY KEY, \
+               parent_id NUMBER(10) REFERENCES {}(id), \
+               code VARCHAR2(20) NOT NULL, \
+               price NUMBER(8,2) DEFAULT 0, \
+               doubled NUMBER GENERATED ALWAYS AS (price * 2) VIRTUAL, \
+               CONSTRAINT {}_code_uq UNIQUE (code), \
+               CONSTRAINT {}_price_ck CHECK (price >= 0))",
+            child, parent, child, child
+        ),
+        format!("COMMENT ON TABLE {} IS 'child rows'", child),
+    ] {
+        let result = driver.execute_query(&statement).await.unwrap();
+        assert!(result.error.is_none(), "{}: {:?}", statement, result.error);
+    }
+
+    let tables = driver.list_tables().await.unwrap();
+    let listed = tables
+        .iter()
+        .find(|t| t.name == child)
+        .expect("child table should be listed");
+    assert_eq!(listed.schema, "DBCOOPER");
+    assert_eq!(listed.table_type, "table");
+    assert_eq!(listed.comment.as_deref(), Some("child rows"));
+
+    let structure = driver.get_table_structure("", &child).await.unwrap();
+    let names: Vec<&str> = structure.columns.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["ID", "PARENT_ID", "CODE", "PRICE", "DOUBLED"]);
+
+    let id = &structure.columns[0];
+    assert!(id.primary_key && id.auto_increment && !id.nullable);
+    assert_eq!(structure.columns[1].data_type, "NUMBER(10)");
+    assert_eq!(structure.columns[2].data_type, "VARCHAR2(20)");
+    assert!(!structure.columns[2].nullable);
+    assert_eq!(structure.columns[3].data_type, "NUMBER(8,2)");
+    assert_eq!(structure.columns[3].default.as_deref(), Some("0"));
+    assert!(structure.columns[4].is_generated);
+    assert_eq!(structure.columns[4].default, None);
+
+    assert_eq!(structure.foreign_keys.len(), 1);
+    assert_eq!(structure.foreign_keys[0].column, "PARENT_ID");
+    assert_eq!(structure.foreign_keys[0].references_table, parent);
+    assert_eq!(structure.foreign_keys[0].references_column, "ID");
+
+    assert_eq!(structure.unique_constraints.len(), 1);
+    assert_eq!(structure.unique_constraints[0].columns, vec!["CODE"]);
+    assert_eq!(structure.check_constraints.len(), 1);
+    assert_eq!(structure.check_constraints[0].expression, "price >= 0");
+    assert!(structure
+        .indexes
+        .iter()
+        .any(|index| index.primary && index.columns == vec!["ID"]));
+
+    assert!(driver
+        .get_table_structure("", "NO_SUCH_TABLE")
+        .await
+        .is_err());
+
+    drop_table(&driver, &child).await;
+    drop_table(&driver, &parent).await;
+}
+
+#[tokio::test]
+async fn test_get_table_data_paging_and_sorting() {
+    let driver = create_test_driver();
+    let table = test_table_name("paging");
+
+    for statement in [
+        format!(
+            "CREATE TABLE {} (id NUMBER(10) PRIMARY KEY, name VARCHAR2(50))",
+            table
+        ),
+        format!(
+            "INSERT INTO {} SELECT level, 'user ' || level FROM dual CONNECT BY level <= 25",
+            table
+        ),
+    ] {
+        let result = driver.execute_query(&statement).await.unwrap();
+        assert!(result.error.is_none(), "{}: {:?}", statement, result.error);
+    }
+
+    let page = driver
+        .get_table_data(
+            "DBCOOPER",
+            &table,
+            2,
+            10,
+            None,
+            Some("ID".to_string()),
+            Some("desc".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.total, 25);
+    let ids: Vec<i64> = page
+        .data
+        .iter()
+        .map(|row| row["ID"].as_i64().unwrap())
+        .collect();
+    assert_eq!(ids, (6..=15).rev().collect::<Vec<_>>());
+
+    let filtered = driver
+        .get_table_data("", &table, 1, 10, Some("id > 20".to_string()), None, None)
+        .await
+        .unwrap();
+    assert_eq!(filtered.total, 5);
+    assert_eq!(filtered.data.len(), 5);
+
+    drop_table(&driver, &table).await;
+}
+
+// ============================================================================
+// Execute Query Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execute_query_select_types() {
+    let driver = create_test_driver();
+
+    let result = driver
+        .execute_query(
+            "SELECT 42 AS int_value, 12345678901234567890.5 AS big_value, \
+             CAST(1.5 AS BINARY_DOUBLE) AS float_value, 'text' AS text_value, \
+             DATE '2024-01-02' AS date_value, HEXTORAW('CAFE') AS raw_value, \
+             NULL AS null_value FROM dual;",
+        )
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.row_count, 1);
+
+    let row = &result.data[0];
+    assert_eq!(row["INT_VALUE"], 42);
+    assert_eq!(row["BIG_VALUE"], "12345678901234567890.5");
+    assert_eq!(row["FLOAT_VALUE"], 1.5);
+    assert_eq!(row["TEXT_VALUE"], "text");
+    assert_eq!(row["DATE_VALUE"], "2024-01-02 00:00:00");
+    assert_eq!(row["RAW_VALUE"], "0xcafe");
+    assert!(row["NULL_VALUE"].is_null());
+}
+
+#[tokio::test]
+async fn test_execute_query_runs_plsql_blocks() {
+    let driver = create_test_driver();
+    let table = test_table_name("plsql");
+
+    let result = driver
+        .execute_query(&format!("CREATE TABLE {} (id NUMBER)", table))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    // The block keeps its closing semicolon, and the insert is committed
+    let result = driver
+        .execute_query(&format!(
+            "BEGIN FOR i IN 1..3 LOOP INSERT INTO {} VALUES (i); END LOOP; END;",
+            table
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    let other = create_test_driver();
+    let result = other
+        .execute_query(&format!("SELECT COUNT(*) AS n FROM {}", table))
+        .await
+        .unwrap();
+    assert_eq!(result.data[0]["N"], 3);
+
+    drop_table(&driver, &table).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_syntax_error() {
+    let driver = create_test_driver();
+
+    let result = driver.execute_query("SELEC 1 FROM dual").await.unwrap();
+    assert!(result.error.is_some(), "Should have error for invalid SQL");
+    assert!(result.data.is_empty());
+}
@@ -0,0 +1,302 @@
+//! Tests for the connection pool manager
+//!
+//! These tests use an in-process mock driver and need no external services.
+//!
+//! Run with: cargo test --test pool_manager_tests
+
+use async_trait::async_trait;
+use dbcooper_lib::database::pool_manager::{spawn_keepalive, ConnectionConfig, PoolManager};
+use dbcooper_lib::database::DatabaseDriver;
+use dbcooper_lib::db::models::{
+    QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure, TestConnectionResult,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Simulates a server that silently drops connections idle for longer than `idle_limit`
+struct IdleDroppingDriver {
+    last_activity: Mutex<Instant>,
+    idle_limit: Duration,
+}
+
+impl IdleDroppingDriver {
+    fn new(idle_limit: Duration) -> Self {
+        Self {
+            last_activity: Mutex::new(Instant::now()),
+            idle_limit,
+        }
+    }
+
+    fn use_connection(&self) -> Result<(), String> {
+        let mut last_activity = self.last_activity.lock().unwrap();
+        if last_activity.elapsed() > self.idle_limit {
+            return Err("Connection reset by peer".to_string());
+        }
+        *last_activity = Instant::now();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DatabaseDriver for IdleDroppingDriver {
+    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+        Ok(match self.use_connection() {
+            Ok(()) => TestConnectionResult {
+                success: true,
+                message: "Connection successful!".to_string(),
+            },
+            Err(e) => TestConnectionResult {
+                success: false,
+                message: e,
+            },
+        })
+    }
+
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        Ok(vec![])
+    }
+
+    async fn get_table_data(
+        &self,
+        _schema: &str,
+        _table: &str,
+        page: i64,
+        limit: i64,
+        _filter: Option<String>,
+        _sort_column: Option<String>,
+        _sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, String> {
+        Ok(TableDataResponse {
+            data: vec![],
+            total: 0,
+            page,
+            limit,
+        })
+    }
+
+    async fn get_table_structure(
+        &self,
+        _schema: &str,
+        _table: &str,
+    ) -> Result<TableStructure, String> {
+        Ok(TableStructure {
+            columns: vec![],
+            indexes: vec![],
+            foreign_keys: vec![],
+            check_constraints: vec![],
+            unique_constraints: vec![],
+            warnings: vec![],
+        })
+    }
+
+    async fn execute_query(&self, _query: &str) -> Result<QueryResult, String> {
+        self.use_connection()?;
+        Ok(QueryResult {
+            data: vec![],
+            row_count: 0,
+            error: None,
+            time_taken_ms: None,
+            ..Default::default()
+        })
+    }
+
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+        Ok(SchemaOverview { tables: vec![] })
+    }
+}
+
+// ============================================================================
+// Keepalive Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_idle_connection_drops_without_keepalive() {
+    let driver = IdleDroppingDriver::new(Duration::from_millis(100));
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    assert!(driver.execute_query("SELECT 1").await.is_err());
+}
+
+#[tokio::test]
+async fn test_keepalive_prevents_idle_drop() {
+    let driver: Arc<Box<dyn DatabaseDriver>> = Arc::new(Box::new(IdleDroppingDriver::new(
+        Duration::from_millis(100),
+    )));
+    let keepalive = spawn_keepalive(driver.clone(), Duration::from_millis(30));
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let result = driver.execute_query("SELECT 1").await;
+    assert!(
+        result.is_ok(),
+        "Query after idle period failed: {:?}",
+        result.err()
+    );
+
+    // Once stopped, the connection goes stale again
+    keepalive.abort();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(driver.execute_query("SELECT 1").await.is_err());
+}
+
+// ============================================================================
+// Idle Eviction Tests
+// ============================================================================
+
+fn sqlite_config(file_path: &str) -> ConnectionConfig {
+    ConnectionConfig {
+        db_type: "sqlite".to_string(),
+        host: None,
+        port: None,
+        database: None,
+        username: None,
+        password: None,
+        ssl: None,
+        file_path: Some(file_path.to_string()),
+        ssh_enabled: false,
+        ssh_host: None,
+        ssh_port: None,
+        ssh_user: None,
+        ssh_password: None,
+        ssh_key_path: None,
+        ssh_use_agent: false,
+        session_setup_sql: None,
+        max_concurrent_queries: None,
+        socket_path: None,
+    }
+}
+
+#[tokio::test]
+async fn test_idle_connection_is_evicted_and_reopened() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let config = sqlite_config(file.path().to_str().unwrap());
+    let manager = PoolManager::new();
+    let ttl = Duration::from_millis(100);
+
+    let first = manager
+        .get_connection("idle", config.clone())
+        .await
+        .unwrap();
+
+    // A connection in use is kept
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(manager.get_cached("idle").await.is_some());
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(manager.evict_idle(ttl).await.is_empty());
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(manager.evict_idle(ttl).await, vec!["idle".to_string()]);
+    assert!(manager.get_cached("idle").await.is_none());
+
+    // The next use opens a fresh driver
+    let second = manager.get_connection("idle", config).await.unwrap();
+    assert!(!Arc::ptr_eq(&first, &second));
+    assert!(second.execute_query("SELECT 1").await.is_ok());
+}
+
+// ============================================================================
+// Query Concurrency Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_concurrent_queries_queue_behind_limit() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let mut config = sqlite_config(file.path().to_str().unwrap());
+    config.max_concurrent_queries = Some(2);
+    let manager = Arc::new(PoolManager::new());
+    manager.connect("limited", config).await.unwrap();
+
+    // Each query counts through a recursive CTE so several overlap in time
+    let query = "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 20000) \
+                 SELECT count(*) AS total FROM n";
+    let tasks: Vec<_> = (0..20)
+        .map(|_| {
+            let manager = manager.clone();
+            tokio::spawn(async move { manager.execute_query("limited", query).await })
+        })
+        .collect();
+
+    for task in tasks {
+        let result = task.await.unwrap().expect("query should wait for a slot");
+        assert!(result.error.is_none(), "{:?}", result.error);
+        assert_eq!(result.data[0]["total"], 20000);
+    }
+}
+
+// ============================================================================
+// SSH Agent Tests
+// ============================================================================
+
+// Both tests read SSH_AUTH_SOCK, and one of them changes it
+static SSH_AUTH_SOCK_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+fn agent_postgres_config(ssh_host: &str, ssh_user: &str) -> ConnectionConfig {
+    ConnectionConfig {
+        db_type: "postgres".to_string(),
+        host: Some("localhost".to_string()),
+        port: Some(5432),
+        database: Some("testdb".to_string()),
+        username: Some("postgres".to_string()),
+        password: Some("postgres".to_string()),
+        ssl: Some(false),
+        file_path: None,
+        ssh_enabled: true,
+        ssh_host: Some(ssh_host.to_string()),
+        ssh_port: Some(22),
+        ssh_user: Some(ssh_user.to_string()),
+        ssh_password: None,
+        ssh_key_path: None,
+        ssh_use_agent: true,
+        session_setup_sql: None,
+        max_concurrent_queries: None,
+        socket_path: None,
+    }
+}
+
+#[tokio::test]
+async fn test_ssh_agent_missing_socket_is_reported() {
+    let _guard = SSH_AUTH_SOCK_LOCK.lock().await;
+    let previous = std::env::var_os("SSH_AUTH_SOCK");
+    std::env::set_var("SSH_AUTH_SOCK", "/nonexistent/dbcooper-agent.sock");
+
+    let result = PoolManager::new()
+        .connect("agent", agent_postgres_config("127.0.0.1", "nobody"))
+        .await;
+
+    match previous {
+        Some(value) => std::env::set_var("SSH_AUTH_SOCK", value),
+        None => std::env::remove_var("SSH_AUTH_SOCK"),
+    }
+    let err = result.err().expect("connect should fail without an agent");
+    assert!(err.contains("SSH agent is not available"), "{}", err);
+}
+
+/// Needs a running ssh-agent holding a key the SSH server accepts, and a PostgreSQL
+/// instance reachable from that server at localhost:5432. Set DBCOOPER_SSH_TEST_HOST
+/// and DBCOOPER_SSH_TEST_USER to run it.
+#[tokio::test]
+async fn test_ssh_agent_tunnel_connects() {
+    let _guard = SSH_AUTH_SOCK_LOCK.lock().await;
+    let (Ok(ssh_host), Ok(ssh_user)) = (
+        std::env::var("DBCOOPER_SSH_TEST_HOST"),
+        std::env::var("DBCOOPER_SSH_TEST_USER"),
+    ) else {
+        eprintln!("Skipping: DBCOOPER_SSH_TEST_HOST / DBCOOPER_SSH_TEST_USER not set");
+        return;
+    };
+    if std::env::var_os("SSH_AUTH_SOCK").is_none() {
+        eprintln!("Skipping: no ssh-agent (SSH_AUTH_SOCK not set)");
+        return;
+    }
+
+    let manager = PoolManager::new();
+    let driver = manager
+        .connect("agent", agent_postgres_config(&ssh_host, &ssh_user))
+        .await
+        .expect("connect through the agent-authenticated tunnel");
+    let result = driver.execute_query("SELECT 1 AS one").await.unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    manager.disconnect("agent").await;
+}
@@ -5,8 +5,19 @@
 //!
 //! Run with: cargo test --test postgres_integration_tests -- --test-threads=1
 
-use dbcooper_lib::database::postgres::PostgresDriver;
-use dbcooper_lib::database::{DatabaseDriver, PostgresConfig};
+use dbcooper_lib::commands::database::{
+    find_referencing_rows, test_connection_deep, undo_last_edit_with_driver,
+};
+use dbcooper_lib::database::notices::{self, NoticeLayer, NOTICE_TARGET};
+use dbcooper_lib::database::pool_manager::{ConnectionConfig, PoolManager};
+use dbcooper_lib::database::postgres::{section_or_warning, PostgresDriver};
+use dbcooper_lib::database::table_watch::start_table_watch;
+use dbcooper_lib::database::undo::{RowEdit, UndoEntry, UndoHistory};
+use dbcooper_lib::database::{display, timeouts, DatabaseDriver, PostgresConfig};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 /// Helper function to create a test PostgreSQL driver
 fn create_test_driver() -> PostgresDriver {
@@ -17,10 +28,17 @@ fn create_test_driver() -> PostgresDriver {
         username: "postgres".to_string(),
         password: "postgres".to_string(),
         ssl: false,
+        socket_path: None,
+        session_setup_sql: None,
     };
     PostgresDriver::new(config)
 }
 
+/// Register the notice layer, as the app does at startup
+fn install_notice_layer() {
+    let _ = tracing_subscriber::registry().with(NoticeLayer).try_init();
+}
+
 /// Generate a unique test table name to avoid conflicts
 fn test_table_name(prefix: &str) -> String {
     format!("test_{}_{}", prefix, uuid::Uuid::new_v4().simple())
@@ -62,6 +80,8 @@ async fn test_connection_failure() {
         username: "postgres".to_string(),
         password: "postgres".to_string(),
         ssl: false,
+        socket_path: None,
+        session_setup_sql: None,
     };
     let driver = PostgresDriver::new(config);
 
@@ -79,6 +99,293 @@ async fn test_connection_failure() {
     );
 }
 
+#[tokio::test]
+async fn test_connection_wrong_password_reports_authentication() {
+    let config = PostgresConfig {
+        host: "localhost".to_string(),
+        port: 5432,
+        database: "testdb".to_string(),
+        username: "postgres".to_string(),
+        password: "definitely-not-the-password".to_string(),
+        ssl: false,
+        socket_path: None,
+        session_setup_sql: None,
+    };
+    let driver = PostgresDriver::new(config);
+
+    let test_result = driver.test_connection().await.unwrap();
+    assert!(!test_result.success, "Wrong password should be rejected");
+    assert!(
+        test_result.message.contains("Authentication failed"),
+        "Message should mention authentication: {}",
+        test_result.message
+    );
+}
+
+#[test]
+fn test_socket_path_connection_string() {
+    let socket_config = |host: &str, socket_path: Option<&str>| PostgresConfig {
+        host: host.to_string(),
+        port: 5432,
+        database: "testdb".to_string(),
+        username: "postgres".to_string(),
+        password: "postgres".to_string(),
+        ssl: false,
+        socket_path: socket_path.map(str::to_string),
+        session_setup_sql: None,
+    };
+
+    let dsn = PostgresDriver::new(socket_config("", Some("/var/run/postgresql")))
+        .build_connection_string()
+        .unwrap();
+    assert_eq!(
+        dsn,
+        "postgres://postgres:postgres@%2Fvar%2Frun%2Fpostgresql:5432/testdb?sslmode=disable"
+    );
+
+    // A host that is a directory is treated as a socket, like libpq
+    let dsn = PostgresDriver::new(socket_config("/tmp", None))
+        .build_connection_string()
+        .unwrap();
+    assert!(dsn.contains("@%2Ftmp:5432/"), "{}", dsn);
+
+    let err = PostgresDriver::new(socket_config("db.example.com", Some("/var/run/postgresql")))
+        .build_connection_string()
+        .unwrap_err();
+    assert!(err.contains("use one or the other"), "{}", err);
+
+    let err = PostgresDriver::new(socket_config("", Some("run/postgresql")))
+        .build_connection_string()
+        .unwrap_err();
+    assert!(err.contains("absolute"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_session_setup_sql_sets_search_path() {
+    let driver = create_test_driver();
+    let schema = test_table_name("setup_schema");
+    driver
+        .execute_query(&format!("CREATE SCHEMA {}", schema))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE {}.setup_marker (label TEXT)",
+            schema
+        ))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "INSERT INTO {}.setup_marker VALUES ('configured')",
+            schema
+        ))
+        .await
+        .unwrap();
+
+    let configured = PostgresDriver::new(PostgresConfig {
+        host: "localhost".to_string(),
+        port: 5432,
+        database: "testdb".to_string(),
+        username: "postgres".to_string(),
+        password: "postgres".to_string(),
+        ssl: false,
+        socket_path: None,
+        session_setup_sql: Some(format!("SET search_path TO {}", schema)),
+    });
+    let result = configured
+        .execute_query("SELECT label FROM setup_marker")
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    assert_eq!(result.data[0]["label"], "configured");
+
+    let _ = driver
+        .execute_query(&format!("DROP SCHEMA {} CASCADE", schema))
+        .await;
+}
+
+#[tokio::test]
+async fn test_connect_timeout_setting_applies() {
+    timeouts::apply_setting(timeouts::CONNECT_TIMEOUT_KEY, "1").unwrap();
+
+    // Non-routable address: the connection attempt hangs until the timeout fires
+    let driver = PostgresDriver::new(PostgresConfig {
+        host: "10.255.255.1".to_string(),
+        port: 5432,
+        database: "testdb".to_string(),
+        username: "postgres".to_string(),
+        password: "postgres".to_string(),
+        ssl: false,
+        socket_path: None,
+        session_setup_sql: None,
+    });
+    timeouts::apply_setting(timeouts::CONNECT_TIMEOUT_KEY, "").unwrap();
+
+    let start = std::time::Instant::now();
+    let result = driver.test_connection().await.unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(!result.success, "Blackholed host should not connect");
+    assert!(
+        result.message.contains("timed out after 1 seconds"),
+        "Unexpected message: {}",
+        result.message
+    );
+    assert!(
+        elapsed < std::time::Duration::from_secs(5),
+        "Should fail after ~1s, took {:?}",
+        elapsed
+    );
+}
+
+#[tokio::test]
+async fn test_query_timeout_reported_in_result() {
+    let driver = create_test_driver();
+
+    let result = timeouts::with_timeout(
+        Some(std::time::Duration::from_secs(1)),
+        driver.execute_query("SELECT pg_sleep(10)"),
+    )
+    .await
+    .expect("Timeouts are reported in the result");
+
+    let error = result.error.expect("pg_sleep should hit the timeout");
+    assert!(
+        error.starts_with(timeouts::TIMEOUT_ERROR_PREFIX),
+        "Unexpected error: {}",
+        error
+    );
+    let elapsed_ms = result
+        .time_taken_ms
+        .expect("Elapsed time should be reported");
+    assert!(
+        (1000..5000).contains(&elapsed_ms),
+        "Elapsed {}ms",
+        elapsed_ms
+    );
+}
+
+#[tokio::test]
+async fn test_backend_pid() {
+    let driver = create_test_driver();
+
+    let pid = driver
+        .backend_pid()
+        .await
+        .expect("Should fetch backend PID");
+    assert!(pid > 0, "Backend PID should be positive, got {}", pid);
+}
+
+#[tokio::test]
+async fn test_pooled_backend_pid_is_a_live_session() {
+    let manager = PoolManager::new();
+    manager
+        .connect(
+            "pid",
+            ConnectionConfig {
+                db_type: "postgres".to_string(),
+                host: Some("localhost".to_string()),
+                port: Some(5432),
+                database: Some("testdb".to_string()),
+                username: Some("postgres".to_string()),
+                password: Some("postgres".to_string()),
+                ssl: Some(false),
+                file_path: None,
+                ssh_enabled: false,
+                ssh_host: None,
+                ssh_port: None,
+                ssh_user: None,
+                ssh_password: None,
+                ssh_key_path: None,
+                ssh_use_agent: false,
+                session_setup_sql: None,
+                max_concurrent_queries: None,
+                socket_path: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let pid = manager.backend_pid("pid").await.unwrap();
+    assert!(pid > 0, "Backend PID should be positive, got {}", pid);
+
+    // The backend belongs to the pool, so it is still open after the call returns
+    let result = manager
+        .execute_query(
+            "pid",
+            &format!(
+                "SELECT count(*) AS sessions FROM pg_stat_activity WHERE pid = {}",
+                pid
+            ),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.data[0]["sessions"], 1);
+    manager.disconnect("pid").await;
+}
+
+#[tokio::test]
+async fn test_get_database_info() {
+    let driver = create_test_driver();
+
+    let info = driver.get_database_info().await.unwrap();
+
+    assert_eq!(info.encoding.as_deref(), Some("UTF8"));
+    assert!(info.collation.is_some_and(|c| !c.is_empty()));
+    assert!(info.timezone.is_some_and(|tz| !tz.is_empty()));
+}
+
+#[tokio::test]
+async fn test_connection_deep_postgres_role_without_select() {
+    let admin = create_test_driver();
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    // Sorts before `public`, so it is the first table the role lists
+    let schema = format!("a_probe_{}", suffix);
+    let role = format!("probe_{}", suffix);
+    for statement in [
+        format!("CREATE ROLE {} LOGIN PASSWORD 'probe_pw'", role),
+        format!("CREATE SCHEMA {}", schema),
+        format!("CREATE TABLE {}.secrets (id INT)", schema),
+        format!("GRANT USAGE ON SCHEMA {} TO {}", schema, role),
+        // Lets the role see the table but not read it
+        format!("GRANT INSERT ON {}.secrets TO {}", schema, role),
+    ] {
+        let result = admin.execute_query(&statement).await.unwrap();
+        assert!(result.error.is_none(), "{:?}", result.error);
+    }
+
+    let result = test_connection_deep(
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some(role.clone()),
+        Some("probe_pw".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let _ = admin
+        .execute_query(&format!("DROP SCHEMA {} CASCADE", schema))
+        .await;
+    let _ = admin.execute_query(&format!("DROP ROLE {}", role)).await;
+
+    assert!(result.can_connect, "{}", result.message);
+    assert!(result.can_list, "{}", result.message);
+    assert!(!result.can_read, "{}", result.message);
+    assert_eq!(result.probed_table, Some(format!("{}.secrets", schema)));
+    assert!(
+        result.message.contains("permission denied"),
+        "{}",
+        result.message
+    );
+}
+
 // ============================================================================
 // List Tables Tests
 // ============================================================================
@@ -169,6 +476,49 @@ async fn test_list_tables_includes_views() {
     drop_table(&driver, &table_name).await;
 }
 
+#[tokio::test]
+async fn test_list_tables_materialized_view_type() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("mv_src");
+    let view_name = format!("{}_mv", table_name);
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    driver
+        .execute_query(&format!(
+            "CREATE MATERIALIZED VIEW \"{}\" AS SELECT * FROM \"{}\"",
+            view_name, table_name
+        ))
+        .await
+        .unwrap();
+
+    let tables = driver.list_tables().await.unwrap();
+    let view = tables
+        .iter()
+        .find(|t| t.name == view_name)
+        .expect("Should include materialized view");
+    assert_eq!(view.table_type, "materialized_view");
+
+    let table = tables.iter().find(|t| t.name == table_name).unwrap();
+    assert_eq!(table.table_type, "table");
+
+    // Cleanup
+    driver
+        .execute_query(&format!(
+            "DROP MATERIALIZED VIEW IF EXISTS \"{}\"",
+            view_name
+        ))
+        .await
+        .unwrap();
+    drop_table(&driver, &table_name).await;
+}
+
 // ============================================================================
 // Get Table Data Tests
 // ============================================================================
@@ -469,6 +819,7 @@ async fn test_get_table_structure_foreign_keys() {
 
     let fk = &structure.foreign_keys[0];
     assert_eq!(fk.column, "parent_id");
+    assert_eq!(fk.references_schema, "public");
     assert_eq!(fk.references_table, parent_table);
     assert_eq!(fk.references_column, "id");
 
@@ -477,248 +828,548 @@ async fn test_get_table_structure_foreign_keys() {
     drop_table(&driver, &parent_table).await;
 }
 
-// ============================================================================
-// Execute Query Tests
-// ============================================================================
+#[tokio::test]
+async fn test_find_referencing_rows_matches_referenced_schema() {
+    let driver = create_test_driver();
+    let schema_a = test_table_name("schema_a");
+    let schema_b = test_table_name("schema_b");
+
+    // Both schemas have a `parent` table, but only schema_b's is referenced
+    for statement in [
+        format!("CREATE SCHEMA \"{}\"", schema_a),
+        format!("CREATE SCHEMA \"{}\"", schema_b),
+        format!("CREATE TABLE \"{}\".parent (id INTEGER PRIMARY KEY)", schema_a),
+        format!("CREATE TABLE \"{}\".parent (id INTEGER PRIMARY KEY)", schema_b),
+        format!(
+            "CREATE TABLE \"{0}\".child (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES \"{0}\".parent(id))",
+            schema_b
+        ),
+        format!("INSERT INTO \"{}\".parent VALUES (1)", schema_a),
+        format!("INSERT INTO \"{}\".parent VALUES (1)", schema_b),
+        format!("INSERT INTO \"{}\".child VALUES (10, 1)", schema_b),
+    ] {
+        let result = driver.execute_query(&statement).await.unwrap();
+        assert!(result.error.is_none(), "{}: {:?}", statement, result.error);
+    }
+
+    let find = |schema: String| {
+        find_referencing_rows(
+            "postgres".to_string(),
+            Some("localhost".to_string()),
+            Some(5432),
+            Some("testdb".to_string()),
+            Some("postgres".to_string()),
+            Some("postgres".to_string()),
+            Some(false),
+            None,
+            None,
+            None,
+            schema,
+            "parent".to_string(),
+            "id".to_string(),
+            json!(1),
+            None,
+        )
+    };
+
+    let groups = find(schema_b.clone()).await.unwrap();
+    assert_eq!(groups.len(), 1, "{:?}", groups);
+    assert_eq!(groups[0].schema, schema_b);
+    assert_eq!(groups[0].data[0]["id"], 10);
+
+    let groups = find(schema_a.clone()).await.unwrap();
+    assert!(groups.is_empty(), "{:?}", groups);
+
+    for schema in [schema_a, schema_b] {
+        let _ = driver
+            .execute_query(&format!("DROP SCHEMA \"{}\" CASCADE", schema))
+            .await;
+    }
+}
 
 #[tokio::test]
-async fn test_execute_query_select() {
+async fn test_get_table_structure_column_comments() {
     let driver = create_test_driver();
-    let table_name = test_table_name("select");
+    let table_name = test_table_name("comment");
 
-    // Create table with data
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, email TEXT)",
             table_name
         ))
         .await
         .unwrap();
-
     driver
         .execute_query(&format!(
-            "INSERT INTO \"{}\" (name) VALUES ('Test')",
+            "COMMENT ON COLUMN \"{}\".email IS 'Primary contact address'",
             table_name
         ))
         .await
         .unwrap();
 
-    let result = driver
-        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
-        .await;
-    assert!(result.is_ok());
+    let structure = driver
+        .get_table_structure("public", &table_name)
+        .await
+        .unwrap();
 
-    let query_result = result.unwrap();
-    assert!(query_result.error.is_none(), "Should not have error");
-    assert_eq!(query_result.row_count, 1);
-    assert!(!query_result.data.is_empty());
-    assert!(query_result.time_taken_ms.is_some());
+    let email_col = structure
+        .columns
+        .iter()
+        .find(|c| c.name == "email")
+        .unwrap();
+    assert_eq!(
+        email_col.comment.as_deref(),
+        Some("Primary contact address")
+    );
+
+    let id_col = structure.columns.iter().find(|c| c.name == "id").unwrap();
+    assert!(id_col.comment.is_none(), "id has no comment");
 
     // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
 #[tokio::test]
-async fn test_execute_query_insert() {
+async fn test_get_table_structure_enum_values() {
     let driver = create_test_driver();
-    let table_name = test_table_name("insert");
+    let table_name = test_table_name("enum");
+    let enum_name = format!("{}_mood", table_name);
 
-    // Create table
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
-            table_name
+            "CREATE TYPE \"{}\" AS ENUM ('sad', 'ok', 'happy')",
+            enum_name
         ))
         .await
         .unwrap();
-
-    // Insert returns the inserted row in PostgreSQL with RETURNING
-    let result = driver
+    driver
         .execute_query(&format!(
-            "INSERT INTO \"{}\" (name) VALUES ('Test') RETURNING *",
-            table_name
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, mood \"{}\")",
+            table_name, enum_name
         ))
-        .await;
-    assert!(result.is_ok());
+        .await
+        .unwrap();
 
-    let query_result = result.unwrap();
-    assert!(query_result.error.is_none(), "INSERT should succeed");
-    assert_eq!(query_result.row_count, 1);
+    let structure = driver
+        .get_table_structure("public", &table_name)
+        .await
+        .unwrap();
+
+    let mood_col = structure.columns.iter().find(|c| c.name == "mood").unwrap();
+    assert_eq!(
+        mood_col.enum_values,
+        Some(vec![
+            "sad".to_string(),
+            "ok".to_string(),
+            "happy".to_string()
+        ])
+    );
+
+    let id_col = structure.columns.iter().find(|c| c.name == "id").unwrap();
+    assert!(id_col.enum_values.is_none(), "id is not an enum");
 
     // Cleanup
     drop_table(&driver, &table_name).await;
+    let _ = driver
+        .execute_query(&format!("DROP TYPE IF EXISTS \"{}\"", enum_name))
+        .await;
 }
 
 #[tokio::test]
-async fn test_execute_query_update() {
+async fn test_get_table_structure_auto_increment() {
     let driver = create_test_driver();
-    let table_name = test_table_name("update");
+    let table_name = test_table_name("autoinc");
 
-    // Create table with data
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, \
+             ident INTEGER GENERATED ALWAYS AS IDENTITY, quantity INTEGER)",
             table_name
         ))
         .await
         .unwrap();
 
-    driver
-        .execute_query(&format!(
-            "INSERT INTO \"{}\" (name, age) VALUES ('Test', 25)",
-            table_name
-        ))
+    let structure = driver
+        .get_table_structure("public", &table_name)
         .await
         .unwrap();
+    let column = |name: &str| structure.columns.iter().find(|c| c.name == name).unwrap();
 
-    // Update with RETURNING
-    let result = driver
-        .execute_query(&format!(
-            "UPDATE \"{}\" SET age = 30 WHERE name = 'Test' RETURNING *",
-            table_name
-        ))
-        .await;
-    assert!(result.is_ok());
-    assert!(result.unwrap().error.is_none());
-
-    // Verify update
-    let select_result = driver
-        .execute_query(&format!(
-            "SELECT age FROM \"{}\" WHERE name = 'Test'",
-            table_name
-        ))
-        .await
-        .unwrap();
-    let age = select_result.data[0].get("age").unwrap().as_i64().unwrap();
-    assert_eq!(age, 30);
+    assert!(column("id").auto_increment, "SERIAL id should be flagged");
+    assert!(
+        column("ident").auto_increment,
+        "identity column should be flagged"
+    );
+    assert!(
+        !column("quantity").auto_increment,
+        "plain integer should not be flagged"
+    );
 
-    // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
 #[tokio::test]
-async fn test_execute_query_delete() {
+async fn test_get_table_structure_check_constraints() {
     let driver = create_test_driver();
-    let table_name = test_table_name("delete");
+    let table_name = test_table_name("checks");
 
-    // Create table with data
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, \
+             age INTEGER CONSTRAINT age_non_negative CHECK (age >= 0))",
             table_name
         ))
         .await
         .unwrap();
 
-    driver
-        .execute_query(&format!(
-            "INSERT INTO \"{}\" (name) VALUES ('Test')",
-            table_name
-        ))
+    let structure = driver
+        .get_table_structure("public", &table_name)
         .await
         .unwrap();
 
-    // Delete with RETURNING
-    let result = driver
+    assert_eq!(structure.check_constraints.len(), 1);
+    let check = &structure.check_constraints[0];
+    assert_eq!(check.name, "age_non_negative");
+    assert!(
+        check.expression.contains("age >= 0"),
+        "Unexpected expression: {}",
+        check.expression
+    );
+
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_get_table_structure_unique_constraints() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("uniques");
+
+    driver
         .execute_query(&format!(
-            "DELETE FROM \"{}\" WHERE name = 'Test' RETURNING *",
+            "CREATE TABLE \"{0}\" (id SERIAL PRIMARY KEY, tenant INTEGER, email TEXT, \
+             CONSTRAINT tenant_email_key UNIQUE (tenant, email))",
             table_name
         ))
-        .await;
-    assert!(result.is_ok());
-    assert!(result.unwrap().error.is_none());
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "CREATE UNIQUE INDEX \"{0}_email_idx\" ON \"{0}\" (lower(email))",
+            table_name
+        ))
+        .await
+        .unwrap();
 
-    // Verify delete
-    let select_result = driver
-        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
+    let structure = driver
+        .get_table_structure("public", &table_name)
         .await
         .unwrap();
-    assert_eq!(select_result.row_count, 0);
 
-    // Cleanup
+    // Only the named constraint is reported; the standalone unique index is not
+    assert_eq!(structure.unique_constraints.len(), 1);
+    let constraint = &structure.unique_constraints[0];
+    assert_eq!(constraint.name, "tenant_email_key");
+    assert_eq!(constraint.columns, vec!["tenant", "email"]);
+
+    // The constraint's backing index stays in the index list
+    assert!(structure
+        .indexes
+        .iter()
+        .any(|i| i.name == "tenant_email_key" && i.unique));
+
     drop_table(&driver, &table_name).await;
 }
 
 #[tokio::test]
-async fn test_execute_query_syntax_error() {
+async fn test_get_table_structure_flags_generated_columns() {
     let driver = create_test_driver();
+    let table_name = test_table_name("generated");
 
-    let result = driver.execute_query("SELECTTTT * FROM nonexistent").await;
-    assert!(result.is_ok());
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, price INTEGER, doubled INTEGER GENERATED ALWAYS AS (price * 2) STORED)",
+            table_name
+        ))
+        .await
+        .unwrap();
 
-    let query_result = result.unwrap();
+    let structure = driver
+        .get_table_structure("public", &table_name)
+        .await
+        .unwrap();
+
+    let doubled = structure
+        .columns
+        .iter()
+        .find(|c| c.name == "doubled")
+        .unwrap();
+    assert!(doubled.is_generated);
+    assert!(!doubled.auto_increment);
+
+    let id = structure.columns.iter().find(|c| c.name == "id").unwrap();
+    assert!(!id.is_generated);
+    assert!(id.auto_increment);
+
+    drop_table(&driver, &table_name).await;
+}
+
+#[test]
+fn test_structure_section_permission_error_becomes_warning() {
+    let mut warnings = Vec::new();
+
+    // A role that cannot read pg_index loses the index section, not the whole structure
+    let columns = section_or_warning("columns", Ok(vec!["id", "name"]), &mut warnings).unwrap();
+    let indexes: Vec<&str> = section_or_warning(
+        "indexes",
+        Err("error returned from database: permission denied for table pg_index".to_string()),
+        &mut warnings,
+    )
+    .unwrap();
+
+    assert_eq!(columns, vec!["id", "name"]);
+    assert!(indexes.is_empty());
+    assert_eq!(warnings.len(), 1);
     assert!(
-        query_result.error.is_some(),
-        "Should have error for invalid SQL"
+        warnings[0].starts_with("Could not read indexes"),
+        "{}",
+        warnings[0]
+    );
+
+    // Anything other than a privilege error still fails the fetch
+    let result: Result<Vec<&str>, String> = section_or_warning(
+        "foreign keys",
+        Err("connection closed".to_string()),
+        &mut warnings,
+    );
+    assert!(result.is_err());
+    assert_eq!(warnings.len(), 1);
+}
+
+#[tokio::test]
+async fn test_mixed_case_table_name_resolves_to_lowercase() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("casefold");
+    let mixed_case = table_name.to_uppercase();
+
+    // Unquoted, so Postgres stores the name lowercased
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE {} (id SERIAL PRIMARY KEY, name TEXT)",
+            mixed_case
+        ))
+        .await
+        .expect("Failed to create test table");
+    driver
+        .execute_query(&format!("INSERT INTO {} (name) VALUES ('a')", mixed_case))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        driver
+            .resolve_table_name("public", &mixed_case)
+            .await
+            .unwrap(),
+        table_name
+    );
+
+    let data = driver
+        .get_table_data("public", &mixed_case, 1, 10, None, None, None)
+        .await
+        .expect("Mixed-case name should find the lowercase table");
+    assert_eq!(data.total, 1);
+
+    let structure = driver
+        .get_table_structure("public", &mixed_case)
+        .await
+        .unwrap();
+    assert_eq!(structure.columns.len(), 2);
+    assert!(
+        structure.warnings.iter().any(|w| w.contains(&table_name)),
+        "Warnings should name the matched table: {:?}",
+        structure.warnings
+    );
+
+    // A quoted mixed-case table is matched exactly
+    let quoted = format!("Quoted_{}", table_name);
+    driver
+        .execute_query(&format!("CREATE TABLE \"{}\" (id INT)", quoted))
+        .await
+        .unwrap();
+    assert_eq!(
+        driver.resolve_table_name("public", &quoted).await.unwrap(),
+        quoted
+    );
+
+    drop_table(&driver, &table_name).await;
+    drop_table(&driver, &quoted).await;
+}
+
+#[tokio::test]
+async fn test_get_partitions_of_range_partitioned_table() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("partitioned");
+
+    for statement in [
+        "CREATE TABLE {0} (id INT, created DATE) PARTITION BY RANGE (created)",
+        "CREATE TABLE {0}_2024 PARTITION OF {0} FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')",
+        "CREATE TABLE {0}_2025 PARTITION OF {0} FOR VALUES FROM ('2025-01-01') TO ('2026-01-01')",
+    ] {
+        let result = driver
+            .execute_query(&statement.replace("{0}", &table_name))
+            .await
+            .unwrap();
+        assert!(result.error.is_none(), "{:?}", result.error);
+    }
+
+    let partitions = driver.get_partitions("public", &table_name).await.unwrap();
+    assert_eq!(partitions.strategy.as_deref(), Some("range"));
+    assert_eq!(partitions.partition_key.as_deref(), Some("RANGE (created)"));
+
+    let names: Vec<&str> = partitions
+        .partitions
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            format!("{}_2024", table_name),
+            format!("{}_2025", table_name)
+        ]
+    );
+    assert!(partitions.partitions[0].bound.contains("2024-01-01"));
+    assert!(partitions.partitions.iter().all(|p| !p.is_partitioned));
+
+    // A child partition is an ordinary table
+    let child = driver
+        .get_partitions("public", &format!("{}_2024", table_name))
+        .await
+        .unwrap();
+    assert!(child.strategy.is_none());
+    assert!(child.partitions.is_empty());
+
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_column_statistics_after_analyze() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("stats");
+
+    // Every fourth `bucket` is NULL
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE {} AS SELECT g AS id, CASE WHEN g % 4 = 0 THEN NULL ELSE g % 10 END AS bucket FROM generate_series(1, 1000) g",
+            table_name
+        ))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!("ANALYZE {}", table_name))
+        .await
+        .unwrap();
+
+    let stats = driver
+        .column_statistics("public", &table_name)
+        .await
+        .unwrap();
+    let bucket = stats.iter().find(|s| s.column == "bucket").unwrap();
+    assert!(
+        (bucket.null_fraction - 0.25).abs() < 0.05,
+        "null fraction: {}",
+        bucket.null_fraction
     );
+    assert_eq!(bucket.distinct_estimate, 10);
+    assert_eq!(bucket.source, "statistics");
+
+    let id = stats.iter().find(|s| s.column == "id").unwrap();
+    assert_eq!(id.null_fraction, 0.0);
+    assert_eq!(id.distinct_estimate, 1000);
+
+    drop_table(&driver, &table_name).await;
 }
 
 // ============================================================================
-// Get Schema Overview Tests
+// Execute Query Tests
 // ============================================================================
 
 #[tokio::test]
-async fn test_get_schema_overview() {
+async fn test_execute_query_select() {
     let driver = create_test_driver();
-    let table_name = test_table_name("schema");
+    let table_name = test_table_name("select");
 
-    // Create a table
+    // Create table with data
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (
-                id SERIAL PRIMARY KEY,
-                name TEXT NOT NULL
-            )",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
             table_name
         ))
         .await
         .unwrap();
 
-    let result = driver.get_schema_overview().await;
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name) VALUES ('Test')",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver
+        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
+        .await;
     assert!(result.is_ok());
 
-    let overview = result.unwrap();
-    assert!(!overview.tables.is_empty(), "Should have tables");
+    let query_result = result.unwrap();
+    assert!(query_result.error.is_none(), "Should not have error");
+    assert_eq!(query_result.row_count, 1);
+    assert!(!query_result.data.is_empty());
+    assert!(query_result.time_taken_ms.is_some());
 
-    // Find our test table
-    let test_table = overview.tables.iter().find(|t| t.name == table_name);
-    assert!(test_table.is_some(), "Should include test table");
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
 
-    let test_table = test_table.unwrap();
-    assert_eq!(test_table.schema, "public");
-    assert_eq!(test_table.columns.len(), 2);
+#[tokio::test]
+async fn test_execute_query_insert() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("insert");
+
+    // Create table
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Insert returns the inserted row in PostgreSQL with RETURNING
+    let result = driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name) VALUES ('Test') RETURNING *",
+            table_name
+        ))
+        .await;
+    assert!(result.is_ok());
+
+    let query_result = result.unwrap();
+    assert!(query_result.error.is_none(), "INSERT should succeed");
+    assert_eq!(query_result.row_count, 1);
 
     // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
-// ============================================================================
-// Data Type Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_various_data_types() {
+async fn test_execute_query_update() {
     let driver = create_test_driver();
-    let table_name = test_table_name("types");
+    let table_name = test_table_name("update");
 
-    // Create table with various PostgreSQL types
+    // Create table with data
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (
-                int2_col SMALLINT,
-                int4_col INTEGER,
-                int8_col BIGINT,
-                float4_col REAL,
-                float8_col DOUBLE PRECISION,
-                numeric_col NUMERIC(10,2),
-                text_col TEXT,
-                varchar_col VARCHAR(255),
-                bool_col BOOLEAN,
-                date_col DATE,
-                timestamp_col TIMESTAMP,
-                json_col JSONB,
-                uuid_col UUID
-            )",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
             table_name
         ))
         .await
@@ -726,258 +1377,1422 @@ async fn test_various_data_types() {
 
     driver
         .execute_query(&format!(
-            "INSERT INTO \"{}\" VALUES (
-                1, 2, 3, 1.5, 2.5, 100.50, 'hello', 'world', true,
-                '2024-01-01', '2024-01-01 12:00:00', '{{\"key\": \"value\"}}',
-                'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'
-            )",
+            "INSERT INTO \"{}\" (name, age) VALUES ('Test', 25)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Update with RETURNING
+    let result = driver
+        .execute_query(&format!(
+            "UPDATE \"{}\" SET age = 30 WHERE name = 'Test' RETURNING *",
+            table_name
+        ))
+        .await;
+    assert!(result.is_ok());
+    assert!(result.unwrap().error.is_none());
+
+    // Verify update
+    let select_result = driver
+        .execute_query(&format!(
+            "SELECT age FROM \"{}\" WHERE name = 'Test'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    let age = select_result.data[0].get("age").unwrap().as_i64().unwrap();
+    assert_eq!(age, 30);
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_delete() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("delete");
+
+    // Create table with data
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name) VALUES ('Test')",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Delete with RETURNING
+    let result = driver
+        .execute_query(&format!(
+            "DELETE FROM \"{}\" WHERE name = 'Test' RETURNING *",
+            table_name
+        ))
+        .await;
+    assert!(result.is_ok());
+    assert!(result.unwrap().error.is_none());
+
+    // Verify delete
+    let select_result = driver
+        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+    assert_eq!(select_result.row_count, 0);
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_syntax_error() {
+    let driver = create_test_driver();
+
+    let result = driver.execute_query("SELECTTTT * FROM nonexistent").await;
+    assert!(result.is_ok());
+
+    let query_result = result.unwrap();
+    assert!(
+        query_result.error.is_some(),
+        "Should have error for invalid SQL"
+    );
+}
+
+#[tokio::test]
+async fn test_execute_query_captures_notices() {
+    install_notice_layer();
+    let driver = create_test_driver();
+
+    let result = driver
+        .execute_query("DO $$ BEGIN RAISE NOTICE 'hi'; RAISE WARNING 'careful'; END $$;")
+        .await
+        .unwrap();
+    assert!(
+        result.error.is_none(),
+        "DO block failed: {:?}",
+        result.error
+    );
+    assert_eq!(
+        result.notices,
+        vec!["hi".to_string(), "careful".to_string()]
+    );
+
+    // Notices from one query do not leak into the next
+    let result = driver.execute_query("SELECT 1 AS one").await.unwrap();
+    assert!(result.notices.is_empty());
+}
+
+#[tokio::test]
+async fn test_notices_ignore_other_tracing_events() {
+    install_notice_layer();
+
+    let ((), collected) = notices::collect(async {
+        tracing::warn!(target: "dbcooper::cassandra", "driver warning");
+        tracing::info!(target: NOTICE_TARGET, "hi");
+    })
+    .await;
+    assert_eq!(collected, vec!["hi".to_string()]);
+}
+
+// ============================================================================
+// Bind Parameter Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execute_query_with_params_filters_rows() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("params");
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, name TEXT, active BOOLEAN, created DATE)",
+            table_name
+        ))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" VALUES (1, 'alice', true, '2024-01-01'), (2, 'bob', false, '2024-02-01'), (3, 'carol', true, '2024-03-01')",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver
+        .execute_query_with_params(
+            &format!(
+                "SELECT name FROM \"{}\" WHERE id > $1 AND active = $2 AND created >= $3",
+                table_name
+            ),
+            &[json!(1), json!(true), json!("2024-02-15")],
+        )
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "Query failed: {:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["name"], "carol");
+
+    // Injection attempts are matched literally against the text column
+    let result = driver
+        .execute_query_with_params(
+            &format!("SELECT * FROM \"{}\" WHERE name = $1", table_name),
+            &[json!(format!("x'; DROP TABLE \"{}\"; --", table_name))],
+        )
+        .await
+        .unwrap();
+    assert!(result.error.is_none());
+    assert_eq!(result.row_count, 0);
+
+    let result = driver
+        .execute_query(&format!("SELECT COUNT(*) AS count FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+    assert_eq!(result.data[0]["count"], 3);
+
+    let err = driver
+        .execute_query_with_params(
+            &format!("SELECT * FROM \"{}\" WHERE id = $1", table_name),
+            &[],
+        )
+        .await
+        .expect_err("Missing parameter should be rejected");
+    assert!(
+        err.contains("expects 1 parameter(s) but 0 were provided"),
+        "{}",
+        err
+    );
+
+    let err = driver
+        .execute_query_with_params(
+            &format!("SELECT * FROM \"{}\" WHERE id = $1", table_name),
+            &[json!("not a number")],
+        )
+        .await
+        .expect_err("Non-numeric value for an integer placeholder should be rejected");
+    assert!(err.contains("Parameter $1 expects int4"), "{}", err);
+
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_execute_query_with_params_numeric_is_exact() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("params_numeric");
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, amount NUMERIC(40, 20))",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // More digits than an f64 holds; a float round trip would change the stored value
+    let amount = "12345678901234567890.12345678901234567891";
+    let result = driver
+        .execute_query_with_params(
+            &format!("INSERT INTO \"{}\" VALUES (1, $1)", table_name),
+            &[json!(amount)],
+        )
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "Insert failed: {:?}", result.error);
+
+    let result = driver
+        .execute_query_with_params(
+            &format!(
+                "SELECT amount::text AS amount FROM \"{}\" WHERE amount = $1",
+                table_name
+            ),
+            &[json!(amount)],
+        )
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "Query failed: {:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["amount"], amount);
+
+    let err = driver
+        .execute_query_with_params(
+            &format!("SELECT * FROM \"{}\" WHERE amount = $1", table_name),
+            &[json!("lots")],
+        )
+        .await
+        .expect_err("Non-numeric value for a numeric placeholder should be rejected");
+    assert!(err.contains("Parameter $1 expects numeric"), "{}", err);
+
+    drop_table(&driver, &table_name).await;
+}
+
+// ============================================================================
+// Get Schema Overview Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_schema_overview() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("schema");
+
+    // Create a table
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (
+                id SERIAL PRIMARY KEY,
+                name TEXT NOT NULL
+            )",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver.get_schema_overview().await;
+    assert!(result.is_ok());
+
+    let overview = result.unwrap();
+    assert!(!overview.tables.is_empty(), "Should have tables");
+
+    // Find our test table
+    let test_table = overview.tables.iter().find(|t| t.name == table_name);
+    assert!(test_table.is_some(), "Should include test table");
+
+    let test_table = test_table.unwrap();
+    assert_eq!(test_table.schema, "public");
+    assert_eq!(test_table.columns.len(), 2);
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+// ============================================================================
+// Export Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_export_table_csv_round_trips_quotes_and_newlines() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("export");
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, body TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let bodies = [
+        "plain",
+        "say \"hello\", world",
+        "line one\nline two",
+        "it's \"quoted\"\r\nand, split",
+    ];
+    for (i, body) in bodies.iter().enumerate() {
+        driver
+            .execute_query(&format!(
+                "INSERT INTO \"{}\" (id, body) VALUES ({}, '{}')",
+                table_name,
+                i + 1,
+                body.replace('\'', "''")
+            ))
+            .await
+            .unwrap();
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let output_path = dir.path().join("export.csv");
+    let summary = driver
+        .export_table_csv(
+            "public",
+            &table_name,
+            output_path.to_str().unwrap(),
+            &CancellationToken::new(),
+        )
+        .await
+        .expect("Export should succeed");
+    assert!(summary.bytes_written > 0);
+    assert_eq!(summary.rows_written, bodies.len() as u64);
+    assert!(!summary.cancelled);
+
+    let mut reader = csv::Reader::from_path(&output_path).unwrap();
+    let headers = reader.headers().unwrap().clone();
+    assert_eq!(headers.iter().collect::<Vec<_>>(), vec!["id", "body"]);
+
+    let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(records.len(), bodies.len());
+    for (i, record) in records.iter().enumerate() {
+        assert_eq!(&record[0], (i + 1).to_string());
+        assert_eq!(&record[1], bodies[i]);
+    }
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_cancelled_copy_export_truncates_to_complete_rows() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("export_cancel");
+
+    for statement in [
+        format!(
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, body TEXT)",
+            table_name
+        ),
+        format!(
+            "INSERT INTO \"{}\" SELECT g, 'line ' || g || E'\\nwith \"quotes\"' FROM generate_series(1, 500000) g",
+            table_name
+        ),
+    ] {
+        let result = driver.execute_query(&statement).await.unwrap();
+        assert!(result.error.is_none(), "{:?}", result.error);
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let output_path = dir.path().join("export.csv");
+    let cancel = CancellationToken::new();
+
+    let export = driver.export_table_csv(
+        "public",
+        &table_name,
+        output_path.to_str().unwrap(),
+        &cancel,
+    );
+    let canceller = async {
+        while tokio::fs::metadata(&output_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0)
+            == 0
+        {
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        cancel.cancel();
+    };
+    let (summary, _) = tokio::join!(export, canceller);
+    let summary = summary.unwrap();
+
+    assert!(summary.cancelled);
+    assert!(summary.rows_written < 500_000);
+    let mut reader = csv::Reader::from_path(&output_path).unwrap();
+    let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(records.len() as u64, summary.rows_written);
+    assert!(records.iter().all(|r| r[1].ends_with("with \"quotes\"")));
+
+    // The abandoned COPY connection is discarded rather than reused
+    let result = driver.execute_query("SELECT 1 AS one").await.unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_import_table_csv_uses_copy() {
+    use std::io::Write;
+
+    let driver = create_test_driver();
+    let table_name = test_table_name("import");
+
+    let result = driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, name TEXT, score NUMERIC)",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    // Header columns out of table order, more rows than one chunk, and an empty field
+    let mut csv_file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+    writeln!(csv_file, "name,id,score").unwrap();
+    for i in 1..=1200 {
+        if i == 7 {
+            writeln!(csv_file, "\"O'Brien, \"\"Jr\"\"\",{},", i).unwrap();
+        } else {
+            writeln!(csv_file, "user {},{},{}.5", i, i, i).unwrap();
+        }
+    }
+    csv_file.flush().unwrap();
+
+    let table_ref = format!("\"public\".\"{}\"", table_name);
+    let summary = driver
+        .import_table_csv(&table_ref, csv_file.path().to_str().unwrap(), false)
+        .await
+        .expect("Import should succeed");
+    assert_eq!(summary.rows_imported, 1200);
+    assert!(summary.transactional);
+
+    let result = driver
+        .execute_query(&format!(
+            "SELECT name, score IS NULL AS no_score, (SELECT COUNT(*) FROM \"{0}\") AS n \
+             FROM \"{0}\" WHERE id = 7",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.data[0]["name"], "O'Brien, \"Jr\"");
+    assert_eq!(result.data[0]["no_score"], true);
+    assert_eq!(result.data[0]["n"], 1200);
+
+    // A bad value fails the single COPY, leaving the table as it was
+    let mut bad_file = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+    writeln!(bad_file, "id,name\n5000,ok\nnot a number,bad").unwrap();
+    bad_file.flush().unwrap();
+    let failed = driver
+        .import_table_csv(&table_ref, bad_file.path().to_str().unwrap(), false)
+        .await;
+    assert!(failed.is_err());
+    let result = driver
+        .execute_query(&format!("SELECT COUNT(*) AS n FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+    assert_eq!(result.data[0]["n"], 1200);
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+// ============================================================================
+// Data Type Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_various_data_types() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("types");
+
+    // Create table with various PostgreSQL types
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (
+                int2_col SMALLINT,
+                int4_col INTEGER,
+                int8_col BIGINT,
+                float4_col REAL,
+                float8_col DOUBLE PRECISION,
+                numeric_col NUMERIC(10,2),
+                text_col TEXT,
+                varchar_col VARCHAR(255),
+                bool_col BOOLEAN,
+                date_col DATE,
+                timestamp_col TIMESTAMP,
+                json_col JSONB,
+                uuid_col UUID
+            )",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" VALUES (
+                1, 2, 3, 1.5, 2.5, 100.50, 'hello', 'world', true,
+                '2024-01-01', '2024-01-01 12:00:00', '{{\"key\": \"value\"}}',
+                'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'
+            )",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver
+        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+
+    assert_eq!(result.row_count, 1);
+    let row = &result.data[0];
+
+    // Verify data types are properly returned
+    assert!(row.get("int2_col").is_some());
+    assert!(row.get("int4_col").is_some());
+    assert!(row.get("int8_col").is_some());
+    assert!(row.get("float4_col").is_some());
+    assert!(row.get("float8_col").is_some());
+    assert!(row.get("text_col").is_some());
+    assert!(row.get("bool_col").is_some());
+    assert!(row.get("date_col").is_some());
+    assert!(row.get("json_col").is_some());
+    assert!(row.get("uuid_col").is_some());
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_hstore_and_range_types() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("ranges");
+
+    let extension = driver
+        .execute_query("CREATE EXTENSION IF NOT EXISTS hstore")
+        .await
+        .unwrap();
+    assert!(extension.error.is_none(), "{:?}", extension.error);
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (
+                attrs hstore,
+                ints int4range,
+                bigs int8range,
+                days daterange,
+                period tsrange,
+                nothing int4range
+            )",
+            table_name
+        ))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" VALUES (
+                'color => red, size => NULL',
+                '[1,10)',
+                '(,100]',
+                '[2024-01-01,2024-02-01)',
+                '[2024-01-01 00:00:00,2024-01-02 00:00:00]',
+                'empty'
+            )",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    let result = driver
+        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+    let row = &result.data[0];
+
+    assert_eq!(row["attrs"], json!({"color": "red", "size": null}));
+    assert_eq!(
+        row["ints"],
+        json!({"lower": 1, "upper": 10, "bounds": "[)"})
+    );
+    // Postgres canonicalizes integer ranges to an exclusive upper bound
+    assert_eq!(
+        row["bigs"],
+        json!({"lower": null, "upper": 101, "bounds": "()"})
+    );
+    assert_eq!(
+        row["days"],
+        json!({"lower": "2024-01-01", "upper": "2024-02-01", "bounds": "[)"})
+    );
+    assert_eq!(
+        row["period"],
+        json!({
+            "lower": "2024-01-01 00:00:00",
+            "upper": "2024-01-02 00:00:00",
+            "bounds": "[]"
+        })
+    );
+    assert_eq!(row["nothing"], json!("empty"));
+
+    drop_table(&driver, &table_name).await;
+}
+
+// ============================================================================
+// Update/Delete Isolation Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_update_only_affects_targeted_row() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("upd_iso");
+
+    // Create table
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Insert test data
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30), ('Bob', 25), ('Charlie', 35)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Update only Bob's age
+    driver
+        .execute_query(&format!(
+            "UPDATE \"{}\" SET age = 99 WHERE name = 'Bob'",
+            table_name
+        ))
+        .await
+        .expect("Update should succeed");
+
+    // Verify Bob was updated
+    let bob = driver
+        .execute_query(&format!(
+            "SELECT age FROM \"{}\" WHERE name = 'Bob'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(bob.data[0].get("age").unwrap().as_i64().unwrap(), 99);
+
+    // Verify Alice was NOT affected
+    let alice = driver
+        .execute_query(&format!(
+            "SELECT age FROM \"{}\" WHERE name = 'Alice'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        alice.data[0].get("age").unwrap().as_i64().unwrap(),
+        30,
+        "Alice's age should remain unchanged"
+    );
+
+    // Verify Charlie was NOT affected
+    let charlie = driver
+        .execute_query(&format!(
+            "SELECT age FROM \"{}\" WHERE name = 'Charlie'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        charlie.data[0].get("age").unwrap().as_i64().unwrap(),
+        35,
+        "Charlie's age should remain unchanged"
+    );
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_delete_only_affects_targeted_row() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("del_iso");
+
+    // Create table
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Insert test data
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name) VALUES ('Alice'), ('Bob'), ('Charlie')",
+            table_name
+        ))
+        .await
+        .unwrap();
+
+    // Delete only Bob
+    driver
+        .execute_query(&format!(
+            "DELETE FROM \"{}\" WHERE name = 'Bob'",
+            table_name
+        ))
+        .await
+        .expect("Delete should succeed");
+
+    // Verify Bob was deleted
+    let bob = driver
+        .execute_query(&format!(
+            "SELECT * FROM \"{}\" WHERE name = 'Bob'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(bob.row_count, 0, "Bob should be deleted");
+
+    // Verify Alice still exists
+    let alice = driver
+        .execute_query(&format!(
+            "SELECT * FROM \"{}\" WHERE name = 'Alice'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(alice.row_count, 1, "Alice should still exist");
+
+    // Verify Charlie still exists
+    let charlie = driver
+        .execute_query(&format!(
+            "SELECT * FROM \"{}\" WHERE name = 'Charlie'",
+            table_name
+        ))
+        .await
+        .unwrap();
+    assert_eq!(charlie.row_count, 1, "Charlie should still exist");
+
+    // Cleanup
+    drop_table(&driver, &table_name).await;
+}
+
+#[tokio::test]
+async fn test_update_does_not_affect_other_tables() {
+    let driver = create_test_driver();
+    let users_table = test_table_name("users");
+    let admins_table = test_table_name("admins");
+
+    // Create two tables with same structure
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+            users_table
+        ))
+        .await
+        .unwrap();
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+            admins_table
+        ))
+        .await
+        .unwrap();
+
+    // Insert same data into both
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30)",
+            users_table
+        ))
+        .await
+        .unwrap();
+
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30)",
+            admins_table
+        ))
+        .await
+        .unwrap();
+
+    // Update only users table
+    driver
+        .execute_query(&format!(
+            "UPDATE \"{}\" SET age = 50 WHERE name = 'Alice'",
+            users_table
+        ))
+        .await
+        .unwrap();
+
+    // Verify users table updated
+    let user = driver
+        .execute_query(&format!(
+            "SELECT age FROM \"{}\" WHERE name = 'Alice'",
+            users_table
+        ))
+        .await
+        .unwrap();
+    assert_eq!(user.data[0].get("age").unwrap().as_i64().unwrap(), 50);
+
+    // Verify admins table NOT affected
+    let admin = driver
+        .execute_query(&format!(
+            "SELECT age FROM \"{}\" WHERE name = 'Alice'",
+            admins_table
+        ))
+        .await
+        .unwrap();
+    assert_eq!(
+        admin.data[0].get("age").unwrap().as_i64().unwrap(),
+        30,
+        "Admins table should NOT be affected"
+    );
+
+    // Cleanup
+    drop_table(&driver, &users_table).await;
+    drop_table(&driver, &admins_table).await;
+}
+
+// ============================================================================
+// Maintenance Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_analyze_table() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("analyze");
+
+    driver
+        .execute_query(&format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
+            table_name
+        ))
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "INSERT INTO \"{}\" (name) VALUES ('a'), ('b'), ('c')",
             table_name
         ))
         .await
         .unwrap();
 
     let result = driver
-        .execute_query(&format!("SELECT * FROM \"{}\"", table_name))
-        .await
-        .unwrap();
-
-    assert_eq!(result.row_count, 1);
-    let row = &result.data[0];
+        .analyze(Some(&format!("public.{}", table_name)))
+        .await;
+    assert!(result.is_ok(), "ANALYZE failed: {:?}", result.err());
 
-    // Verify data types are properly returned
-    assert!(row.get("int2_col").is_some());
-    assert!(row.get("int4_col").is_some());
-    assert!(row.get("int8_col").is_some());
-    assert!(row.get("float4_col").is_some());
-    assert!(row.get("float8_col").is_some());
-    assert!(row.get("text_col").is_some());
-    assert!(row.get("bool_col").is_some());
-    assert!(row.get("date_col").is_some());
-    assert!(row.get("json_col").is_some());
-    assert!(row.get("uuid_col").is_some());
+    // VACUUM must not be wrapped in a transaction block
+    let result = driver.vacuum(Some(&table_name), false).await;
+    assert!(result.is_ok(), "VACUUM failed: {:?}", result.err());
 
-    // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
 // ============================================================================
-// Update/Delete Isolation Tests
+// Table Watch Tests
 // ============================================================================
 
 #[tokio::test]
-async fn test_update_only_affects_targeted_row() {
+async fn test_watch_table_receives_insert() {
     let driver = create_test_driver();
-    let table_name = test_table_name("upd_iso");
+    let table_name = test_table_name("watch");
 
-    // Create table
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
             table_name
         ))
         .await
         .unwrap();
 
-    // Insert test data
-    driver
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let handle = start_table_watch(&driver, "public", &table_name, move |change| {
+        let _ = tx.send(change);
+    })
+    .await
+    .expect("Failed to start table watch");
+
+    // Insert from a separate connection
+    let writer = create_test_driver();
+    writer
         .execute_query(&format!(
-            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30), ('Bob', 25), ('Charlie', 35)",
+            "INSERT INTO \"{}\" (name) VALUES ('Alice')",
             table_name
         ))
         .await
         .unwrap();
 
-    // Update only Bob's age
+    let change = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+        .await
+        .expect("Timed out waiting for change event")
+        .expect("Watch channel closed");
+    assert_eq!(change.operation, "INSERT");
+    assert_eq!(change.schema, "public");
+    assert_eq!(change.table, table_name);
+    assert_eq!(change.row["name"], "Alice");
+
+    handle.abort();
     driver
+        .remove_table_watch("public", &table_name)
+        .await
+        .expect("Failed to remove table watch");
+
+    let name = PostgresDriver::watch_object_name("public", &table_name);
+    let leftovers = driver
         .execute_query(&format!(
-            "UPDATE \"{}\" SET age = 99 WHERE name = 'Bob'",
-            table_name
+            "SELECT (SELECT count(*) FROM pg_trigger WHERE tgname = '{0}') \
+             + (SELECT count(*) FROM pg_proc WHERE proname = '{0}') AS n",
+            name
         ))
         .await
-        .expect("Update should succeed");
+        .unwrap();
+    assert_eq!(
+        leftovers.data[0]["n"], 0,
+        "Trigger and function should be dropped"
+    );
 
-    // Verify Bob was updated
-    let bob = driver
+    drop_table(&driver, &table_name).await;
+}
+
+// ============================================================================
+// Lock Diagnostics Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_blocking_queries_reports_row_lock_wait() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("locks");
+
+    driver
         .execute_query(&format!(
-            "SELECT age FROM \"{}\" WHERE name = 'Bob'",
+            "CREATE TABLE \"{}\" (id INTEGER PRIMARY KEY, name TEXT)",
             table_name
         ))
         .await
         .unwrap();
-    assert_eq!(bob.data[0].get("age").unwrap().as_i64().unwrap(), 99);
-
-    // Verify Alice was NOT affected
-    let alice = driver
+    driver
         .execute_query(&format!(
-            "SELECT age FROM \"{}\" WHERE name = 'Alice'",
+            "INSERT INTO \"{}\" (id, name) VALUES (1, 'a')",
             table_name
         ))
         .await
         .unwrap();
-    assert_eq!(
-        alice.data[0].get("age").unwrap().as_i64().unwrap(),
-        30,
-        "Alice's age should remain unchanged"
-    );
 
-    // Verify Charlie was NOT affected
-    let charlie = driver
-        .execute_query(&format!(
-            "SELECT age FROM \"{}\" WHERE name = 'Charlie'",
+    // First connection holds the row lock inside an open transaction
+    let mut holder = driver.begin_transaction().await.unwrap();
+    holder
+        .execute(&format!(
+            "UPDATE \"{}\" SET name = 'held' WHERE id = 1",
             table_name
         ))
         .await
         .unwrap();
-    assert_eq!(
-        charlie.data[0].get("age").unwrap().as_i64().unwrap(),
-        35,
-        "Charlie's age should remain unchanged"
+
+    // Second connection blocks trying to update the same row
+    let waiter_table = table_name.clone();
+    let waiter = tokio::spawn(async move {
+        create_test_driver()
+            .execute_query(&format!(
+                "UPDATE \"{}\" SET name = 'waiting' WHERE id = 1",
+                waiter_table
+            ))
+            .await
+    });
+
+    let mut found = None;
+    for _ in 0..50 {
+        let blocking = driver.blocking_queries().await.unwrap();
+        found = blocking.into_iter().find(|b| {
+            b.blocked_query
+                .as_deref()
+                .is_some_and(|q| q.contains(&table_name) && q.contains("waiting"))
+        });
+        if found.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+
+    let pair = found.expect("Blocked update should be reported");
+    assert_ne!(pair.blocked_pid, pair.blocking_pid);
+    assert!(
+        pair.blocking_query
+            .as_deref()
+            .is_some_and(|q| q.contains("held")),
+        "Blocking session should be the lock holder, got {:?}",
+        pair.blocking_query
     );
+    assert_eq!(pair.lock_type, "transactionid");
+
+    holder.rollback().await.unwrap();
+    let result = waiter.await.unwrap().unwrap();
+    assert!(result.error.is_none(), "Waiter failed: {:?}", result.error);
 
-    // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
+// ============================================================================
+// Routine Tests
+// ============================================================================
+
 #[tokio::test]
-async fn test_delete_only_affects_targeted_row() {
+async fn test_list_routines_includes_sql_function() {
     let driver = create_test_driver();
-    let table_name = test_table_name("del_iso");
+    let function_name = test_table_name("add_one");
 
-    // Create table
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
-            table_name
+            "CREATE FUNCTION public.\"{}\"(x integer) RETURNS integer LANGUAGE sql AS 'SELECT x + 1'",
+            function_name
         ))
         .await
         .unwrap();
 
-    // Insert test data
-    driver
-        .execute_query(&format!(
-            "INSERT INTO \"{}\" (name) VALUES ('Alice'), ('Bob'), ('Charlie')",
-            table_name
-        ))
+    let routines = driver.list_routines("public").await.unwrap();
+    let routine = routines
+        .iter()
+        .find(|r| r.name == function_name)
+        .expect("Created function should be listed");
+    assert_eq!(routine.schema, "public");
+    assert_eq!(routine.routine_type, "function");
+    assert_eq!(routine.return_type.as_deref(), Some("integer"));
+    assert_eq!(routine.arguments, "x integer");
+    assert_eq!(routine.language.as_deref(), Some("SQL"));
+
+    let definition = driver
+        .get_routine_definition("public", &function_name)
         .await
         .unwrap();
+    assert!(
+        definition.contains("x + 1"),
+        "Unexpected definition: {}",
+        definition
+    );
 
-    // Delete only Bob
-    driver
+    let _ = driver
         .execute_query(&format!(
-            "DELETE FROM \"{}\" WHERE name = 'Bob'",
-            table_name
+            "DROP FUNCTION IF EXISTS public.\"{}\"",
+            function_name
         ))
-        .await
-        .expect("Delete should succeed");
+        .await;
+}
 
-    // Verify Bob was deleted
-    let bob = driver
+// ============================================================================
+// Sequence Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_sequence_value_read_and_reset() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("seq");
+
+    driver
         .execute_query(&format!(
-            "SELECT * FROM \"{}\" WHERE name = 'Bob'",
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT)",
             table_name
         ))
         .await
         .unwrap();
-    assert_eq!(bob.row_count, 0, "Bob should be deleted");
 
-    // Verify Alice still exists
-    let alice = driver
+    let fresh = driver
+        .sequence_value("public", &table_name, "id")
+        .await
+        .unwrap();
+    assert_eq!(fresh.last_value, 1);
+    assert!(!fresh.is_called);
+    assert_eq!(fresh.sequence_name, format!("public.{}_id_seq", table_name));
+
+    driver
         .execute_query(&format!(
-            "SELECT * FROM \"{}\" WHERE name = 'Alice'",
+            "INSERT INTO \"{}\" (name) VALUES ('a'), ('b'), ('c')",
             table_name
         ))
         .await
         .unwrap();
-    assert_eq!(alice.row_count, 1, "Alice should still exist");
+    let after_insert = driver
+        .sequence_value("public", &table_name, "id")
+        .await
+        .unwrap();
+    assert_eq!(after_insert.last_value, 3);
+    assert!(after_insert.is_called);
 
-    // Verify Charlie still exists
-    let charlie = driver
+    // Reset so the next insert gets id 100
+    let reset = driver
+        .set_sequence_value("public", &table_name, "id", 99, true)
+        .await
+        .unwrap();
+    assert_eq!(reset.last_value, 99);
+    let inserted = driver
         .execute_query(&format!(
-            "SELECT * FROM \"{}\" WHERE name = 'Charlie'",
+            "INSERT INTO \"{}\" (name) VALUES ('d') RETURNING id",
             table_name
         ))
         .await
         .unwrap();
-    assert_eq!(charlie.row_count, 1, "Charlie should still exist");
+    assert_eq!(inserted.data[0]["id"], 100);
+
+    // Columns without a sequence are reported as such
+    let err = driver
+        .sequence_value("public", &table_name, "name")
+        .await
+        .unwrap_err();
+    assert!(err.contains("not backed by a sequence"), "{}", err);
 
-    // Cleanup
     drop_table(&driver, &table_name).await;
 }
 
+// ============================================================================
+// Display Timezone Tests
+// ============================================================================
+
 #[tokio::test]
-async fn test_update_does_not_affect_other_tables() {
+async fn test_timestamptz_uses_display_timezone() {
     let driver = create_test_driver();
-    let users_table = test_table_name("users");
-    let admins_table = test_table_name("admins");
+    let query = "SELECT TIMESTAMPTZ '2024-01-15 12:00:00+00' AS at, \
+                 TIMESTAMP '2024-01-15 12:00:00' AS naive";
+
+    let utc = driver.execute_query(query).await.unwrap();
+    assert_eq!(utc.data[0]["at"], "2024-01-15 12:00:00 UTC");
+
+    display::apply_setting(display::DISPLAY_TIMEZONE_KEY, "Asia/Kolkata").unwrap();
+    let local = driver.execute_query(query).await;
+    display::apply_setting(display::DISPLAY_TIMEZONE_KEY, "").unwrap();
+
+    let local = local.unwrap();
+    assert_eq!(local.data[0]["at"], "2024-01-15T17:30:00+05:30");
+    // Timestamps without a timezone are shown as stored
+    assert_eq!(local.data[0]["naive"], "2024-01-15 12:00:00");
+
+    assert!(display::apply_setting(display::DISPLAY_TIMEZONE_KEY, "Mars/Olympus").is_err());
+}
+
+// ============================================================================
+// Index Suggestion Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_suggest_indexes_for_filtered_seq_scan() {
+    let driver = create_test_driver();
+    let table_name = test_table_name("idx_suggest");
 
-    // Create two tables with same structure
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
-            users_table
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, email TEXT, status TEXT)",
+            table_name
         ))
         .await
         .unwrap();
-
     driver
         .execute_query(&format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
-            admins_table
+            "INSERT INTO \"{}\" (email, status) \
+             SELECT 'user' || g || '@example.com', 'active' FROM generate_series(1, 1000) g",
+            table_name
         ))
         .await
         .unwrap();
 
-    // Insert same data into both
+    let query = format!(
+        "SELECT * FROM \"{}\" WHERE email = 'user5@example.com'",
+        table_name
+    );
+    let suggestions = driver.suggest_indexes(&query).await.unwrap();
+    assert_eq!(suggestions.len(), 1, "{:?}", suggestions);
+    assert_eq!(suggestions[0].table, table_name);
+    assert_eq!(suggestions[0].columns, vec!["email".to_string()]);
+    assert_eq!(
+        suggestions[0].create_statement,
+        format!("CREATE INDEX ON \"public\".\"{}\" (\"email\");", table_name)
+    );
+
+    // Once the column is indexed there is nothing left to suggest
     driver
-        .execute_query(&format!(
-            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30)",
-            users_table
-        ))
+        .execute_query(&suggestions[0].create_statement)
         .await
         .unwrap();
+    let suggestions = driver.suggest_indexes(&query).await.unwrap();
+    assert!(suggestions.is_empty(), "{:?}", suggestions);
 
-    driver
-        .execute_query(&format!(
-            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30)",
-            admins_table
-        ))
+    drop_table(&driver, &table_name).await;
+}
+
+// ============================================================================
+// Large Object Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_large_object_write_and_read_back() {
+    let driver = create_test_driver();
+    let bytes: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+
+    let oid = driver.write_large_object(None, &bytes).await.unwrap();
+    let object = driver.read_large_object(oid, 1000).await.unwrap();
+    assert_eq!(object.oid, oid);
+    assert_eq!(object.size, 1000);
+    assert!(!object.truncated);
+    assert!(object.preview.is_none(), "binary data has no text preview");
+    let decoded =
+        dbcooper_lib::database::redis::decode_base64_value(&object.preview_base64).unwrap();
+    assert_eq!(decoded, bytes);
+
+    // Replacing with shorter content leaves nothing of the old bytes behind
+    let oid = driver
+        .write_large_object(Some(oid), "héllo".as_bytes())
         .await
         .unwrap();
+    let object = driver.read_large_object(oid, 1000).await.unwrap();
+    assert_eq!(object.size, 6);
+    assert_eq!(object.preview.as_deref(), Some("héllo"));
+
+    // A preview cutting the two-byte character keeps the whole characters before it
+    let preview = driver.read_large_object(oid, 2).await.unwrap();
+    assert!(preview.truncated);
+    assert_eq!(preview.preview.as_deref(), Some("h"));
 
-    // Update only users table
     driver
+        .execute_query(&format!("SELECT lo_unlink({})", oid))
+        .await
+        .unwrap();
+    assert!(driver.read_large_object(oid, 10).await.is_err());
+}
+
+// ============================================================================
+// Table Permission Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_table_permissions_lists_grants_and_policies() {
+    let driver = create_test_driver();
+    let table = test_table_name("perms");
+    let role = format!("reader_{}", uuid::Uuid::new_v4().simple());
+
+    for statement in [
+        format!("CREATE TABLE \"{}\" (id INT, owner TEXT)", table),
+        format!("CREATE ROLE {}", role),
+        format!("GRANT SELECT ON \"{}\" TO {}", table, role),
+    ] {
+        let result = driver.execute_query(&statement).await.unwrap();
+        assert!(result.error.is_none(), "{:?}", result.error);
+    }
+
+    let permissions = driver.table_permissions("public", &table).await.unwrap();
+    assert!(permissions
+        .grants
+        .iter()
+        .any(|g| g.grantee == role && g.privilege_type == "SELECT" && !g.is_grantable));
+    assert!(!permissions
+        .grants
+        .iter()
+        .any(|g| g.grantee == role && g.privilege_type == "UPDATE"));
+    assert!(!permissions.rls_enabled);
+    assert!(permissions.policies.is_empty());
+
+    for statement in [
+        format!("ALTER TABLE \"{}\" ENABLE ROW LEVEL SECURITY", table),
+        format!(
+            "CREATE POLICY own_rows ON \"{}\" FOR SELECT TO {} USING (owner = current_user)",
+            table, role
+        ),
+    ] {
+        driver.execute_query(&statement).await.unwrap();
+    }
+    let permissions = driver.table_permissions("public", &table).await.unwrap();
+    assert!(permissions.rls_enabled);
+    assert!(!permissions.rls_forced);
+    assert_eq!(permissions.policies.len(), 1);
+    assert_eq!(permissions.policies[0].name, "own_rows");
+    assert_eq!(permissions.policies[0].command, "SELECT");
+    assert_eq!(permissions.policies[0].roles, vec![role.clone()]);
+
+    drop_table(&driver, &table).await;
+    let _ = driver.execute_query(&format!("DROP ROLE {}", role)).await;
+
+    assert!(driver.table_permissions("public", &table).await.is_err());
+}
+
+// ============================================================================
+// Multiple Result Set Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_execute_multi_result_expands_refcursors() {
+    let driver = create_test_driver();
+    let function = format!("test_two_sets_{}", uuid::Uuid::new_v4().simple());
+    let result = driver
         .execute_query(&format!(
-            "UPDATE \"{}\" SET age = 50 WHERE name = 'Alice'",
-            users_table
+            r#"CREATE FUNCTION {}() RETURNS SETOF refcursor AS $$
+            DECLARE
+                numbers refcursor := 'numbers';
+                greeting refcursor := 'greeting';
+            BEGIN
+                OPEN numbers FOR SELECT 1 AS n UNION ALL SELECT 2;
+                RETURN NEXT numbers;
+                OPEN greeting FOR SELECT 'hello' AS word;
+                RETURN NEXT greeting;
+            END
+            $$ LANGUAGE plpgsql"#,
+            function
         ))
         .await
         .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
 
-    // Verify users table updated
-    let user = driver
+    let sets = driver
+        .execute_multi_result(&format!("SELECT * FROM {}()", function))
+        .await
+        .unwrap();
+    let _ = driver
+        .execute_query(&format!("DROP FUNCTION {}()", function))
+        .await;
+
+    assert_eq!(sets.len(), 2, "{:?}", sets);
+    assert_eq!(sets[0].data, vec![json!({"n": 1}), json!({"n": 2})]);
+    assert_eq!(sets[1].data, vec![json!({"word": "hello"})]);
+}
+
+#[tokio::test]
+async fn test_execute_multi_result_batch_of_statements() {
+    let driver = create_test_driver();
+
+    let sets = driver
+        .execute_multi_result("SELECT 1 AS a; SELECT 2 AS b, 'x' AS c")
+        .await
+        .unwrap();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0].data, vec![json!({"a": 1})]);
+    assert_eq!(sets[1].data, vec![json!({"b": 2, "c": "x"})]);
+
+    // A failing statement reports its error instead of partial results
+    let sets = driver
+        .execute_multi_result("SELECT 1; SELECT * FROM missing_table_for_multi_result")
+        .await
+        .unwrap();
+    assert_eq!(sets.len(), 1);
+    assert!(sets[0].error.is_some());
+}
+
+// ============================================================================
+// Undo Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_undo_reinserts_row_with_typed_binds() {
+    let driver = create_test_driver();
+    let table = test_table_name("undo_typed");
+
+    driver
         .execute_query(&format!(
-            "SELECT age FROM \"{}\" WHERE name = 'Alice'",
-            users_table
+            "CREATE TABLE \"{}\" (id INT PRIMARY KEY, amount NUMERIC(12,2), active BOOLEAN, \
+             created TIMESTAMP, meta JSONB, ref UUID, note TEXT)",
+            table
         ))
         .await
         .unwrap();
-    assert_eq!(user.data[0].get("age").unwrap().as_i64().unwrap(), 50);
-
-    // Verify admins table NOT affected
-    let admin = driver
+    driver
         .execute_query(&format!(
-            "SELECT age FROM \"{}\" WHERE name = 'Alice'",
-            admins_table
+            "INSERT INTO \"{}\" VALUES (1, 12.50, true, '2024-01-02 03:04:05.25', \
+             '{{\"tags\": [\"a\"]}}', 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', 'it''s')",
+            table
         ))
         .await
         .unwrap();
-    assert_eq!(
-        admin.data[0].get("age").unwrap().as_i64().unwrap(),
-        30,
-        "Admins table should NOT be affected"
+    let select = format!("SELECT * FROM \"{}\"", table);
+    let before = driver.execute_query(&select).await.unwrap().data;
+    let row = before[0].as_object().unwrap().clone();
+    driver
+        .execute_query(&format!("DELETE FROM \"{}\"", table))
+        .await
+        .unwrap();
+
+    let history = UndoHistory::new();
+    history.record(
+        "conn",
+        UndoEntry {
+            db_type: "postgres".to_string(),
+            identity: "testdb".to_string(),
+            edit: RowEdit::Delete {
+                schema: "public".to_string(),
+                table: table.clone(),
+                table_ref: format!("\"public\".\"{}\"", table),
+                row,
+            },
+        },
     );
+    let undone = undo_last_edit_with_driver(&history, "testdb", &driver, "conn")
+        .await
+        .unwrap();
+    assert_eq!(undone.remaining, 0);
+    assert_eq!(driver.execute_query(&select).await.unwrap().data, before);
 
-    // Cleanup
-    drop_table(&driver, &users_table).await;
-    drop_table(&driver, &admins_table).await;
+    drop_table(&driver, &table).await;
 }
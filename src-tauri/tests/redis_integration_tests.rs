@@ -7,8 +7,13 @@
 
 use std::collections::HashMap;
 
-use dbcooper_lib::database::redis::RedisDriver;
+use dbcooper_lib::commands::database::select_redis_db;
+use dbcooper_lib::database::redis::{
+    decode_base64_value, parse_cluster_nodes, RedisDriver, STRING_PREVIEW_LIMIT,
+};
 use dbcooper_lib::database::{DatabaseDriver, RedisConfig};
+use dbcooper_lib::db::migrate;
+use sqlx::sqlite::SqlitePoolOptions;
 
 /// Helper function to create a test Redis driver
 fn create_test_driver() -> RedisDriver {
@@ -78,6 +83,82 @@ async fn test_connection_failure() {
     assert!(test_result.message.contains("Connection failed"));
 }
 
+#[tokio::test]
+async fn test_select_db_isolates_keys() {
+    let driver = create_test_driver();
+    let key = test_key("selectdb");
+
+    driver.select_db(3).await.expect("SELECT 3 should succeed");
+    assert_eq!(driver.current_db(), 3);
+    driver.set_key(&key, "in db 3", None).await.unwrap();
+    assert!(driver.get_key_details(&key).await.is_ok());
+
+    driver.select_db(4).await.expect("SELECT 4 should succeed");
+    assert_eq!(driver.current_db(), 4);
+    let details = driver.get_key_details(&key).await;
+    assert!(
+        details.is_err(),
+        "Key from db 3 should not be visible in db 4"
+    );
+
+    // Cleanup
+    driver.select_db(3).await.unwrap();
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_redis_select_db_applies_to_later_commands() {
+    let db_file = tempfile::NamedTempFile::new().unwrap();
+    let pool = SqlitePoolOptions::new()
+        .connect(&format!("sqlite:{}?mode=rwc", db_file.path().display()))
+        .await
+        .unwrap();
+    migrate(&pool).await.unwrap();
+    let uuid = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO connections (uuid, name, host, port, database, username, password, db_type) \
+         VALUES (?, 'redis', 'localhost', 6379, '3', '', '', 'redis')",
+    )
+    .bind(&uuid)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let db3 = RedisDriver::new(RedisConfig {
+        host: "localhost".to_string(),
+        port: 6379,
+        password: None,
+        db: Some(3),
+        tls: false,
+    });
+    let key = test_key("selectdb-saved");
+    db3.set_key(&key, "in db 3", None).await.unwrap();
+
+    select_redis_db(&pool, &uuid, 4).await.unwrap();
+
+    // Commands build their driver from the saved connection, which now points at db 4
+    let (database,): (String,) = sqlx::query_as("SELECT database FROM connections WHERE uuid = ?")
+        .bind(&uuid)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(database, "4");
+    let db4 = RedisDriver::new(RedisConfig {
+        host: "localhost".to_string(),
+        port: 6379,
+        password: None,
+        db: database.parse().ok(),
+        tls: false,
+    });
+    assert!(
+        db4.get_key_details(&key).await.is_err(),
+        "Key from db 3 should not be visible after selecting db 4"
+    );
+
+    // Cleanup
+    cleanup_keys!(db3, &key);
+}
+
 // ============================================================================
 // DatabaseDriver Trait Tests
 // ============================================================================
@@ -100,7 +181,9 @@ async fn test_list_tables_returns_keyspace() {
 async fn test_get_table_data_returns_empty() {
     let driver = create_test_driver();
 
-    let result = driver.get_table_data("redis", "keys", 1, 10, None, None, None).await;
+    let result = driver
+        .get_table_data("redis", "keys", 1, 10, None, None, None)
+        .await;
     assert!(result.is_ok());
 
     let data = result.unwrap();
@@ -174,6 +257,23 @@ async fn test_execute_query_invalid_command() {
     );
 }
 
+#[tokio::test]
+async fn test_execute_query_config_get_returns_rows() {
+    let driver = create_test_driver();
+
+    let result = driver.execute_query("CONFIG GET maxmemory*").await.unwrap();
+    assert!(result.error.is_none(), "CONFIG GET should not error");
+    assert!(result.row_count > 1, "Should return one row per setting");
+    assert_eq!(result.row_count as usize, result.data.len());
+
+    for row in &result.data {
+        let field = row["field"].as_str().expect("Row should have a field");
+        assert!(field.starts_with("maxmemory"));
+        assert!(row.get("value").is_some(), "Row should have a value");
+    }
+    assert!(result.data.iter().any(|r| r["field"] == "maxmemory"));
+}
+
 // ============================================================================
 // String Key Tests
 // ============================================================================
@@ -218,6 +318,139 @@ async fn test_set_string_key_with_ttl() {
     cleanup_keys!(driver, &key);
 }
 
+#[tokio::test]
+async fn test_json_string_key_parsed_and_edited() {
+    let driver = create_test_driver();
+    let key = test_key("json");
+
+    driver
+        .set_key(&key, r#"{"name":"alice","tags":["a","b"]}"#, None)
+        .await
+        .unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    let parsed = details.parsed_json.expect("JSON string should be parsed");
+    assert_eq!(parsed["name"], "alice");
+    assert_eq!(parsed["tags"][1], "b");
+
+    let updated = serde_json::json!({"name": "bob", "tags": []});
+    driver.set_json(&key, &updated, Some(60)).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.parsed_json, Some(updated));
+    assert!(details.ttl > 0 && details.ttl <= 60);
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_plain_string_key_not_parsed_as_json() {
+    let driver = create_test_driver();
+    let key = test_key("not_json");
+
+    driver.set_key(&key, "42", None).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert!(details.parsed_json.is_none());
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_binary_string_key_round_trips_via_base64() {
+    let driver = create_test_driver();
+    let key = test_key("binary");
+    let copy = test_key("binary_copy");
+    let bytes: Vec<u8> = vec![0xff, 0xfe, 0x00, 0x80, b'a'];
+
+    driver.set_key_bytes(&key, &bytes, None).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.value, "<binary data: 5 bytes>");
+    assert_eq!(details.length, Some(5));
+    let encoded = details
+        .value_base64
+        .expect("Non-UTF-8 value should be base64 encoded");
+    assert_eq!(decode_base64_value(&encoded).unwrap(), bytes);
+
+    // Writing the base64 back produces an identical value
+    driver
+        .set_key_bytes(&copy, &decode_base64_value(&encoded).unwrap(), None)
+        .await
+        .unwrap();
+    let copied = driver.get_key_details(&copy).await.unwrap();
+    assert_eq!(copied.value_base64, Some(encoded));
+
+    // UTF-8 values keep the plain string form only
+    driver.set_key(&key, "plain", None).await.unwrap();
+    assert!(driver
+        .get_key_details(&key)
+        .await
+        .unwrap()
+        .value_base64
+        .is_none());
+
+    cleanup_keys!(driver, &key, &copy);
+}
+
+#[tokio::test]
+async fn test_large_string_key_previewed_and_fetched_in_full() {
+    let driver = create_test_driver();
+    let key = test_key("large_string");
+    // Two-byte characters after one ASCII byte, so the preview cut lands inside one
+    let value = format!("a{}end", "é".repeat(STRING_PREVIEW_LIMIT));
+
+    driver.set_key(&key, &value, None).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert!(details.truncated);
+    assert_eq!(details.length, Some(value.len()));
+    let preview = details.value.as_str().expect("Preview should stay text");
+    assert_eq!(preview.len(), STRING_PREVIEW_LIMIT - 1);
+    assert!(value.starts_with(preview));
+    assert!(details.parsed_json.is_none());
+
+    let full = driver.get_full_value(&key).await.unwrap();
+    assert_eq!(full.value.as_str(), Some(value.as_str()));
+    assert_eq!(full.length, value.len());
+    assert!(full.value_base64.is_none());
+
+    // Values within the limit come back whole
+    driver.set_key(&key, "short", None).await.unwrap();
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert!(!details.truncated);
+    assert_eq!(details.value, "short");
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_get_full_value_rejects_non_string_keys() {
+    let driver = create_test_driver();
+    let key = test_key("full_value_list");
+
+    assert!(driver
+        .get_full_value(&key)
+        .await
+        .unwrap_err()
+        .contains("does not exist"));
+
+    driver
+        .set_list_key(&key, &["a".to_string()], None)
+        .await
+        .unwrap();
+    let err = driver.get_full_value(&key).await.unwrap_err();
+    assert!(err.contains("not a string"), "{}", err);
+
+    cleanup_keys!(driver, &key);
+}
+
+#[test]
+fn test_decode_base64_value_rejects_invalid_input() {
+    let err = decode_base64_value("not base64!").unwrap_err();
+    assert!(err.contains("Invalid base64 value"), "{}", err);
+}
+
 // ============================================================================
 // List Key Tests
 // ============================================================================
@@ -277,6 +510,32 @@ async fn test_set_list_key_with_ttl() {
     cleanup_keys!(driver, &key);
 }
 
+#[tokio::test]
+async fn test_large_list_preview_bounded_and_paged() {
+    let driver = create_test_driver();
+    let key = test_key("list_large");
+
+    let values: Vec<String> = (0..2000).map(|i| format!("item_{}", i)).collect();
+    driver.set_list_key(&key, &values, None).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.length, Some(2000));
+    assert!(details.truncated);
+    assert_eq!(
+        details.value.as_array().unwrap().len(),
+        dbcooper_lib::database::redis::COLLECTION_PREVIEW_LIMIT
+    );
+
+    let mut seen = Vec::new();
+    for start in (0..2000).step_by(300) {
+        let page = driver.lrange(&key, start, start + 299).await.unwrap();
+        seen.extend(page);
+    }
+    assert_eq!(seen, values);
+
+    cleanup_keys!(driver, &key);
+}
+
 // ============================================================================
 // Set Key Tests
 // ============================================================================
@@ -338,6 +597,65 @@ async fn test_set_set_key_empty_values_error() {
     assert!(result.is_err(), "Empty set should error");
 }
 
+#[tokio::test]
+async fn test_large_set_preview_bounded_and_scanned() {
+    let driver = create_test_driver();
+    let key = test_key("set_large");
+
+    let values: Vec<String> = (0..2000).map(|i| format!("member_{}", i)).collect();
+    driver.set_set_key(&key, &values, None).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.length, Some(2000));
+    assert!(details.truncated);
+    assert_eq!(
+        details.value.as_array().unwrap().len(),
+        dbcooper_lib::database::redis::COLLECTION_PREVIEW_LIMIT
+    );
+
+    let mut seen = std::collections::HashSet::new();
+    let mut cursor = 0;
+    loop {
+        let page = driver.sscan(&key, cursor, Some(250)).await.unwrap();
+        for member in page.value.as_array().unwrap() {
+            seen.insert(member.as_str().unwrap().to_string());
+        }
+        cursor = page.cursor;
+        if page.scan_complete {
+            break;
+        }
+    }
+    assert_eq!(seen, values.into_iter().collect());
+
+    cleanup_keys!(driver, &key);
+}
+
+#[tokio::test]
+async fn test_set_preview_at_limit_is_complete() {
+    let driver = create_test_driver();
+    let key = test_key("set_at_limit");
+
+    let limit = dbcooper_lib::database::redis::COLLECTION_PREVIEW_LIMIT;
+    let values: Vec<String> = (0..limit).map(|i| format!("member_{}", i)).collect();
+    driver.set_set_key(&key, &values, None).await.unwrap();
+
+    // Every member fits in the preview, each listed once, so the value can be saved back
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.length, Some(limit));
+    assert!(!details.truncated);
+    let loaded: std::collections::HashSet<&str> = details
+        .value
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member.as_str().unwrap())
+        .collect();
+    assert_eq!(loaded.len(), limit);
+    assert_eq!(details.value.as_array().unwrap().len(), limit);
+
+    cleanup_keys!(driver, &key);
+}
+
 // ============================================================================
 // Hash Key Tests
 // ============================================================================
@@ -380,6 +698,41 @@ async fn test_set_hash_key_empty_fields_error() {
     assert!(result.is_err(), "Empty hash should error");
 }
 
+#[tokio::test]
+async fn test_large_hash_preview_bounded_and_scanned() {
+    let driver = create_test_driver();
+    let key = test_key("hash_large");
+
+    let fields: HashMap<String, String> = (0..2000)
+        .map(|i| (format!("field_{}", i), format!("value_{}", i)))
+        .collect();
+    driver.set_hash_key(&key, &fields, None).await.unwrap();
+
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.length, Some(2000));
+    assert!(details.truncated);
+    assert_eq!(
+        details.value.as_object().unwrap().len(),
+        dbcooper_lib::database::redis::COLLECTION_PREVIEW_LIMIT
+    );
+
+    let mut seen = HashMap::new();
+    let mut cursor = 0;
+    loop {
+        let page = driver.hscan(&key, cursor, Some(250)).await.unwrap();
+        for (field, value) in page.value.as_object().unwrap() {
+            seen.insert(field.clone(), value.as_str().unwrap().to_string());
+        }
+        cursor = page.cursor;
+        if page.scan_complete {
+            break;
+        }
+    }
+    assert_eq!(seen, fields);
+
+    cleanup_keys!(driver, &key);
+}
+
 // ============================================================================
 // Sorted Set (ZSet) Key Tests
 // ============================================================================
@@ -404,14 +757,84 @@ async fn test_set_and_get_zset_key() {
     assert_eq!(details.key_type, "zset");
     assert_eq!(details.length, Some(3));
 
-    // ZSet values are returned as [[member, score], ...] or similar
+    // ZSet values are returned as [{member, score}, ...] ordered by score
     let zset_values = details.value.as_array().unwrap();
     assert_eq!(zset_values.len(), 3);
+    assert_eq!(
+        zset_values[0],
+        serde_json::json!({"member": "bob", "score": 85.5})
+    );
+    assert_eq!(
+        zset_values[1],
+        serde_json::json!({"member": "charlie", "score": 92.0})
+    );
+    assert_eq!(
+        zset_values[2],
+        serde_json::json!({"member": "alice", "score": 100.0})
+    );
 
     // Cleanup
     cleanup_keys!(driver, &key);
 }
 
+#[tokio::test]
+async fn test_zrange_pages_large_zset_by_rank() {
+    let driver = create_test_driver();
+    let key = test_key("zset_large");
+
+    let members: Vec<(String, f64)> = (0..1000)
+        .map(|i| (format!("member_{:04}", i), i as f64))
+        .collect();
+    driver.set_zset_key(&key, &members, None).await.unwrap();
+
+    // The eager fetch is capped and flagged as truncated
+    let details = driver.get_key_details(&key).await.unwrap();
+    assert_eq!(details.length, Some(1000));
+    assert!(details.truncated);
+    assert_eq!(
+        details.value.as_array().unwrap().len(),
+        dbcooper_lib::database::redis::COLLECTION_PREVIEW_LIMIT
+    );
+
+    // Page through every member by rank
+    let page_size = 250;
+    let mut seen = Vec::new();
+    for page in 0..4 {
+        let start = page * page_size;
+        let value = driver
+            .zrange(&key, start, start + page_size - 1, true)
+            .await
+            .unwrap();
+        let entries = value.as_array().unwrap();
+        assert_eq!(entries.len(), page_size as usize);
+        for entry in entries {
+            seen.push(entry["member"].as_str().unwrap().to_string());
+        }
+    }
+    let expected: Vec<String> = members.iter().map(|(m, _)| m.clone()).collect();
+    assert_eq!(seen, expected);
+
+    // Without scores only member names are returned
+    let names = driver.zrange(&key, -2, -1, false).await.unwrap();
+    assert_eq!(names, serde_json::json!(["member_0998", "member_0999"]));
+
+    // Score ranges honour exclusive bounds and the limit
+    let by_score = driver
+        .zrangebyscore(&key, "(10", "+inf", Some(3))
+        .await
+        .unwrap();
+    assert_eq!(
+        by_score,
+        serde_json::json!([
+            {"member": "member_0011", "score": 11.0},
+            {"member": "member_0012", "score": 12.0},
+            {"member": "member_0013", "score": 13.0}
+        ])
+    );
+
+    cleanup_keys!(driver, &key);
+}
+
 #[tokio::test]
 async fn test_set_zset_key_empty_members_error() {
     let driver = create_test_driver();
@@ -474,7 +897,10 @@ async fn test_search_keys_with_limit() {
 
     // Search with limit of 2
     let pattern = format!("{}:*", prefix);
-    let result = driver.search_keys(&pattern, 2, 0, |_, _, _, _| {}).await.unwrap();
+    let result = driver
+        .search_keys(&pattern, 2, 0, |_, _, _, _| {})
+        .await
+        .unwrap();
     assert!(result.keys.len() <= 2, "Should respect limit");
 
     // Cleanup
@@ -533,6 +959,38 @@ async fn test_delete_nonexistent_key() {
     );
 }
 
+#[tokio::test]
+async fn test_delete_keys_by_pattern() {
+    let driver = create_test_driver();
+    let prefix = format!("test:delete_bulk:{}", uuid::Uuid::new_v4());
+    let keys: Vec<String> = (0..5).map(|i| format!("{}:{}", prefix, i)).collect();
+
+    for key in &keys {
+        driver.set_key(key, "value", None).await.unwrap();
+    }
+
+    let pattern = format!("{}:*", prefix);
+    let deleted = driver
+        .delete_keys_by_pattern(&pattern, false)
+        .await
+        .expect("Bulk delete should succeed");
+    assert_eq!(deleted, keys.len() as u64);
+
+    let remaining = driver
+        .search_keys(&pattern, 100, 0, |_, _, _, _| {})
+        .await
+        .unwrap();
+    assert!(remaining.keys.is_empty(), "No matching keys should remain");
+}
+
+#[tokio::test]
+async fn test_delete_keys_by_pattern_requires_confirm_for_wildcard() {
+    let driver = create_test_driver();
+
+    let result = driver.delete_keys_by_pattern("*", false).await;
+    assert!(result.is_err(), "Bare wildcard should require confirmation");
+}
+
 // ============================================================================
 // TTL Tests
 // ============================================================================
@@ -583,6 +1041,44 @@ async fn test_update_ttl_remove_expiration() {
     cleanup_keys!(driver, &key);
 }
 
+#[tokio::test]
+async fn test_expire_keys_by_pattern() {
+    let driver = create_test_driver();
+    let prefix = format!("test:expire_bulk:{}", uuid::Uuid::new_v4());
+    let keys: Vec<String> = (0..5).map(|i| format!("{}:{}", prefix, i)).collect();
+
+    for key in &keys {
+        driver.set_key(key, "value", None).await.unwrap();
+    }
+
+    let updated = driver
+        .expire_keys(&format!("{}:*", prefix), 120, false)
+        .await
+        .expect("Bulk expire should succeed");
+    assert_eq!(updated, keys.len() as u64);
+
+    for key in &keys {
+        let details = driver.get_key_details(key).await.unwrap();
+        assert!(
+            details.ttl > 0 && details.ttl <= 120,
+            "TTL should be set on {}",
+            key
+        );
+    }
+
+    for key in &keys {
+        cleanup_keys!(driver, key);
+    }
+}
+
+#[tokio::test]
+async fn test_expire_keys_requires_confirm_for_wildcard() {
+    let driver = create_test_driver();
+
+    let result = driver.expire_keys("*", 60, false).await;
+    assert!(result.is_err(), "Bare wildcard should require confirmation");
+}
+
 // ============================================================================
 // Get Key Details Tests
 // ============================================================================
@@ -619,3 +1115,46 @@ async fn test_get_key_details_includes_metadata() {
 
     cleanup_keys!(driver, &key);
 }
+
+// ============================================================================
+// Replication Status Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_replication_status_of_standalone_server() {
+    let driver = create_test_driver();
+
+    let status = driver.replication_status().await.unwrap();
+
+    assert_eq!(status.role, "master");
+    assert!(status.connected_slaves >= 0);
+    assert!(status.master_repl_offset >= 0);
+    // Only replicas report a link to their master
+    assert!(status.master_link_status.is_none());
+    assert!(
+        status.cluster.is_none(),
+        "Test server is not in cluster mode"
+    );
+}
+
+#[test]
+fn test_parse_cluster_nodes() {
+    let nodes = parse_cluster_nodes(
+        "07c37dfeb235213a872192d90877d0cd55635b91 127.0.0.1:30004@31004,node-4 slave e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 0 1426238317239 4 connected\n\
+         e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca 127.0.0.1:30001@31001 myself,master - 0 0 1 connected 0-5460 5461\n",
+    );
+
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes[0].address, "127.0.0.1:30004");
+    assert_eq!(nodes[0].flags, vec!["slave"]);
+    assert_eq!(
+        nodes[0].master_id.as_deref(),
+        Some("e7d1eecce10fd6bb5eb35b9f99a514335d9ba9ca")
+    );
+    assert!(nodes[0].slots.is_empty());
+
+    assert_eq!(nodes[1].flags, vec!["myself", "master"]);
+    assert_eq!(nodes[1].master_id, None);
+    assert_eq!(nodes[1].link_state, "connected");
+    assert_eq!(nodes[1].slots, vec!["0-5460", "5461"]);
+}
@@ -0,0 +1,217 @@
+//! Tests for the connection retry policy and reachability cache
+//!
+//! These tests exercise the retry loop and the failed-connection cache with mock
+//! operations and an unused local port, and need no external services.
+//!
+//! Run with: cargo test --test retry_tests
+
+use dbcooper_lib::database::postgres::PostgresDriver;
+use dbcooper_lib::database::reachability::{self, REACHABILITY_CACHE_KEY};
+use dbcooper_lib::database::retry::{apply_setting, Backoff, RetryPolicy, RETRY_BACKOFF_KEY};
+use dbcooper_lib::database::{DatabaseDriver, PostgresConfig};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_retry_fails_twice_then_succeeds() {
+    let policy = RetryPolicy {
+        max_attempts: 3,
+        base_delay_ms: 20,
+        backoff: Backoff::Exponential,
+    };
+
+    let mut attempts = Vec::new();
+    let started = Instant::now();
+    let result = policy
+        .run(|attempt| {
+            attempts.push((attempt, started.elapsed()));
+            async move {
+                if attempt < 3 {
+                    Err(format!("connection refused ({})", attempt))
+                } else {
+                    Ok("connected")
+                }
+            }
+        })
+        .await;
+
+    assert_eq!(result.unwrap(), "connected");
+    assert_eq!(attempts.len(), 3);
+    assert_eq!(
+        attempts.iter().map(|(n, _)| *n).collect::<Vec<_>>(),
+        vec![1, 2, 3]
+    );
+    // 20ms before the second attempt, then 40ms before the third
+    assert!(attempts[1].1 >= Duration::from_millis(20));
+    assert!(attempts[2].1 - attempts[1].1 >= Duration::from_millis(40));
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_attempts() {
+    let policy = RetryPolicy {
+        max_attempts: 2,
+        base_delay_ms: 1,
+        backoff: Backoff::Fixed,
+    };
+
+    let mut count = 0;
+    let result: Result<(), String> = policy
+        .run(|attempt| {
+            count += 1;
+            async move { Err(format!("failure {}", attempt)) }
+        })
+        .await;
+
+    assert_eq!(count, 2);
+    assert_eq!(result.unwrap_err(), "failure 2");
+}
+
+#[test]
+fn test_retry_delays() {
+    let fixed = RetryPolicy {
+        max_attempts: 4,
+        base_delay_ms: 100,
+        backoff: Backoff::Fixed,
+    };
+    assert_eq!(fixed.delay_before(1), Duration::ZERO);
+    assert_eq!(fixed.delay_before(3), Duration::from_millis(100));
+
+    let exponential = RetryPolicy {
+        backoff: Backoff::Exponential,
+        ..fixed
+    };
+    assert_eq!(exponential.delay_before(2), Duration::from_millis(100));
+    assert_eq!(exponential.delay_before(4), Duration::from_millis(400));
+}
+
+#[test]
+fn test_retry_backoff_setting_rejects_unknown_value() {
+    assert!(apply_setting(RETRY_BACKOFF_KEY, "linear").is_err());
+    assert!(apply_setting(RETRY_BACKOFF_KEY, "fixed").is_ok());
+    assert_eq!(RetryPolicy::current().backoff, Backoff::Fixed);
+    apply_setting(RETRY_BACKOFF_KEY, "").unwrap();
+    assert_eq!(RetryPolicy::current().backoff, Backoff::Exponential);
+}
+
+// ============================================================================
+// Reachability Cache Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_reachability_guard_skips_attempts_after_failure() {
+    let target = format!("mock://{}", uuid::Uuid::new_v4());
+    let attempts = AtomicU32::new(0);
+    let connect = || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>("connection refused".to_string())
+    };
+
+    assert_eq!(
+        reachability::guard(&target, connect()).await.unwrap_err(),
+        "connection refused"
+    );
+    let cached = reachability::guard(&target, connect()).await.unwrap_err();
+    assert!(
+        cached.starts_with("connection refused (cached"),
+        "{}",
+        cached
+    );
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+    // Clearing, as an explicit connection test does, lets the next attempt through
+    reachability::clear(&target);
+    assert!(reachability::guard(&target, async { Ok(()) }).await.is_ok());
+    assert!(reachability::cached_failure(&target).is_none());
+}
+
+#[tokio::test]
+async fn test_reachability_guard_does_not_cache_rejected_logins() {
+    let target = format!("mock://{}", uuid::Uuid::new_v4());
+    let attempts = AtomicU32::new(0);
+    let connect = || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>(
+            "Authentication failed: password authentication failed for user \"app\"".to_string(),
+        )
+    };
+
+    // The server answered, so fixing the password must take effect on the next attempt
+    reachability::guard(&target, connect()).await.unwrap_err();
+    let second = reachability::guard(&target, connect()).await.unwrap_err();
+    assert!(!second.contains("cached"), "{}", second);
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    assert!(reachability::cached_failure(&target).is_none());
+
+    assert!(reachability::is_unreachable(
+        "Failed to connect to PostgreSQL: error communicating with database: Connection refused (os error 111)"
+    ));
+    assert!(reachability::is_unreachable(
+        "Connection timed out after 15 seconds"
+    ));
+    assert!(!reachability::is_unreachable(
+        "Failed to connect to PostgreSQL: error with configuration: TLS upgrade required by connect options but not supported by the server"
+    ));
+}
+
+#[tokio::test]
+async fn test_dead_postgres_host_fails_fast_from_cache() {
+    // Nothing listens on port 1, so every attempt is refused
+    let config = PostgresConfig {
+        host: "127.0.0.1".to_string(),
+        port: 1,
+        database: format!("unreachable_{}", uuid::Uuid::new_v4().simple()),
+        username: "postgres".to_string(),
+        password: "postgres".to_string(),
+        ssl: false,
+        socket_path: None,
+        session_setup_sql: None,
+    };
+
+    let first = PostgresDriver::new(config.clone()).list_tables().await;
+    let error = first.unwrap_err();
+
+    // A new driver for the same target, as each command creates, hits the cache
+    let started = Instant::now();
+    let second = PostgresDriver::new(config.clone()).list_tables().await;
+    assert!(started.elapsed() < Duration::from_millis(50));
+    let cached = second.unwrap_err();
+    assert!(cached.starts_with(&error), "{} / {}", error, cached);
+    assert!(cached.contains("cached"), "{}", cached);
+
+    // An explicit connection test tries again rather than reporting the cached error
+    let test = PostgresDriver::new(config).test_connection().await.unwrap();
+    assert!(!test.success);
+    assert!(!test.message.contains("cached"), "{}", test.message);
+}
+
+#[tokio::test]
+async fn test_keepalive_ping_keeps_cached_failure() {
+    let config = PostgresConfig {
+        host: "127.0.0.1".to_string(),
+        port: 1,
+        database: format!("unreachable_{}", uuid::Uuid::new_v4().simple()),
+        username: "postgres".to_string(),
+        password: "postgres".to_string(),
+        ssl: false,
+        socket_path: None,
+        session_setup_sql: None,
+    };
+    let driver = PostgresDriver::new(config);
+    let error = driver.list_tables().await.unwrap_err();
+
+    // Unlike an explicit connection test, a ping reports the cached error and leaves it
+    let ping = driver.ping().await.unwrap_err();
+    assert!(ping.starts_with(&error), "{} / {}", error, ping);
+    assert!(ping.contains("cached"), "{}", ping);
+    let cached = driver.list_tables().await.unwrap_err();
+    assert!(cached.contains("cached"), "{}", cached);
+}
+
+#[test]
+fn test_reachability_setting_rejects_invalid_value() {
+    assert!(reachability::apply_setting(REACHABILITY_CACHE_KEY, "soon").is_err());
+    assert!(reachability::apply_setting(REACHABILITY_CACHE_KEY, "10").is_ok());
+    assert_eq!(reachability::cache_duration(), Duration::from_secs(10));
+    reachability::apply_setting(REACHABILITY_CACHE_KEY, "").unwrap();
+    assert_eq!(reachability::cache_duration(), Duration::from_secs(5));
+}
@@ -7,7 +7,9 @@ use std::path::PathBuf;
 use tempfile::{tempdir, TempDir};
 
 // Re-export the modules we need to test
-use dbcooper_lib::database::sqlite::SqliteDriver;
+use dbcooper_lib::database::export::ExportManager;
+use dbcooper_lib::database::query_stream::QueryStreamManager;
+use dbcooper_lib::database::sqlite::{SqliteDriver, IN_MEMORY_PATH};
 use dbcooper_lib::database::{DatabaseDriver, SqliteConfig};
 
 /// Helper function to create a test SQLite driver with a temporary database
@@ -15,6 +17,7 @@ fn create_test_driver(temp_dir: &TempDir) -> (SqliteDriver, PathBuf) {
     let db_path = temp_dir.path().join("test.db");
     let config = SqliteConfig {
         file_path: db_path.to_string_lossy().to_string(),
+        session_setup_sql: None,
     };
     (SqliteDriver::new(config), db_path)
 }
@@ -101,6 +104,37 @@ async fn test_connection_creates_database() {
     assert!(db_path.exists(), "Database should exist after connection");
 }
 
+#[tokio::test]
+async fn test_in_memory_database_persists_across_calls() {
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: IN_MEMORY_PATH.to_string(),
+        session_setup_sql: None,
+    });
+    assert!(driver.is_in_memory());
+
+    let result = driver
+        .execute_query("CREATE TABLE scratch (id INTEGER PRIMARY KEY, note TEXT)")
+        .await
+        .unwrap();
+    assert!(result.error.is_none(), "Create failed: {:?}", result.error);
+
+    driver
+        .execute_query("INSERT INTO scratch (note) VALUES ('kept')")
+        .await
+        .unwrap();
+
+    let tables = driver.list_tables().await.unwrap();
+    assert!(tables.iter().any(|t| t.name == "scratch"));
+
+    let result = driver
+        .execute_query("SELECT note FROM scratch")
+        .await
+        .unwrap();
+    assert!(result.error.is_none());
+    assert_eq!(result.data.len(), 1);
+    assert_eq!(result.data[0]["note"], "kept");
+}
+
 // ============================================================================
 // List Tables Tests
 // ============================================================================
@@ -184,6 +218,57 @@ async fn test_list_tables_includes_views() {
     assert_eq!(view.unwrap().table_type, "view", "Type should be 'view'");
 }
 
+#[tokio::test]
+async fn test_list_tables_paged_with_filter() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let (driver, _) = create_test_driver(&temp_dir);
+
+    for i in 0..12 {
+        driver
+            .execute_query(&format!("CREATE TABLE orders_{:02} (id INTEGER)", i))
+            .await
+            .unwrap();
+    }
+    for i in 0..5 {
+        driver
+            .execute_query(&format!("CREATE TABLE users_{:02} (id INTEGER)", i))
+            .await
+            .unwrap();
+    }
+
+    let all = driver.list_tables().await.unwrap();
+    assert_eq!(all.len(), 17, "Unfiltered listing should be unchanged");
+
+    let page = driver
+        .list_tables_paged(Some("ORDERS"), Some(2), Some(5))
+        .await
+        .unwrap();
+    let names: Vec<&str> = page.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "orders_05",
+            "orders_06",
+            "orders_07",
+            "orders_08",
+            "orders_09"
+        ]
+    );
+
+    let last_page = driver
+        .list_tables_paged(Some("orders"), Some(3), Some(5))
+        .await
+        .unwrap();
+    assert_eq!(last_page.len(), 2);
+
+    // LIKE wildcards in the filter are matched literally
+    let literal = driver
+        .list_tables_paged(Some("%"), None, None)
+        .await
+        .unwrap();
+    assert!(literal.is_empty());
+}
+
 // ============================================================================
 // Get Table Data Tests
 // ============================================================================
@@ -193,7 +278,9 @@ async fn test_get_table_data_empty_table() {
     let temp_dir = tempdir().expect("Failed to create temp directory");
     let driver = create_driver_with_table(&temp_dir).await;
 
-    let result = driver.get_table_data("main", "users", 1, 10, None, None, None).await;
+    let result = driver
+        .get_table_data("main", "users", 1, 10, None, None, None)
+        .await;
     assert!(result.is_ok());
 
     let data = result.unwrap();
@@ -219,7 +306,9 @@ async fn test_get_table_data_with_rows() {
         .await
         .expect("Failed to insert test data");
 
-    let result = driver.get_table_data("main", "users", 1, 10, None, None, None).await;
+    let result = driver
+        .get_table_data("main", "users", 1, 10, None, None, None)
+        .await;
     assert!(result.is_ok());
 
     let data = result.unwrap();
@@ -287,7 +376,15 @@ async fn test_get_table_data_with_filter() {
         .expect("Failed to insert test data");
 
     let result = driver
-        .get_table_data("main", "users", 1, 10, Some("age > 25".to_string()), None, None)
+        .get_table_data(
+            "main",
+            "users",
+            1,
+            10,
+            Some("age > 25".to_string()),
+            None,
+            None,
+        )
         .await;
     assert!(result.is_ok());
 
@@ -339,6 +436,38 @@ async fn test_get_table_structure_columns() {
     assert_eq!(active_col.default.as_ref().unwrap(), "1");
 }
 
+#[tokio::test]
+async fn test_get_table_structure_auto_increment() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let driver = create_driver_with_table(&temp_dir).await;
+
+    let structure = driver.get_table_structure("main", "users").await.unwrap();
+
+    let id_col = structure.columns.iter().find(|c| c.name == "id").unwrap();
+    assert!(id_col.auto_increment, "id should be auto-increment");
+
+    let age_col = structure.columns.iter().find(|c| c.name == "age").unwrap();
+    assert!(!age_col.auto_increment, "age should not be auto-increment");
+}
+
+#[tokio::test]
+async fn test_get_table_structure_auto_increment_lowercase_integer() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let (driver, _) = create_test_driver(&temp_dir);
+    driver
+        .execute_query("CREATE TABLE notes (id integer PRIMARY KEY, body text)")
+        .await
+        .unwrap();
+
+    let structure = driver.get_table_structure("main", "notes").await.unwrap();
+
+    let id_col = structure.columns.iter().find(|c| c.name == "id").unwrap();
+    assert!(
+        id_col.auto_increment,
+        "a lowercase integer primary key is still a rowid alias"
+    );
+}
+
 #[tokio::test]
 async fn test_get_table_structure_indexes() {
     let temp_dir = tempdir().expect("Failed to create temp directory");
@@ -418,6 +547,43 @@ async fn test_get_table_structure_foreign_keys() {
     assert_eq!(fk.references_column, "id");
 }
 
+#[tokio::test]
+async fn test_get_table_structure_check_constraints() {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let (driver, _) = create_test_driver(&temp_dir);
+
+    driver
+        .execute_query(
+            r#"
+            CREATE TABLE products (
+                id INTEGER PRIMARY KEY,
+                price REAL CHECK (price > 0),
+                label TEXT DEFAULT 'check (me)',
+                CONSTRAINT "label_length" CHECK (length(label) <= 20)
+            )
+            "#,
+        )
+        .await
+        .unwrap();
+
+    let structure = driver
+        .get_table_structure("main", "products")
+        .await
+        .unwrap();
+    let checks: Vec<(&str, &str)> = structure
+        .check_constraints
+        .iter()
+        .map(|c| (c.name.as_str(), c.expression.as_str()))
+        .collect();
+    assert_eq!(
+        checks,
+        vec![
+            ("check_1", "price > 0"),
+            ("label_length", "length(label) <= 20")
+        ]
+    );
+}
+
 // ============================================================================
 // Execute Query Tests
 // ============================================================================
@@ -521,6 +687,170 @@ async fn test_execute_query_syntax_error() {
     assert!(query_result.data.is_empty());
 }
 
+// ============================================================================
+// Streamed Query Tests
+// ============================================================================
+
+/// Counts far past anything a test waits for, so only cancellation ends the fetch
+const ENDLESS_ROWS_QUERY: &str = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 1000000000) SELECT x FROM c";
+
+#[tokio::test]
+async fn test_stream_query_delivers_batches() {
+    let temp_dir = tempdir().unwrap();
+    let (driver, _) = create_test_driver(&temp_dir);
+    let manager = QueryStreamManager::new();
+    let cancel = manager.start("batches").await;
+
+    let mut sizes = Vec::new();
+    let delivered = driver
+        .stream_query(
+            "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 250) SELECT x FROM c",
+            100,
+            &cancel,
+            &mut |rows| sizes.push(rows.len()),
+        )
+        .await
+        .unwrap();
+    manager.finish("batches", &cancel).await;
+
+    assert_eq!(delivered, 250);
+    assert_eq!(sizes, vec![100, 100, 50]);
+    assert!(
+        !manager.cancel("batches").await,
+        "Finished stream should be forgotten"
+    );
+}
+
+#[tokio::test]
+async fn test_stream_query_cancel_stops_fetch_and_releases_connection() {
+    // In-memory databases use a single pooled connection, so a leaked fetch would block the next query
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: IN_MEMORY_PATH.to_string(),
+        session_setup_sql: None,
+    });
+    let manager = QueryStreamManager::new();
+    let cancel = manager.start("grid").await;
+
+    let canceller = cancel.clone();
+    let mut batches = 0;
+    let delivered = tokio::time::timeout(
+        std::time::Duration::from_secs(10),
+        driver.stream_query(ENDLESS_ROWS_QUERY, 100, &cancel, &mut |rows| {
+            assert_eq!(rows.len(), 100);
+            batches += 1;
+            if batches == 3 {
+                canceller.cancel();
+            }
+        }),
+    )
+    .await
+    .expect("Fetch loop should exit promptly after cancellation")
+    .unwrap();
+
+    assert_eq!(batches, 3);
+    assert_eq!(delivered, 300);
+
+    let result = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        driver.execute_query("SELECT 1 AS one"),
+    )
+    .await
+    .expect("Connection should be back in the pool")
+    .unwrap();
+    assert_eq!(result.data[0]["one"], 1);
+}
+
+#[tokio::test]
+async fn test_query_stream_manager_cancel() {
+    let manager = QueryStreamManager::new();
+
+    let first = manager.start("editor").await;
+    let second = manager.start("editor").await;
+    assert!(
+        first.is_cancelled(),
+        "Restarting a stream cancels the old one"
+    );
+    assert!(!second.is_cancelled());
+
+    assert!(manager.cancel("editor").await);
+    assert!(second.is_cancelled());
+    assert!(!manager.cancel("editor").await);
+}
+
+// ============================================================================
+// CSV Export Cancellation Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_cancel_export_leaves_partial_csv() {
+    let temp_dir = tempdir().unwrap();
+    let (driver, _) = create_test_driver(&temp_dir);
+    let total_rows = 200_000;
+
+    driver
+        .execute_query("CREATE TABLE big (id INTEGER PRIMARY KEY, label TEXT)")
+        .await
+        .unwrap();
+    driver
+        .execute_query(&format!(
+            "INSERT INTO big (label) WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < {}) SELECT 'row ' || x FROM c",
+            total_rows
+        ))
+        .await
+        .unwrap();
+
+    let output_path = temp_dir.path().join("big.csv");
+    let output = output_path.to_str().unwrap();
+    let manager = ExportManager::new();
+    let handle = manager.start("big").await;
+
+    let export = async {
+        let result = driver
+            .export_table_csv("main", "big", output, &handle.cancel)
+            .await;
+        let rows_written = result.as_ref().ok().map(|summary| summary.rows_written);
+        manager.finish("big", handle, rows_written).await;
+        result
+    };
+    // Cancel as soon as the first batch reaches the file
+    let canceller = async {
+        loop {
+            let written = tokio::fs::metadata(&output_path)
+                .await
+                .map(|m| m.len())
+                .unwrap_or(0);
+            if written > 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        manager.cancel("big").await
+    };
+    let (summary, cancelled_rows) = tokio::join!(export, canceller);
+    let summary = summary.unwrap();
+
+    assert!(summary.cancelled);
+    assert!(summary.rows_written > 0);
+    assert!(summary.rows_written < total_rows);
+    assert_eq!(cancelled_rows, Some(summary.rows_written));
+
+    // The partial file holds only complete rows
+    let mut reader = csv::Reader::from_path(&output_path).unwrap();
+    let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(records.len() as u64, summary.rows_written);
+    assert!(records.iter().all(|r| r.len() == 2));
+    assert_eq!(
+        std::fs::metadata(&output_path).unwrap().len(),
+        summary.bytes_written
+    );
+
+    assert_eq!(
+        manager.cancel("big").await,
+        None,
+        "Finished export should be forgotten"
+    );
+}
+
 // ============================================================================
 // Get Schema Overview Tests
 // ============================================================================
@@ -928,3 +1258,59 @@ async fn test_delete_with_no_matching_rows() {
     let count = get_row_count(&driver, "users").await;
     assert_eq!(count, 2, "Both rows should still exist");
 }
+
+// ============================================================================
+// Transaction Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_transaction_rollback_to_savepoint() {
+    let temp_dir = tempdir().unwrap();
+    let driver = create_driver_with_table(&temp_dir).await;
+
+    let mut tx = driver.begin_transaction().await.unwrap();
+    tx.execute("INSERT INTO users (name) VALUES ('A')")
+        .await
+        .unwrap();
+    tx.savepoint("after_a").await.unwrap();
+    tx.execute("INSERT INTO users (name) VALUES ('B')")
+        .await
+        .unwrap();
+    tx.rollback_to_savepoint("after_a").await.unwrap();
+    tx.commit().await.unwrap();
+
+    let result = driver
+        .execute_query("SELECT name FROM users ORDER BY id")
+        .await
+        .unwrap();
+    assert_eq!(result.row_count, 1, "Only row A should persist");
+    assert_eq!(result.data[0]["name"], "A");
+}
+
+// ============================================================================
+// Maintenance Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_vacuum_database_file() {
+    let temp_dir = tempdir().unwrap();
+    let driver = create_driver_with_table(&temp_dir).await;
+
+    driver
+        .execute_query("INSERT INTO users (name, email) VALUES ('Vacuum', 'v@example.com')")
+        .await
+        .unwrap();
+    driver.execute_query("DELETE FROM users").await.unwrap();
+
+    let result = driver.vacuum().await;
+    assert!(result.is_ok(), "VACUUM failed: {:?}", result.err());
+}
+
+#[tokio::test]
+async fn test_integrity_check_healthy_database() {
+    let temp_dir = tempdir().unwrap();
+    let driver = create_driver_with_table(&temp_dir).await;
+
+    let report = driver.integrity_check().await.unwrap();
+    assert_eq!(report, vec!["ok".to_string()]);
+}
@@ -5,11 +5,48 @@
 //!
 //! Run with: cargo test --test unified_commands_tests -- --test-threads=1
 
+use async_trait::async_trait;
 use dbcooper_lib::commands::database::{
-    delete_table_row, insert_table_row, unified_execute_query, unified_get_table_data,
-    unified_get_table_structure, unified_list_tables, unified_test_connection, update_table_row,
+    bind_query_variables, clone_table_structure, column_aggregate, delete_table_row,
+    execute_script, find_referencing_rows, follow_foreign_key, get_database_info,
+    get_driver_capabilities, get_row_by_primary_key, get_server_overview, insert_table_row,
+    is_read_only_query, postgres_analyze, postgres_vacuum, profile_columns, sample_table,
+    set_table_comment, sqlite_vacuum, stop_watch_table, stream_schema_tables,
+    substitute_query_variables, test_connection_deep, undo_last_edit, undo_last_edit_with_driver,
+    unified_execute_query, unified_get_table_data, unified_get_table_structure,
+    unified_list_tables, unified_test_connection, update_table_row, update_table_row_with_raw_sql,
+    watch_table_changes, QueryOptions, SchemaStreamEvent,
+};
+use dbcooper_lib::commands::export::{
+    export_er_mermaid, export_query_parquet, import_clipboard_data, import_table_csv,
+    materialize_query,
+};
+use dbcooper_lib::commands::pool::pool_delete_table_row;
+use dbcooper_lib::commands::settings::set_connection_variable;
+use dbcooper_lib::commands::slow_queries::{self, list_slow_queries, SLOW_QUERY_THRESHOLD_KEY};
+use dbcooper_lib::database::export::{arrow_type_for, write_rows_parquet};
+use dbcooper_lib::database::import::{
+    import_pasted_data, infer_column_type, parse_pasted_data, InferredType,
+};
+use dbcooper_lib::database::pool_manager::PoolManager;
+use dbcooper_lib::database::script::split_statements;
+use dbcooper_lib::database::sqlite::SqliteDriver;
+use dbcooper_lib::database::table_watch::TableWatchManager;
+use dbcooper_lib::database::undo::{driver_identity, UndoHistory};
+use dbcooper_lib::database::{
+    display, query_cache, timeouts, DatabaseDriver, SqliteConfig, TxHandle,
+};
+use dbcooper_lib::db::migrate;
+use dbcooper_lib::db::models::{
+    DriverCapabilities, QueryResult, SchemaOverview, TableDataResponse, TableInfo, TableStructure,
+    TestConnectionResult,
 };
 use serde_json::json;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use tauri::test::{mock_app, MockRuntime};
+use tauri::{App, Manager};
 use tempfile::NamedTempFile;
 
 /// Helper to create a temporary SQLite file path
@@ -18,6 +55,89 @@ fn temp_sqlite_path() -> String {
     temp_file.path().to_string_lossy().to_string()
 }
 
+/// Mock app managing a migrated app database, for commands that look up saved connections.
+/// The temp file must stay alive for the duration of the test.
+async fn test_app() -> (App<MockRuntime>, NamedTempFile) {
+    let db_file = NamedTempFile::new().expect("Failed to create temp file");
+    let pool = SqlitePoolOptions::new()
+        .connect(&format!("sqlite:{}?mode=rwc", db_file.path().display()))
+        .await
+        .expect("Failed to create pool");
+    migrate(&pool)
+        .await
+        .expect("Failed to migrate app database");
+
+    let app = mock_app();
+    app.manage(pool);
+    app.manage(UndoHistory::new());
+    (app, db_file)
+}
+
+/// Save a read-only connection in the mock app's database, returning its uuid
+async fn save_read_only_connection(app: &App<MockRuntime>) -> String {
+    let uuid = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO connections (uuid, name, host, port, database, username, password, read_only) \
+         VALUES (?, 'read-only', '', 0, '', '', '', 1)",
+    )
+    .bind(&uuid)
+    .execute(app.state::<SqlitePool>().inner())
+    .await
+    .expect("Failed to save connection");
+    uuid
+}
+
+/// Save a connection with a default query timeout in the mock app's database, returning
+/// its uuid
+async fn save_connection_with_timeout(app: &App<MockRuntime>, timeout_secs: i64) -> String {
+    let uuid = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO connections (uuid, name, host, port, database, username, password, query_timeout_secs) \
+         VALUES (?, 'timeout', '', 0, '', '', '', ?)",
+    )
+    .bind(&uuid)
+    .bind(timeout_secs)
+    .execute(app.state::<SqlitePool>().inner())
+    .await
+    .expect("Failed to save connection");
+    uuid
+}
+
+/// Save a SQLite connection to `file_path` in the mock app's database, returning its uuid
+async fn save_sqlite_connection(app: &App<MockRuntime>, file_path: &str) -> String {
+    let uuid = uuid::Uuid::new_v4().to_string();
+    sqlx::query(
+        "INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type, file_path) \
+         VALUES (?, 'sqlite', 'sqlite', '', 0, '', '', '', 'sqlite', ?)",
+    )
+    .bind(&uuid)
+    .bind(file_path)
+    .execute(app.state::<SqlitePool>().inner())
+    .await
+    .expect("Failed to save connection");
+    uuid
+}
+
+/// Poll the mock app's database until `query` returns a row, for values written in the
+/// background after a command returns
+async fn wait_for_row<T>(app: &App<MockRuntime>, query: &str, uuid: &str) -> T
+where
+    T: for<'r> sqlx::FromRow<'r, sqlx::sqlite::SqliteRow> + Send + Unpin,
+{
+    for _ in 0..100 {
+        let row: Option<T> = sqlx::query_as(query)
+            .bind(uuid)
+            .fetch_optional(app.state::<SqlitePool>().inner())
+            .await
+            .expect("Failed to query app database");
+        if let Some(row) = row {
+            return row;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    panic!("No row for {} after waiting", query);
+}
+
 /// Helper to generate unique table name
 fn test_table_name(prefix: &str) -> String {
     format!("test_{}_{}", prefix, uuid::Uuid::new_v4().simple())
@@ -40,6 +160,8 @@ async fn test_unified_test_connection_sqlite_success() {
         None,
         None,
         Some(file_path),
+        None,
+        None,
     )
     .await;
 
@@ -60,6 +182,8 @@ async fn test_unified_test_connection_postgres_success() {
         Some("postgres".to_string()),
         Some(false),
         None,
+        None,
+        None,
     )
     .await;
 
@@ -83,23 +207,122 @@ async fn test_unified_test_connection_invalid_db_type() {
         None,
         None,
         None,
+        None,
+        None,
     )
     .await;
 
     assert!(result.is_err(), "Should error on unknown database type");
 }
 
+// ============================================================================
+// test_connection_deep Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_connection_deep_sqlite_all_checks_pass() {
+    let file_path = temp_sqlite_path();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    driver
+        .execute_query("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT)")
+        .await
+        .unwrap();
+    driver
+        .execute_query("INSERT INTO items (name) VALUES ('first')")
+        .await
+        .unwrap();
+
+    let result = test_connection_deep(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert!(result.can_connect, "{}", result.message);
+    assert!(result.can_list, "{}", result.message);
+    assert!(result.can_read, "{}", result.message);
+    assert_eq!(result.probed_table.as_deref(), Some("main.items"));
+}
+
+// ============================================================================
+// get_driver_capabilities Tests
+// ============================================================================
+
+#[test]
+fn test_driver_capabilities_per_database() {
+    let caps = |db_type: &str| get_driver_capabilities(db_type.to_string()).unwrap();
+
+    let postgres = caps("postgres");
+    assert!(postgres.supports_transactions);
+    assert!(postgres.supports_foreign_keys);
+    assert!(postgres.supports_row_editing);
+    assert!(postgres.supports_schemas);
+    assert!(postgres.supports_explain);
+
+    let sqlite = caps("sqlite");
+    assert!(sqlite.supports_transactions);
+    assert!(sqlite.supports_foreign_keys);
+    assert!(sqlite.supports_row_editing);
+    assert!(!sqlite.supports_schemas);
+    assert!(sqlite.supports_explain);
+
+    let clickhouse = caps("clickhouse");
+    assert!(!clickhouse.supports_transactions);
+    assert!(!clickhouse.supports_foreign_keys);
+    assert!(!clickhouse.supports_row_editing);
+    assert!(clickhouse.supports_schemas);
+    assert!(clickhouse.supports_explain);
+
+    // Key-value and search stores support none of the relational features
+    assert_eq!(caps("redis"), DriverCapabilities::default());
+    assert_eq!(caps("opensearch"), DriverCapabilities::default());
+
+    // Aliases resolve to the same driver
+    assert_eq!(caps("postgresql"), postgres);
+    assert_eq!(caps("sqlite3"), sqlite);
+
+    let err = get_driver_capabilities("mongodb".to_string()).unwrap_err();
+    assert!(err.contains("Unsupported database type"), "{}", err);
+}
+
+#[test]
+fn test_driver_capabilities_match_driver_instance() {
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: temp_sqlite_path(),
+        session_setup_sql: None,
+    });
+    assert_eq!(
+        driver.capabilities(),
+        get_driver_capabilities("sqlite".to_string()).unwrap()
+    );
+}
+
 // ============================================================================
 // unified_list_tables Tests
 // ============================================================================
 
 #[tokio::test]
 async fn test_unified_list_tables_sqlite() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
     let table_name = test_table_name("list");
 
     // First create a table using execute_query
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -108,10 +331,14 @@ async fn test_unified_list_tables_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -132,6 +359,12 @@ async fn test_unified_list_tables_sqlite() {
         None,
         None,
         None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
     )
     .await;
 
@@ -161,6 +394,12 @@ async fn test_unified_list_tables_postgres() {
         None,
         None,
         None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
     )
     .await;
 
@@ -173,11 +412,14 @@ async fn test_unified_list_tables_postgres() {
 
 #[tokio::test]
 async fn test_unified_get_table_data_sqlite() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
     let table_name = test_table_name("data");
 
     // Create table with data
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -186,15 +428,21 @@ async fn test_unified_get_table_data_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
 
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -203,10 +451,14 @@ async fn test_unified_get_table_data_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "INSERT INTO {} (name) VALUES ('Alice'), ('Bob')",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -220,6 +472,8 @@ async fn test_unified_get_table_data_sqlite() {
         None,
         None,
         Some(file_path),
+        None,
+        None,
         "main".to_string(),
         table_name,
         1,
@@ -227,6 +481,7 @@ async fn test_unified_get_table_data_sqlite() {
         None,
         None,
         None,
+        None,
     )
     .await;
 
@@ -238,11 +493,14 @@ async fn test_unified_get_table_data_sqlite() {
 
 #[tokio::test]
 async fn test_unified_get_table_data_with_filter() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
     let table_name = test_table_name("filter");
 
     // Create table with data
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -251,15 +509,21 @@ async fn test_unified_get_table_data_with_filter() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
 
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -268,10 +532,14 @@ async fn test_unified_get_table_data_with_filter() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "INSERT INTO {} (name, age) VALUES ('Alice', 30), ('Bob', 25), ('Charlie', 35)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -285,6 +553,8 @@ async fn test_unified_get_table_data_with_filter() {
         None,
         None,
         Some(file_path),
+        None,
+        None,
         "main".to_string(),
         table_name,
         1,
@@ -292,6 +562,7 @@ async fn test_unified_get_table_data_with_filter() {
         Some("age > 25".to_string()),
         None,
         None,
+        None,
     )
     .await;
 
@@ -300,17 +571,124 @@ async fn test_unified_get_table_data_with_filter() {
     assert_eq!(data.data.len(), 2, "Should return 2 rows matching filter");
 }
 
+#[tokio::test]
+async fn test_type_overrides_parse_text_as_json() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("overrides");
+    let run = |query: String, type_overrides: Option<HashMap<String, String>>| {
+        unified_execute_query(
+            app.handle().clone(),
+            app.state(),
+            "sqlite".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(file_path.clone()),
+            None,
+            None,
+            query,
+            None,
+            Some(QueryOptions {
+                type_overrides: type_overrides.unwrap_or_default(),
+                ..Default::default()
+            }),
+        )
+    };
+
+    run(
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, payload TEXT, price TEXT)",
+            table_name
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    run(
+        format!(
+            r#"INSERT INTO {} (payload, price) VALUES ('{{"user": {{"name": "ada", "tags": [1, 2]}}}}', '$1,250.50')"#,
+            table_name
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Without overrides the text comes back as a string
+    let plain = run(format!("SELECT payload FROM {}", table_name), None)
+        .await
+        .unwrap();
+    assert!(plain.data[0]["payload"].is_string());
+
+    let overrides = HashMap::from([
+        ("payload".to_string(), "json".to_string()),
+        ("price".to_string(), "float".to_string()),
+        ("id".to_string(), "string".to_string()),
+    ]);
+    let result = run(
+        format!("SELECT id, payload, price FROM {}", table_name),
+        Some(overrides.clone()),
+    )
+    .await
+    .unwrap();
+    let row = &result.data[0];
+    assert_eq!(row["payload"]["user"]["name"], json!("ada"));
+    assert_eq!(row["payload"]["user"]["tags"], json!([1, 2]));
+    assert_eq!(row["price"], json!(1250.5));
+    assert_eq!(row["id"], json!("1"));
+
+    // Table data honours the same overrides
+    let page = unified_get_table_data(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        1,
+        10,
+        None,
+        None,
+        None,
+        Some(overrides),
+    )
+    .await
+    .unwrap();
+    assert_eq!(page.data[0]["payload"]["user"]["name"], json!("ada"));
+
+    let err = run(
+        format!("SELECT payload FROM {}", table_name),
+        Some(HashMap::from([("payload".to_string(), "xml".to_string())])),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.contains("Unknown type override"), "{}", err);
+}
+
 // ============================================================================
 // unified_get_table_structure Tests
 // ============================================================================
 
 #[tokio::test]
 async fn test_unified_get_table_structure_sqlite() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
     let table_name = test_table_name("struct");
 
     // Create table
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -319,10 +697,14 @@ async fn test_unified_get_table_structure_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT NOT NULL, email TEXT)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
@@ -336,6 +718,8 @@ async fn test_unified_get_table_structure_sqlite() {
         None,
         None,
         Some(file_path),
+        None,
+        None,
         "main".to_string(),
         table_name,
     )
@@ -357,11 +741,14 @@ async fn test_unified_get_table_structure_sqlite() {
 
 #[tokio::test]
 async fn test_unified_execute_query_select() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
     let table_name = test_table_name("exec");
 
     // Create table with data
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -370,15 +757,21 @@ async fn test_unified_execute_query_select() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
             "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
 
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -387,12 +780,18 @@ async fn test_unified_execute_query_select() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!("INSERT INTO {} (name) VALUES ('Test')", table_name),
+        None,
+        None,
     )
     .await
     .unwrap();
 
     let result = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -401,7 +800,11 @@ async fn test_unified_execute_query_select() {
         None,
         None,
         Some(file_path),
+        None,
+        None,
         format!("SELECT * FROM {}", table_name),
+        None,
+        None,
     )
     .await;
 
@@ -413,9 +816,12 @@ async fn test_unified_execute_query_select() {
 
 #[tokio::test]
 async fn test_unified_execute_query_syntax_error() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
 
     let result = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -424,7 +830,11 @@ async fn test_unified_execute_query_syntax_error() {
         None,
         None,
         Some(file_path),
+        None,
+        None,
         "SELECTTT * FROM nonexistent".to_string(),
+        None,
+        None,
     )
     .await;
 
@@ -433,17 +843,179 @@ async fn test_unified_execute_query_syntax_error() {
     assert!(query_result.error.is_some(), "Should have syntax error");
 }
 
-// ============================================================================
-// update_table_row Tests
-// ============================================================================
+#[tokio::test]
+async fn test_unified_execute_query_records_connection_usage() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let uuid = save_sqlite_connection(&app, &file_path).await;
+
+    for _ in 0..2 {
+        let result = unified_execute_query(
+            app.handle().clone(),
+            app.state(),
+            "sqlite".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(file_path.clone()),
+            None,
+            None,
+            "SELECT 1".to_string(),
+            Some(uuid.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(result.error.is_none(), "{:?}", result.error);
+    }
+
+    // Usage is recorded in the background
+    let (query_count, last_connected_at): (i64, Option<String>) = wait_for_row(
+        &app,
+        "SELECT query_count, last_connected_at FROM connections \
+         WHERE uuid = ? AND query_count = 2 AND last_connected_at IS NOT NULL",
+        &uuid,
+    )
+    .await;
+    assert_eq!(query_count, 2);
+    assert!(last_connected_at.is_some());
+}
 
 #[tokio::test]
-async fn test_update_table_row_sqlite() {
+async fn test_unified_execute_query_remembers_last_query() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
-    let table_name = test_table_name("upd");
+    let uuid = save_sqlite_connection(&app, &file_path).await;
 
-    // Create table with data
-    let _ = unified_execute_query(
+    for query in ["SELECT 1 AS first", "SELECT 2 AS second"] {
+        unified_execute_query(
+            app.handle().clone(),
+            app.state(),
+            "sqlite".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(file_path.clone()),
+            None,
+            None,
+            query.to_string(),
+            Some(uuid.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        // Saved in the background, replacing the previous query
+        let (last_query,): (String,) = wait_for_row(
+            &app,
+            &format!(
+                "SELECT value FROM settings WHERE key = 'last_query:' || ? AND value = '{}'",
+                query
+            ),
+            &uuid,
+        )
+        .await;
+        assert_eq!(last_query, query);
+    }
+}
+
+#[tokio::test]
+async fn test_unified_execute_query_records_slow_queries() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let uuid = save_sqlite_connection(&app, &file_path).await;
+    let slow_query = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 2000000) SELECT count(*) FROM c";
+    let run = |query: &str| {
+        unified_execute_query(
+            app.handle().clone(),
+            app.state(),
+            "sqlite".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(file_path.clone()),
+            None,
+            None,
+            query.to_string(),
+            Some(uuid.clone()),
+            None,
+        )
+    };
+
+    // Off until a threshold is configured
+    slow_queries::apply_setting(SLOW_QUERY_THRESHOLD_KEY, "").unwrap();
+    run(slow_query).await.unwrap();
+
+    slow_queries::apply_setting(SLOW_QUERY_THRESHOLD_KEY, "5").unwrap();
+    let slow = run(slow_query).await.unwrap();
+    assert!(slow.time_taken_ms.unwrap() > 5);
+    // A query under the threshold is not logged
+    run("SELECT 1").await.unwrap();
+
+    let (query, duration_ms): (String, i64) = wait_for_row(
+        &app,
+        "SELECT query, duration_ms FROM slow_queries WHERE connection_uuid = ?",
+        &uuid,
+    )
+    .await;
+    assert_eq!(query, slow_query);
+    assert!(duration_ms > 5, "Recorded {}ms", duration_ms);
+
+    let logged = list_slow_queries(app.state::<SqlitePool>().inner(), 10)
+        .await
+        .unwrap();
+    assert_eq!(logged.len(), 1);
+
+    slow_queries::apply_setting(SLOW_QUERY_THRESHOLD_KEY, "").unwrap();
+}
+
+#[tokio::test]
+async fn test_unified_execute_query_cache_ttl() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("cache");
+
+    for query in [
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
+            table_name
+        ),
+        format!("INSERT INTO {} (name) VALUES ('original')", table_name),
+    ] {
+        unified_execute_query(
+            app.handle().clone(),
+            app.state(),
+            "sqlite".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(file_path.clone()),
+            None,
+            None,
+            query,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let select = format!("SELECT name FROM {}", table_name);
+    let first = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -452,15 +1024,23 @@ async fn test_update_table_row_sqlite() {
         None,
         None,
         Some(file_path.clone()),
-        format!(
-            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)",
-            table_name
-        ),
+        None,
+        None,
+        select.clone(),
+        None,
+        Some(QueryOptions {
+            cache_ttl_secs: Some(60),
+            ..Default::default()
+        }),
     )
     .await
     .unwrap();
+    assert!(!first.from_cache);
+    assert_eq!(first.data[0]["name"], "original");
 
-    let _ = unified_execute_query(
+    let cached = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -469,19 +1049,24 @@ async fn test_update_table_row_sqlite() {
         None,
         None,
         Some(file_path.clone()),
-        format!(
-            "INSERT INTO {} (id, name, age) VALUES (1, 'Alice', 30)",
-            table_name
-        ),
+        None,
+        None,
+        format!("{}  ;", select.replace(" FROM ", "\n  FROM ")),
+        None,
+        Some(QueryOptions {
+            cache_ttl_secs: Some(60),
+            ..Default::default()
+        }),
     )
     .await
     .unwrap();
+    assert!(cached.from_cache, "Second run should be served from cache");
+    assert_eq!(cached.data[0]["name"], "original");
 
-    // Update the row
-    let mut updates = serde_json::Map::new();
-    updates.insert("age".to_string(), json!(35));
-
-    let result = update_table_row(
+    // Writes to the same database drop its cached results
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -490,20 +1075,18 @@ async fn test_update_table_row_sqlite() {
         None,
         None,
         Some(file_path.clone()),
-        "main".to_string(),
-        table_name.clone(),
-        vec!["id".to_string()],
-        vec![json!(1)],
-        updates,
+        None,
+        None,
+        format!("UPDATE {} SET name = 'changed'", table_name),
+        None,
+        None,
     )
-    .await;
-
-    assert!(result.is_ok());
-    let query_result = result.unwrap();
-    assert!(query_result.error.is_none(), "Update should succeed");
+    .await
+    .unwrap();
 
-    // Verify the update
-    let select = unified_execute_query(
+    let fresh = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -511,125 +1094,304 @@ async fn test_update_table_row_sqlite() {
         None,
         None,
         None,
-        Some(file_path),
-        format!("SELECT age FROM {} WHERE id = 1", table_name),
+        Some(file_path.clone()),
+        None,
+        None,
+        select,
+        None,
+        Some(QueryOptions {
+            cache_ttl_secs: Some(60),
+            ..Default::default()
+        }),
     )
     .await
     .unwrap();
+    assert!(
+        !fresh.from_cache,
+        "A write should invalidate cached results"
+    );
+    assert_eq!(fresh.data[0]["name"], "changed");
+}
 
+#[test]
+fn test_query_cache_normalizes_outside_quotes_only() {
     assert_eq!(
-        select.data[0].get("age").unwrap().as_i64().unwrap(),
-        35,
-        "Age should be updated to 35"
+        query_cache::normalize_query("SELECT  *\n FROM t  ;"),
+        "SELECT * FROM t"
+    );
+    assert_eq!(
+        query_cache::normalize_query("SELECT 'a   b' ,  \"x  y\""),
+        "SELECT 'a   b' , \"x  y\""
+    );
+    assert_ne!(
+        query_cache::normalize_query("SELECT * FROM t WHERE s = 'a  b'"),
+        query_cache::normalize_query("SELECT * FROM t WHERE s = 'a b'")
     );
 }
 
+#[test]
+fn test_query_cache_rejects_side_effect_selects() {
+    assert!(query_cache::is_cacheable("SELECT * FROM t", "postgres"));
+    assert!(query_cache::is_cacheable(
+        "select 'for update' FROM t",
+        "postgres"
+    ));
+    assert!(!query_cache::is_cacheable(
+        "SELECT * INTO copy FROM t",
+        "postgres"
+    ));
+    assert!(!query_cache::is_cacheable(
+        "SELECT nextval('s')",
+        "postgres"
+    ));
+    assert!(!query_cache::is_cacheable(
+        "SELECT * FROM t FOR UPDATE",
+        "postgres"
+    ));
+    assert!(!query_cache::is_cacheable(
+        "SELECT * FROM t FOR NO KEY UPDATE",
+        "postgres"
+    ));
+    assert!(!query_cache::is_cacheable(
+        "SELECT 1; DELETE FROM t",
+        "postgres"
+    ));
+    assert!(!query_cache::is_cacheable("UPDATE t SET a = 1", "postgres"));
+}
+
+/// Recursive CTE that keeps SQLite busy far longer than any timeout used below
+const SLOW_SQLITE_QUERY: &str = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 1000000000) SELECT count(*) FROM c";
+
 #[tokio::test]
-async fn test_update_table_row_postgres() {
-    let table_name = test_table_name("upd");
+async fn test_unified_execute_query_applies_connection_timeout() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
 
-    // Create table
-    let _ = unified_execute_query(
-        "postgres".to_string(),
-        Some("localhost".to_string()),
-        Some(5432),
-        Some("testdb".to_string()),
-        Some("postgres".to_string()),
-        Some("postgres".to_string()),
-        Some(false),
+    let started = std::time::Instant::now();
+    let result = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
         None,
-        format!(
-            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        SLOW_SQLITE_QUERY.to_string(),
+        Some(save_connection_with_timeout(&app, 1).await),
+        None,
+    )
+    .await;
+
+    let result = result.expect("Timeouts are reported in the result");
+    let err = result
+        .error
+        .expect("Query should hit the connection's timeout");
+    assert!(
+        err.starts_with(timeouts::TIMEOUT_ERROR_PREFIX),
+        "Unexpected error: {}",
+        err
+    );
+    assert!(
+        err.contains("timed out after 1 seconds"),
+        "Unexpected error: {}",
+        err
+    );
+    let elapsed_ms = result
+        .time_taken_ms
+        .expect("Elapsed time should be reported");
+    assert!(
+        (1000..10_000).contains(&elapsed_ms),
+        "Elapsed {}ms",
+        elapsed_ms
+    );
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_unified_execute_query_timeout_override_wins() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+
+    // A relaxed connection default is overridden by a tighter per-call timeout
+    let started = std::time::Instant::now();
+    let result = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        SLOW_SQLITE_QUERY.to_string(),
+        Some(save_connection_with_timeout(&app, 600).await),
+        Some(QueryOptions {
+            timeout_secs: Some(1),
+            ..Default::default()
+        }),
+    )
+    .await;
+
+    let result = result.expect("Timeouts are reported in the result");
+    let err = result.error.expect("Query should hit the per-call timeout");
+    assert!(
+        err.starts_with(timeouts::TIMEOUT_ERROR_PREFIX),
+        "Unexpected error: {}",
+        err
+    );
+    assert!(
+        err.contains("timed out after 1 seconds"),
+        "Unexpected error: {}",
+        err
+    );
+    let elapsed_ms = result
+        .time_taken_ms
+        .expect("Elapsed time should be reported");
+    assert!(
+        (1000..10_000).contains(&elapsed_ms),
+        "Elapsed {}ms",
+        elapsed_ms
+    );
+    assert!(started.elapsed() < std::time::Duration::from_secs(10));
+}
+
+/// Run a statement through `unified_execute_query` on a SQLite file with bind parameters
+async fn execute_sqlite_with_params(
+    file_path: &str,
+    query: &str,
+    params: Option<Vec<serde_json::Value>>,
+) -> Result<dbcooper_lib::db::models::QueryResult, String> {
+    let (app, _app_db) = test_app().await;
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.to_string()),
+        None,
+        None,
+        query.to_string(),
+        None,
+        Some(QueryOptions {
+            params: params.unwrap_or_default(),
+            ..Default::default()
+        }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_unified_execute_query_with_bind_params() {
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("params");
+
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, score REAL)",
             table_name
         ),
+        None,
     )
     .await
     .unwrap();
+    for (name, score) in [("alice", 1.5), ("bob", 2.5), ("carol", 3.5)] {
+        execute_sqlite_with_params(
+            &file_path,
+            &format!("INSERT INTO {} (name, score) VALUES (?, ?)", table_name),
+            Some(vec![json!(name), json!(score)]),
+        )
+        .await
+        .unwrap();
+    }
 
-    // Insert data
-    let _ = unified_execute_query(
-        "postgres".to_string(),
-        Some("localhost".to_string()),
-        Some(5432),
-        Some("testdb".to_string()),
-        Some("postgres".to_string()),
-        Some("postgres".to_string()),
-        Some(false),
-        None,
-        format!(
-            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30)",
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "SELECT name FROM {} WHERE score > ? AND name <> ? ORDER BY name",
             table_name
         ),
+        Some(vec![json!(2), json!("carol")]),
     )
     .await
     .unwrap();
+    assert!(result.error.is_none(), "Query failed: {:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["name"], "bob");
 
-    // Get the inserted ID
-    let select = unified_execute_query(
-        "postgres".to_string(),
-        Some("localhost".to_string()),
-        Some(5432),
-        Some("testdb".to_string()),
-        Some("postgres".to_string()),
-        Some("postgres".to_string()),
-        Some(false),
-        None,
-        format!("SELECT id FROM \"{}\" WHERE name = 'Alice'", table_name),
+    // A bound value is compared literally, never parsed as SQL
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT * FROM {} WHERE name = ?", table_name),
+        Some(vec![json!("x' OR '1'='1")]),
     )
     .await
     .unwrap();
+    assert!(result.error.is_none());
+    assert_eq!(result.row_count, 0);
 
-    let id = select.data[0].get("id").unwrap().as_i64().unwrap();
-
-    // Update the row
-    let mut updates = serde_json::Map::new();
-    updates.insert("age".to_string(), json!(99));
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT * FROM {} WHERE name = ?", table_name),
+        Some(vec![json!(format!("x'; DROP TABLE {}; --", table_name))]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.row_count, 0);
 
-    let result = update_table_row(
-        "postgres".to_string(),
-        Some("localhost".to_string()),
-        Some(5432),
-        Some("testdb".to_string()),
-        Some("postgres".to_string()),
-        Some("postgres".to_string()),
-        Some(false),
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT COUNT(*) AS count FROM {}", table_name),
         None,
-        "public".to_string(),
-        table_name.clone(),
-        vec!["id".to_string()],
-        vec![json!(id)],
-        updates,
     )
-    .await;
+    .await
+    .unwrap();
+    assert_eq!(result.data[0]["count"], 3);
+}
 
-    assert!(result.is_ok());
+#[tokio::test]
+async fn test_unified_execute_query_rejects_param_count_mismatch() {
+    let file_path = temp_sqlite_path();
 
-    // Cleanup
-    let _ = unified_execute_query(
-        "postgres".to_string(),
-        Some("localhost".to_string()),
-        Some(5432),
-        Some("testdb".to_string()),
-        Some("postgres".to_string()),
-        Some("postgres".to_string()),
-        Some(false),
-        None,
-        format!("DROP TABLE IF EXISTS \"{}\"", table_name),
-    )
-    .await;
+    let result =
+        execute_sqlite_with_params(&file_path, "SELECT ? AS a, ? AS b", Some(vec![json!(1)])).await;
+
+    let err = result.expect_err("Missing parameter should be rejected");
+    assert!(
+        err.contains("expects 2 parameter(s) but 1 were provided"),
+        "{}",
+        err
+    );
 }
 
 // ============================================================================
-// delete_table_row Tests
+// update_table_row Tests
 // ============================================================================
 
 #[tokio::test]
-async fn test_delete_table_row_sqlite() {
+async fn test_update_table_row_sqlite() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
-    let table_name = test_table_name("del");
+    let table_name = test_table_name("upd");
 
     // Create table with data
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -638,15 +1400,21 @@ async fn test_delete_table_row_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
-            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
 
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -655,16 +1423,25 @@ async fn test_delete_table_row_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         format!(
-            "INSERT INTO {} (id, name) VALUES (1, 'Alice'), (2, 'Bob')",
+            "INSERT INTO {} (id, name, age) VALUES (1, 'Alice', 30)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
 
-    // Delete one row
-    let result = delete_table_row(
+    // Update the row
+    let mut updates = serde_json::Map::new();
+    updates.insert("age".to_string(), json!(35));
+
+    let result = update_table_row(
+        app.state(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -673,18 +1450,26 @@ async fn test_delete_table_row_sqlite() {
         None,
         None,
         Some(file_path.clone()),
+        None,
+        None,
         "main".to_string(),
         table_name.clone(),
         vec!["id".to_string()],
         vec![json!(1)],
+        updates,
+        None,
     )
     .await;
 
     assert!(result.is_ok());
-    assert!(result.unwrap().error.is_none(), "Delete should succeed");
+    let query_result = result.unwrap();
+    assert!(query_result.result.error.is_none(), "Update should succeed");
+    assert_eq!(query_result.changed_columns, vec!["age".to_string()]);
 
-    // Verify deletion
+    // Verify the update
     let select = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -693,52 +1478,56 @@ async fn test_delete_table_row_sqlite() {
         None,
         None,
         Some(file_path),
-        format!("SELECT COUNT(*) as cnt FROM {}", table_name),
+        None,
+        None,
+        format!("SELECT age FROM {} WHERE id = 1", table_name),
+        None,
+        None,
     )
     .await
     .unwrap();
 
     assert_eq!(
-        select.data[0].get("cnt").unwrap().as_i64().unwrap(),
-        1,
-        "Should have 1 row remaining"
+        select.data[0].get("age").unwrap().as_i64().unwrap(),
+        35,
+        "Age should be updated to 35"
     );
 }
 
-// ============================================================================
-// insert_table_row Tests
-// ============================================================================
-
 #[tokio::test]
-async fn test_insert_table_row_sqlite() {
+async fn test_update_table_row_reports_changed_columns() {
+    let (app, _app_db) = test_app().await;
     let file_path = temp_sqlite_path();
-    let table_name = test_table_name("ins");
-
-    // Create table
-    let _ = unified_execute_query(
-        "sqlite".to_string(),
-        None,
-        None,
-        None,
-        None,
-        None,
+    let table_name = test_table_name("upd_cols");
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER, city TEXT)",
+            table_name
+        ),
         None,
-        Some(file_path.clone()),
-        format!(
-            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)",
+    )
+    .await
+    .unwrap();
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "INSERT INTO {} VALUES (1, 'Alice', 30, 'Paris')",
             table_name
         ),
+        None,
     )
     .await
     .unwrap();
 
-    // Insert a row
-    let values = vec![
-        json!({"column": "name", "value": "Alice"}),
-        json!({"column": "age", "value": 30}),
-    ];
+    let mut updates = serde_json::Map::new();
+    updates.insert("city".to_string(), json!("Oslo"));
+    updates.insert("age".to_string(), json!(31));
+    let expected: Vec<String> = updates.keys().cloned().collect();
 
-    let result = insert_table_row(
+    let result = update_table_row(
+        app.state(),
+        app.state(),
         "sqlite".to_string(),
         None,
         None,
@@ -746,44 +1535,38 @@ async fn test_insert_table_row_sqlite() {
         None,
         None,
         None,
-        Some(file_path.clone()),
-        "main".to_string(),
-        table_name.clone(),
-        values,
-    )
-    .await;
-
-    assert!(result.is_ok());
-    assert!(result.unwrap().error.is_none(), "Insert should succeed");
-
-    // Verify insertion
-    let select = unified_execute_query(
-        "sqlite".to_string(),
-        None,
-        None,
-        None,
+        Some(file_path),
         None,
         None,
+        "main".to_string(),
+        table_name,
+        vec!["id".to_string()],
+        vec![json!(1)],
+        updates,
         None,
-        Some(file_path),
-        format!("SELECT * FROM {}", table_name),
     )
     .await
     .unwrap();
 
-    assert_eq!(select.row_count, 1, "Should have 1 row");
-    assert_eq!(
-        select.data[0].get("name").unwrap().as_str().unwrap(),
-        "Alice"
-    );
+    assert!(result.result.error.is_none());
+    assert_eq!(result.changed_columns, expected);
+    assert_eq!(result.changed_columns, vec!["city", "age"]);
+
+    // The changed columns sit alongside the query result fields
+    let payload = serde_json::to_value(&result).unwrap();
+    assert_eq!(payload["changed_columns"], json!(["city", "age"]));
+    assert!(payload.get("row_count").is_some());
 }
 
 #[tokio::test]
-async fn test_insert_table_row_postgres() {
-    let table_name = test_table_name("ins");
+async fn test_update_table_row_postgres() {
+    let (app, _app_db) = test_app().await;
+    let table_name = test_table_name("upd");
 
     // Create table
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "postgres".to_string(),
         Some("localhost".to_string()),
         Some(5432),
@@ -792,21 +1575,72 @@ async fn test_insert_table_row_postgres() {
         Some("postgres".to_string()),
         Some(false),
         None,
+        None,
+        None,
         format!(
             "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
             table_name
         ),
+        None,
+        None,
     )
     .await
     .unwrap();
 
-    // Insert a row
-    let values = vec![
-        json!({"column": "name", "value": "Bob"}),
-        json!({"column": "age", "value": 25}),
-    ];
+    // Insert data
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        format!(
+            "INSERT INTO \"{}\" (name, age) VALUES ('Alice', 30)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
 
-    let result = insert_table_row(
+    // Get the inserted ID
+    let select = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        format!("SELECT id FROM \"{}\" WHERE name = 'Alice'", table_name),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let id = select.data[0].get("id").unwrap().as_i64().unwrap();
+
+    // Update the row
+    let mut updates = serde_json::Map::new();
+    updates.insert("age".to_string(), json!(99));
+
+    let result = update_table_row(
+        app.state(),
+        app.state(),
         "postgres".to_string(),
         Some("localhost".to_string()),
         Some(5432),
@@ -815,9 +1649,14 @@ async fn test_insert_table_row_postgres() {
         Some("postgres".to_string()),
         Some(false),
         None,
+        None,
+        None,
         "public".to_string(),
         table_name.clone(),
-        values,
+        vec!["id".to_string()],
+        vec![json!(id)],
+        updates,
+        None,
     )
     .await;
 
@@ -825,6 +1664,8 @@ async fn test_insert_table_row_postgres() {
 
     // Cleanup
     let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
         "postgres".to_string(),
         Some("localhost".to_string()),
         Some(5432),
@@ -833,7 +1674,2890 @@ async fn test_insert_table_row_postgres() {
         Some("postgres".to_string()),
         Some(false),
         None,
+        None,
+        None,
         format!("DROP TABLE IF EXISTS \"{}\"", table_name),
+        None,
+        None,
+    )
+    .await;
+}
+
+// ============================================================================
+// delete_table_row Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_delete_table_row_sqlite() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("del");
+
+    // Create table with data
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "INSERT INTO {} (id, name) VALUES (1, 'Alice'), (2, 'Bob')",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Delete one row
+    let result = delete_table_row(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+        None,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    assert!(result.unwrap().error.is_none(), "Delete should succeed");
+
+    // Verify deletion
+    let select = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        format!("SELECT COUNT(*) as cnt FROM {}", table_name),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        select.data[0].get("cnt").unwrap().as_i64().unwrap(),
+        1,
+        "Should have 1 row remaining"
+    );
+}
+
+// ============================================================================
+// undo_last_edit Tests
+// ============================================================================
+
+async fn sqlite_rows(file_path: &str, query: String) -> Vec<serde_json::Value> {
+    let (app, _app_db) = test_app().await;
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.to_string()),
+        None,
+        None,
+        query,
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+    .data
+}
+
+#[tokio::test]
+async fn test_undo_deleted_row_restores_identical_values() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("undo_delete");
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+
+    sqlite_rows(
+        &file_path,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, score REAL, note TEXT)",
+            table_name
+        ),
+    )
+    .await;
+    sqlite_rows(
+        &file_path,
+        format!(
+            "INSERT INTO {} VALUES (1, 'Alice', 9.5, NULL), (2, 'O''Brien', 7.25, 'keep')",
+            table_name
+        ),
+    )
+    .await;
+    let select = format!("SELECT * FROM {} ORDER BY id", table_name);
+    let before = sqlite_rows(&file_path, select.clone()).await;
+
+    let deleted = delete_table_row(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(2)],
+        Some(connection_uuid.clone()),
+    )
+    .await
+    .unwrap();
+    assert!(deleted.error.is_none(), "{:?}", deleted.error);
+    assert_eq!(sqlite_rows(&file_path, select.clone()).await.len(), 1);
+
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    let history = app.state::<UndoHistory>();
+    let identity = driver_identity("sqlite", None, None, None, Some(&file_path));
+    let undone = undo_last_edit_with_driver(&history, &identity, &driver, &connection_uuid)
+        .await
+        .unwrap();
+    assert_eq!(undone.table, table_name);
+    assert_eq!(undone.remaining, 0);
+    assert_eq!(sqlite_rows(&file_path, select).await, before);
+
+    let err = undo_last_edit_with_driver(&history, &identity, &driver, &connection_uuid)
+        .await
+        .unwrap_err();
+    assert_eq!(err, "Nothing to undo");
+}
+
+#[tokio::test]
+async fn test_undo_update_restores_previous_values() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("undo_update");
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+
+    sqlite_rows(
+        &file_path,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)",
+            table_name
+        ),
+    )
+    .await;
+    sqlite_rows(
+        &file_path,
+        format!("INSERT INTO {} VALUES (1, 'Alice', 30)", table_name),
     )
     .await;
+
+    let mut updates = serde_json::Map::new();
+    updates.insert("name".to_string(), json!("Alicia"));
+    updates.insert("age".to_string(), json!(31));
+    update_table_row(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+        updates,
+        Some(connection_uuid.clone()),
+    )
+    .await
+    .unwrap();
+    let select = format!("SELECT name, age FROM {}", table_name);
+    assert_eq!(
+        sqlite_rows(&file_path, select.clone()).await,
+        vec![json!({"name": "Alicia", "age": 31})]
+    );
+
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    let identity = driver_identity("sqlite", None, None, None, Some(&file_path));
+    undo_last_edit_with_driver(
+        &app.state::<UndoHistory>(),
+        &identity,
+        &driver,
+        &connection_uuid,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        sqlite_rows(&file_path, select).await,
+        vec![json!({"name": "Alice", "age": 30})]
+    );
+}
+
+#[tokio::test]
+async fn test_undo_raw_sql_update_restores_previous_values() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("undo_raw");
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+
+    sqlite_rows(
+        &file_path,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, seen TEXT)",
+            table_name
+        ),
+    )
+    .await;
+    sqlite_rows(
+        &file_path,
+        format!("INSERT INTO {} VALUES (1, 'Alice', NULL)", table_name),
+    )
+    .await;
+
+    let result = update_table_row_with_raw_sql(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+        vec![
+            json!({"column": "name", "value": "Alicia", "isRawSql": false}),
+            json!({"column": "seen", "value": "datetime('now')", "isRawSql": true}),
+        ],
+        Some(connection_uuid.clone()),
+    )
+    .await
+    .unwrap();
+    assert!(result.error.is_none(), "{:?}", result.error);
+
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    let identity = driver_identity("sqlite", None, None, None, Some(&file_path));
+    undo_last_edit_with_driver(
+        &app.state::<UndoHistory>(),
+        &identity,
+        &driver,
+        &connection_uuid,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        sqlite_rows(&file_path, format!("SELECT name, seen FROM {}", table_name)).await,
+        vec![json!({"name": "Alice", "seen": null})]
+    );
+}
+
+#[tokio::test]
+async fn test_undo_refuses_edit_made_on_another_database() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("undo_other");
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+
+    sqlite_rows(
+        &file_path,
+        format!("CREATE TABLE {} (id INTEGER PRIMARY KEY)", table_name),
+    )
+    .await;
+    sqlite_rows(&file_path, format!("INSERT INTO {} VALUES (1)", table_name)).await;
+
+    delete_table_row(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+        Some(connection_uuid.clone()),
+    )
+    .await
+    .unwrap();
+
+    // The saved connection now points at another file
+    let other_path = temp_sqlite_path();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: other_path.clone(),
+        session_setup_sql: None,
+    });
+    let history = app.state::<UndoHistory>();
+    let identity = driver_identity("sqlite", None, None, None, Some(&other_path));
+    let err = undo_last_edit_with_driver(&history, &identity, &driver, &connection_uuid)
+        .await
+        .unwrap_err();
+    assert!(err.contains("different database"), "{}", err);
+    assert_eq!(history.depth(&connection_uuid), 1);
+}
+
+#[tokio::test]
+async fn test_undo_pooled_delete() {
+    let (app, _app_db) = test_app().await;
+    app.manage(PoolManager::new());
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("undo_pool");
+    let connection_uuid = uuid::Uuid::new_v4().to_string();
+
+    sqlx::query(
+        "INSERT INTO connections (uuid, type, name, host, port, database, username, password, db_type, file_path) \
+         VALUES (?, 'sqlite', 'Undo', '', 0, '', '', '', 'sqlite', ?)",
+    )
+    .bind(&connection_uuid)
+    .bind(&file_path)
+    .execute(app.state::<SqlitePool>().inner())
+    .await
+    .unwrap();
+    sqlite_rows(
+        &file_path,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, score REAL)",
+            table_name
+        ),
+    )
+    .await;
+    sqlite_rows(
+        &file_path,
+        format!("INSERT INTO {} VALUES (1, 'Alice', 9.5)", table_name),
+    )
+    .await;
+    let select = format!("SELECT * FROM {}", table_name);
+    let before = sqlite_rows(&file_path, select.clone()).await;
+
+    let deleted = pool_delete_table_row(
+        app.state(),
+        app.state(),
+        app.state(),
+        connection_uuid.clone(),
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+    )
+    .await
+    .unwrap();
+    assert!(deleted.error.is_none(), "{:?}", deleted.error);
+    assert!(sqlite_rows(&file_path, select.clone()).await.is_empty());
+
+    let undone = undo_last_edit(app.state(), app.state(), connection_uuid.clone())
+        .await
+        .unwrap();
+    assert_eq!(undone.table, table_name);
+    assert_eq!(sqlite_rows(&file_path, select).await, before);
+}
+
+// ============================================================================
+// insert_table_row Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_insert_table_row_sqlite() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("ins");
+
+    // Create table
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, age INTEGER)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Insert a row
+    let values = vec![
+        json!({"column": "name", "value": "Alice"}),
+        json!({"column": "age", "value": 30}),
+    ];
+
+    let result = insert_table_row(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        values,
+        None,
+    )
+    .await;
+
+    assert!(result.is_ok());
+    let result = result.unwrap();
+    assert!(result.error.is_none(), "Insert should succeed");
+
+    // The inserted row comes back, including the generated id
+    assert_eq!(result.data.len(), 1, "Insert should return the new row");
+    assert_eq!(result.data[0]["id"], json!(1));
+    assert_eq!(result.data[0]["name"], json!("Alice"));
+    assert_eq!(result.data[0]["age"], json!(30));
+
+    // Verify insertion
+    let select = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        format!("SELECT * FROM {}", table_name),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(select.row_count, 1, "Should have 1 row");
+    assert_eq!(
+        select.data[0].get("name").unwrap().as_str().unwrap(),
+        "Alice"
+    );
+}
+
+#[tokio::test]
+async fn test_insert_table_row_postgres() {
+    let (app, _app_db) = test_app().await;
+    let table_name = test_table_name("ins");
+
+    // Create table
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        format!(
+            "CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY, name TEXT, age INTEGER)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Insert a row
+    let values = vec![
+        json!({"column": "name", "value": "Bob"}),
+        json!({"column": "age", "value": 25}),
+    ];
+
+    let result = insert_table_row(
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        "public".to_string(),
+        table_name.clone(),
+        values,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert!(result.error.is_none(), "Insert failed: {:?}", result.error);
+    assert_eq!(result.data.len(), 1, "Insert should return the new row");
+    assert_eq!(result.data[0]["id"], json!(1));
+    assert_eq!(result.data[0]["name"], json!("Bob"));
+
+    // Cleanup
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        format!("DROP TABLE IF EXISTS \"{}\"", table_name),
+        None,
+        None,
+    )
+    .await;
+}
+
+// ============================================================================
+// Generated Column Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_generated_columns_are_flagged_and_not_writable() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("gen");
+
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, price INTEGER, doubled INTEGER GENERATED ALWAYS AS (price * 2) VIRTUAL)",
+            table_name
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let structure = unified_get_table_structure(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+    )
+    .await
+    .unwrap();
+    let doubled = structure
+        .columns
+        .iter()
+        .find(|c| c.name == "doubled")
+        .expect("Generated column should be listed");
+    assert!(doubled.is_generated);
+    assert!(structure
+        .columns
+        .iter()
+        .filter(|c| c.name != "doubled")
+        .all(|c| !c.is_generated));
+
+    // An empty value for the generated column is skipped on insert
+    let result = insert_table_row(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec![
+            json!({"column": "id", "value": 1, "isRawSql": false}),
+            json!({"column": "price", "value": 21, "isRawSql": false}),
+            json!({"column": "doubled", "value": null, "isRawSql": false}),
+        ],
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(result.error.is_none(), "Insert failed: {:?}", result.error);
+
+    let err = insert_table_row(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec![
+            json!({"column": "id", "value": 2, "isRawSql": false}),
+            json!({"column": "doubled", "value": 5, "isRawSql": false}),
+        ],
+        None,
+    )
+    .await
+    .expect_err("Setting a generated column on insert should be rejected");
+    assert!(err.contains("'doubled' is a generated column"), "{}", err);
+
+    let mut updates = serde_json::Map::new();
+    updates.insert("doubled".to_string(), json!(100));
+    let err = update_table_row(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+        updates,
+        None,
+    )
+    .await
+    .expect_err("Updating a generated column should be rejected");
+    assert!(err.contains("'doubled' is a generated column"), "{}", err);
+
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT doubled FROM {} WHERE id = 1", table_name),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.data[0]["doubled"], 42);
+}
+
+// ============================================================================
+// Read-only Connection Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_read_only_blocks_writes_and_allows_select() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("ro");
+    let read_only = save_read_only_connection(&app).await;
+
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Writes are rejected before reaching the database
+    let insert = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!("INSERT INTO {} (id, name) VALUES (1, 'Alice')", table_name),
+        Some(read_only.clone()),
+        None,
+    )
+    .await;
+    assert_eq!(insert.unwrap_err(), "Connection is read-only");
+
+    // A write hidden behind a read is also rejected
+    let chained = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!("SELECT 1; DROP TABLE {}", table_name),
+        Some(read_only.clone()),
+        None,
+    )
+    .await;
+    assert_eq!(chained.unwrap_err(), "Connection is read-only");
+
+    let delete = delete_table_row(
+        app.state(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        vec!["id".to_string()],
+        vec![json!(1)],
+        Some(read_only.clone()),
+    )
+    .await;
+    assert_eq!(delete.unwrap_err(), "Connection is read-only");
+
+    // Reads still work
+    let select = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        format!("SELECT COUNT(*) as cnt FROM {}", table_name),
+        Some(read_only.clone()),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(select.error.is_none());
+    assert_eq!(select.data[0]["cnt"], 0);
+}
+
+#[tokio::test]
+async fn test_read_only_blocks_table_watch_triggers() {
+    let (app, _app_db) = test_app().await;
+    app.manage(TableWatchManager::new());
+    let read_only = save_read_only_connection(&app).await;
+
+    // Rejected before connecting, so no server is needed
+    let watch = watch_table_changes(
+        app.handle().clone(),
+        app.state(),
+        app.state(),
+        Some("127.0.0.1".to_string()),
+        Some(1),
+        Some("app".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        "public".to_string(),
+        "orders".to_string(),
+        Some(read_only.clone()),
+    )
+    .await;
+    assert_eq!(watch.unwrap_err(), "Connection is read-only");
+
+    let stop = stop_watch_table(
+        app.state(),
+        app.state(),
+        Some("127.0.0.1".to_string()),
+        Some(1),
+        Some("app".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        "public".to_string(),
+        "orders".to_string(),
+        Some(read_only),
+    )
+    .await;
+    assert_eq!(stop.unwrap_err(), "Connection is read-only");
+}
+
+#[tokio::test]
+async fn test_read_only_blocks_maintenance_commands() {
+    let (app, _app_db) = test_app().await;
+    let read_only = save_read_only_connection(&app).await;
+
+    // Rejected before connecting, so no server is needed
+    let analyze = postgres_analyze(
+        app.state(),
+        Some("127.0.0.1".to_string()),
+        Some(1),
+        Some("app".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(read_only.clone()),
+    )
+    .await;
+    assert_eq!(analyze.unwrap_err(), "Connection is read-only");
+
+    let vacuum = postgres_vacuum(
+        app.state(),
+        Some("127.0.0.1".to_string()),
+        Some(1),
+        Some("app".to_string()),
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some("orders".to_string()),
+        Some(true),
+        Some(read_only.clone()),
+    )
+    .await;
+    assert_eq!(vacuum.unwrap_err(), "Connection is read-only");
+
+    let vacuum = sqlite_vacuum(app.state(), temp_sqlite_path(), Some(read_only)).await;
+    assert_eq!(vacuum.unwrap_err(), "Connection is read-only");
+}
+
+#[test]
+fn test_is_read_only_query_classification() {
+    assert!(is_read_only_query("SELECT * FROM users", "postgres"));
+    assert!(is_read_only_query(
+        "  -- comment\nshow tables",
+        "clickhouse"
+    ));
+    assert!(is_read_only_query("EXPLAIN SELECT 1", "postgres"));
+    assert!(is_read_only_query("DESCRIBE users", "clickhouse"));
+    assert!(is_read_only_query(
+        "WITH t AS (SELECT 1) SELECT * FROM t",
+        "postgres"
+    ));
+    assert!(is_read_only_query("HGETALL user:1", "redis"));
+
+    assert!(!is_read_only_query(
+        "UPDATE users SET name = 'x'",
+        "postgres"
+    ));
+    assert!(!is_read_only_query(
+        "EXPLAIN ANALYZE DELETE FROM users",
+        "postgres"
+    ));
+    assert!(!is_read_only_query(
+        "WITH d AS (DELETE FROM users RETURNING *) SELECT * FROM d",
+        "postgres"
+    ));
+    assert!(!is_read_only_query(
+        "SELECT * INTO backup FROM users",
+        "postgres"
+    ));
+    assert!(!is_read_only_query(
+        "SELECT nextval('users_id_seq')",
+        "postgres"
+    ));
+    assert!(!is_read_only_query(
+        "SELECT pg_catalog.setval('users_id_seq', 1)",
+        "postgres"
+    ));
+    assert!(is_read_only_query(
+        "SELECT 'nextval(' AS label, \"setval\" FROM t",
+        "postgres"
+    ));
+    assert!(!is_read_only_query("SET user:1 value", "redis"));
+    assert!(!is_read_only_query("", "sqlite"));
+}
+
+// ============================================================================
+// set_table_comment Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_set_table_comment_postgres() {
+    let (app, _app_db) = test_app().await;
+    let table_name = test_table_name("tblcomment");
+
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        format!("CREATE TABLE \"{}\" (id SERIAL PRIMARY KEY)", table_name),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let result = set_table_comment(
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        "public".to_string(),
+        table_name.clone(),
+        Some("Customer's orders".to_string()),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(
+        result.error.is_none(),
+        "Comment should be set: {:?}",
+        result.error
+    );
+
+    let tables = unified_list_tables(
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let table = tables.iter().find(|t| t.name == table_name).unwrap();
+    assert_eq!(table.comment.as_deref(), Some("Customer's orders"));
+
+    // Cleanup
+    let _ = unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "postgres".to_string(),
+        Some("localhost".to_string()),
+        Some(5432),
+        Some("testdb".to_string()),
+        Some("postgres".to_string()),
+        Some("postgres".to_string()),
+        Some(false),
+        None,
+        None,
+        None,
+        format!("DROP TABLE IF EXISTS \"{}\"", table_name),
+        None,
+        None,
+    )
+    .await;
+}
+
+// ============================================================================
+// Query Variable Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_unified_execute_query_substitutes_variables() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("vars");
+
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, tenant_id TEXT)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "INSERT INTO {} (tenant_id) VALUES ('acme'), ('globex'), ('o''neil\\')",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    let uuid = save_sqlite_connection(&app, &file_path).await;
+    let select = |query: String| {
+        unified_execute_query(
+            app.handle().clone(),
+            app.state(),
+            "sqlite".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(file_path.clone()),
+            None,
+            None,
+            query,
+            Some(uuid.clone()),
+            Some(QueryOptions {
+                use_variables: true,
+                ..Default::default()
+            }),
+        )
+    };
+    let query = format!(
+        "SELECT tenant_id, '{{{{tenant_id}}}}' AS literal FROM {} \
+         WHERE tenant_id = {{{{tenant_id}}}} -- {{{{not_defined}}}}",
+        table_name
+    );
+
+    // Values come from the variables saved for the connection
+    set_connection_variable(
+        app.state(),
+        uuid.clone(),
+        "tenant_id".to_string(),
+        Some("acme".to_string()),
+    )
+    .await
+    .unwrap();
+    let result = select(query.clone()).await.unwrap();
+    assert!(result.error.is_none(), "Query failed: {:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["tenant_id"], "acme");
+    // Placeholders inside literals and comments are left as written
+    assert_eq!(result.data[0]["literal"], "{{tenant_id}}");
+
+    // Bound rather than spliced in, so quotes and backslashes need no escaping
+    set_connection_variable(
+        app.state(),
+        uuid.clone(),
+        "tenant_id".to_string(),
+        Some("o'neil\\".to_string()),
+    )
+    .await
+    .unwrap();
+    let result = select(query).await.unwrap();
+    assert!(result.error.is_none(), "Query failed: {:?}", result.error);
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["tenant_id"], "o'neil\\");
+
+    let undefined = select(format!(
+        "SELECT * FROM {} WHERE id = {{{{id}}}}",
+        table_name
+    ))
+    .await;
+    assert_eq!(undefined.unwrap_err(), "Undefined query variable: id");
+}
+
+#[test]
+fn test_substitute_query_variables() {
+    let variables = HashMap::from([("name".to_string(), "O'Brien".to_string())]);
+
+    let sql = substitute_query_variables(
+        "SELECT * FROM users WHERE name = {{ name }}",
+        &variables,
+        "postgres",
+    )
+    .unwrap();
+    assert_eq!(sql, "SELECT * FROM users WHERE name = 'O''Brien'");
+
+    let redis = substitute_query_variables("GET user:{{name}}", &variables, "redis").unwrap();
+    assert_eq!(redis, "GET user:O'Brien");
+
+    let missing = substitute_query_variables("SELECT {{missing}}", &variables, "postgres");
+    assert!(missing.unwrap_err().contains("missing"));
+}
+
+#[test]
+fn test_bind_query_variables() {
+    let variables = HashMap::from([
+        ("name".to_string(), "O'Brien".to_string()),
+        ("id".to_string(), "7".to_string()),
+    ]);
+    let query = "SELECT '{{name}}', $tag${{id}}$tag$ FROM users /* {{x}} */ \
+                 WHERE name = {{name}} AND id = {{ id }}";
+
+    let (sql, params) = bind_query_variables(query, &variables, "postgres").unwrap();
+    assert_eq!(
+        sql,
+        "SELECT '{{name}}', $tag${{id}}$tag$ FROM users /* {{x}} */ WHERE name = $1 AND id = $2"
+    );
+    assert_eq!(params, vec![json!("O'Brien"), json!("7")]);
+
+    let (sql, params) = bind_query_variables(query, &variables, "sqlite").unwrap();
+    assert!(sql.ends_with("WHERE name = ? AND id = ?"), "{}", sql);
+    assert_eq!(params.len(), 2);
+
+    // Drivers without bind parameters, and scripts, get escaped literals
+    let (sql, params) = bind_query_variables("SELECT {{name}}", &variables, "clickhouse").unwrap();
+    assert_eq!(sql, "SELECT 'O''Brien'");
+    assert!(params.is_empty());
+    let (sql, params) =
+        bind_query_variables("SELECT {{id}}; SELECT {{id}}", &variables, "postgres").unwrap();
+    assert_eq!(sql, "SELECT '7'; SELECT '7'");
+    assert!(params.is_empty());
+}
+
+// ============================================================================
+// column_aggregate Tests
+// ============================================================================
+
+/// Create a SQLite table with a numeric `amount` column for aggregate tests
+async fn seed_aggregate_table(file_path: &str, table_name: &str) {
+    let (app, _app_db) = test_app().await;
+    let file_path = file_path.to_string();
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, label TEXT, amount INTEGER)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    unified_execute_query(
+        app.handle().clone(),
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        format!(
+            "INSERT INTO {} (label, amount) VALUES ('a', 10), ('b', 20), ('c', 45)",
+            table_name
+        ),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_column_aggregate_avg_and_max() {
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("agg");
+    seed_aggregate_table(&file_path, &table_name).await;
+
+    let avg = column_aggregate(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        "amount".to_string(),
+        "avg".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(avg.as_f64(), Some(25.0));
+
+    let max = column_aggregate(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        "amount".to_string(),
+        "MAX".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(max.as_i64(), Some(45));
+}
+
+#[tokio::test]
+async fn test_column_aggregate_with_filter() {
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("agg_filter");
+    seed_aggregate_table(&file_path, &table_name).await;
+
+    let avg = column_aggregate(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        "amount".to_string(),
+        "AVG".to_string(),
+        Some("amount > 10".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(avg.as_f64(), Some(32.5));
+
+    let max = column_aggregate(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        "amount".to_string(),
+        "MAX".to_string(),
+        Some("amount < 45".to_string()),
+    )
+    .await
+    .unwrap();
+    assert_eq!(max.as_i64(), Some(20));
+}
+
+#[tokio::test]
+async fn test_column_aggregate_rejects_invalid_requests() {
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("agg_invalid");
+    seed_aggregate_table(&file_path, &table_name).await;
+
+    let bad_agg = column_aggregate(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        "amount".to_string(),
+        "MEDIAN".to_string(),
+        None,
+    )
+    .await;
+    assert!(bad_agg.is_err(), "Unknown aggregate should be rejected");
+
+    let text_sum = column_aggregate(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table_name.clone(),
+        "label".to_string(),
+        "SUM".to_string(),
+        None,
+    )
+    .await;
+    assert!(
+        text_sum.is_err(),
+        "SUM over a text column should be rejected"
+    );
+}
+
+// ============================================================================
+// sample_table Tests
+// ============================================================================
+
+async fn sample_sqlite_table(
+    file_path: &str,
+    table: &str,
+    n: i64,
+) -> Result<dbcooper_lib::db::models::QueryResult, String> {
+    sample_table(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.to_string()),
+        None,
+        None,
+        "main".to_string(),
+        table.to_string(),
+        n,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_sample_table_returns_at_most_n_rows() {
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("sample");
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} AS WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 50) SELECT x AS id FROM c",
+            table_name
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let sample = sample_sqlite_table(&file_path, &table_name, 5)
+        .await
+        .unwrap();
+    assert_eq!(sample.row_count, 5);
+    assert!(sample
+        .data
+        .iter()
+        .all(|row| (1..=50).contains(&row["id"].as_i64().unwrap())));
+
+    // Asking for more rows than the table has returns the whole table
+    let sample = sample_sqlite_table(&file_path, &table_name, 500)
+        .await
+        .unwrap();
+    assert_eq!(sample.row_count, 50);
+
+    let err = sample_sqlite_table(&file_path, &table_name, 0)
+        .await
+        .unwrap_err();
+    assert!(err.contains("at least 1"), "{}", err);
+}
+
+// ============================================================================
+// profile_columns Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_profile_columns_samples_sqlite_tables() {
+    let file_path = temp_sqlite_path();
+    let table_name = test_table_name("profile");
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} AS WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE x < 20) SELECT x AS id, CASE WHEN x % 4 = 0 THEN NULL ELSE x % 3 END AS bucket FROM c",
+            table_name
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let profiles = profile_columns(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        "main".to_string(),
+        table_name,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(profiles.len(), 2);
+    assert_eq!(profiles[0].column, "id");
+    assert_eq!(profiles[0].null_fraction, 0.0);
+    assert_eq!(profiles[0].distinct_estimate, 20);
+    assert_eq!(profiles[1].column, "bucket");
+    assert_eq!(profiles[1].null_fraction, 0.25);
+    assert_eq!(profiles[1].distinct_estimate, 3);
+    assert!(profiles.iter().all(|p| p.source == "sample"));
+}
+
+// ============================================================================
+// get_server_overview Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_server_overview_counts_sqlite_tables() {
+    let file_path = temp_sqlite_path();
+    for (table, rows) in [("overview_a", 3), ("overview_b", 2), ("overview_c", 0)] {
+        execute_sqlite_with_params(
+            &file_path,
+            &format!("CREATE TABLE {} (id INTEGER PRIMARY KEY)", table),
+            None,
+        )
+        .await
+        .unwrap();
+        for _ in 0..rows {
+            execute_sqlite_with_params(
+                &file_path,
+                &format!("INSERT INTO {} DEFAULT VALUES", table),
+                None,
+            )
+            .await
+            .unwrap();
+        }
+    }
+    execute_sqlite_with_params(
+        &file_path,
+        "CREATE VIEW overview_view AS SELECT * FROM overview_a",
+        None,
+    )
+    .await
+    .unwrap();
+
+    let overview = get_server_overview(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(overview.namespaces.len(), 1);
+    assert_eq!(overview.namespaces[0].name, "main");
+    assert_eq!(overview.namespaces[0].table_count, 3);
+    assert_eq!(overview.total_tables, 3);
+    assert_eq!(overview.total_estimated_rows, 5);
+}
+
+// ============================================================================
+// stream_schema_overview Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_stream_schema_tables_emits_each_table_then_completes() {
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: temp_sqlite_path(),
+        session_setup_sql: None,
+    });
+    for table in ["authors", "books", "reviews"] {
+        let result = driver
+            .execute_query(&format!(
+                "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)",
+                table
+            ))
+            .await
+            .unwrap();
+        assert!(result.error.is_none(), "Create failed: {:?}", result.error);
+    }
+
+    let mut events = Vec::new();
+    let count = stream_schema_tables(&driver, "overview-1", |event| events.push(event))
+        .await
+        .unwrap();
+
+    assert_eq!(count, 3);
+    assert_eq!(
+        events.len(),
+        4,
+        "Expected one event per table plus completion"
+    );
+
+    let mut names = Vec::new();
+    for (i, event) in events[..3].iter().enumerate() {
+        match event {
+            SchemaStreamEvent::Table(payload) => {
+                assert_eq!(payload.overview_id, "overview-1");
+                assert_eq!(payload.index, i);
+                assert_eq!(payload.total, 3);
+                assert_eq!(payload.table.columns.len(), 2);
+                names.push(payload.table.name.clone());
+            }
+            SchemaStreamEvent::Complete(_) => panic!("Completion emitted before all tables"),
+        }
+    }
+    names.sort();
+    assert_eq!(names, vec!["authors", "books", "reviews"]);
+
+    match &events[3] {
+        SchemaStreamEvent::Complete(payload) => {
+            assert_eq!(payload.overview_id, "overview-1");
+            assert_eq!(payload.table_count, 3);
+        }
+        SchemaStreamEvent::Table(_) => panic!("Last event should be schema-complete"),
+    }
+}
+
+// ============================================================================
+// export_query_parquet Tests
+// ============================================================================
+
+/// Read a Parquet file back, returning its Arrow schema and total row count
+fn read_parquet(path: &str) -> (arrow_schema::SchemaRef, usize) {
+    let file = std::fs::File::open(path).expect("Parquet file should exist");
+    let builder =
+        parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    let schema = builder.schema().clone();
+    let rows = builder
+        .build()
+        .unwrap()
+        .map(|batch| batch.unwrap().num_rows())
+        .sum();
+    (schema, rows)
+}
+
+#[tokio::test]
+async fn test_export_query_parquet_sqlite() {
+    let file_path = temp_sqlite_path();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    for statement in [
+        "CREATE TABLE readings (id INTEGER, value REAL, label TEXT, taken_at DATETIME)",
+        "INSERT INTO readings VALUES (1, 1.5, 'a', '2024-01-02 03:04:05')",
+        "INSERT INTO readings VALUES (2, NULL, NULL, '2024-01-03 00:00:00')",
+        "INSERT INTO readings VALUES (3, 2.25, 'c', NULL)",
+    ] {
+        driver.execute_query(statement).await.unwrap();
+    }
+
+    let output = NamedTempFile::new().unwrap();
+    let output_path = output.path().to_string_lossy().to_string();
+    let rows = export_query_parquet(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        "SELECT * FROM readings ORDER BY id".to_string(),
+        output_path.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(rows, 3);
+
+    let (schema, row_count) = read_parquet(&output_path);
+    assert_eq!(row_count, 3);
+    let types: Vec<(&str, &arrow_schema::DataType)> = schema
+        .fields()
+        .iter()
+        .map(|f| (f.name().as_str(), f.data_type()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("id", &arrow_schema::DataType::Int64),
+            ("value", &arrow_schema::DataType::Float64),
+            ("label", &arrow_schema::DataType::Utf8),
+            (
+                "taken_at",
+                &arrow_schema::DataType::Timestamp(arrow_schema::TimeUnit::Microsecond, None)
+            ),
+        ]
+    );
+    assert!(schema.fields().iter().all(|f| f.is_nullable()));
+}
+
+#[tokio::test]
+async fn test_export_query_parquet_empty_result_keeps_columns() {
+    let file_path = temp_sqlite_path();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    driver
+        .execute_query("CREATE TABLE events (id INTEGER, happened_on DATE, note TEXT)")
+        .await
+        .unwrap();
+
+    let output = NamedTempFile::new().unwrap();
+    let output_path = output.path().to_string_lossy().to_string();
+    let rows = export_query_parquet(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        "SELECT * FROM events".to_string(),
+        output_path.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(rows, 0);
+
+    let (schema, row_count) = read_parquet(&output_path);
+    assert_eq!(row_count, 0);
+    let types: Vec<(&str, &arrow_schema::DataType)> = schema
+        .fields()
+        .iter()
+        .map(|f| (f.name().as_str(), f.data_type()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("id", &arrow_schema::DataType::Int64),
+            ("happened_on", &arrow_schema::DataType::Date32),
+            ("note", &arrow_schema::DataType::Utf8),
+        ]
+    );
+}
+
+#[test]
+fn test_arrow_type_for_database_types() {
+    use arrow_schema::{DataType, TimeUnit};
+    assert_eq!(arrow_type_for("INT8"), Some(DataType::Int64));
+    assert_eq!(arrow_type_for("Nullable(Int32)"), Some(DataType::Int64));
+    assert_eq!(
+        arrow_type_for("LowCardinality(String)"),
+        Some(DataType::Utf8)
+    );
+    assert_eq!(
+        arrow_type_for("DateTime64(3, 'UTC')"),
+        Some(DataType::Timestamp(TimeUnit::Microsecond, None))
+    );
+    // Exact and unsigned 64-bit numerics stay text so no value loses precision
+    assert_eq!(arrow_type_for("NUMERIC"), Some(DataType::Utf8));
+    assert_eq!(arrow_type_for("UInt64"), Some(DataType::Utf8));
+    assert_eq!(arrow_type_for("NULL"), None);
+}
+
+#[test]
+fn test_write_rows_parquet_infers_boolean_and_json_columns() {
+    let rows = vec![
+        json!({ "flag": true, "meta": { "k": 1 }, "empty": null }),
+        json!({ "flag": null, "meta": [1, 2], "empty": null }),
+    ];
+    let output = NamedTempFile::new().unwrap();
+    let output_path = output.path().to_string_lossy().to_string();
+
+    assert_eq!(write_rows_parquet(&rows, &output_path).unwrap(), 2);
+
+    let (schema, row_count) = read_parquet(&output_path);
+    assert_eq!(row_count, 2);
+    assert_eq!(
+        schema.field_with_name("flag").unwrap().data_type(),
+        &arrow_schema::DataType::Boolean
+    );
+    assert_eq!(
+        schema.field_with_name("meta").unwrap().data_type(),
+        &arrow_schema::DataType::Utf8
+    );
+    assert_eq!(
+        schema.field_with_name("empty").unwrap().data_type(),
+        &arrow_schema::DataType::Utf8
+    );
+}
+
+// ============================================================================
+// materialize_query Tests
+// ============================================================================
+
+/// Materialize `query` into `target` on a SQLite file
+async fn materialize_sqlite(
+    file_path: &str,
+    query: &str,
+    target: &str,
+    replace: Option<bool>,
+) -> Result<i64, String> {
+    let (app, _app_db) = test_app().await;
+    materialize_query(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.to_string()),
+        None,
+        None,
+        query.to_string(),
+        "main".to_string(),
+        target.to_string(),
+        replace,
+        None,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_materialize_query_creates_filtered_table() {
+    let file_path = temp_sqlite_path();
+    let source = test_table_name("orders");
+    let target = test_table_name("big_orders");
+
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, customer TEXT, total INTEGER)",
+            source
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "INSERT INTO {} (customer, total) VALUES ('alice', 10), ('bob', 250), ('carol', 500)",
+            source
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let row_count = materialize_sqlite(
+        &file_path,
+        &format!("SELECT customer, total FROM {} WHERE total > 100;", source),
+        &target,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(row_count, 2);
+
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT customer, total FROM {} ORDER BY total", target),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.row_count, 2);
+    assert_eq!(result.data[0]["customer"], "bob");
+    assert_eq!(result.data[1]["customer"], "carol");
+    assert_eq!(result.data[1]["total"], 500);
+
+    // The source table is untouched
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT COUNT(*) AS count FROM {}", source),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.data[0]["count"], 3);
+}
+
+#[tokio::test]
+async fn test_materialize_query_existing_target_requires_replace() {
+    let file_path = temp_sqlite_path();
+    let target = test_table_name("snapshot");
+
+    assert_eq!(
+        materialize_sqlite(&file_path, "SELECT 1 AS n", &target, None)
+            .await
+            .unwrap(),
+        1
+    );
+
+    let err = materialize_sqlite(
+        &file_path,
+        "SELECT 2 AS n UNION ALL SELECT 3",
+        &target,
+        None,
+    )
+    .await
+    .expect_err("Existing target should be rejected without replace");
+    assert!(err.contains("already exists"), "{}", err);
+
+    let row_count = materialize_sqlite(
+        &file_path,
+        "SELECT 2 AS n UNION ALL SELECT 3",
+        &target,
+        Some(true),
+    )
+    .await
+    .unwrap();
+    assert_eq!(row_count, 2);
+
+    // A failing query leaves the existing table untouched
+    materialize_sqlite(
+        &file_path,
+        "SELECT n FROM missing_table",
+        &target,
+        Some(true),
+    )
+    .await
+    .expect_err("Query against a missing table should fail");
+    let result = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT COUNT(*) AS count FROM {}", target),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(result.data[0]["count"], 2);
+
+    let err = materialize_sqlite(
+        &file_path,
+        &format!("DELETE FROM {}", target),
+        &test_table_name("never"),
+        None,
+    )
+    .await
+    .expect_err("Write statements should not be materialized");
+    assert!(err.contains("Only SELECT queries"), "{}", err);
+}
+
+// ============================================================================
+// follow_foreign_key Tests
+// ============================================================================
+
+/// Create `parent` and `child` tables where each child row references a parent
+async fn seed_foreign_key_tables(file_path: &str, parent: &str, child: &str) {
+    for query in [
+        format!("CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT)", parent),
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES {}(id), owner_id INTEGER REFERENCES {})",
+            child, parent, parent
+        ),
+        format!("INSERT INTO {} (id, name) VALUES (1, 'alpha'), (2, 'beta')", parent),
+        format!("INSERT INTO {} (id, parent_id, owner_id) VALUES (10, 2, 1)", child),
+    ] {
+        execute_sqlite_with_params(file_path, &query, None)
+            .await
+            .unwrap();
+    }
+}
+
+async fn follow_sqlite_fk(
+    file_path: &str,
+    table: &str,
+    fk_column: &str,
+    value: serde_json::Value,
+) -> Result<dbcooper_lib::db::models::QueryResult, String> {
+    follow_foreign_key(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.to_string()),
+        None,
+        None,
+        "main".to_string(),
+        table.to_string(),
+        fk_column.to_string(),
+        value,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_follow_foreign_key_returns_parent_row() {
+    let file_path = temp_sqlite_path();
+    let parent = test_table_name("fk_parent");
+    let child = test_table_name("fk_child");
+    seed_foreign_key_tables(&file_path, &parent, &child).await;
+
+    let result = follow_sqlite_fk(&file_path, &child, "parent_id", json!(2))
+        .await
+        .unwrap();
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["id"], json!(2));
+    assert_eq!(result.data[0]["name"], json!("beta"));
+}
+
+#[tokio::test]
+async fn test_follow_foreign_key_to_implicit_primary_key() {
+    let file_path = temp_sqlite_path();
+    let parent = test_table_name("fk_parent");
+    let child = test_table_name("fk_child");
+    seed_foreign_key_tables(&file_path, &parent, &child).await;
+
+    // `REFERENCES parent` without a column targets the parent's primary key
+    let result = follow_sqlite_fk(&file_path, &child, "owner_id", json!(1))
+        .await
+        .unwrap();
+    assert_eq!(result.row_count, 1);
+    assert_eq!(result.data[0]["name"], json!("alpha"));
+}
+
+#[tokio::test]
+async fn test_follow_foreign_key_without_fk_metadata() {
+    let file_path = temp_sqlite_path();
+    let parent = test_table_name("fk_parent");
+    let child = test_table_name("fk_child");
+    seed_foreign_key_tables(&file_path, &parent, &child).await;
+
+    let err = follow_sqlite_fk(&file_path, &parent, "name", json!("alpha"))
+        .await
+        .unwrap_err();
+    assert!(err.contains("has no foreign key"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_find_referencing_rows_groups_children_by_table() {
+    let file_path = temp_sqlite_path();
+    let parent = test_table_name("fk_parent");
+    let child = test_table_name("fk_child");
+    let other = test_table_name("fk_other");
+    seed_foreign_key_tables(&file_path, &parent, &child).await;
+    for query in [
+        format!(
+            "INSERT INTO {} (id, parent_id, owner_id) VALUES (11, 2, 1), (12, 1, 1)",
+            child
+        ),
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES {}(id))",
+            other, parent
+        ),
+        format!("INSERT INTO {} (id, parent_id) VALUES (20, 1)", other),
+    ] {
+        execute_sqlite_with_params(&file_path, &query, None)
+            .await
+            .unwrap();
+    }
+
+    let groups = find_referencing_rows(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        parent.clone(),
+        "id".to_string(),
+        json!(2),
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(groups.len(), 1, "{:?}", groups);
+    assert_eq!(groups[0].table, child);
+    assert_eq!(groups[0].column, "parent_id");
+    assert_eq!(groups[0].row_count, 2);
+    let ids: Vec<_> = groups[0].data.iter().map(|row| row["id"].clone()).collect();
+    assert!(ids.contains(&json!(10)) && ids.contains(&json!(11)));
+
+    // Parent 1 is referenced from both tables, including through the implicit primary key
+    let groups = find_referencing_rows(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        "main".to_string(),
+        parent,
+        "id".to_string(),
+        json!(1),
+        Some(1),
+    )
+    .await
+    .unwrap();
+
+    let mut found: Vec<(String, String, usize)> = groups
+        .iter()
+        .map(|g| (g.table.clone(), g.column.clone(), g.data.len()))
+        .collect();
+    found.sort();
+    let mut expected = vec![
+        (child.clone(), "owner_id".to_string(), 1),
+        (child.clone(), "parent_id".to_string(), 1),
+        (other.clone(), "parent_id".to_string(), 1),
+    ];
+    expected.sort();
+    assert_eq!(found, expected);
+}
+
+// ============================================================================
+// get_row_by_primary_key Tests
+// ============================================================================
+
+async fn sqlite_row_by_pk(
+    file_path: &str,
+    table: &str,
+    columns: &[&str],
+    values: Vec<serde_json::Value>,
+) -> Result<serde_json::Value, String> {
+    get_row_by_primary_key(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.to_string()),
+        None,
+        None,
+        "main".to_string(),
+        table.to_string(),
+        columns.iter().map(|c| c.to_string()).collect(),
+        values,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_get_row_by_single_primary_key() {
+    let file_path = temp_sqlite_path();
+    let table = test_table_name("pk_row");
+    for query in [
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, status TEXT DEFAULT 'new')",
+            table
+        ),
+        format!(
+            "INSERT INTO {} (id, name) VALUES (1, 'alpha'), (2, 'beta')",
+            table
+        ),
+    ] {
+        execute_sqlite_with_params(&file_path, &query, None)
+            .await
+            .unwrap();
+    }
+
+    // Values filled in by the database come back with the row
+    let row = sqlite_row_by_pk(&file_path, &table, &["id"], vec![json!(2)])
+        .await
+        .unwrap();
+    assert_eq!(row["name"], json!("beta"));
+    assert_eq!(row["status"], json!("new"));
+
+    let err = sqlite_row_by_pk(&file_path, &table, &["id"], vec![json!(3)])
+        .await
+        .unwrap_err();
+    assert!(err.contains("No row"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_get_row_by_composite_primary_key() {
+    let file_path = temp_sqlite_path();
+    let table = test_table_name("pk_composite");
+    for query in [
+        format!(
+            "CREATE TABLE {} (tenant TEXT, id INTEGER, note TEXT, PRIMARY KEY (tenant, id))",
+            table
+        ),
+        format!(
+            "INSERT INTO {} (tenant, id, note) VALUES ('a', 1, 'first'), ('b', 1, 'second')",
+            table
+        ),
+    ] {
+        execute_sqlite_with_params(&file_path, &query, None)
+            .await
+            .unwrap();
+    }
+
+    let row = sqlite_row_by_pk(
+        &file_path,
+        &table,
+        &["tenant", "id"],
+        vec![json!("b"), json!(1)],
+    )
+    .await
+    .unwrap();
+    assert_eq!(row["note"], json!("second"));
+
+    // Part of the key matches both rows
+    let err = sqlite_row_by_pk(&file_path, &table, &["id"], vec![json!(1)])
+        .await
+        .unwrap_err();
+    assert!(err.contains("More than one row"), "{}", err);
+}
+
+// ============================================================================
+// get_database_info Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_get_database_info_sqlite_encoding() {
+    let file_path = temp_sqlite_path();
+
+    let info = get_database_info(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(info.encoding.as_deref(), Some("UTF-8"));
+    assert_eq!(info.collation.as_deref(), Some("BINARY"));
+    assert!(info.timezone.is_none());
+}
+
+// ============================================================================
+// clone_table_structure Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_clone_table_structure_sqlite() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let source = test_table_name("clone_src");
+    let target = test_table_name("clone_dst");
+
+    for statement in [
+        format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE, \
+             score REAL DEFAULT 0 CHECK (score >= 0))",
+            source
+        ),
+        format!("CREATE INDEX idx_{0}_score ON {0} (score)", source),
+        format!(
+            "INSERT INTO {} (email, score) VALUES ('a@example.com', 1), ('b@example.com', 2)",
+            source
+        ),
+    ] {
+        execute_sqlite_with_params(&file_path, &statement, None)
+            .await
+            .unwrap();
+    }
+
+    clone_table_structure(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        source.clone(),
+        target.clone(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    let structure_of = |table: String| {
+        let file_path = file_path.clone();
+        async move {
+            unified_get_table_structure(
+                "sqlite".to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(file_path),
+                None,
+                None,
+                "main".to_string(),
+                table,
+            )
+            .await
+            .unwrap()
+        }
+    };
+    let src_structure = structure_of(source.clone()).await;
+    let dst_structure = structure_of(target.clone()).await;
+
+    let describe = |structure: &dbcooper_lib::db::models::TableStructure| {
+        structure
+            .columns
+            .iter()
+            .map(|c| {
+                (
+                    c.name.clone(),
+                    c.data_type.clone(),
+                    c.nullable,
+                    c.default.clone(),
+                    c.primary_key,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(describe(&dst_structure), describe(&src_structure));
+    assert_eq!(dst_structure.indexes.len(), src_structure.indexes.len());
+    assert_eq!(dst_structure.check_constraints.len(), 1);
+
+    let count = execute_sqlite_with_params(
+        &file_path,
+        &format!("SELECT COUNT(*) AS n FROM {}", target),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(count.data[0]["n"], 0);
+}
+
+// ============================================================================
+// import_table_csv Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_import_table_csv_reads_gzip_in_batches() {
+    let (app, _app_db) = test_app().await;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let file_path = temp_sqlite_path();
+    let table = test_table_name("imported");
+    execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "CREATE TABLE {} (id INTEGER PRIMARY KEY, name TEXT, score REAL)",
+            table
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+
+    // More rows than one insert batch, with a quote and an empty field
+    let csv_file = tempfile::Builder::new()
+        .suffix(".csv.gz")
+        .tempfile()
+        .unwrap();
+    let mut encoder = GzEncoder::new(csv_file.reopen().unwrap(), flate2::Compression::default());
+    writeln!(encoder, "id,name,score").unwrap();
+    for i in 1..=1200 {
+        if i == 7 {
+            writeln!(encoder, "{},O'Brien,", i).unwrap();
+        } else {
+            writeln!(encoder, "{},user {},{}.5", i, i, i).unwrap();
+        }
+    }
+    encoder.finish().unwrap();
+
+    let summary = import_table_csv(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "main".to_string(),
+        table.clone(),
+        csv_file.path().to_string_lossy().to_string(),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(summary.rows_imported, 1200);
+    assert!(summary.transactional);
+
+    let rows = execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "SELECT COUNT(*) AS n, SUM(id) AS total FROM {} UNION ALL \
+             SELECT score IS NULL, name = 'O''Brien' FROM {} WHERE id = 7",
+            table, table
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(rows.data[0]["n"], 1200);
+    assert_eq!(rows.data[0]["total"], 1200 * 1201 / 2);
+    assert_eq!(rows.data[1]["n"], 1);
+    assert_eq!(rows.data[1]["total"], 1);
+}
+
+// ============================================================================
+// import_clipboard_data Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_import_clipboard_csv_infers_types() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table = test_table_name("pasted");
+    let data = "id,price,active,created_at,zip,note\n\
+                1,9.5,true,2024-01-02 03:04:05,02134,first\n\
+                2,10,FALSE,2024-02-03T04:05:06Z,90210,\n\
+                3,-1.25,false,2024-03-04,10001,\"with, comma\"\n";
+
+    let summary = import_clipboard_data(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        data.to_string(),
+        "csv".to_string(),
+        table.clone(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(summary.rows_imported, 3);
+    assert!(summary.transactional);
+    let types: Vec<(&str, InferredType, &str)> = summary
+        .columns
+        .iter()
+        .map(|c| (c.name.as_str(), c.inferred_type, c.sql_type.as_str()))
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            ("id", InferredType::Integer, "INTEGER"),
+            ("price", InferredType::Float, "REAL"),
+            ("active", InferredType::Boolean, "BOOLEAN"),
+            ("created_at", InferredType::Timestamp, "TIMESTAMP"),
+            // Leading zeros keep zip codes as text
+            ("zip", InferredType::String, "TEXT"),
+            ("note", InferredType::String, "TEXT"),
+        ]
+    );
+
+    let rows = execute_sqlite_with_params(
+        &file_path,
+        &format!(
+            "SELECT typeof(id) AS id_type, price, active, zip, note FROM {} ORDER BY id",
+            table
+        ),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(rows.data[0]["id_type"], "integer");
+    assert_eq!(rows.data[0]["price"], 9.5);
+    assert_eq!(rows.data[0]["active"], 1);
+    assert_eq!(rows.data[0]["zip"], "02134");
+    assert_eq!(rows.data[1]["active"], 0);
+    assert!(rows.data[1]["note"].is_null());
+    assert_eq!(rows.data[2]["price"], -1.25);
+    assert_eq!(rows.data[2]["note"], "with, comma");
+
+    // The table now exists, so a second paste into it fails
+    let again = import_clipboard_data(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        data.to_string(),
+        "csv".to_string(),
+        table,
+        None,
+    )
+    .await;
+    assert!(again.is_err());
+}
+
+#[test]
+fn test_parse_pasted_json_array() {
+    let (headers, rows) = parse_pasted_data(
+        r#"[{"id": 1, "tags": ["a"]}, {"id": 2, "name": "b", "tags": null}]"#,
+        "json",
+    )
+    .unwrap();
+    assert_eq!(headers, vec!["id", "tags", "name"]);
+    assert_eq!(
+        rows,
+        vec![
+            vec![Some("1".to_string()), Some("[\"a\"]".to_string()), None],
+            vec![Some("2".to_string()), None, Some("b".to_string())],
+        ]
+    );
+    assert_eq!(
+        infer_column_type(rows.iter().filter_map(|r| r[0].as_deref())),
+        InferredType::Integer
+    );
+
+    assert!(parse_pasted_data(r#"{"id": 1}"#, "json").is_err());
+    assert!(parse_pasted_data("id\n1", "xml").is_err());
+}
+
+/// Claims transaction support but cannot open one, recording every statement it runs
+#[derive(Default)]
+struct BeginFailsDriver {
+    executed: std::sync::Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl DatabaseDriver for BeginFailsDriver {
+    fn capabilities(&self) -> DriverCapabilities {
+        DriverCapabilities {
+            supports_transactions: true,
+            ..DriverCapabilities::default()
+        }
+    }
+
+    async fn test_connection(&self) -> Result<TestConnectionResult, String> {
+        Err("not connected".to_string())
+    }
+
+    async fn list_tables(&self) -> Result<Vec<TableInfo>, String> {
+        Ok(vec![])
+    }
+
+    async fn get_table_data(
+        &self,
+        _schema: &str,
+        _table: &str,
+        _page: i64,
+        _limit: i64,
+        _filter: Option<String>,
+        _sort_column: Option<String>,
+        _sort_direction: Option<String>,
+    ) -> Result<TableDataResponse, String> {
+        Err("not connected".to_string())
+    }
+
+    async fn get_table_structure(
+        &self,
+        _schema: &str,
+        _table: &str,
+    ) -> Result<TableStructure, String> {
+        Err("not connected".to_string())
+    }
+
+    async fn execute_query(&self, query: &str) -> Result<QueryResult, String> {
+        self.executed.lock().unwrap().push(query.to_string());
+        Ok(QueryResult::default())
+    }
+
+    async fn get_schema_overview(&self) -> Result<SchemaOverview, String> {
+        Err("not connected".to_string())
+    }
+
+    async fn begin_transaction(&self) -> Result<TxHandle, String> {
+        Err("BEGIN failed: connection reset".to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_import_pasted_data_fails_when_begin_fails() {
+    let driver = BeginFailsDriver::default();
+
+    let err = import_pasted_data(&driver, "postgres", "\"pasted\"", "id,name\n1,a\n", "csv")
+        .await
+        .unwrap_err();
+
+    assert_eq!(err, "BEGIN failed: connection reset");
+    // Nothing ran outside the transaction that could not be opened
+    assert!(driver.executed.lock().unwrap().is_empty());
+}
+
+// ============================================================================
+// execute_script Tests
+// ============================================================================
+
+#[test]
+fn test_split_statements_ignores_quoted_semicolons() {
+    let script = "INSERT INTO t VALUES ('a;b', \"c;d\"); -- trailing; comment\n\
+                  /* block; comment */ SELECT 1;;\n\
+                  CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql;\n\
+                  SELECT $1 -- no semicolon at the end";
+
+    assert_eq!(
+        split_statements(script),
+        vec![
+            "INSERT INTO t VALUES ('a;b', \"c;d\")",
+            "-- trailing; comment\n/* block; comment */ SELECT 1",
+            "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql",
+            "SELECT $1 -- no semicolon at the end",
+        ]
+    );
+    assert!(split_statements(" ; -- only a comment").is_empty());
+}
+
+#[tokio::test]
+async fn test_execute_script_caps_each_statement() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+    let table = test_table_name("script");
+    let script = format!(
+        "CREATE TABLE {table} (n INTEGER);
+         INSERT INTO {table} WITH RECURSIVE c(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM c WHERE n < 50) SELECT n FROM c;
+         SELECT n FROM {table} ORDER BY n;
+         SELECT COUNT(*) AS total FROM {table};"
+    );
+
+    display::apply_setting(display::MAX_RESULT_ROWS_KEY, "10").unwrap();
+    let results = execute_script(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        script,
+        None,
+    )
+    .await;
+    display::apply_setting(display::MAX_RESULT_ROWS_KEY, "").unwrap();
+
+    let results = results.unwrap();
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|r| r.result.error.is_none()));
+
+    // The large SELECT is cut to the cap and flagged; the others are untouched
+    let large = &results[2];
+    assert!(large.truncated);
+    assert_eq!(large.result.data.len(), 10);
+    assert_eq!(large.result.row_count, 50);
+    assert_eq!(large.result.data[9]["n"], 10);
+    assert!(!results[0].truncated && !results[1].truncated);
+    assert!(!results[3].truncated);
+    assert_eq!(results[3].result.data[0]["total"], 50);
+}
+
+#[tokio::test]
+async fn test_execute_script_stops_at_first_error() {
+    let (app, _app_db) = test_app().await;
+    let file_path = temp_sqlite_path();
+
+    let results = execute_script(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path.clone()),
+        None,
+        None,
+        "SELECT 1; SELECT * FROM missing_table; SELECT 2".to_string(),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results[1].result.error.is_some());
+
+    // Read-only connections reject scripts containing writes
+    let err = execute_script(
+        app.state(),
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+        "SELECT 1; DELETE FROM t".to_string(),
+        Some(save_read_only_connection(&app).await),
+    )
+    .await
+    .unwrap_err();
+    assert!(err.contains("read-only"), "{}", err);
+}
+
+// ============================================================================
+// export_er_mermaid Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_export_er_mermaid_includes_entities_and_relationship() {
+    let file_path = temp_sqlite_path();
+    let driver = SqliteDriver::new(SqliteConfig {
+        file_path: file_path.clone(),
+        session_setup_sql: None,
+    });
+    driver
+        .execute_query("CREATE TABLE customers (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await
+        .unwrap();
+    driver
+        .execute_query(
+            "CREATE TABLE orders (id INTEGER PRIMARY KEY, \
+             customer_id INTEGER NOT NULL REFERENCES customers(id), total REAL)",
+        )
+        .await
+        .unwrap();
+
+    let diagram = export_er_mermaid(
+        "sqlite".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        Some(file_path),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    assert!(diagram.starts_with("erDiagram\n"), "{}", diagram);
+    assert!(diagram.contains("    customers {"), "{}", diagram);
+    assert!(diagram.contains("    orders {"), "{}", diagram);
+    assert!(diagram.contains(" id PK\n"), "{}", diagram);
+    assert!(diagram.contains(" customer_id FK\n"), "{}", diagram);
+    assert!(
+        diagram.contains("    customers ||--o{ orders : \"customer_id\""),
+        "{}",
+        diagram
+    );
 }
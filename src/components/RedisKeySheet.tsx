@@ -63,6 +63,9 @@ export function RedisKeySheet({
 	const [newZsetMember, setNewZsetMember] = useState("");
 	const [newZsetScore, setNewZsetScore] = useState("");
 
+	// Editing a partly loaded value would replace the key with just the loaded part
+	const partialValue = mode === "edit" && keyDetails?.truncated === true;
+
 	// Initialize form when opening or keyDetails changes
 	useEffect(() => {
 		if (open) {
@@ -91,8 +94,9 @@ export function RedisKeySheet({
 					if (Array.isArray(value)) {
 						setZsetMembers(
 							value.map((item) => {
-								if (Array.isArray(item) && item.length === 2) {
-									return { member: String(item[0]), score: Number(item[1]) };
+								if (typeof item === "object" && item !== null && "member" in item) {
+									const entry = item as { member: unknown; score: unknown };
+									return { member: String(entry.member), score: Number(entry.score) };
 								}
 								return { member: String(item), score: 0 };
 							}),
@@ -208,6 +212,11 @@ export function RedisKeySheet({
 			return;
 		}
 
+		if (partialValue) {
+			toast.error("Only part of this key was loaded, so it cannot be saved from here");
+			return;
+		}
+
 		let value: unknown;
 		const ttlValue = ttlEnabled && ttl.trim() ? parseInt(ttl, 10) : undefined;
 
@@ -513,6 +522,14 @@ export function RedisKeySheet({
 						</Select>
 					</div>
 
+					{partialValue && (
+						<p className="text-xs text-destructive">
+							This key is too large to load in full ({keyDetails?.length}{" "}
+							{keyDetails?.key_type === "string" ? "bytes" : "entries"}). Saving is disabled
+							so the part that was not loaded is kept.
+						</p>
+					)}
+
 					{renderValueEditor()}
 
 					<div className="space-y-2">
@@ -552,7 +569,7 @@ export function RedisKeySheet({
 					<Button variant="outline" onClick={() => onOpenChange(false)}>
 						Cancel
 					</Button>
-					<Button onClick={handleSave} disabled={saving}>
+					<Button onClick={handleSave} disabled={saving || partialValue}>
 						{saving ? <Spinner /> : <FloppyDisk className="w-4 h-4" />}
 						{mode === "add" ? "Add Key" : "Save Changes"}
 					</Button>
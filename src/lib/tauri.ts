@@ -70,6 +70,7 @@ export interface IndexInfo {
 export interface ForeignKeyInfo {
 	name: string;
 	column: string;
+	references_schema: string;
 	references_table: string;
 	references_column: string;
 }
@@ -137,6 +138,15 @@ export interface RedisKeyDetails {
 	encoding?: string;
 	size?: number;
 	length?: number;
+	// Set when `value` holds only the first entries of the key (or the start of a long string)
+	truncated: boolean;
+}
+
+export interface RedisStringValue {
+	key: string;
+	value: unknown;
+	value_base64?: string | null;
+	length: number;
 }
 
 // Export/Import types
@@ -277,6 +287,7 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
 			}),
 
 		listTables: (connection: Connection) =>
@@ -289,6 +300,7 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				file_path: connection.file_path,
+				session_setup_sql: connection.session_setup_sql,
 				ssh_enabled: connection.ssh_enabled === 1,
 				ssh_host: connection.ssh_host,
 				ssh_port: connection.ssh_port,
@@ -315,6 +327,7 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
 				schema,
 				table,
 				page,
@@ -336,6 +349,7 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
 				schema,
 				table,
 			}),
@@ -350,6 +364,8 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
+				connectionUuid: connection.uuid,
 				query,
 			}),
 
@@ -389,6 +405,8 @@ export const api = {
 					password: connection.password,
 					ssl: connection.ssl === 1,
 					filePath: connection.file_path,
+					sessionSetupSql: connection.session_setup_sql,
+					connectionUuid: connection.uuid,
 					schema,
 					table,
 					primaryKeyColumns,
@@ -409,6 +427,8 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
+				connectionUuid: connection.uuid,
 				schema,
 				table,
 				primaryKeyColumns,
@@ -433,6 +453,8 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
+				connectionUuid: connection.uuid,
 				schema,
 				table,
 				primaryKeyColumns,
@@ -465,6 +487,8 @@ export const api = {
 				password: connection.password,
 				ssl: connection.ssl === 1,
 				filePath: connection.file_path,
+				sessionSetupSql: connection.session_setup_sql,
+				connectionUuid: connection.uuid,
 				schema,
 				table,
 				values: values.map((v) => ({
@@ -497,6 +521,12 @@ export const api = {
 				key,
 			}),
 
+		getFullValue: (connectionUuid: string, key: string) =>
+			invoke<RedisStringValue>("redis_get_full_value", {
+				uuid: connectionUuid,
+				key,
+			}),
+
 		deleteKey: (connectionUuid: string, key: string) =>
 			invoke<boolean>("redis_delete_key", {
 				uuid: connectionUuid,
@@ -2670,7 +2670,23 @@ export function ConnectionDetails() {
 		setRedisKeySheetOpen(true);
 	};
 
-	const handleRedisEditKey = () => {
+	const handleRedisEditKey = async () => {
+		// Long strings are loaded as a preview; edit the complete value instead
+		if (connection && redisKeyDetails?.truncated && redisKeyDetails.key_type === "string") {
+			try {
+				const full = await api.redis.getFullValue(connection.uuid, redisKeyDetails.key);
+				setRedisKeyDetails({
+					...redisKeyDetails,
+					value: full.value,
+					length: full.length,
+					truncated: false,
+				});
+			} catch (error) {
+				console.error("Failed to load full Redis value:", error);
+				toast.error("Failed to load the full value of this key");
+				return;
+			}
+		}
 		setRedisKeySheetMode("edit");
 		setRedisKeySheetOpen(true);
 	};
@@ -3020,7 +3036,16 @@ export function ConnectionDetails() {
 									{/* Value */}
 									<div>
 										<div className="flex items-center justify-between mb-3">
-											<h3 className="text-sm font-medium">Value</h3>
+											<h3 className="text-sm font-medium">
+												Value
+												{redisKeyDetails.truncated && (
+													<span className="ml-2 text-xs font-normal text-muted-foreground">
+														(partial, {redisKeyDetails.length}{" "}
+														{redisKeyDetails.key_type === "string" ? "bytes" : "entries"} in
+														total)
+													</span>
+												)}
+											</h3>
 											<Button
 												variant="ghost"
 												size="sm"
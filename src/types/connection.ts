@@ -13,6 +13,7 @@ export interface Connection {
 	ssl: number;
 	db_type: string;
 	file_path: string | null;
+	session_setup_sql?: string | null;
 	ssh_enabled: number;
 	ssh_host: string;
 	ssh_port: number;
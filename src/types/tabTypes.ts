@@ -25,6 +25,7 @@ export interface TableStructureData {
 export interface ForeignKeyInfo {
 	name: string;
 	column: string;
+	references_schema: string;
 	references_table: string;
 	references_column: string;
 }